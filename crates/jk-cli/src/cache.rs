@@ -0,0 +1,371 @@
+//! Replays read-only `jj` output while the repository's operation head stays put.
+//!
+//! Moving between the log, diff, and show views for the same revision asks `jj` for the same
+//! rendered output again and again. A read-only command's output only changes when the repository
+//! moves to a new operation, so [`JjOutputCache`] keys each output by the process argv, working
+//! directory, and the operation heads found on disk. Any operation, from `jk` or another process,
+//! rewrites those heads and retires the older entries on the next lookup.
+//!
+//! Working-copy edits only become an operation once some `jj` command snapshots them, so a spec
+//! that snapshots the working copy could replay output from before the user's last edit. Only
+//! `--ignore-working-copy` runs are cached; everything else always reaches `jj`.
+
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+use jk_core::{ExecutionMode, JjCommandSpec, SafetyClass};
+
+use crate::{JjCommandRunner, repository_store};
+
+const OUTPUT_CACHE_LIMIT: usize = 64;
+
+/// Successful read-only `jj` outputs keyed by command and repository operation.
+#[derive(Clone, Debug, Default)]
+pub struct JjOutputCache {
+    entries: VecDeque<(CacheKey, Output)>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct CacheKey {
+    store: PathBuf,
+    operation: String,
+    cwd: Option<PathBuf>,
+    argv: Vec<OsString>,
+}
+
+impl JjOutputCache {
+    /// Returns the number of retained outputs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether no outputs are retained.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drops every retained output.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Drops outputs recorded at an older operation of the same repository.
+    fn retire_stale(&mut self, store: &Path, operation: &str) {
+        self.entries
+            .retain(|(key, _)| key.store != store || key.operation == operation);
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<&Output> {
+        self.entries
+            .iter()
+            .find(|(cached, _)| cached == key)
+            .map(|(_, output)| output)
+    }
+
+    fn insert(&mut self, key: CacheKey, output: Output) {
+        self.entries.retain(|(cached, _)| *cached != key);
+        if self.entries.len() == OUTPUT_CACHE_LIMIT {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((key, output));
+    }
+}
+
+/// Answers read-only specs from a [`JjOutputCache`] before falling back to another runner.
+///
+/// Only specs that render read-only output without stdin or a working-copy snapshot are cached,
+/// and only successful runs are stored. Everything else, and every spec whose repository operation
+/// heads cannot be read, goes straight to the wrapped runner.
+#[derive(Debug)]
+pub struct CachingJjCommandRunner<'a, R> {
+    inner: R,
+    cache: &'a mut JjOutputCache,
+    last_run_was_cached: bool,
+}
+
+impl<'a, R> CachingJjCommandRunner<'a, R> {
+    /// Creates a caching runner that stores outputs in `cache`.
+    pub const fn new(inner: R, cache: &'a mut JjOutputCache) -> Self {
+        Self {
+            inner,
+            cache,
+            last_run_was_cached: false,
+        }
+    }
+}
+
+impl<R> JjCommandRunner for CachingJjCommandRunner<'_, R>
+where
+    R: JjCommandRunner,
+{
+    fn run(&mut self, spec: &JjCommandSpec) -> std::io::Result<Output> {
+        self.last_run_was_cached = false;
+        let Some(key) = cache_key(spec) else {
+            return self.inner.run(spec);
+        };
+
+        self.cache.retire_stale(&key.store, &key.operation);
+        if let Some(output) = self.cache.get(&key) {
+            self.last_run_was_cached = true;
+            return Ok(output.clone());
+        }
+
+        let output = self.inner.run(spec)?;
+        if output.status.success() {
+            self.cache.insert(key, output.clone());
+        }
+        Ok(output)
+    }
+
+    fn last_run_was_cached(&self) -> bool {
+        self.last_run_was_cached
+    }
+}
+
+fn cache_key(spec: &JjCommandSpec) -> Option<CacheKey> {
+    if spec.mode() != ExecutionMode::RenderReadOnly
+        || spec.safety() != SafetyClass::ReadOnly
+        || spec.stdin().is_some()
+        || spec.global_options().snapshots_working_copy()
+    {
+        return None;
+    }
+
+    let base = match spec.cwd() {
+        Some(cwd) => cwd.to_path_buf(),
+        None => std::env::current_dir().ok()?,
+    };
+    let start = spec
+        .repository()
+        .map_or_else(|| base.clone(), |repository| base.join(repository));
+    let store = repository_store(&start)?;
+    let operation = operation_heads(&store)?;
+    Some(CacheKey {
+        store,
+        operation,
+        cwd: spec.cwd().map(Path::to_path_buf),
+        argv: spec.process_argv(),
+    })
+}
+
+/// Returns the current operation heads, sorted and joined, as one comparable key.
+fn operation_heads(store: &Path) -> Option<String> {
+    let mut heads = fs::read_dir(store.join("op_heads").join("heads"))
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .collect::<Vec<_>>();
+    if heads.is_empty() {
+        return None;
+    }
+    heads.sort_unstable();
+    Some(heads.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    #[cfg(unix)]
+    use std::os::unix::process::ExitStatusExt;
+
+    use jk_core::{
+        CommandHistory, CommandSource, GlobalOptions, SourceAction, SourceView, WorkingCopyPolicy,
+    };
+
+    use super::*;
+    use crate::RecordingJjCommandRunner;
+
+    #[cfg(unix)]
+    fn exit_status(code: i32) -> std::process::ExitStatus {
+        std::process::ExitStatus::from_raw(code << 8)
+    }
+
+    #[cfg(not(unix))]
+    fn exit_status(code: i32) -> std::process::ExitStatus {
+        std::process::Command::new(if cfg!(windows) { "cmd" } else { "sh" })
+            .args(if cfg!(windows) {
+                vec!["/C".to_owned(), format!("exit {code}")]
+            } else {
+                vec!["-c".to_owned(), format!("exit {code}")]
+            })
+            .status()
+            .expect("run exit status helper")
+    }
+
+    struct CountingRunner {
+        runs: usize,
+        code: i32,
+    }
+
+    impl JjCommandRunner for CountingRunner {
+        fn run(&mut self, _spec: &JjCommandSpec) -> std::io::Result<Output> {
+            self.runs += 1;
+            Ok(Output {
+                status: exit_status(self.code),
+                stdout: format!("run {}\n", self.runs).into_bytes(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    /// A throwaway workspace with one operation head.
+    struct Workspace(PathBuf);
+
+    impl Workspace {
+        fn new(name: &str) -> Self {
+            let root =
+                std::env::temp_dir().join(format!("jk-output-cache-{name}-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            let workspace = Self(root);
+            workspace.move_head_to("aaaa");
+            workspace
+        }
+
+        fn heads(&self) -> PathBuf {
+            self.0.join(".jj/repo/op_heads/heads")
+        }
+
+        fn move_head_to(&self, operation: &str) {
+            let _ = fs::remove_dir_all(self.heads());
+            fs::create_dir_all(self.heads()).expect("create op heads");
+            fs::write(self.heads().join(operation), "").expect("write op head");
+        }
+
+        /// Returns a read-only spec that leaves the working copy alone, as cached specs must.
+        fn spec(&self, argv: &[&str]) -> JjCommandSpec {
+            JjCommandSpec::render_read_only(argv.iter().copied()).with_global_options(
+                GlobalOptions::default()
+                    .with_repository(&self.0)
+                    .with_working_copy(WorkingCopyPolicy::Ignore),
+            )
+        }
+    }
+
+    impl Drop for Workspace {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn stdout(output: &Output) -> String {
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    #[test]
+    fn replays_read_only_output_until_the_operation_head_moves() -> std::io::Result<()> {
+        let workspace = Workspace::new("replay");
+        let mut cache = JjOutputCache::default();
+        let mut runner =
+            CachingJjCommandRunner::new(CountingRunner { runs: 0, code: 0 }, &mut cache);
+        let show = workspace.spec(&["show", "aaa"]);
+
+        assert_eq!(stdout(&runner.run(&show)?), "run 1\n");
+        assert!(!runner.last_run_was_cached());
+        assert_eq!(stdout(&runner.run(&show)?), "run 1\n");
+        assert!(runner.last_run_was_cached());
+        assert_eq!(
+            stdout(&runner.run(&workspace.spec(&["diff", "-r", "aaa"]))?),
+            "run 2\n"
+        );
+
+        workspace.move_head_to("bbbb");
+        assert_eq!(stdout(&runner.run(&show)?), "run 3\n");
+        assert!(!runner.last_run_was_cached());
+        assert_eq!(cache.len(), 1, "outputs from the old operation are retired");
+        Ok(())
+    }
+
+    #[test]
+    fn command_history_marks_replayed_outputs() -> std::io::Result<()> {
+        let workspace = Workspace::new("history");
+        let mut cache = JjOutputCache::default();
+        let mut history = CommandHistory::new(4);
+        let mut runner = RecordingJjCommandRunner::new(
+            CachingJjCommandRunner::new(CountingRunner { runs: 0, code: 0 }, &mut cache),
+            &mut history,
+            CommandSource::new(SourceView::Log, SourceAction::OpenShow),
+        );
+        let show = workspace.spec(&["show", "aaa"]);
+
+        runner.run(&show)?;
+        runner.run(&show)?;
+
+        let hits = history
+            .records()
+            .map(|record| record.cache_hit)
+            .collect::<Vec<_>>();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits.iter().filter(|hit| **hit).count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn mutations_failures_and_unknown_repositories_always_run() -> std::io::Result<()> {
+        let workspace = Workspace::new("bypass");
+        let mut cache = JjOutputCache::default();
+        let mut runner =
+            CachingJjCommandRunner::new(CountingRunner { runs: 0, code: 0 }, &mut cache);
+        let describe = workspace
+            .spec(&["describe", "-m", "x"])
+            .with_mode(ExecutionMode::ConfirmMutation)
+            .with_safety(SafetyClass::LocalMetadata);
+
+        runner.run(&describe)?;
+        runner.run(&describe)?;
+        assert!(!runner.last_run_was_cached());
+        let outside = JjCommandSpec::render_read_only(["log"])
+            .with_repository(std::env::temp_dir().join("jk-output-cache-missing"));
+        runner.run(&outside)?;
+        runner.run(&outside)?;
+        assert!(!runner.last_run_was_cached());
+
+        let mut failing =
+            CachingJjCommandRunner::new(CountingRunner { runs: 0, code: 1 }, &mut cache);
+        failing.run(&workspace.spec(&["show", "missing"]))?;
+        assert_eq!(
+            stdout(&failing.run(&workspace.spec(&["show", "missing"]))?),
+            "run 2\n"
+        );
+        assert!(cache.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn working_copy_snapshots_run_again_after_a_file_edit() -> std::io::Result<()> {
+        let workspace = Workspace::new("snapshot");
+        let mut cache = JjOutputCache::default();
+        let mut runner =
+            CachingJjCommandRunner::new(CountingRunner { runs: 0, code: 0 }, &mut cache);
+        let diff =
+            JjCommandSpec::render_read_only(["diff", "-r", "@"]).with_repository(&workspace.0);
+        fs::write(workspace.0.join("file.txt"), "before\n")?;
+
+        assert_eq!(stdout(&runner.run(&diff)?), "run 1\n");
+        fs::write(workspace.0.join("file.txt"), "after\n")?;
+        assert_eq!(stdout(&runner.run(&diff)?), "run 2\n");
+        assert!(!runner.last_run_was_cached());
+        assert!(cache.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn secondary_workspaces_follow_the_repo_pointer() {
+        let workspace = Workspace::new("secondary");
+        let secondary = workspace.0.join("secondary");
+        fs::create_dir_all(secondary.join(".jj")).expect("create secondary workspace");
+        fs::create_dir_all(secondary.join("src")).expect("create secondary source directory");
+        fs::write(secondary.join(".jj/repo"), "../../.jj/repo\n").expect("write repo pointer");
+
+        let store = repository_store(&secondary.join("src"));
+
+        assert_eq!(
+            store.as_deref().and_then(operation_heads),
+            Some("aaaa".to_owned())
+        );
+    }
+}
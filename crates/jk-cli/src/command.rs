@@ -1,8 +1,11 @@
 //! Shared execution adapter for typed `jj` command specs.
 
-use std::io::Write;
+use std::io::{Read, Write};
 use std::process::{Command, Output, Stdio};
-use std::time::SystemTime;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 use jk_core::{
     ColorPolicy, CommandHistory, CommandRecordFinish, CommandRecordStart, CommandResultSummary,
@@ -12,6 +15,7 @@ use jk_core::{
 };
 
 const HISTORY_STREAM_LIMIT: usize = 8 * 1024;
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 /// Runs typed `jj` command specs.
 ///
@@ -28,6 +32,12 @@ pub trait JjCommandRunner {
     ///
     /// Returns the underlying I/O error when spawning, writing, or waiting fails.
     fn run(&mut self, spec: &JjCommandSpec) -> std::io::Result<Output>;
+
+    /// Returns whether the last [`JjCommandRunner::run`] call replayed cached output instead of
+    /// running `jj`.
+    fn last_run_was_cached(&self) -> bool {
+        false
+    }
 }
 
 /// Executes `jj` commands with the system `jj` binary.
@@ -44,6 +54,95 @@ impl JjCommandRunner for SystemJjCommandRunner {
     }
 }
 
+/// Executes `jj` like [`SystemJjCommandRunner`], killing the child once `cancelled` is set.
+///
+/// Background loads use this so work nobody is waiting for stops instead of running to completion.
+/// A cancelled run returns an [`std::io::ErrorKind::Interrupted`] error; a run that starts after
+/// cancellation never spawns `jj`.
+#[derive(Clone, Debug)]
+pub struct CancellableJjCommandRunner {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellableJjCommandRunner {
+    /// Creates a runner that watches `cancelled` while `jj` runs.
+    #[must_use]
+    pub const fn new(cancelled: Arc<AtomicBool>) -> Self {
+        Self { cancelled }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl JjCommandRunner for CancellableJjCommandRunner {
+    fn run(&mut self, spec: &JjCommandSpec) -> std::io::Result<Output> {
+        if self.is_cancelled() {
+            return Err(cancelled_error());
+        }
+
+        let mut command = build_jj_command(spec);
+        command.stdout(Stdio::piped());
+        command.stderr(Stdio::piped());
+        command.stdin(if spec.stdin().is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        });
+        let mut child = command.spawn()?;
+        if let Some(stdin) = spec.stdin()
+            && let Some(mut child_stdin) = child.stdin.take()
+        {
+            child_stdin.write_all(stdin.as_bytes())?;
+        }
+
+        // Drain both pipes on their own threads so a chatty `jj` cannot block on a full pipe while
+        // this thread polls for exit or cancellation.
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if self.is_cancelled() {
+                child.kill()?;
+                child.wait()?;
+                return Err(cancelled_error());
+            }
+            thread::sleep(CANCEL_POLL_INTERVAL);
+        };
+
+        Ok(Output {
+            status,
+            stdout: join_drain(stdout)?,
+            stderr: join_drain(stderr)?,
+        })
+    }
+}
+
+type DrainHandle = thread::JoinHandle<std::io::Result<Vec<u8>>>;
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> DrainHandle {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut bytes)?;
+        }
+        Ok(bytes)
+    })
+}
+
+fn join_drain(handle: DrainHandle) -> std::io::Result<Vec<u8>> {
+    handle
+        .join()
+        .map_err(|_| std::io::Error::other("jj output reader panicked"))?
+}
+
+fn cancelled_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Interrupted, "jj run cancelled")
+}
+
 /// Records command-history entries around another `jj` runner.
 ///
 /// Each call to [`JjCommandRunner::run`] records one command-history entry, so loaders that call
@@ -78,7 +177,8 @@ where
             .start(CommandRecordStart::from_spec(spec, self.source.clone()));
         let result = self.inner.run(spec);
         let finish = match &result {
-            Ok(output) => finish_from_output(output, SystemTime::now()),
+            Ok(output) => finish_from_output(output, SystemTime::now())
+                .with_cache_hit(self.inner.last_run_was_cached()),
             Err(error) => {
                 CommandRecordFinish::from_spawn_error(error.to_string(), "", "", SystemTime::now())
             }
@@ -86,6 +186,10 @@ where
         self.history.finish(&pending, finish);
         result
     }
+
+    fn last_run_was_cached(&self) -> bool {
+        self.inner.last_run_was_cached()
+    }
 }
 
 impl<R> RecordingJjCommandRunner<'_, R>
@@ -287,6 +391,17 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn cancelled_runner_does_not_spawn_jj() {
+        let mut runner = CancellableJjCommandRunner::new(Arc::new(AtomicBool::new(true)));
+
+        let error = runner
+            .run(&JjCommandSpec::render_read_only(["log"]))
+            .expect_err("cancelled before spawning");
+
+        assert_eq!(error.kind(), io::ErrorKind::Interrupted);
+    }
+
     #[test]
     fn command_adapter_forces_color_and_cleans_color_env() {
         let command = build_jj_command(&JjCommandSpec::render_read_only(["log"]));
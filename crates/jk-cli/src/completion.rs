@@ -0,0 +1,715 @@
+//! Completion candidates for revsets and `jj` command lines.
+//!
+//! The engine is shared by the log revset prompt and `:` command mode. Repository names such as
+//! bookmarks, tags, remotes, workspaces, and `revset-aliases` come from a handful of cheap read-only
+//! `jj` queries; change ids come from the log snapshot the user is already looking at; subcommands,
+//! flags, and revset functions come from a static vocabulary. Queries are best-effort, so a `jj`
+//! version that rejects one of them only loses that group of candidates.
+
+use std::collections::HashSet;
+
+use jk_core::{
+    ColorPolicy, GlobalOptions, JjCommandSpec, LogEntry, OutputPolicy, WorkingCopyPolicy,
+};
+
+use crate::command::JjCommandRunner;
+
+mod vocabulary;
+
+use vocabulary::{
+    COMMAND_FLAGS, GLOBAL_FLAGS, NESTED_SUBCOMMANDS, REVSET_FLAGS, REVSET_FUNCTIONS,
+    REVSET_POSITIONAL_COMMANDS, SUBCOMMANDS,
+};
+
+const MAX_CANDIDATES: usize = 50;
+const CHANGE_ID_PREFIX_LEN: usize = 8;
+const NAME_TEMPLATE: &str = "name ++ \"\\n\"";
+const REVSET_ALIASES_KEY: &str = "revset-aliases";
+
+/// What a completion candidate names.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CompletionKind {
+    /// A `jj` subcommand.
+    Subcommand,
+    /// A command-line flag.
+    Flag,
+    /// A local bookmark name.
+    Bookmark,
+    /// A tag name.
+    Tag,
+    /// A git remote name.
+    Remote,
+    /// A workspace name, completed as its working-copy revset `name@`.
+    Workspace,
+    /// A visible change-id prefix from the current log.
+    ChangeId,
+    /// A built-in revset function.
+    RevsetFunction,
+    /// A configured `revset-aliases` entry.
+    RevsetAlias,
+}
+
+impl CompletionKind {
+    /// Returns the short label shown beside candidates.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Subcommand => "command",
+            Self::Flag => "flag",
+            Self::Bookmark => "bookmark",
+            Self::Tag => "tag",
+            Self::Remote => "remote",
+            Self::Workspace => "workspace",
+            Self::ChangeId => "change",
+            Self::RevsetFunction => "function",
+            Self::RevsetAlias => "alias",
+        }
+    }
+}
+
+/// One suggested replacement for the token being completed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompletionCandidate {
+    /// Text that replaces the token.
+    pub text: String,
+    /// What the text names.
+    pub kind: CompletionKind,
+    /// Optional context, such as a change description.
+    pub detail: Option<String>,
+}
+
+impl CompletionCandidate {
+    fn new(text: impl Into<String>, kind: CompletionKind) -> Self {
+        Self {
+            text: text.into(),
+            kind,
+            detail: None,
+        }
+    }
+
+    fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// Candidates for the token that starts at byte offset `replace_start` of the input.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Completion {
+    /// Byte offset in the input where the completed token starts.
+    pub replace_start: usize,
+    /// Matching candidates in display order.
+    pub candidates: Vec<CompletionCandidate>,
+}
+
+impl Completion {
+    /// Returns `input` with the completed token replaced by candidate `index`.
+    #[must_use]
+    pub fn apply(&self, input: &str, index: usize) -> Option<String> {
+        let candidate = self.candidates.get(index)?;
+        let mut completed = input.get(..self.replace_start)?.to_owned();
+        completed.push_str(&candidate.text);
+        Some(completed)
+    }
+}
+
+/// Repository names loaded from `jj` for completion.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CompletionSources {
+    /// Local bookmark names.
+    pub bookmarks: Vec<String>,
+    /// Tag names.
+    pub tags: Vec<String>,
+    /// Git remote names.
+    pub remotes: Vec<String>,
+    /// Workspace names.
+    pub workspaces: Vec<String>,
+    /// Configured revset alias names, such as `mine()` or `wip`.
+    pub revset_aliases: Vec<String>,
+}
+
+/// Loads completion sources with read-only `jj` queries.
+#[derive(Clone, Debug, Default)]
+pub struct JjCompletion {
+    global_options: GlobalOptions,
+}
+
+impl JjCompletion {
+    /// Sets the repository path passed to `jj --repository`.
+    #[must_use]
+    pub fn with_repository(mut self, repository: impl Into<std::path::PathBuf>) -> Self {
+        self.global_options = self.global_options.with_repository(repository);
+        self
+    }
+
+    /// Loads bookmarks, tags, remotes, workspaces, and revset aliases.
+    ///
+    /// Each query is independent; one that fails or is unsupported leaves its list empty.
+    pub fn load_sources_with_runner(&self, runner: &mut impl JjCommandRunner) -> CompletionSources {
+        CompletionSources {
+            bookmarks: self.run_names(runner, &["bookmark", "list", "-T", NAME_TEMPLATE]),
+            tags: self.run_names(runner, &["tag", "list", "-T", NAME_TEMPLATE]),
+            remotes: self.run_names(runner, &["git", "remote", "list"]),
+            workspaces: self.run_names(runner, &["workspace", "list", "-T", NAME_TEMPLATE]),
+            revset_aliases: self
+                .run_lines(runner, &["config", "list", REVSET_ALIASES_KEY])
+                .iter()
+                .filter_map(|line| parse_revset_alias_name(line))
+                .collect(),
+        }
+    }
+
+    /// Returns the read-only spec for one completion query.
+    #[must_use]
+    pub fn spec_for(&self, argv: &[&str]) -> JjCommandSpec {
+        let global_options = self
+            .global_options
+            .clone()
+            .with_working_copy(WorkingCopyPolicy::Ignore)
+            .with_output(OutputPolicy {
+                color: ColorPolicy::Never,
+                ..OutputPolicy::default()
+            });
+        JjCommandSpec::render_read_only(argv.iter().copied())
+            .with_global_options(global_options)
+            .with_title(format!("jj {}", argv.first().copied().unwrap_or_default()))
+    }
+
+    /// Runs a query and returns the first whitespace-separated field of each line, deduplicated.
+    fn run_names(&self, runner: &mut impl JjCommandRunner, argv: &[&str]) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.run_lines(runner, argv)
+            .iter()
+            .filter_map(|line| line.split_whitespace().next())
+            .filter(|name| seen.insert((*name).to_owned()))
+            .map(ToOwned::to_owned)
+            .collect()
+    }
+
+    fn run_lines(&self, runner: &mut impl JjCommandRunner, argv: &[&str]) -> Vec<String> {
+        match runner.run(&self.spec_for(argv)) {
+            Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(ToOwned::to_owned)
+                .collect(),
+            Ok(_) | Err(_) => Vec::new(),
+        }
+    }
+}
+
+/// Parses the alias name from a `jj config list revset-aliases` line.
+///
+/// Lines look like `revset-aliases.'mine()' = "..."` or `revset-aliases.wip = "..."`.
+fn parse_revset_alias_name(line: &str) -> Option<String> {
+    let key = line.strip_prefix(REVSET_ALIASES_KEY)?.strip_prefix('.')?;
+    let (name, _) = key.split_once(" = ")?;
+    let name = name.trim().trim_matches(|c| c == '\'' || c == '"');
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+/// Completes the revset token at the end of `input`.
+#[must_use]
+pub fn complete_revset(
+    input: &str,
+    sources: &CompletionSources,
+    entries: &[LogEntry],
+) -> Completion {
+    let mut token_start = input
+        .char_indices()
+        .rev()
+        .take_while(|(_, character)| is_revset_name_char(*character))
+        .last()
+        .map_or(input.len(), |(index, _)| index);
+    if let Some(range) = input[token_start..].rfind("..") {
+        token_start += range + 2;
+    }
+    let token = &input[token_start..];
+
+    if let Some(at) = token.rfind('@') {
+        let remote_prefix = &token[at + 1..];
+        return Completion {
+            replace_start: token_start + at + 1,
+            candidates: matching(
+                sources
+                    .remotes
+                    .iter()
+                    .map(|remote| CompletionCandidate::new(remote, CompletionKind::Remote)),
+                remote_prefix,
+            ),
+        };
+    }
+
+    let candidates =
+        sources
+            .bookmarks
+            .iter()
+            .map(|bookmark| CompletionCandidate::new(bookmark, CompletionKind::Bookmark))
+            .chain(
+                sources
+                    .tags
+                    .iter()
+                    .map(|tag| CompletionCandidate::new(tag, CompletionKind::Tag)),
+            )
+            .chain(change_id_candidates(entries, token))
+            .chain(sources.revset_aliases.iter().map(|alias| {
+                CompletionCandidate::new(alias_insert_text(alias), CompletionKind::RevsetAlias)
+            }))
+            .chain(REVSET_FUNCTIONS.iter().map(|function| {
+                CompletionCandidate::new(*function, CompletionKind::RevsetFunction)
+            }))
+            .chain(sources.workspaces.iter().map(|workspace| {
+                CompletionCandidate::new(format!("{workspace}@"), CompletionKind::Workspace)
+            }));
+    Completion {
+        replace_start: token_start,
+        candidates: matching(candidates, token),
+    }
+}
+
+/// Completes `token`, which starts at byte offset `token_start`, after the parsed `previous`
+/// command-mode arguments.
+///
+/// `previous` excludes the optional leading `jj`.
+#[must_use]
+pub fn complete_command_arg(
+    previous: &[String],
+    token: &str,
+    token_start: usize,
+    sources: &CompletionSources,
+    entries: &[LogEntry],
+) -> Completion {
+    let command = command_path(previous);
+    let previous_arg = previous.last().map(String::as_str);
+
+    if let Some(flag) = previous_arg.filter(|arg| arg.starts_with('-'))
+        && let Some(kind) = flag_value_kind(&command, flag)
+    {
+        return value_completion(kind, token, token_start, sources, entries);
+    }
+
+    if token.starts_with('-') {
+        let flags = COMMAND_FLAGS
+            .iter()
+            .find(|(path, _)| *path == command)
+            .map(|(_, flags)| *flags)
+            .unwrap_or_default();
+        return Completion {
+            replace_start: token_start,
+            candidates: matching(
+                flags
+                    .iter()
+                    .chain(GLOBAL_FLAGS)
+                    .map(|flag| CompletionCandidate::new(*flag, CompletionKind::Flag)),
+                token,
+            ),
+        };
+    }
+
+    let positionals = previous.iter().filter(|arg| !arg.starts_with('-')).count();
+    if positionals == 0 {
+        return Completion {
+            replace_start: token_start,
+            candidates: matching(
+                SUBCOMMANDS
+                    .iter()
+                    .map(|command| CompletionCandidate::new(*command, CompletionKind::Subcommand)),
+                token,
+            ),
+        };
+    }
+    if positionals == 1
+        && let Some((_, nested)) = NESTED_SUBCOMMANDS
+            .iter()
+            .find(|(group, _)| Some(*group) == previous.first().map(String::as_str))
+    {
+        return Completion {
+            replace_start: token_start,
+            candidates: matching(
+                nested
+                    .iter()
+                    .map(|command| CompletionCandidate::new(*command, CompletionKind::Subcommand)),
+                token,
+            ),
+        };
+    }
+
+    positional_kind(&command).map_or_else(
+        || Completion {
+            replace_start: token_start,
+            candidates: Vec::new(),
+        },
+        |kind| value_completion(kind, token, token_start, sources, entries),
+    )
+}
+
+/// Value shapes that command arguments can take.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ValueKind {
+    Revset,
+    Bookmark,
+    Tag,
+    Remote,
+    Workspace,
+}
+
+fn value_completion(
+    kind: ValueKind,
+    token: &str,
+    token_start: usize,
+    sources: &CompletionSources,
+    entries: &[LogEntry],
+) -> Completion {
+    let names = |names: &[String], kind| {
+        matching(
+            names
+                .iter()
+                .map(|name| CompletionCandidate::new(name, kind)),
+            token,
+        )
+    };
+    let candidates = match kind {
+        ValueKind::Revset => {
+            let completion = complete_revset(token, sources, entries);
+            return Completion {
+                replace_start: token_start + completion.replace_start,
+                candidates: completion.candidates,
+            };
+        }
+        ValueKind::Bookmark => names(&sources.bookmarks, CompletionKind::Bookmark),
+        ValueKind::Tag => names(&sources.tags, CompletionKind::Tag),
+        ValueKind::Remote => names(&sources.remotes, CompletionKind::Remote),
+        ValueKind::Workspace => names(&sources.workspaces, CompletionKind::Workspace),
+    };
+    Completion {
+        replace_start: token_start,
+        candidates,
+    }
+}
+
+/// Returns the command path, such as `log` or `git push`, from parsed arguments.
+fn command_path(previous: &[String]) -> String {
+    let mut positionals = previous.iter().filter(|arg| !arg.starts_with('-'));
+    let Some(command) = positionals.next() else {
+        return String::new();
+    };
+    let nested = NESTED_SUBCOMMANDS
+        .iter()
+        .find(|(group, _)| group == command)
+        .and_then(|(_, nested)| {
+            positionals
+                .next()
+                .filter(|subcommand| nested.contains(&subcommand.as_str()))
+        });
+    nested.map_or_else(
+        || command.clone(),
+        |subcommand| format!("{command} {subcommand}"),
+    )
+}
+
+fn flag_value_kind(command: &str, flag: &str) -> Option<ValueKind> {
+    let git_ref_command = command.starts_with("git ");
+    match flag {
+        "--remote" => Some(ValueKind::Remote),
+        "-b" | "--bookmark" | "--branch" if git_ref_command => Some(ValueKind::Bookmark),
+        "-c" | "--change" if git_ref_command => Some(ValueKind::Revset),
+        "-b" | "--branch" if command == "rebase" => Some(ValueKind::Revset),
+        flag if REVSET_FLAGS.contains(&flag) => Some(ValueKind::Revset),
+        _ => None,
+    }
+}
+
+fn positional_kind(command: &str) -> Option<ValueKind> {
+    match command {
+        "bookmark delete" | "bookmark forget" | "bookmark move" | "bookmark rename"
+        | "bookmark set" | "bookmark track" | "bookmark untrack" => Some(ValueKind::Bookmark),
+        "tag delete" | "tag set" => Some(ValueKind::Tag),
+        "workspace forget" => Some(ValueKind::Workspace),
+        command if REVSET_POSITIONAL_COMMANDS.contains(&command) => Some(ValueKind::Revset),
+        _ => None,
+    }
+}
+
+fn change_id_candidates<'a>(
+    entries: &'a [LogEntry],
+    token: &'a str,
+) -> impl Iterator<Item = CompletionCandidate> + 'a {
+    entries
+        .iter()
+        .filter(|entry| !entry.change_id().is_empty())
+        .map(move |entry| {
+            let prefix_len = token.len().max(CHANGE_ID_PREFIX_LEN);
+            let prefix = entry
+                .change_id()
+                .get(..prefix_len)
+                .unwrap_or_else(|| entry.change_id());
+            CompletionCandidate::new(prefix, CompletionKind::ChangeId).with_detail(entry.summary())
+        })
+}
+
+/// Returns the text inserted for an alias: arguments are left for the user to type.
+fn alias_insert_text(alias: &str) -> String {
+    match alias.split_once('(') {
+        Some((name, rest)) if rest != ")" => format!("{name}("),
+        _ => alias.to_owned(),
+    }
+}
+
+/// Keeps candidates that extend `token`, dropping duplicate texts and capping the list.
+fn matching(
+    candidates: impl Iterator<Item = CompletionCandidate>,
+    token: &str,
+) -> Vec<CompletionCandidate> {
+    let mut seen = HashSet::new();
+    candidates
+        .filter(|candidate| candidate.text.starts_with(token))
+        .filter(|candidate| seen.insert(candidate.text.clone()))
+        .take(MAX_CANDIDATES)
+        .collect()
+}
+
+const fn is_revset_name_char(character: char) -> bool {
+    character.is_ascii_alphanumeric() || matches!(character, '_' | '-' | '.' | '/' | '@')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::io;
+    #[cfg(unix)]
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::Output;
+
+    use super::*;
+
+    fn sources() -> CompletionSources {
+        CompletionSources {
+            bookmarks: vec!["main".to_owned(), "feature-x".to_owned()],
+            tags: vec!["v1.0".to_owned()],
+            remotes: vec!["origin".to_owned(), "upstream".to_owned()],
+            workspaces: vec!["default".to_owned()],
+            revset_aliases: vec!["mine()".to_owned(), "stack(x)".to_owned(), "wip".to_owned()],
+        }
+    }
+
+    fn entries() -> Vec<LogEntry> {
+        vec![
+            LogEntry::new("mzvwutvlkqwt", "commit1", "first change"),
+            LogEntry::new("kkmpptxzrspx", "commit2", ""),
+        ]
+    }
+
+    fn texts(completion: &Completion) -> Vec<&str> {
+        completion
+            .candidates
+            .iter()
+            .map(|candidate| candidate.text.as_str())
+            .collect()
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| (*arg).to_owned()).collect()
+    }
+
+    #[test]
+    fn revset_completes_names_functions_aliases_and_change_ids() {
+        let completion = complete_revset("main | m", &sources(), &entries());
+
+        assert_eq!(completion.replace_start, 7);
+        assert_eq!(
+            texts(&completion),
+            ["main", "mzvwutvl", "mine()", "merges()", "mutable()"]
+        );
+        assert_eq!(
+            completion.candidates[1].detail.as_deref(),
+            Some("first change")
+        );
+        assert_eq!(
+            completion.apply("main | m", 0).as_deref(),
+            Some("main | main")
+        );
+    }
+
+    #[test]
+    fn revset_completes_remotes_after_at_and_ranges() {
+        let completion = complete_revset("main@up", &sources(), &entries());
+        assert_eq!(completion.replace_start, 5);
+        assert_eq!(texts(&completion), ["upstream"]);
+
+        let completion = complete_revset("trunk()..fea", &sources(), &entries());
+        assert_eq!(completion.replace_start, 9);
+        assert_eq!(texts(&completion), ["feature-x"]);
+    }
+
+    #[test]
+    fn revset_alias_insert_text_leaves_arguments_open() {
+        let completion = complete_revset("st", &sources(), &[]);
+
+        assert_eq!(texts(&completion), ["stack("]);
+        assert_eq!(completion.candidates[0].kind, CompletionKind::RevsetAlias);
+    }
+
+    #[test]
+    fn command_completes_subcommands_and_nested_groups() {
+        let completion = complete_command_arg(&[], "sq", 0, &sources(), &[]);
+        assert_eq!(texts(&completion), ["squash"]);
+
+        let completion = complete_command_arg(&args(&["bookmark"]), "s", 9, &sources(), &[]);
+        assert_eq!(texts(&completion), ["set"]);
+        assert_eq!(completion.replace_start, 9);
+    }
+
+    #[test]
+    fn command_completes_command_and_global_flags() {
+        let completion = complete_command_arg(&args(&["log"]), "--re", 4, &sources(), &[]);
+
+        assert_eq!(
+            texts(&completion),
+            ["--reversed", "--revisions", "--repository"]
+        );
+    }
+
+    #[test]
+    fn command_completes_flag_values_by_kind() {
+        let completion = complete_command_arg(&args(&["rebase", "-d"]), "ma", 10, &sources(), &[]);
+        assert_eq!(texts(&completion), ["main"]);
+        assert_eq!(completion.replace_start, 10);
+
+        let completion = complete_command_arg(
+            &args(&["git", "push", "--remote"]),
+            "o",
+            20,
+            &sources(),
+            &[],
+        );
+        assert_eq!(texts(&completion), ["origin"]);
+
+        let completion =
+            complete_command_arg(&args(&["git", "push", "-b"]), "f", 15, &sources(), &[]);
+        assert_eq!(texts(&completion), ["feature-x"]);
+    }
+
+    #[test]
+    fn command_completes_positional_revsets_and_names() {
+        let completion = complete_command_arg(&args(&["edit"]), "kk", 5, &sources(), &entries());
+        assert_eq!(texts(&completion), ["kkmpptxz"]);
+        assert_eq!(
+            completion.candidates[0].detail.as_deref(),
+            Some("(no description set)")
+        );
+
+        let completion =
+            complete_command_arg(&args(&["bookmark", "delete"]), "", 16, &sources(), &[]);
+        assert_eq!(texts(&completion), ["main", "feature-x"]);
+
+        let completion = complete_command_arg(&args(&["status"]), "", 7, &sources(), &[]);
+        assert!(completion.candidates.is_empty());
+    }
+
+    #[test]
+    fn alias_names_parse_from_config_list_lines() {
+        assert_eq!(
+            parse_revset_alias_name("revset-aliases.'mine()' = \"author(me)\""),
+            Some("mine()".to_owned())
+        );
+        assert_eq!(
+            parse_revset_alias_name("revset-aliases.wip = \"description(wip)\""),
+            Some("wip".to_owned())
+        );
+        assert_eq!(parse_revset_alias_name("ui.color = \"always\""), None);
+    }
+
+    #[test]
+    fn sources_load_each_query_best_effort() {
+        let mut runner = FakeRunner::new(vec![
+            success("main\nmain\nfeature\n"),
+            failure(),
+            success("origin https://example.test/repo.git\n"),
+            success("default\n"),
+            success("revset-aliases.'mine()' = \"author(me)\"\n"),
+        ]);
+
+        let sources = JjCompletion::default().load_sources_with_runner(&mut runner);
+
+        assert_eq!(
+            sources,
+            CompletionSources {
+                bookmarks: vec!["main".to_owned(), "feature".to_owned()],
+                tags: Vec::new(),
+                remotes: vec!["origin".to_owned()],
+                workspaces: vec!["default".to_owned()],
+                revset_aliases: vec!["mine()".to_owned()],
+            }
+        );
+        assert_eq!(runner.argv[0], ["bookmark", "list", "-T", NAME_TEMPLATE]);
+        assert_eq!(runner.argv[4], ["config", "list", "revset-aliases"]);
+        let spec = JjCompletion::default().spec_for(&["tag", "list"]);
+        assert!(
+            spec.process_argv()
+                .iter()
+                .any(|arg| arg == "--ignore-working-copy")
+        );
+    }
+
+    fn success(stdout: &str) -> Output {
+        Output {
+            status: exit_status(0),
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: Vec::new(),
+        }
+    }
+
+    fn failure() -> Output {
+        Output {
+            status: exit_status(1),
+            stdout: Vec::new(),
+            stderr: b"error: unrecognized subcommand".to_vec(),
+        }
+    }
+
+    #[cfg(unix)]
+    fn exit_status(code: i32) -> std::process::ExitStatus {
+        std::process::ExitStatus::from_raw(code << 8)
+    }
+
+    #[cfg(not(unix))]
+    fn exit_status(code: i32) -> std::process::ExitStatus {
+        std::process::Command::new(if cfg!(windows) { "cmd" } else { "sh" })
+            .args(if cfg!(windows) {
+                vec!["/C".into(), format!("exit {code}").into()]
+            } else {
+                vec!["-c".into(), format!("exit {code}").into()]
+            })
+            .status()
+            .unwrap()
+    }
+
+    struct FakeRunner {
+        results: VecDeque<io::Result<Output>>,
+        argv: Vec<Vec<String>>,
+    }
+
+    impl FakeRunner {
+        fn new(results: Vec<Output>) -> Self {
+            Self {
+                results: results.into_iter().map(Ok).collect(),
+                argv: Vec::new(),
+            }
+        }
+    }
+
+    impl JjCommandRunner for FakeRunner {
+        fn run(&mut self, spec: &JjCommandSpec) -> io::Result<Output> {
+            self.argv.push(
+                spec.argv()
+                    .iter()
+                    .map(|arg| arg.to_string_lossy().into_owned())
+                    .collect(),
+            );
+            self.results
+                .pop_front()
+                .unwrap_or_else(|| Err(io::Error::other("fake runner results already consumed")))
+        }
+    }
+}
@@ -0,0 +1,317 @@
+//! Static `jj` vocabulary used by completion.
+//!
+//! These tables cover the commands and flags `jk` users reach for most often. They are suggestions,
+//! not a parser: `jj` still validates whatever the user submits, so a missing flag only means one
+//! less completion candidate.
+
+/// Top-level `jj` subcommands.
+pub(super) const SUBCOMMANDS: &[&str] = &[
+    "abandon",
+    "absorb",
+    "bookmark",
+    "commit",
+    "config",
+    "describe",
+    "diff",
+    "diffedit",
+    "duplicate",
+    "edit",
+    "evolog",
+    "file",
+    "fix",
+    "git",
+    "help",
+    "interdiff",
+    "log",
+    "new",
+    "next",
+    "op",
+    "parallelize",
+    "prev",
+    "rebase",
+    "redo",
+    "resolve",
+    "restore",
+    "revert",
+    "root",
+    "show",
+    "sign",
+    "simplify-parents",
+    "sparse",
+    "split",
+    "squash",
+    "status",
+    "tag",
+    "undo",
+    "unsign",
+    "util",
+    "version",
+    "workspace",
+];
+
+/// Nested subcommands for command groups.
+pub(super) const NESTED_SUBCOMMANDS: &[(&str, &[&str])] = &[
+    (
+        "bookmark",
+        &[
+            "create", "delete", "forget", "list", "move", "rename", "set", "track", "untrack",
+        ],
+    ),
+    ("config", &["edit", "get", "list", "path", "set", "unset"]),
+    (
+        "file",
+        &["annotate", "chmod", "list", "show", "track", "untrack"],
+    ),
+    (
+        "git",
+        &[
+            "clone", "export", "fetch", "import", "init", "push", "remote", "root",
+        ],
+    ),
+    (
+        "op",
+        &["abandon", "diff", "log", "restore", "revert", "show"],
+    ),
+    ("tag", &["delete", "list", "set"]),
+    (
+        "workspace",
+        &["add", "forget", "list", "rename", "root", "update-stale"],
+    ),
+];
+
+/// Flags accepted by every `jj` command.
+pub(super) const GLOBAL_FLAGS: &[&str] = &[
+    "--at-operation",
+    "--color",
+    "--config",
+    "--config-file",
+    "--debug",
+    "--ignore-immutable",
+    "--ignore-working-copy",
+    "--no-pager",
+    "--quiet",
+    "--repository",
+];
+
+/// Command-specific flags keyed by the command path joined with spaces.
+pub(super) const COMMAND_FLAGS: &[(&str, &[&str])] = &[
+    ("abandon", &["--retain-bookmarks", "--restore-descendants"]),
+    ("bookmark create", &["--revision"]),
+    ("bookmark move", &["--allow-backwards", "--from", "--to"]),
+    ("bookmark set", &["--allow-backwards", "--revision"]),
+    (
+        "describe",
+        &["--edit", "--message", "--reset-author", "--stdin"],
+    ),
+    (
+        "diff",
+        &[
+            "--color-words",
+            "--context",
+            "--from",
+            "--git",
+            "--ignore-all-space",
+            "--ignore-space-change",
+            "--name-only",
+            "--revisions",
+            "--stat",
+            "--summary",
+            "--to",
+            "--tool",
+        ],
+    ),
+    (
+        "duplicate",
+        &["--destination", "--insert-after", "--insert-before"],
+    ),
+    (
+        "evolog",
+        &[
+            "--limit",
+            "--no-graph",
+            "--patch",
+            "--revision",
+            "--template",
+        ],
+    ),
+    ("git fetch", &["--all-remotes", "--branch", "--remote"]),
+    (
+        "git push",
+        &[
+            "--all",
+            "--allow-new",
+            "--bookmark",
+            "--change",
+            "--deleted",
+            "--dry-run",
+            "--remote",
+            "--revisions",
+            "--tracked",
+        ],
+    ),
+    (
+        "log",
+        &[
+            "--git",
+            "--limit",
+            "--no-graph",
+            "--patch",
+            "--reversed",
+            "--revisions",
+            "--stat",
+            "--summary",
+            "--template",
+        ],
+    ),
+    (
+        "new",
+        &[
+            "--insert-after",
+            "--insert-before",
+            "--message",
+            "--no-edit",
+        ],
+    ),
+    ("op diff", &["--from", "--operation", "--patch", "--to"]),
+    ("op log", &["--limit", "--no-graph", "--template"]),
+    ("op restore", &["--what"]),
+    ("op show", &["--patch"]),
+    (
+        "rebase",
+        &[
+            "--branch",
+            "--destination",
+            "--insert-after",
+            "--insert-before",
+            "--onto",
+            "--revisions",
+            "--skip-emptied",
+            "--source",
+        ],
+    ),
+    (
+        "restore",
+        &[
+            "--changes-in",
+            "--from",
+            "--interactive",
+            "--into",
+            "--restore-descendants",
+        ],
+    ),
+    (
+        "show",
+        &[
+            "--color-words",
+            "--git",
+            "--stat",
+            "--summary",
+            "--template",
+        ],
+    ),
+    (
+        "split",
+        &["--interactive", "--message", "--parallel", "--revision"],
+    ),
+    (
+        "squash",
+        &[
+            "--from",
+            "--interactive",
+            "--into",
+            "--keep-emptied",
+            "--message",
+            "--revision",
+            "--use-destination-message",
+        ],
+    ),
+];
+
+/// Flags whose value is a revset.
+pub(super) const REVSET_FLAGS: &[&str] = &[
+    "-A",
+    "-B",
+    "-d",
+    "-o",
+    "-r",
+    "-s",
+    "--changes-in",
+    "--destination",
+    "--from",
+    "--insert-after",
+    "--insert-before",
+    "--into",
+    "--onto",
+    "--revision",
+    "--revisions",
+    "--source",
+    "--to",
+];
+
+/// Commands whose positional arguments are revsets.
+pub(super) const REVSET_POSITIONAL_COMMANDS: &[&str] = &[
+    "abandon",
+    "describe",
+    "duplicate",
+    "edit",
+    "new",
+    "parallelize",
+    "revert",
+    "show",
+    "sign",
+    "simplify-parents",
+    "unsign",
+];
+
+/// Revset functions with the text inserted on completion.
+///
+/// Functions that take arguments insert only the opening parenthesis.
+pub(super) const REVSET_FUNCTIONS: &[&str] = &[
+    "all()",
+    "ancestors(",
+    "at_operation(",
+    "author(",
+    "author_date(",
+    "author_email(",
+    "author_name(",
+    "bookmarks(",
+    "builtin_immutable_heads()",
+    "children(",
+    "coalesce(",
+    "committer(",
+    "committer_date(",
+    "conflicts()",
+    "connected(",
+    "descendants(",
+    "description(",
+    "diff_contains(",
+    "empty()",
+    "exactly(",
+    "files(",
+    "first_ancestors(",
+    "fork_point(",
+    "git_head()",
+    "git_refs()",
+    "heads(",
+    "immutable()",
+    "immutable_heads()",
+    "latest(",
+    "merges()",
+    "mine()",
+    "mutable()",
+    "none()",
+    "parents(",
+    "present(",
+    "reachable(",
+    "remote_bookmarks(",
+    "root()",
+    "roots(",
+    "signed()",
+    "subject(",
+    "tags(",
+    "tracked_remote_bookmarks(",
+    "trunk()",
+    "untracked_remote_bookmarks(",
+    "visible_heads()",
+    "working_copies()",
+];
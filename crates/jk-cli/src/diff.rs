@@ -4,7 +4,10 @@ use std::path::PathBuf;
 #[cfg(test)]
 use std::process::Command;
 
-use jk_core::{DiffFileStat, DiffSnapshot, JjCommandSpec};
+use jk_core::{
+    ColorPolicy, DiffFileStat, DiffSnapshot, GlobalOptions, JjCommandSpec, OutputPolicy,
+    WorkingCopyPolicy,
+};
 use thiserror::Error;
 
 #[cfg(test)]
@@ -12,6 +15,11 @@ use crate::command::build_jj_command;
 use crate::command::{JjCommandRunner, SystemJjCommandRunner};
 
 const DIFF_COMMAND: &str = "diff";
+const INTERDIFF_COMMAND: &str = "interdiff";
+/// Config table holding external diff and merge tools.
+const MERGE_TOOLS_KEY: &str = "merge-tools";
+/// Template printing one change id per line.
+const CHANGE_ID_TEMPLATE: &str = "change_id ++ \"\\n\"";
 
 /// Rendered `jj diff` output shape.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -31,6 +39,8 @@ pub enum DiffFormat {
     Git,
     /// Render `jj diff --color-words`.
     ColorWords,
+    /// Load `jj diff --git` for `jk` to lay out in old/new columns.
+    SideBySide,
 }
 
 impl DiffFormat {
@@ -43,7 +53,7 @@ impl DiffFormat {
             Self::Stat => Some("--stat"),
             Self::Types => Some("--types"),
             Self::NameOnly => Some("--name-only"),
-            Self::Git => Some("--git"),
+            Self::Git | Self::SideBySide => Some("--git"),
             Self::ColorWords => Some("--color-words"),
         }
     }
@@ -59,6 +69,124 @@ impl DiffFormat {
             Self::NameOnly => "name only",
             Self::Git => "git",
             Self::ColorWords => "color words",
+            Self::SideBySide => "side by side",
+        }
+    }
+}
+
+/// Whitespace handling passed to `jj diff`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DiffWhitespace {
+    /// Compare whitespace exactly.
+    #[default]
+    Exact,
+    /// Render `jj diff --ignore-all-space`.
+    IgnoreAll,
+    /// Render `jj diff --ignore-space-change`.
+    IgnoreChange,
+}
+
+impl DiffWhitespace {
+    /// Returns the `jj diff` flag for this mode, when exact comparison needs no flag.
+    #[must_use]
+    pub const fn flag(self) -> Option<&'static str> {
+        match self {
+            Self::Exact => None,
+            Self::IgnoreAll => Some("--ignore-all-space"),
+            Self::IgnoreChange => Some("--ignore-space-change"),
+        }
+    }
+
+    /// Returns a compact user-visible label for this mode.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::IgnoreAll => "ignore all",
+            Self::IgnoreChange => "ignore changes",
+        }
+    }
+
+    /// Returns the next mode in view-option order.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Exact => Self::IgnoreAll,
+            Self::IgnoreAll => Self::IgnoreChange,
+            Self::IgnoreChange => Self::Exact,
+        }
+    }
+}
+
+/// Output options shared by every [`DiffQuery`] shape.
+///
+/// An external `tool` replaces jj's own rendering, so the format flag is left out while one is
+/// set; context and whitespace flags still apply.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DiffOptions {
+    /// Rendered output shape.
+    pub format: DiffFormat,
+    /// Lines of context around each change, or jj's default when `None`.
+    pub context: Option<usize>,
+    /// Whitespace handling.
+    pub whitespace: DiffWhitespace,
+    /// External diff formatter from `[merge-tools]`, passed as `--tool`.
+    pub tool: Option<String>,
+}
+
+impl DiffOptions {
+    /// Returns these options with a different rendered output format.
+    #[must_use]
+    pub const fn with_format(mut self, format: DiffFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Returns these options with a different context line count.
+    #[must_use]
+    pub const fn with_context(mut self, context: Option<usize>) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Returns these options with a different whitespace mode.
+    #[must_use]
+    pub const fn with_whitespace(mut self, whitespace: DiffWhitespace) -> Self {
+        self.whitespace = whitespace;
+        self
+    }
+
+    /// Returns these options with a different external formatter.
+    #[must_use]
+    pub fn with_tool(mut self, tool: Option<String>) -> Self {
+        self.tool = tool;
+        self
+    }
+
+    /// Returns the `jj diff` arguments for these options, in jj's help order.
+    #[must_use]
+    pub fn argv(&self) -> Vec<String> {
+        let mut argv = Vec::new();
+        if let Some(tool) = &self.tool {
+            argv.extend(["--tool".to_owned(), tool.clone()]);
+        } else if let Some(flag) = self.format.flag() {
+            argv.push(flag.to_owned());
+        }
+        if let Some(context) = self.context {
+            argv.extend(["--context".to_owned(), context.to_string()]);
+        }
+        if let Some(flag) = self.whitespace.flag() {
+            argv.push(flag.to_owned());
+        }
+        argv
+    }
+}
+
+impl From<DiffFormat> for DiffOptions {
+    fn from(format: DiffFormat) -> Self {
+        Self {
+            format,
+            ..Self::default()
         }
     }
 }
@@ -70,8 +198,8 @@ pub enum DiffQuery {
     Revision {
         /// Revision to diff against its parent.
         rev: String,
-        /// Rendered output shape.
-        format: DiffFormat,
+        /// Output options.
+        options: DiffOptions,
     },
     /// Diff two revisions.
     FromTo {
@@ -79,41 +207,76 @@ pub enum DiffQuery {
         from: String,
         /// Ending revision.
         to: String,
-        /// Rendered output shape.
-        format: DiffFormat,
+        /// Output options.
+        options: DiffOptions,
+    },
+    /// Compare the changes two revisions make, as `jj interdiff` does for two versions of a patch.
+    Interdiff {
+        /// Earlier version.
+        from: String,
+        /// Later version.
+        to: String,
+        /// Output options.
+        options: DiffOptions,
     },
 }
 
 impl DiffQuery {
-    /// Returns the query format.
+    /// Returns the query output options.
     #[must_use]
-    pub const fn format(&self) -> DiffFormat {
+    pub const fn options(&self) -> &DiffOptions {
         match self {
-            Self::Revision { format, .. } | Self::FromTo { format, .. } => *format,
+            Self::Revision { options, .. }
+            | Self::FromTo { options, .. }
+            | Self::Interdiff { options, .. } => options,
         }
     }
 
+    /// Returns the query format.
+    #[must_use]
+    pub const fn format(&self) -> DiffFormat {
+        self.options().format
+    }
+
     /// Returns a compact label for places that still expect one diff target.
     #[must_use]
     pub fn target_label(&self) -> String {
         match self {
             Self::Revision { rev, .. } => rev.clone(),
-            Self::FromTo { from, to, .. } => format!("{from}..{to}"),
+            Self::FromTo { from, to, .. } | Self::Interdiff { from, to, .. } => {
+                format!("{from}..{to}")
+            }
+        }
+    }
+
+    /// Returns the compared revisions for queries that span two revisions.
+    #[must_use]
+    pub const fn range(&self) -> Option<(&str, &str)> {
+        match self {
+            Self::Revision { .. } => None,
+            Self::FromTo { from, to, .. } | Self::Interdiff { from, to, .. } => {
+                Some((from.as_str(), to.as_str()))
+            }
         }
     }
 
-    /// Returns this query with a different rendered output format.
+    /// Returns this query with different output options and the same target.
     #[must_use]
-    pub fn with_format(&self, format: DiffFormat) -> Self {
+    pub fn with_options(&self, options: DiffOptions) -> Self {
         match self {
             Self::Revision { rev, .. } => Self::Revision {
                 rev: rev.clone(),
-                format,
+                options,
             },
             Self::FromTo { from, to, .. } => Self::FromTo {
                 from: from.clone(),
                 to: to.clone(),
-                format,
+                options,
+            },
+            Self::Interdiff { from, to, .. } => Self::Interdiff {
+                from: from.clone(),
+                to: to.clone(),
+                options,
             },
         }
     }
@@ -127,6 +290,7 @@ impl DiffQuery {
 #[derive(Clone, Debug, Default)]
 pub struct JjDiff {
     repository: Option<PathBuf>,
+    working_copy: Option<WorkingCopyPolicy>,
 }
 
 impl JjDiff {
@@ -137,6 +301,16 @@ impl JjDiff {
         self
     }
 
+    /// Sets how `jj` treats the working copy; unset uses jj's default snapshot.
+    ///
+    /// [`WorkingCopyPolicy::Ignore`] reads the last snapshot, which lets views opened right after a
+    /// log refresh replay cached output.
+    #[must_use]
+    pub const fn with_working_copy(mut self, working_copy: WorkingCopyPolicy) -> Self {
+        self.working_copy = Some(working_copy);
+        self
+    }
+
     /// Loads the rendered diff for `query`.
     ///
     /// # Errors
@@ -159,17 +333,30 @@ impl JjDiff {
         let spec = self.spec_for(query);
         let rendered = Self::run(runner, &spec)?;
         let stats_output;
-        let stats = if query.format() == DiffFormat::Stat {
+        let stats = if query.format() == DiffFormat::Stat && query.options().tool.is_none() {
             &rendered
         } else {
-            stats_output = Self::run(runner, &self.spec_for(&query.with_format(DiffFormat::Stat)))?;
+            let stats_options = query
+                .options()
+                .clone()
+                .with_format(DiffFormat::Stat)
+                .with_tool(None);
+            stats_output = Self::run(runner, &self.spec_for(&query.with_options(stats_options)))?;
             &stats_output
         };
         let file_stats = parse_stats_lines(stats);
 
-        Ok(DiffSnapshot::new(query.target_label(), rendered)
-            .with_file_stats(file_stats)
-            .with_title(spec.title()))
+        let snapshot =
+            DiffSnapshot::new(query.target_label(), rendered).with_file_stats(file_stats);
+        Ok(match query {
+            DiffQuery::Revision { .. } => snapshot.with_title(spec.title()),
+            DiffQuery::FromTo { from, to, .. } => snapshot
+                .with_title(format!("{from} → {to}  {}", spec.title()))
+                .with_range(from, to),
+            DiffQuery::Interdiff { from, to, .. } => snapshot
+                .with_title(format!("interdiff {from} → {to}  {}", spec.title()))
+                .with_range(from, to),
+        })
     }
 
     /// Loads the rendered diff for `change_id`.
@@ -182,29 +369,132 @@ impl JjDiff {
     pub fn load(&self, change_id: &str) -> Result<DiffSnapshot, JjDiffError> {
         self.load_query(&DiffQuery::Revision {
             rev: change_id.to_owned(),
-            format: DiffFormat::Patch,
+            options: DiffOptions::default(),
         })
     }
 
+    /// Loads `query` as a plain git patch, whatever format the view renders it in.
+    ///
+    /// The format and tool options are replaced with `--git`; context and whitespace options are
+    /// kept so the patch matches what the user is reviewing. Color is stripped for pasting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `jj` cannot be executed or exits unsuccessfully.
+    pub fn load_patch_with_runner(
+        &self,
+        query: &DiffQuery,
+        runner: &mut impl JjCommandRunner,
+    ) -> Result<String, JjDiffError> {
+        let options = query
+            .options()
+            .clone()
+            .with_format(DiffFormat::Git)
+            .with_tool(None);
+        let patch = Self::run(runner, &self.spec_for(&query.with_options(options)))?;
+        Ok(strip_ansi(&patch))
+    }
+
     /// Returns the command spec for `query`.
     #[must_use]
     pub fn spec_for(&self, query: &DiffQuery) -> JjCommandSpec {
+        let options = query.options().argv();
+        let options = options.iter().map(String::as_str);
         match query {
-            DiffQuery::Revision { rev, format } => {
-                let mut argv = vec![DIFF_COMMAND, "-r", rev.as_str()];
-                if let Some(flag) = format.flag() {
-                    argv.push(flag);
-                }
-                self.spec(argv)
-            }
-            DiffQuery::FromTo { from, to, format } => {
-                let mut argv = vec![DIFF_COMMAND, "--from", from.as_str(), "--to", to.as_str()];
-                if let Some(flag) = format.flag() {
-                    argv.push(flag);
-                }
-                self.spec(argv)
+            DiffQuery::Revision { rev, .. } => self.spec(
+                [DIFF_COMMAND, "-r", rev.as_str()]
+                    .into_iter()
+                    .chain(options),
+            ),
+            DiffQuery::FromTo { from, to, .. } => self.spec(
+                [DIFF_COMMAND, "--from", from.as_str(), "--to", to.as_str()]
+                    .into_iter()
+                    .chain(options),
+            ),
+            DiffQuery::Interdiff { from, to, .. } => self.spec(
+                [
+                    INTERDIFF_COMMAND,
+                    "--from",
+                    from.as_str(),
+                    "--to",
+                    to.as_str(),
+                ]
+                .into_iter()
+                .chain(options),
+            ),
+        }
+    }
+
+    /// Returns the read-only `jj config list merge-tools` spec behind the tool options.
+    #[must_use]
+    pub fn diff_tools_spec(&self) -> JjCommandSpec {
+        self.plain_spec(
+            ["config", "list", MERGE_TOOLS_KEY],
+            format!("jj config list {MERGE_TOOLS_KEY}"),
+        )
+    }
+
+    /// Returns the read-only `jj log` spec listing the change ids `revset` resolves to.
+    #[must_use]
+    pub fn change_ids_spec(&self, revset: &str) -> JjCommandSpec {
+        self.plain_spec(
+            ["log", "--no-graph", "-r", revset, "-T", CHANGE_ID_TEMPLATE],
+            format!("jj log -r {revset} -T change_id"),
+        )
+    }
+
+    /// Resolves the revisions `query` compares to change ids and returns them as one stable key.
+    ///
+    /// Revset labels such as `@` or `main..@` point elsewhere as the repository moves, while
+    /// change ids keep naming the same changes across rewrites. Each end of a range is resolved on
+    /// its own and joined with `..`; revsets matching several changes join their ids with `,`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `jj` cannot be executed, exits unsuccessfully, or a revset is empty.
+    pub fn load_change_key_with_runner(
+        &self,
+        query: &DiffQuery,
+        runner: &mut impl JjCommandRunner,
+    ) -> Result<String, JjDiffError> {
+        let mut resolve = |revset: &str| {
+            let stdout = Self::run(runner, &self.change_ids_spec(revset))?;
+            let ids = stdout
+                .lines()
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .collect::<Vec<_>>();
+            if ids.is_empty() {
+                Err(JjDiffError::CommandFailed(format!(
+                    "revset `{revset}` matched no changes"
+                )))
+            } else {
+                Ok(ids.join(","))
             }
+        };
+        match query.range() {
+            Some((from, to)) => Ok(format!("{}..{}", resolve(from)?, resolve(to)?)),
+            None => resolve(&query.target_label()),
+        }
+    }
+
+    /// Loads the names of configured tools that can format a diff, in config order.
+    ///
+    /// A tool counts when it sets `diff-args`. jj exits unsuccessfully when the table does not
+    /// exist, so a failed exit means "no tools" rather than an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `jj` cannot be executed.
+    pub fn load_diff_tools_with_runner(
+        &self,
+        runner: &mut impl JjCommandRunner,
+    ) -> Result<Vec<String>, JjDiffError> {
+        let output = runner.run(&self.diff_tools_spec())?;
+        if !output.status.success() {
+            return Ok(Vec::new());
         }
+        Ok(parse_diff_tools(&String::from_utf8_lossy(&output.stdout)))
     }
 
     fn run(runner: &mut impl JjCommandRunner, spec: &JjCommandSpec) -> Result<String, JjDiffError> {
@@ -221,7 +511,7 @@ impl JjDiff {
     fn diff_command(&self, change_id: &str) -> Command {
         build_jj_command(&self.spec_for(&DiffQuery::Revision {
             rev: change_id.to_owned(),
-            format: DiffFormat::Patch,
+            options: DiffOptions::default(),
         }))
     }
 
@@ -229,12 +519,38 @@ impl JjDiff {
     fn stats_command(&self, change_id: &str) -> Command {
         build_jj_command(&self.spec_for(&DiffQuery::Revision {
             rev: change_id.to_owned(),
-            format: DiffFormat::Stat,
+            options: DiffFormat::Stat.into(),
         }))
     }
 
+    /// Returns a colorless read-only spec that leaves the working copy alone.
+    fn plain_spec<'a>(
+        &self,
+        argv: impl IntoIterator<Item = &'a str>,
+        title: String,
+    ) -> JjCommandSpec {
+        let global_options = GlobalOptions::default()
+            .with_working_copy(WorkingCopyPolicy::Ignore)
+            .with_output(OutputPolicy {
+                color: ColorPolicy::Never,
+                ..OutputPolicy::default()
+            });
+        let spec = JjCommandSpec::render_read_only(argv)
+            .with_global_options(global_options)
+            .with_title(title);
+        if let Some(repository) = &self.repository {
+            spec.with_repository(repository)
+        } else {
+            spec
+        }
+    }
+
     fn spec<'a>(&self, argv: impl IntoIterator<Item = &'a str>) -> JjCommandSpec {
-        let spec = JjCommandSpec::render_read_only(argv);
+        let mut spec = JjCommandSpec::render_read_only(argv);
+        if let Some(working_copy) = self.working_copy {
+            spec =
+                spec.with_global_options(GlobalOptions::default().with_working_copy(working_copy));
+        }
         if let Some(repository) = &self.repository {
             spec.with_repository(repository)
         } else {
@@ -243,6 +559,28 @@ impl JjDiff {
     }
 }
 
+/// Parses `jj config list merge-tools` lines such as `merge-tools.difft.diff-args = [...]`.
+fn parse_diff_tools(stdout: &str) -> Vec<String> {
+    let mut tools = Vec::new();
+    for line in stdout.lines() {
+        let Some((key, _)) = line.split_once(" = ") else {
+            continue;
+        };
+        let Some((name, "diff-args")) = key
+            .strip_prefix(MERGE_TOOLS_KEY)
+            .and_then(|key| key.strip_prefix('.'))
+            .and_then(|key| key.rsplit_once('.'))
+        else {
+            continue;
+        };
+        let name = name.trim_matches(|c| c == '\'' || c == '"');
+        if !name.is_empty() && !tools.iter().any(|tool| tool == name) {
+            tools.push(name.to_owned());
+        }
+    }
+    tools
+}
+
 /// Parses per-file diff stats from jj's rendered `--stat` rows.
 fn parse_stats_lines(stdout: &str) -> Vec<DiffFileStat> {
     let mut stats = Vec::new();
@@ -321,6 +659,8 @@ pub enum JjDiffError {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
+
     use super::*;
 
     #[test]
@@ -346,7 +686,7 @@ mod tests {
     fn revision_query_builds_targeted_jj_diff_spec() {
         let spec = JjDiff::default().spec_for(&DiffQuery::Revision {
             rev: "abc123".to_owned(),
-            format: DiffFormat::Patch,
+            options: DiffOptions::default(),
         });
 
         let argv = spec
@@ -384,12 +724,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ignoring_the_working_copy_keeps_the_repository() {
+        let spec = JjDiff::default()
+            .with_repository("/tmp/repo")
+            .with_working_copy(WorkingCopyPolicy::Ignore)
+            .spec_for(&DiffQuery::Revision {
+                rev: "abc123".to_owned(),
+                options: DiffOptions::default(),
+            });
+
+        assert!(!spec.global_options().snapshots_working_copy());
+        assert_eq!(spec.repository(), Some(Path::new("/tmp/repo")));
+        assert!(
+            spec.process_argv()
+                .iter()
+                .any(|arg| arg == "--ignore-working-copy")
+        );
+    }
+
     #[test]
     fn from_to_stat_query_builds_canonical_spec() {
         let spec = JjDiff::default().spec_for(&DiffQuery::FromTo {
             from: "main".to_owned(),
             to: "@".to_owned(),
-            format: DiffFormat::Stat,
+            options: DiffFormat::Stat.into(),
         });
 
         let argv = spec
@@ -402,6 +761,28 @@ mod tests {
         assert_eq!(spec.title(), "jj diff --from main --to @ --stat");
     }
 
+    #[test]
+    fn interdiff_query_keeps_both_ends_and_options() {
+        let query = DiffQuery::Interdiff {
+            from: "abc".to_owned(),
+            to: "def".to_owned(),
+            options: DiffFormat::Git.into(),
+        };
+
+        let spec = JjDiff::default().spec_for(&query);
+
+        assert_eq!(spec.title(), "jj interdiff --from abc --to def --git");
+        assert_eq!(query.range(), Some(("abc", "def")));
+        assert_eq!(
+            query.with_options(DiffOptions::default()),
+            DiffQuery::Interdiff {
+                from: "abc".to_owned(),
+                to: "def".to_owned(),
+                options: DiffOptions::default(),
+            }
+        );
+    }
+
     #[test]
     fn revision_query_builds_supported_format_specs() {
         for (format, flag) in [
@@ -410,10 +791,11 @@ mod tests {
             (DiffFormat::NameOnly, "--name-only"),
             (DiffFormat::Git, "--git"),
             (DiffFormat::ColorWords, "--color-words"),
+            (DiffFormat::SideBySide, "--git"),
         ] {
             let spec = JjDiff::default().spec_for(&DiffQuery::Revision {
                 rev: "abc123".to_owned(),
-                format,
+                options: format.into(),
             });
             let argv = spec
                 .argv()
@@ -426,6 +808,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn options_keep_their_flags_across_query_shapes() {
+        let options = DiffOptions::from(DiffFormat::Git)
+            .with_context(Some(1))
+            .with_whitespace(DiffWhitespace::IgnoreChange);
+        let revision = DiffQuery::Revision {
+            rev: "abc123".to_owned(),
+            options: options.clone(),
+        };
+        let from_to = DiffQuery::FromTo {
+            from: "main".to_owned(),
+            to: "@".to_owned(),
+            options: DiffOptions::default(),
+        }
+        .with_options(options.with_tool(Some("difft".to_owned())));
+
+        assert_eq!(
+            JjDiff::default().spec_for(&revision).title(),
+            "jj diff -r abc123 --git --context 1 --ignore-space-change"
+        );
+        assert_eq!(
+            JjDiff::default().spec_for(&from_to).title(),
+            "jj diff --from main --to @ --tool difft --context 1 --ignore-space-change"
+        );
+        assert_eq!(from_to.format(), DiffFormat::Git);
+    }
+
+    #[test]
+    fn change_ids_spec_reads_plain_ids_without_a_snapshot() {
+        let spec = JjDiff::default()
+            .with_repository("/tmp/repo")
+            .change_ids_spec("main..@");
+        let argv = spec
+            .process_argv()
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(spec.title(), "jj log -r main..@ -T change_id");
+        assert_eq!(spec.repository(), Some(Path::new("/tmp/repo")));
+        assert!(argv.contains(&"--ignore-working-copy".to_owned()));
+        assert!(argv.windows(2).any(|pair| pair == ["--color", "never"]));
+        assert!(argv.ends_with(&[
+            "log".to_owned(),
+            "--no-graph".to_owned(),
+            "-r".to_owned(),
+            "main..@".to_owned(),
+            "-T".to_owned(),
+            CHANGE_ID_TEMPLATE.to_owned(),
+        ]));
+    }
+
+    #[test]
+    fn parses_configured_diff_tools_once_in_config_order() {
+        let output = concat!(
+            "merge-tools.difft.program = \"difft\"\n",
+            "merge-tools.difft.diff-args = [\"--color=always\", \"$left\", \"$right\"]\n",
+            "merge-tools.meld.merge-args = [\"$left\", \"$base\", \"$right\"]\n",
+            "merge-tools.'delta side'.diff-args = [\"$left\", \"$right\"]\n",
+        );
+
+        assert_eq!(parse_diff_tools(output), ["difft", "delta side"]);
+    }
+
     #[test]
     fn stats_command_uses_jj_diff_stat_output() {
         let command = JjDiff::default().stats_command("abc123");
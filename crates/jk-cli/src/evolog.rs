@@ -2,7 +2,7 @@
 
 use std::path::PathBuf;
 
-use jk_core::{InspectionSnapshot, JjCommandSpec};
+use jk_core::{GlobalOptions, InspectionSnapshot, JjCommandSpec, WorkingCopyPolicy};
 use thiserror::Error;
 
 use crate::command::{JjCommandRunner, SystemJjCommandRunner};
@@ -45,6 +45,7 @@ impl From<String> for EvologQuery {
 #[derive(Clone, Debug, Default)]
 pub struct JjEvolog {
     repository: Option<PathBuf>,
+    working_copy: Option<WorkingCopyPolicy>,
 }
 
 impl JjEvolog {
@@ -55,6 +56,16 @@ impl JjEvolog {
         self
     }
 
+    /// Sets how `jj` treats the working copy; unset uses jj's default snapshot.
+    ///
+    /// [`WorkingCopyPolicy::Ignore`] reads the last snapshot, which lets views opened right after a
+    /// log refresh replay cached output.
+    #[must_use]
+    pub const fn with_working_copy(mut self, working_copy: WorkingCopyPolicy) -> Self {
+        self.working_copy = Some(working_copy);
+        self
+    }
+
     /// Loads the rendered evolog output for `query`.
     ///
     /// # Errors
@@ -82,7 +93,11 @@ impl JjEvolog {
     /// Returns the command spec for `query`.
     #[must_use]
     pub fn spec_for(&self, query: &EvologQuery) -> JjCommandSpec {
-        let spec = JjCommandSpec::render_read_only([EVOLOG_COMMAND, "-r", query.rev()]);
+        let mut spec = JjCommandSpec::render_read_only([EVOLOG_COMMAND, "-r", query.rev()]);
+        if let Some(working_copy) = self.working_copy {
+            spec =
+                spec.with_global_options(GlobalOptions::default().with_working_copy(working_copy));
+        }
         if let Some(repository) = &self.repository {
             spec.with_repository(repository)
         } else {
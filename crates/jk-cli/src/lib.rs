@@ -10,9 +10,12 @@
 //! rendered-output-first boundary for selected-change inspection. This is a temporary integration
 //! boundary until `jj-cli` / `jj-lib` can provide both pieces without parsing command output.
 
+mod cache;
 mod command;
+mod store;
 
 pub mod abandon;
+pub mod completion;
 pub mod describe;
 pub mod diff;
 pub mod edit;
@@ -20,23 +23,39 @@ pub mod evolog;
 pub mod log;
 pub mod new;
 pub mod operation;
+pub mod rebase;
 pub mod recovery;
+pub mod restore;
 pub mod show;
+pub mod squash;
 pub mod status;
 pub mod workspaces;
 
 pub use abandon::{AbandonQuery, JjAbandon};
-pub use command::{JjCommandRunner, RecordingJjCommandRunner, SystemJjCommandRunner};
+pub use cache::{CachingJjCommandRunner, JjOutputCache};
+pub use command::{
+    CancellableJjCommandRunner, JjCommandRunner, RecordingJjCommandRunner, SystemJjCommandRunner,
+};
+pub use completion::{
+    Completion, CompletionCandidate, CompletionKind, CompletionSources, JjCompletion,
+    complete_command_arg, complete_revset,
+};
 pub use describe::{DescribeQuery, JjDescribe};
-pub use diff::{DiffFormat, DiffQuery, JjDiff, JjDiffError};
+pub use diff::{DiffFormat, DiffOptions, DiffQuery, DiffWhitespace, JjDiff, JjDiffError};
 pub use edit::{EditQuery, JjEdit};
 pub use evolog::{EvologQuery, JjEvolog, JjEvologError};
-pub use log::{JjLog, JjLogCommand, JjLogError, LogTemplateSelection};
+pub use log::{
+    JjLog, JjLogCommand, JjLogError, LogTemplateSelection, RevisionCandidate, RevsetPreset,
+};
 pub use new::{JjNew, NewQuery};
 pub use operation::{JjOperation, JjOperationError, OperationQuery};
+pub use rebase::{JjRebase, RebaseQuery};
 pub use recovery::{JjRecovery, RecoveryCommand};
+pub use restore::{JjRestore, RestoreQuery};
 pub use show::{JjShow, JjShowError, ShowQuery};
+pub use squash::{JjSquash, SquashQuery};
 pub use status::{JjStatus, JjStatusError, StatusQuery};
+pub use store::repository_store;
 pub use workspaces::{
     JjWorkspaces, JjWorkspacesError, WorkspaceInspectionQuery, WorkspaceListParseError,
     WorkspaceListSnapshot, WorkspaceSummary,
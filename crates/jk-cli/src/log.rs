@@ -4,18 +4,28 @@ use std::path::PathBuf;
 #[cfg(test)]
 use std::process::Command;
 
-use jk_core::{GlobalOptions, JjCommandSpec, LogSnapshot, WorkingCopyPolicy};
+use jk_core::{
+    ColorPolicy, GlobalOptions, JjCommandSpec, LogEntry, LogSnapshot, OutputPolicy,
+    WorkingCopyPolicy,
+};
 use thiserror::Error;
 
 #[cfg(test)]
 use crate::command::build_jj_command;
 use crate::command::{JjCommandRunner, SystemJjCommandRunner};
 
+mod inline;
+mod presets;
 mod rendered;
 mod semantic;
 
+use inline::{inline_log_template, split_inline_records};
+pub use presets::RevsetPreset;
+use presets::{REVSET_PRESETS_KEY, parse_revset_presets};
 use rendered::assign_rendered_lines;
-use semantic::{LOG_TEMPLATE, parse_log_json_lines};
+pub(crate) use rendered::commit_row_lines;
+pub use semantic::RevisionCandidate;
+use semantic::{FINDER_TEMPLATE, LOG_TEMPLATE, parse_finder_lines, parse_log_json_lines};
 
 const LOG_COMMAND: &str = "log";
 const COMFORTABLE_LOG_TEMPLATE: &str = "builtin_log_comfortable";
@@ -25,13 +35,21 @@ const DETAILED_LOG_TEMPLATE: &str = "builtin_log_detailed";
 const ONELINE_LOG_TEMPLATE: &str = "builtin_log_oneline";
 const REDACTED_LOG_TEMPLATE: &str = "builtin_log_redacted";
 const TEMPLATE_TITLE_LIMIT: usize = 48;
+/// What jj prints when it rejects a `-T` template, such as the inline wrapper.
+const TEMPLATE_ERROR: &str = "Failed to parse template";
+const FINDER_REVSET: &str = "all()";
+const LOG_REVSET_KEY: &str = "revsets.log";
+/// jj's built-in `revsets.log`, for when the configured value cannot be read.
+const BUILTIN_LOG_REVSET: &str =
+    "present(@) | ancestors(immutable_heads().., 2) | present(trunk())";
 
 /// Loads a log-like view from the local `jj` command.
 ///
 /// The configured-default view intentionally invokes bare `jj` so jj owns its configured default
-/// command, revset, graph, and template. A second semantic pass consumes newline-delimited JSON
-/// emitted by jj's own template engine. That second pass is a narrow adapter while the direct
-/// `jj-cli`/`jj-lib` integration contract is still being proved.
+/// command, revset, graph, and template. Semantic records come from jj's own template engine: hidden
+/// inside the rendered rows when `jk` knows the template text, or from a second newline-delimited
+/// JSON pass when jj's configured template is in use. That side channel is a narrow adapter while
+/// the direct `jj-cli`/`jj-lib` integration contract is still being proved.
 ///
 /// This bridge exists because the reusable jj layers do not currently expose the exact contract
 /// `jk` needs. `jj-lib` owns repository and revset machinery, but not the configured CLI log view.
@@ -49,9 +67,13 @@ pub struct JjLog {
     repository: Option<PathBuf>,
     command: JjLogCommand,
     limit: Option<usize>,
+    page_size: Option<usize>,
+    pages: usize,
     template: LogTemplateSelection,
     custom_template: Option<String>,
     revset: Option<String>,
+    revset_preset: Option<String>,
+    revset_presets: Vec<RevsetPreset>,
 }
 
 impl Default for JjLog {
@@ -60,9 +82,13 @@ impl Default for JjLog {
             repository: None,
             command: JjLogCommand::ConfiguredDefault,
             limit: None,
+            page_size: None,
+            pages: 1,
             template: LogTemplateSelection::Configured,
             custom_template: None,
             revset: None,
+            revset_preset: None,
+            revset_presets: Vec::new(),
         }
     }
 }
@@ -129,6 +155,33 @@ impl JjLog {
         self
     }
 
+    /// Loads the log in pages of `page_size` entries when no explicit limit is set.
+    ///
+    /// Each page reruns `jj` with a larger `-n` rather than offsetting, because jj has no offset
+    /// option and graph rows only line up when rendered in one pass.
+    #[must_use]
+    pub const fn with_page_size(mut self, page_size: Option<usize>) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Extends the next load by one page.
+    #[must_use]
+    pub const fn with_next_page(mut self) -> Self {
+        self.pages = self.pages.saturating_add(1);
+        self
+    }
+
+    /// Returns the `-n` passed to `jj`: the explicit limit, or the size of the loaded pages.
+    #[must_use]
+    pub const fn effective_limit(&self) -> Option<usize> {
+        match (self.limit, self.page_size) {
+            (Some(limit), _) => Some(limit),
+            (None, Some(page_size)) => Some(page_size.saturating_mul(self.pages)),
+            (None, None) => None,
+        }
+    }
+
     /// Sets the rendered log template selection.
     #[must_use]
     pub fn with_template(mut self, template: LogTemplateSelection) -> Self {
@@ -174,15 +227,135 @@ impl JjLog {
     pub fn with_revset(mut self, revset: impl Into<String>) -> Self {
         self.command = JjLogCommand::Log;
         self.revset = Some(revset.into());
+        self.revset_preset = None;
+        self.pages = 1;
+        self
+    }
+
+    /// Clears the revset so `jj log` falls back to the configured `revsets.log` default.
+    #[must_use]
+    pub fn without_revset(mut self) -> Self {
+        self.revset = None;
+        self.revset_preset = None;
+        self.pages = 1;
+        self
+    }
+
+    /// Returns the revset passed to `jj log -r`, if any.
+    #[must_use]
+    pub fn revset(&self) -> Option<&str> {
+        self.revset.as_deref()
+    }
+
+    /// Sets the revset from a named preset; the preset name is shown in the log title.
+    #[must_use]
+    pub fn with_revset_preset(self, preset: &RevsetPreset) -> Self {
+        let mut source = self.with_revset(preset.revset());
+        source.revset_preset = Some(preset.name().to_owned());
+        source
+    }
+
+    /// Returns the name of the preset that set the current revset, if any.
+    #[must_use]
+    pub fn revset_preset(&self) -> Option<&str> {
+        self.revset_preset.as_deref()
+    }
+
+    /// Sets the revset presets offered by view options.
+    #[must_use]
+    pub fn with_revset_presets(mut self, presets: Vec<RevsetPreset>) -> Self {
+        self.revset_presets = presets;
         self
     }
 
+    /// Returns the revset presets offered by view options.
+    #[must_use]
+    pub fn revset_presets(&self) -> &[RevsetPreset] {
+        &self.revset_presets
+    }
+
+    /// Returns the read-only `jj config list jk.revsets` spec behind revset presets.
+    #[must_use]
+    pub fn revset_presets_spec(&self) -> JjCommandSpec {
+        let argv = ["config", "list", REVSET_PRESETS_KEY];
+        let global_options = GlobalOptions::default()
+            .with_working_copy(WorkingCopyPolicy::Ignore)
+            .with_output(OutputPolicy {
+                color: ColorPolicy::Never,
+                ..OutputPolicy::default()
+            });
+        let spec = JjCommandSpec::render_read_only(argv)
+            .with_global_options(global_options)
+            .with_title(format!("jj config list {REVSET_PRESETS_KEY}"));
+        if let Some(repository) = &self.repository {
+            spec.with_repository(repository)
+        } else {
+            spec
+        }
+    }
+
+    /// Loads `[jk.revsets]` presets from jj config.
+    ///
+    /// jj exits unsuccessfully when the table does not exist, so an empty stdout and a failed exit
+    /// both mean "no presets" rather than an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `jj` cannot be executed.
+    pub fn load_revset_presets_with_runner(
+        &self,
+        runner: &mut impl JjCommandRunner,
+    ) -> Result<Vec<RevsetPreset>, JjLogError> {
+        let output = runner.run(&self.revset_presets_spec())?;
+        if !output.status.success() {
+            return Ok(Vec::new());
+        }
+        Ok(parse_revset_presets(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// Returns the read-only `jj config get revsets.log` spec behind the default log revset.
+    #[must_use]
+    pub fn default_revset_spec(&self) -> JjCommandSpec {
+        let global_options = GlobalOptions::default()
+            .with_working_copy(WorkingCopyPolicy::Ignore)
+            .with_output(OutputPolicy {
+                color: ColorPolicy::Never,
+                ..OutputPolicy::default()
+            });
+        let spec = JjCommandSpec::render_read_only(["config", "get", LOG_REVSET_KEY])
+            .with_global_options(global_options)
+            .with_title(format!("jj config get {LOG_REVSET_KEY}"));
+        if let Some(repository) = &self.repository {
+            spec.with_repository(repository)
+        } else {
+            spec
+        }
+    }
+
+    /// Loads the revset `jj log` shows without `-r`: the configured `revsets.log`.
+    ///
+    /// Callers only need a scope to union more revisions into, so a failed read falls back to jj's
+    /// built-in default rather than reporting an error.
+    pub fn load_default_revset_with_runner(&self, runner: &mut impl JjCommandRunner) -> String {
+        runner
+            .run(&self.default_revset_spec())
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+            .filter(|revset| !revset.is_empty())
+            .unwrap_or_else(|| BUILTIN_LOG_REVSET.to_owned())
+    }
+
     /// Loads a rendered log snapshot and semantic entries from `jj`.
     ///
-    /// This method executes `jj` twice: once for the user's rendered log output and once with a
-    /// JSON template for navigation metadata. A failed retry is useful when the repository state or
-    /// `jj` configuration has changed; parse and unsupported-command errors usually need
-    /// configuration or integration changes instead.
+    /// With a known rendered template this method executes `jj` once and splits the hidden semantic
+    /// records out of the rendered rows. jj's configured template cannot be wrapped, so it runs `jj`
+    /// twice instead: once for the user's rendered log output and once with a JSON template for
+    /// navigation metadata. A wrapped template that jj rejects also falls back to two passes. A
+    /// failed retry is useful when the repository state or `jj` configuration has changed; parse
+    /// and unsupported-command errors usually need configuration or integration changes instead.
     ///
     /// # Errors
     ///
@@ -206,16 +379,108 @@ impl JjLog {
     ) -> Result<LogSnapshot, JjLogError> {
         let command_args = self.command_args();
         let rendered_spec = self.command_spec(DefaultCommandMode::Rendered, &command_args);
-        let rendered = Self::run(runner, DefaultCommandMode::Rendered, &rendered_spec)?;
+        let single_pass = if self.rendered_template().is_some() {
+            // jj can reject the wrapped template while still rendering the template on its own, so
+            // a template error falls back to two passes. Any other failure, such as a bad revset,
+            // would fail both passes too and is reported as is.
+            match Self::run(
+                runner,
+                DefaultCommandMode::Inline,
+                &self.command_spec(DefaultCommandMode::Inline, &command_args),
+            ) {
+                Ok(stdout) => split_inline_records(&stdout)?,
+                Err(JjLogError::CommandFailed(stderr)) if stderr.contains(TEMPLATE_ERROR) => None,
+                Err(error) => return Err(error),
+            }
+        } else {
+            None
+        };
+        let (rendered, entries) = match single_pass {
+            Some(loaded) => loaded,
+            None => self.load_two_pass(runner, &rendered_spec, &command_args)?,
+        };
+        // A full page may hide older entries; an explicit `-n` is the user's final word.
+        let more_available = self.limit.is_none()
+            && self
+                .effective_limit()
+                .is_some_and(|limit| entries.len() >= limit);
+
+        let title = self.revset_preset.as_ref().map_or_else(
+            || rendered_spec.title().to_owned(),
+            |preset| format!("{}  [{preset}]", rendered_spec.title()),
+        );
+        Ok(LogSnapshot::new(rendered, entries)
+            .with_title(title)
+            .with_more_available(more_available))
+    }
+
+    /// Loads rendered and semantic output separately and aligns them by detected commit rows.
+    fn load_two_pass(
+        &self,
+        runner: &mut impl JjCommandRunner,
+        rendered_spec: &JjCommandSpec,
+        command_args: &[String],
+    ) -> Result<(String, Vec<LogEntry>), JjLogError> {
+        let rendered = Self::run(runner, DefaultCommandMode::Rendered, rendered_spec)?;
         let semantic = Self::run(
             runner,
             DefaultCommandMode::Json,
-            &self.command_spec(DefaultCommandMode::Json, &command_args),
+            &self.command_spec(DefaultCommandMode::Json, command_args),
         )?;
         let entries = parse_log_json_lines(&semantic)?;
         let entries = assign_rendered_lines(entries, &rendered)?;
+        Ok((rendered, entries))
+    }
 
-        Ok(LogSnapshot::new(rendered, entries).with_title(rendered_spec.title()))
+    /// Returns the bounded `jj log -r 'all()'` spec behind the revision finder.
+    ///
+    /// The finder ignores the active revset on purpose: it is how users reach revisions the current
+    /// log does not show. `limit` keeps very large repositories responsive.
+    #[must_use]
+    pub fn revision_finder_spec(&self, limit: usize) -> JjCommandSpec {
+        let argv = [
+            LOG_COMMAND.to_owned(),
+            "-r".to_owned(),
+            FINDER_REVSET.to_owned(),
+            "--no-graph".to_owned(),
+            "-n".to_owned(),
+            limit.to_string(),
+            "-T".to_owned(),
+            FINDER_TEMPLATE.to_owned(),
+        ];
+        let global_options = GlobalOptions::default()
+            .with_working_copy(WorkingCopyPolicy::Ignore)
+            .with_output(OutputPolicy {
+                color: ColorPolicy::Never,
+                ..OutputPolicy::default()
+            });
+        let spec = JjCommandSpec::render_read_only(argv)
+            .with_global_options(global_options)
+            .with_title(format!("jj log -r '{FINDER_REVSET}' -n {limit}"));
+        if let Some(repository) = &self.repository {
+            spec.with_repository(repository)
+        } else {
+            spec
+        }
+    }
+
+    /// Loads up to `limit` revisions from the whole repository for fuzzy finding.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `jj` cannot be executed, exits unsuccessfully, or emits a record that
+    /// does not match the finder template.
+    pub fn load_revision_candidates_with_runner(
+        &self,
+        runner: &mut impl JjCommandRunner,
+        limit: usize,
+    ) -> Result<Vec<RevisionCandidate>, JjLogError> {
+        let stdout = Self::run(
+            runner,
+            DefaultCommandMode::Rendered,
+            &self.revision_finder_spec(limit),
+        )?;
+        parse_finder_lines(&stdout)
     }
 
     fn run(
@@ -247,16 +512,18 @@ impl JjLog {
 
     fn command_spec(&self, mode: DefaultCommandMode, command_args: &[String]) -> JjCommandSpec {
         let mut argv = command_args.to_vec();
-        if let Some(limit) = self.limit {
+        if let Some(limit) = self.effective_limit() {
             argv.push("-n".to_owned());
             argv.push(limit.to_string());
         }
-        if mode == DefaultCommandMode::Json {
-            argv.push("-T".to_owned());
-            argv.push(LOG_TEMPLATE.to_owned());
-        } else if let Some(template) = self.rendered_template() {
+        let template = match mode {
+            DefaultCommandMode::Json => Some(LOG_TEMPLATE.to_owned()),
+            DefaultCommandMode::Inline => self.rendered_template().map(inline_log_template),
+            DefaultCommandMode::Rendered => self.rendered_template().map(ToOwned::to_owned),
+        };
+        if let Some(template) = template {
             argv.push("-T".to_owned());
-            argv.push(template.to_owned());
+            argv.push(template);
         }
 
         let global_options = GlobalOptions::default().with_working_copy(WorkingCopyPolicy::Ignore);
@@ -371,6 +638,8 @@ fn template_title(template: &str) -> String {
 enum DefaultCommandMode {
     Rendered,
     Json,
+    /// Rendered output with semantic records hidden in each commit row.
+    Inline,
 }
 
 /// Error returned while loading log entries from `jj`.
@@ -430,8 +699,178 @@ pub enum JjLogError {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::VecDeque;
+    use std::io;
+    #[cfg(unix)]
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::Output;
+
     use super::*;
 
+    /// Returns outputs in order and records the `-T` argument of each run.
+    struct FakeRunner {
+        stdouts: VecDeque<&'static str>,
+        templates: Vec<Option<String>>,
+        failures: usize,
+        failure_stderr: &'static str,
+    }
+
+    impl FakeRunner {
+        fn new(stdouts: impl IntoIterator<Item = &'static str>) -> Self {
+            Self {
+                stdouts: stdouts.into_iter().collect(),
+                templates: Vec::new(),
+                failures: 0,
+                failure_stderr: "Error: Failed to parse template",
+            }
+        }
+
+        /// Exits unsuccessfully on the first run, then returns `stdouts` in order.
+        fn failing_first(stdouts: impl IntoIterator<Item = &'static str>) -> Self {
+            Self {
+                failures: 1,
+                ..Self::new(std::iter::once("").chain(stdouts))
+            }
+        }
+    }
+
+    impl JjCommandRunner for FakeRunner {
+        fn run(&mut self, spec: &JjCommandSpec) -> io::Result<Output> {
+            let argv = spec
+                .argv()
+                .iter()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            self.templates.push(
+                argv.windows(2)
+                    .find(|args| args[0] == "-T")
+                    .map(|args| args[1].clone()),
+            );
+            let stdout = self
+                .stdouts
+                .pop_front()
+                .ok_or_else(|| io::Error::other("fake runner results already consumed"))?;
+            let failed = self.templates.len() <= self.failures;
+            Ok(Output {
+                status: exit_status(i32::from(failed)),
+                stdout: stdout.as_bytes().to_vec(),
+                stderr: if failed {
+                    self.failure_stderr.as_bytes().to_vec()
+                } else {
+                    Vec::new()
+                },
+            })
+        }
+    }
+
+    #[cfg(unix)]
+    fn exit_status(code: i32) -> std::process::ExitStatus {
+        std::process::ExitStatus::from_raw(code << 8)
+    }
+
+    #[cfg(not(unix))]
+    fn exit_status(code: i32) -> std::process::ExitStatus {
+        std::process::Command::new(if cfg!(windows) { "cmd" } else { "sh" })
+            .args(if cfg!(windows) {
+                vec!["/C".into(), format!("exit {code}").into()]
+            } else {
+                vec!["-c".into(), format!("exit {code}").into()]
+            })
+            .status()
+            .unwrap()
+    }
+
+    const SEMANTIC_STDOUT: &str = concat!(
+        "{\"change_id\":\"aaa\",\"commit_id\":\"111\",\"description\":\"first\"}\t\"\"\n",
+        "{\"change_id\":\"bbb\",\"commit_id\":\"222\",\"description\":\"second\"}\t\"\"\n",
+    );
+
+    #[test]
+    fn known_template_loads_rendered_rows_and_entries_in_one_pass() -> Result<(), JjLogError> {
+        let source = JjLog::default().with_template(LogTemplateSelection::Compact);
+        let mut runner = FakeRunner::new([concat!(
+            "@  \u{1e}{\"change_id\":\"aaa\",\"commit_id\":\"111\",\"description\":\"first\"}\t\"\"\u{1f}aaa\n",
+            "│  first\n",
+            "○  \u{1e}{\"change_id\":\"bbb\",\"commit_id\":\"222\",\"description\":\"second\"}\t\"\"\u{1f}bbb\n",
+        )]);
+
+        let (_, rendered, entries) = source.load_with_runner(&mut runner)?.into_parts();
+
+        assert_eq!(
+            runner.templates,
+            [Some(inline_log_template(COMPACT_LOG_TEMPLATE))]
+        );
+        assert_eq!(rendered, "@  aaa\n│  first\n○  bbb\n");
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.change_id(), entry.rendered_line()))
+                .collect::<Vec<_>>(),
+            [("aaa", 0), ("bbb", 2)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn configured_template_and_stripped_records_fall_back_to_two_passes() -> Result<(), JjLogError>
+    {
+        let mut runner = FakeRunner::new(["@  aaa\n○  bbb\n", SEMANTIC_STDOUT]);
+        let snapshot = JjLog::default().load_with_runner(&mut runner)?;
+        assert_eq!(runner.templates, [None, Some(LOG_TEMPLATE.to_owned())]);
+        assert_eq!(snapshot.entries()[1].rendered_line(), 1);
+
+        let source = JjLog::default().with_template(LogTemplateSelection::Oneline);
+        let mut runner = FakeRunner::new(["@  aaa\n○  bbb\n", "@  aaa\n○  bbb\n", SEMANTIC_STDOUT]);
+        let snapshot = source.load_with_runner(&mut runner)?;
+        assert_eq!(
+            runner.templates,
+            [
+                Some(inline_log_template(ONELINE_LOG_TEMPLATE)),
+                Some(ONELINE_LOG_TEMPLATE.to_owned()),
+                Some(LOG_TEMPLATE.to_owned()),
+            ]
+        );
+        assert_eq!(snapshot.entries().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn failed_wrapped_template_falls_back_to_two_passes() -> Result<(), JjLogError> {
+        let source = JjLog::default().with_template(LogTemplateSelection::Compact);
+        let mut runner = FakeRunner::failing_first(["@  aaa\n○  bbb\n", SEMANTIC_STDOUT]);
+
+        let snapshot = source.load_with_runner(&mut runner)?;
+
+        assert_eq!(
+            runner.templates,
+            [
+                Some(inline_log_template(COMPACT_LOG_TEMPLATE)),
+                Some(COMPACT_LOG_TEMPLATE.to_owned()),
+                Some(LOG_TEMPLATE.to_owned()),
+            ]
+        );
+        assert_eq!(snapshot.rendered(), "@  aaa\n○  bbb\n");
+        assert_eq!(snapshot.entries().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn other_failures_of_the_wrapped_template_run_are_reported_without_a_retry() {
+        let source = JjLog::default().with_template(LogTemplateSelection::Compact);
+        let mut runner = FakeRunner {
+            failure_stderr: "Error: Revision `nope` doesn't exist",
+            ..FakeRunner::failing_first(["@  aaa\n", SEMANTIC_STDOUT])
+        };
+
+        let error = source.load_with_runner(&mut runner);
+
+        assert!(matches!(
+            error,
+            Err(JjLogError::CommandFailed(stderr)) if stderr.contains("doesn't exist")
+        ));
+        assert_eq!(runner.templates.len(), 1);
+    }
+
     #[test]
     fn rendered_command_forces_color_and_ignores_color_suppression_env() {
         let command_args = vec!["log".to_owned()];
@@ -515,6 +954,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn paged_log_grows_limit_by_page_and_resets_for_new_revsets() {
+        let source = JjLog::default().with_page_size(Some(50));
+        assert_eq!(source.effective_limit(), Some(50));
+
+        let source = source.with_next_page().with_next_page();
+        assert_eq!(source.effective_limit(), Some(150));
+        let args = source
+            .command_spec(DefaultCommandMode::Json, &source.command_args())
+            .argv()
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert!(args.windows(2).any(|args| args == ["-n", "150"]));
+
+        assert_eq!(source.clone().with_revset("@").effective_limit(), Some(50));
+        assert_eq!(source.with_limit(Some(3)).effective_limit(), Some(3));
+    }
+
+    #[test]
+    fn paging_stops_once_a_page_returns_fewer_rows_than_requested() -> Result<(), JjLogError> {
+        let source = JjLog::default().with_page_size(Some(2));
+        let mut runner = FakeRunner::new(["@  aaa\n○  bbb\n", SEMANTIC_STDOUT]);
+        assert!(source.load_with_runner(&mut runner)?.more_available());
+
+        let source = source.with_next_page();
+        let mut runner = FakeRunner::new(["@  aaa\n○  bbb\n", SEMANTIC_STDOUT]);
+        assert!(!source.load_with_runner(&mut runner)?.more_available());
+        Ok(())
+    }
+
     #[test]
     fn command_title_names_jj_command_context() {
         let command_args = vec!["log".to_owned(), "-r".to_owned(), "@".to_owned()];
@@ -645,4 +1115,82 @@ mod tests {
                 .any(|args| args == ["log", "-r", "older::newer"])
         );
     }
+
+    #[test]
+    fn revision_finder_spec_loads_bounded_all_revisions_without_graph() {
+        let spec = JjLog::default()
+            .with_revset("mine()")
+            .with_repository("/tmp/repo")
+            .revision_finder_spec(200);
+        let argv = spec
+            .process_argv()
+            .into_iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+
+        assert!(
+            argv.windows(6)
+                .any(|args| args == ["log", "-r", "all()", "--no-graph", "-n", "200"])
+        );
+        assert!(argv.iter().any(|arg| arg == "--ignore-working-copy"));
+        assert!(argv.windows(2).any(|args| args == ["--color", "never"]));
+        assert!(
+            argv.windows(2)
+                .any(|args| args == ["--repository", "/tmp/repo"])
+        );
+        assert_eq!(spec.title(), "jj log -r 'all()' -n 200");
+    }
+
+    #[test]
+    fn without_revset_falls_back_to_configured_default() {
+        let source = JjLog::default().with_revset("mine()");
+        assert_eq!(source.revset(), Some("mine()"));
+
+        let source = source.without_revset();
+
+        assert_eq!(source.revset(), None);
+        assert_eq!(source.command_args(), ["log"]);
+    }
+
+    #[test]
+    fn revset_preset_sets_revset_until_another_revset_replaces_it() {
+        let preset = RevsetPreset::new("stack", "trunk()..@");
+        let source = JjLog::default().with_revset_preset(&preset);
+
+        assert_eq!(source.revset(), Some("trunk()..@"));
+        assert_eq!(source.revset_preset(), Some("stack"));
+        assert_eq!(source.command_args(), ["log", "-r", "trunk()..@"]);
+        assert_eq!(source.clone().with_revset("mine()").revset_preset(), None);
+        assert_eq!(source.without_revset().revset_preset(), None);
+    }
+
+    #[test]
+    fn revset_presets_spec_lists_jk_revsets_config() {
+        let spec = JjLog::default().revset_presets_spec();
+        let argv = spec
+            .argv()
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(argv, ["config", "list", "jk.revsets"]);
+        assert_eq!(spec.title(), "jj config list jk.revsets");
+    }
+
+    #[test]
+    fn default_revset_reads_revsets_log_or_falls_back_to_builtin() {
+        let mut runner = FakeRunner::new(["mine() | @\n"]);
+        assert_eq!(
+            JjLog::default().load_default_revset_with_runner(&mut runner),
+            "mine() | @"
+        );
+
+        let mut runner = FakeRunner::new([]);
+        assert_eq!(
+            JjLog::default().load_default_revset_with_runner(&mut runner),
+            BUILTIN_LOG_REVSET
+        );
+        let spec = JjLog::default().default_revset_spec();
+        assert_eq!(spec.title(), "jj config get revsets.log");
+    }
 }
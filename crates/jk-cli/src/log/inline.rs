@@ -0,0 +1,101 @@
+//! Single-pass log loading with semantic records hidden in the rendered stream.
+//!
+//! When `jk` knows the rendered template text, it prefixes it with the semantic record wrapped in
+//! ASCII record and unit separators. Those control characters never print, and JSON escapes them
+//! inside strings, so one `jj` run yields both the rendered graph and the entries, each already tied
+//! to the line that carries it. That halves the process count and removes the race where an
+//! operation lands between the rendered and semantic passes.
+//!
+//! jj's configured template is only known to jj, so it cannot be wrapped here; those loads keep the
+//! two-pass path in the parent module.
+
+use jk_core::LogEntry;
+
+use super::JjLogError;
+use super::semantic::{LOG_RECORD_TEMPLATE, parse_log_record};
+
+/// Marks the start of an inline semantic record.
+const RECORD_START: char = '\u{1e}';
+/// Marks the end of an inline semantic record and the start of the rendered template output.
+const RECORD_END: char = '\u{1f}';
+
+/// Wraps a rendered template so each commit row starts with its hidden semantic record.
+pub(super) fn inline_log_template(rendered_template: &str) -> String {
+    format!("\"\\x1e\" ++ {LOG_RECORD_TEMPLATE} ++ \"\\x1f\" ++ ({rendered_template})")
+}
+
+/// Splits hidden semantic records out of single-pass output.
+///
+/// Returns `None` when no line carries a record, which means `jj` dropped the separators and the
+/// caller should fall back to separate rendered and semantic passes.
+pub(super) fn split_inline_records(
+    stdout: &str,
+) -> Result<Option<(String, Vec<LogEntry>)>, JjLogError> {
+    let mut rendered = String::with_capacity(stdout.len());
+    let mut entries = Vec::new();
+
+    for (index, line) in stdout.split_inclusive('\n').enumerate() {
+        let record = line.find(RECORD_START).and_then(|start| {
+            let end = start + line[start..].find(RECORD_END)?;
+            Some((start, end))
+        });
+        let Some((start, end)) = record else {
+            rendered.push_str(line);
+            continue;
+        };
+
+        let entry = parse_log_record(index + 1, &line[start + RECORD_START.len_utf8()..end])?;
+        entries.push(entry.with_rendered_line(index));
+        rendered.push_str(&line[..start]);
+        rendered.push_str(&line[end + RECORD_END.len_utf8()..]);
+    }
+
+    Ok((!entries.is_empty()).then_some((rendered, entries)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_rendered_template_after_the_hidden_record() {
+        let template = inline_log_template("builtin_log_compact");
+
+        assert!(template.starts_with("\"\\x1e\" ++ json(self)"));
+        assert!(template.ends_with(" ++ \"\\x1f\" ++ (builtin_log_compact)"));
+    }
+
+    #[test]
+    fn splits_records_from_rendered_rows_and_keeps_their_lines() -> Result<(), JjLogError> {
+        let stdout = concat!(
+            "\u{1b}[1m@\u{1b}[0m  \u{1e}{\"change_id\":\"aaa\",\"commit_id\":\"111\",\"description\":\"first\\n\\nbody\"}",
+            "\t\"body\"\t\"main\\n\\n\\n\"\u{1f}aaa first\n",
+            "│  first body\n",
+            "○  \u{1e}{\"change_id\":\"bbb\",\"commit_id\":\"222\",\"description\":\"second\"}\t\"\"\u{1f}bbb second\n",
+            "~\n",
+        );
+
+        let Some((rendered, entries)) = split_inline_records(stdout)? else {
+            panic!("records are present");
+        };
+
+        assert_eq!(
+            rendered,
+            "\u{1b}[1m@\u{1b}[0m  aaa first\n│  first body\n○  bbb second\n~\n"
+        );
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].change_id(), "aaa");
+        assert_eq!(entries[0].details(), "body");
+        assert_eq!(entries[0].local_bookmarks(), ["main"]);
+        assert_eq!(entries[0].rendered_line(), 0);
+        assert_eq!(entries[1].change_id(), "bbb");
+        assert_eq!(entries[1].rendered_line(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_records_ask_for_the_two_pass_fallback() -> Result<(), JjLogError> {
+        assert_eq!(split_inline_records("@  aaa first\n○  bbb second\n")?, None);
+        Ok(())
+    }
+}
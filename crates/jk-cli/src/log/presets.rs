@@ -0,0 +1,93 @@
+//! Named log revsets read from the `[jk.revsets]` jj config table.
+//!
+//! Presets live in jj's own config so they follow the same user, repo, and workspace layering as
+//! `revsets.log`. `jj config list` prints each entry as a TOML `key = value` line; only string
+//! values are presets, anything else is ignored.
+
+/// Config table holding revset presets.
+pub(super) const REVSET_PRESETS_KEY: &str = "jk.revsets";
+
+/// One named revset from `[jk.revsets]`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevsetPreset {
+    name: String,
+    revset: String,
+}
+
+impl RevsetPreset {
+    /// Creates a preset named `name` for `revset`.
+    #[must_use]
+    pub fn new(name: impl Into<String>, revset: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            revset: revset.into(),
+        }
+    }
+
+    /// Returns the config key name shown in selectors and titles.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the revset passed to `jj log -r`.
+    #[must_use]
+    pub fn revset(&self) -> &str {
+        &self.revset
+    }
+}
+
+/// Parses `jj config list jk.revsets` output into presets, keeping config order.
+pub(super) fn parse_revset_presets(stdout: &str) -> Vec<RevsetPreset> {
+    stdout.lines().filter_map(parse_preset_line).collect()
+}
+
+/// Parses one line such as `jk.revsets.mine = "mine()"` or `jk.revsets.'my stack' = 'trunk()..@'`.
+fn parse_preset_line(line: &str) -> Option<RevsetPreset> {
+    let key = line.strip_prefix(REVSET_PRESETS_KEY)?.strip_prefix('.')?;
+    let (name, value) = key.split_once(" = ")?;
+    let name = name.trim().trim_matches(|c| c == '\'' || c == '"');
+    let revset = parse_toml_string(value.trim())?;
+    (!name.is_empty() && !revset.trim().is_empty()).then(|| RevsetPreset::new(name, revset))
+}
+
+/// Decodes a single-line TOML basic or literal string.
+fn parse_toml_string(value: &str) -> Option<String> {
+    if let Some(literal) = value
+        .strip_prefix('\'')
+        .and_then(|value| value.strip_suffix('\''))
+    {
+        return Some(literal.to_owned());
+    }
+    // TOML basic-string escapes are a superset of the ones jj prints, and all of those are valid
+    // JSON escapes.
+    value
+        .starts_with('"')
+        .then(|| serde_json::from_str(value).ok())
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_basic_and_literal_string_presets_in_config_order() {
+        let output = concat!(
+            "jk.revsets.mine = \"mine()\"\n",
+            "jk.revsets.'my stack' = 'trunk()..@'\n",
+            "jk.revsets.quoted = \"description(\\\"wip\\\")\"\n",
+            "jk.revsets.limit = 10\n",
+            "revsets.log = \"@\"\n",
+        );
+
+        assert_eq!(
+            parse_revset_presets(output),
+            vec![
+                RevsetPreset::new("mine", "mine()"),
+                RevsetPreset::new("my stack", "trunk()..@"),
+                RevsetPreset::new("quoted", "description(\"wip\")"),
+            ]
+        );
+    }
+}
@@ -37,7 +37,10 @@ pub(super) fn assign_rendered_lines(
 }
 
 /// Returns rendered line indexes that look like commit rows.
-fn commit_row_lines(rendered: &str) -> impl Iterator<Item = usize> + '_ {
+///
+/// `jj op log` draws operation rows with the same graph node markers, so operation alignment reuses
+/// this detector.
+pub fn commit_row_lines(rendered: &str) -> impl Iterator<Item = usize> + '_ {
     rendered
         .lines()
         .enumerate()
@@ -60,7 +63,7 @@ fn is_commit_row(line: &str) -> bool {
 
 /// Returns whether a graph item character represents a commit row.
 const fn is_commit_marker(character: char) -> bool {
-    matches!(character, '@' | '○' | '◆' | '×' | '+')
+    matches!(character, '@' | '○' | '◉' | '◆' | '×' | '+')
 }
 
 /// Returns whether a character can appear before a commit marker in the graph.
@@ -1,8 +1,13 @@
 //! Semantic log parsing from a narrow `jj` template.
 //!
-//! The template emits one JSON commit object plus one JSON details string per commit row. The
-//! parser accepts that stream even when it is embedded in rendered graph lines, because `jj` may
-//! still print graph prefixes around the template output.
+//! The template emits one JSON commit object, one JSON details string, and one JSON refs string per
+//! commit row. The parser accepts that stream even when it is embedded in rendered graph lines,
+//! because `jj` may still print graph prefixes around the template output.
+//!
+//! `json(self)` already carries parents and signatures. Bookmarks, tags, and state flags are not
+//! part of the commit's JSON form, so the refs string packs them as four newline-separated,
+//! space-joined lists: local bookmarks, remote bookmarks, tags, and flag keywords. jj ref names
+//! cannot contain whitespace, which keeps that packing unambiguous.
 //!
 //! The template pass is a temporary semantic side channel. It lets jj keep ownership of configured
 //! templates, colors, graph layout, and default revsets while `jk` gets enough typed state to drive
@@ -10,16 +15,45 @@
 //! alongside rendered output, this module should collapse into that API instead of learning more of
 //! jj's display behavior.
 
-use jk_core::LogEntry;
+use jk_core::{ChangeFlag, LogEntry, Signature};
 use serde::Deserialize;
 
 use super::JjLogError;
 
+/// One semantic record: commit JSON, details string, and packed refs string, tab-separated.
+macro_rules! log_record_template {
+    () => {
+        concat!(
+            "json(self)",
+            " ++ \"\\t\" ++ ",
+            "json(description.remove_prefix(description.first_line()).trim_start())",
+            " ++ \"\\t\" ++ ",
+            "json(stringify(",
+            "local_bookmarks.map(|bookmark| bookmark.name()).join(\" \")",
+            " ++ \"\\n\" ++ ",
+            "remote_bookmarks.map(|bookmark| bookmark.name() ++ \"@\" ++ bookmark.remote()).join(\" \")",
+            " ++ \"\\n\" ++ ",
+            "tags.map(|tag| tag.name()).join(\" \")",
+            " ++ \"\\n\" ++ ",
+            "separate(\" \", if(empty, \"empty\"), if(conflict, \"conflict\"), if(immutable, \"immutable\"),",
+            " if(divergent, \"divergent\"), if(hidden, \"hidden\"),",
+            " if(current_working_copy, \"current_working_copy\"))",
+            "))",
+        )
+    };
+}
+
 /// `jj` template used to emit semantic records for navigation.
-pub(super) const LOG_TEMPLATE: &str = concat!(
+pub(super) const LOG_TEMPLATE: &str = concat!(log_record_template!(), " ++ \"\\n\"");
+
+/// Semantic record template without a trailing newline, for embedding in a rendered template.
+pub(super) const LOG_RECORD_TEMPLATE: &str = log_record_template!();
+
+/// `jj` template used by the revision finder: commit JSON plus space-separated local bookmarks.
+pub(super) const FINDER_TEMPLATE: &str = concat!(
     "json(self)",
     " ++ \"\\t\" ++ ",
-    "json(description.remove_prefix(description.first_line()).trim_start())",
+    "json(stringify(local_bookmarks.map(|bookmark| bookmark.name()).join(\" \")))",
     " ++ \"\\n\"",
 );
 
@@ -29,6 +63,94 @@ struct JjCommit {
     change_id: String,
     commit_id: String,
     description: String,
+    #[serde(default)]
+    parents: Vec<String>,
+    #[serde(default)]
+    author: JjSignature,
+    #[serde(default)]
+    committer: JjSignature,
+}
+
+/// Commit fields the revision finder matches on.
+#[derive(Debug, Deserialize)]
+struct FinderCommit {
+    change_id: String,
+    commit_id: String,
+    description: String,
+    #[serde(default)]
+    author: JjSignature,
+}
+
+/// Signature fields emitted inside `json(self)`.
+#[derive(Debug, Default, Deserialize)]
+struct JjSignature {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    email: String,
+    #[serde(default)]
+    timestamp: String,
+}
+
+impl From<JjSignature> for Signature {
+    fn from(signature: JjSignature) -> Self {
+        Self {
+            name: signature.name,
+            email: signature.email,
+            timestamp: signature.timestamp,
+        }
+    }
+}
+
+/// One revision offered by the fuzzy revision finder.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevisionCandidate {
+    /// Full change id.
+    pub change_id: String,
+    /// Full commit id.
+    pub commit_id: String,
+    /// Full description.
+    pub description: String,
+    /// Author name, falling back to the email when the name is empty.
+    pub author: String,
+    /// Local bookmarks pointing at the revision.
+    pub bookmarks: Vec<String>,
+}
+
+/// Parses revision finder records emitted by [`FINDER_TEMPLATE`].
+pub(super) fn parse_finder_lines(stdout: &str) -> Result<Vec<RevisionCandidate>, JjLogError> {
+    let mut candidates = Vec::new();
+
+    for (index, line) in stdout.lines().enumerate() {
+        let Some(json_start) = line.find('{') else {
+            continue;
+        };
+
+        let (commit_json, bookmarks_json) = split_commit_record(index + 1, &line[json_start..])?;
+        let commit: FinderCommit =
+            serde_json::from_str(commit_json).map_err(|source| JjLogError::Parse {
+                line: index + 1,
+                source,
+            })?;
+        let bookmarks = parse_details(index + 1, bookmarks_json)?;
+        let author = if commit.author.name.is_empty() {
+            commit.author.email
+        } else {
+            commit.author.name
+        };
+        candidates.push(RevisionCandidate {
+            change_id: commit.change_id,
+            commit_id: commit.commit_id,
+            description: commit.description,
+            author,
+            bookmarks: bookmarks
+                .split_whitespace()
+                .map(ToOwned::to_owned)
+                .collect(),
+        });
+    }
+
+    Ok(candidates)
 }
 
 /// Parses semantic log records emitted by [`LOG_TEMPLATE`].
@@ -44,19 +166,52 @@ pub(super) fn parse_log_json_lines(stdout: &str) -> Result<Vec<LogEntry>, JjLogE
             continue;
         };
 
-        let (commit_json, details_json) = split_commit_record(index + 1, &line[json_start..])?;
-        let commit = parse_commit(index + 1, commit_json)?;
-        let details = parse_details(index + 1, details_json)?;
-        entries.push(
-            LogEntry::new(commit.change_id, commit.commit_id, commit.description)
-                .with_details(details),
-        );
+        entries.push(parse_log_record(index + 1, &line[json_start..])?);
     }
 
     Ok(entries)
 }
 
-/// Splits one template record into commit JSON and details JSON.
+/// Parses one record emitted by [`LOG_RECORD_TEMPLATE`], reporting errors against `line`.
+pub(super) fn parse_log_record(line: usize, record: &str) -> Result<LogEntry, JjLogError> {
+    let (commit_json, fields) = split_commit_record(line, record)?;
+    let (details_json, refs_json) = fields.split_once('\t').unwrap_or((fields, "\"\""));
+    let commit = parse_commit(line, commit_json)?;
+    let details = parse_details(line, details_json)?;
+    let refs = parse_details(line, refs_json)?;
+    Ok(entry_from_record(commit, details, &refs))
+}
+
+/// Builds a log entry from the commit object, details string, and packed refs string.
+fn entry_from_record(commit: JjCommit, details: String, refs: &str) -> LogEntry {
+    let mut lists = refs.split('\n').map(|list| {
+        list.split_whitespace()
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>()
+    });
+    let mut next_list = || lists.next().unwrap_or_default();
+    let local_bookmarks = next_list();
+    // `@git` refs mirror local bookmarks in colocated repositories and add nothing to show.
+    let remote_bookmarks = next_list()
+        .into_iter()
+        .filter(|bookmark| !bookmark.ends_with("@git"))
+        .collect();
+    let tags = next_list();
+    let flags = next_list()
+        .iter()
+        .filter_map(|keyword| ChangeFlag::from_keyword(keyword))
+        .collect();
+
+    LogEntry::new(commit.change_id, commit.commit_id, commit.description)
+        .with_details(details)
+        .with_parents(commit.parents)
+        .with_bookmarks(local_bookmarks, remote_bookmarks)
+        .with_tags(tags)
+        .with_signatures(commit.author.into(), commit.committer.into())
+        .with_flags(flags)
+}
+
+/// Splits one template record into commit JSON and the remaining template fields.
 fn split_commit_record(line: usize, text: &str) -> Result<(&str, &str), JjLogError> {
     text.split_once('\t')
         .ok_or(JjLogError::MissingDetails { line })
@@ -97,6 +252,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parses_parents_refs_signatures_and_flags() -> Result<(), JjLogError> {
+        let output = concat!(
+            "○  {\"change_id\":\"aaa\",\"commit_id\":\"111\",\"description\":\"first\",",
+            "\"parents\":[\"p1\",\"p2\"],",
+            "\"author\":{\"name\":\"Ada\",\"email\":\"ada@example.com\",\"timestamp\":\"2026-01-02T03:04:05+00:00\"},",
+            "\"committer\":{\"name\":\"Bot\",\"email\":\"bot@example.com\",\"timestamp\":\"2026-01-03T00:00:00+00:00\"}}",
+            "\t\"\"",
+            "\t\"main feature\\nmain@origin main@git\\nv1.0\\nimmutable conflict current_working_copy\"\n",
+        );
+
+        let entries = parse_log_json_lines(output)?;
+
+        let entry = &entries[0];
+        assert_eq!(entry.parents(), ["p1", "p2"]);
+        assert_eq!(entry.local_bookmarks(), ["main", "feature"]);
+        assert_eq!(entry.remote_bookmarks(), ["main@origin"]);
+        assert_eq!(entry.tags(), ["v1.0"]);
+        assert_eq!(entry.author().name, "Ada");
+        assert_eq!(entry.author().timestamp, "2026-01-02T03:04:05+00:00");
+        assert_eq!(entry.committer().email, "bot@example.com");
+        assert_eq!(
+            entry.flags(),
+            [
+                ChangeFlag::Immutable,
+                ChangeFlag::Conflict,
+                ChangeFlag::WorkingCopy
+            ]
+        );
+        assert!(!entry.has_flag(ChangeFlag::Empty));
+        Ok(())
+    }
+
     #[test]
     fn reports_parse_line_number() {
         let output = concat!(
@@ -109,6 +297,39 @@ mod tests {
         assert!(matches!(error, Some(JjLogError::Parse { line: 2, .. })));
     }
 
+    #[test]
+    fn parses_finder_records_with_author_and_bookmarks() -> Result<(), JjLogError> {
+        let output = concat!(
+            "{\"change_id\":\"aaa\",\"commit_id\":\"111\",\"description\":\"first\\n\",",
+            "\"author\":{\"name\":\"Ada\",\"email\":\"ada@example.com\"}}\t\"main feature\"\n",
+            "{\"change_id\":\"bbb\",\"commit_id\":\"222\",\"description\":\"\",",
+            "\"author\":{\"name\":\"\",\"email\":\"bot@example.com\"}}\t\"\"\n",
+        );
+
+        let candidates = parse_finder_lines(output)?;
+
+        assert_eq!(
+            candidates,
+            vec![
+                RevisionCandidate {
+                    change_id: "aaa".to_owned(),
+                    commit_id: "111".to_owned(),
+                    description: "first\n".to_owned(),
+                    author: "Ada".to_owned(),
+                    bookmarks: vec!["main".to_owned(), "feature".to_owned()],
+                },
+                RevisionCandidate {
+                    change_id: "bbb".to_owned(),
+                    commit_id: "222".to_owned(),
+                    description: String::new(),
+                    author: "bot@example.com".to_owned(),
+                    bookmarks: Vec::new(),
+                },
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     fn reports_missing_details_field() {
         let output = "{\"change_id\":\"aaa\",\"commit_id\":\"111\",\"description\":\"first\"}\n";
@@ -1,14 +1,25 @@
 //! Read-only `jj op ...` command integration.
 
-use jk_core::{GlobalOptions, InspectionSnapshot, JjCommandSpec};
+use jk_core::{
+    ColorPolicy, GlobalOptions, InspectionSnapshot, JjCommandSpec, OperationLogSnapshot,
+    OutputPolicy, WorkingCopyPolicy,
+};
 use thiserror::Error;
 
 use crate::command::{JjCommandRunner, SystemJjCommandRunner};
 
+mod semantic;
+
+use semantic::{OPERATION_LOG_TEMPLATE, assign_rendered_lines, parse_operation_json_lines};
+
 const OP_COMMAND: &str = "op";
 const LOG_COMMAND: &str = "log";
 const SHOW_COMMAND: &str = "show";
 const DIFF_COMMAND: &str = "diff";
+const PATCH_FLAG: &str = "--patch";
+const TEMPLATE_FLAG: &str = "-T";
+const CURRENT_ID_TEMPLATE: &str = "id ++ \"\\n\"";
+const OPERATION_USERNAME_KEY: &str = "operation.username";
 
 /// Canonical query shapes supported by operation inspection.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -24,6 +35,17 @@ pub enum OperationQuery {
     Diff {
         /// Operation to diff.
         operation: String,
+        /// Whether to pass `--patch` and render content changes.
+        patch: bool,
+    },
+    /// Render `jj op diff --from FROM --to TO`.
+    DiffFromTo {
+        /// Starting operation.
+        from: String,
+        /// Ending operation.
+        to: String,
+        /// Whether to pass `--patch` and render content changes.
+        patch: bool,
     },
 }
 
@@ -47,6 +69,45 @@ impl OperationQuery {
     pub fn diff(operation: impl Into<String>) -> Self {
         Self::Diff {
             operation: operation.into(),
+            patch: false,
+        }
+    }
+
+    /// Creates a `jj op diff --from FROM --to TO` query.
+    #[must_use]
+    pub fn diff_from_to(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self::DiffFromTo {
+            from: from.into(),
+            to: to.into(),
+            patch: false,
+        }
+    }
+
+    /// Returns whether this operation diff query renders content patches.
+    #[must_use]
+    pub const fn patch(&self) -> bool {
+        match self {
+            Self::Log | Self::Show { .. } => false,
+            Self::Diff { patch, .. } | Self::DiffFromTo { patch, .. } => *patch,
+        }
+    }
+
+    /// Returns this query with `--patch` enabled or disabled.
+    ///
+    /// Queries other than operation diffs are returned unchanged.
+    #[must_use]
+    pub fn with_patch(&self, patch: bool) -> Self {
+        match self {
+            Self::Log | Self::Show { .. } => self.clone(),
+            Self::Diff { operation, .. } => Self::Diff {
+                operation: operation.clone(),
+                patch,
+            },
+            Self::DiffFromTo { from, to, .. } => Self::DiffFromTo {
+                from: from.clone(),
+                to: to.clone(),
+                patch,
+            },
         }
     }
 
@@ -55,7 +116,8 @@ impl OperationQuery {
     pub fn target_label(&self) -> String {
         match self {
             Self::Log => "operations".to_owned(),
-            Self::Show { operation } | Self::Diff { operation } => operation.clone(),
+            Self::Show { operation } | Self::Diff { operation, .. } => operation.clone(),
+            Self::DiffFromTo { from, to, .. } => format!("{from}..{to}"),
         }
     }
 }
@@ -108,14 +170,186 @@ impl JjOperation {
         Ok(InspectionSnapshot::new(query.target_label(), rendered).with_title(spec.title()))
     }
 
+    /// Loads `jj op log` with semantic operation records aligned to the rendered rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `jj` cannot be executed, exits unsuccessfully, emits a record that does
+    /// not match the expected schema, or the rendered rows cannot be aligned with the records.
+    pub fn load_log(&self) -> Result<OperationLogSnapshot, JjOperationError> {
+        self.load_log_with_runner(&mut SystemJjCommandRunner)
+    }
+
+    /// Loads `jj op log` with semantic operation records using the provided command runner.
+    ///
+    /// The rendered pass keeps the user's `jj op log` template and graph. A second pass with a narrow
+    /// JSON template supplies typed operation fields, which are aligned to rendered graph rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `jj` cannot be executed, exits unsuccessfully, emits a record that does
+    /// not match the expected schema, or the rendered rows cannot be aligned with the records.
+    pub fn load_log_with_runner(
+        &self,
+        runner: &mut impl JjCommandRunner,
+    ) -> Result<OperationLogSnapshot, JjOperationError> {
+        let rendered_spec = self.spec_for(&OperationQuery::Log);
+        let rendered = Self::run(runner, &rendered_spec)?;
+        let semantic = Self::run(runner, &self.semantic_log_spec())?;
+        let entries = parse_operation_json_lines(&semantic)?;
+        let entries = assign_rendered_lines(entries, &rendered)?;
+
+        Ok(OperationLogSnapshot::new(rendered, entries).with_title(rendered_spec.title()))
+    }
+
+    /// Returns the `jj op log` spec that emits semantic operation records.
+    #[must_use]
+    pub fn semantic_log_spec(&self) -> JjCommandSpec {
+        JjCommandSpec::render_read_only([
+            OP_COMMAND,
+            LOG_COMMAND,
+            TEMPLATE_FLAG,
+            OPERATION_LOG_TEMPLATE,
+        ])
+        .with_global_options(self.global_options.clone())
+        .with_title(format!("jj {OP_COMMAND} {LOG_COMMAND}"))
+    }
+
+    /// Loads the id of the current operation.
+    ///
+    /// The probe snapshots the working copy like any other command, so the returned operation
+    /// includes uncommitted file changes and restoring to it cannot discard them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `jj` cannot be executed, exits unsuccessfully, or prints no operation id.
+    pub fn load_current_id_with_runner(
+        &self,
+        runner: &mut impl JjCommandRunner,
+    ) -> Result<String, JjOperationError> {
+        let stdout = Self::run(runner, &self.current_id_spec())?;
+        stdout
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(ToOwned::to_owned)
+            .ok_or(JjOperationError::MissingCurrentOperation)
+    }
+
+    /// Returns the `jj op log` spec that prints only the current operation id.
+    #[must_use]
+    pub fn current_id_spec(&self) -> JjCommandSpec {
+        let global_options = self.global_options.clone().with_output(OutputPolicy {
+            color: ColorPolicy::Never,
+            ..OutputPolicy::default()
+        });
+        JjCommandSpec::render_read_only([
+            OP_COMMAND,
+            LOG_COMMAND,
+            "--no-graph",
+            "-n",
+            "1",
+            TEMPLATE_FLAG,
+            CURRENT_ID_TEMPLATE,
+        ])
+        .with_global_options(global_options)
+        .with_title("jj op log -n 1")
+    }
+
+    /// Loads the user name `jj` records on new operations, from `operation.username`.
+    ///
+    /// jj exits unsuccessfully when the key is unset, so a failed exit returns `None` rather than
+    /// an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `jj` cannot be executed.
+    pub fn load_operation_username_with_runner(
+        &self,
+        runner: &mut impl JjCommandRunner,
+    ) -> Result<Option<String>, JjOperationError> {
+        let output = runner.run(&self.operation_username_spec())?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(ToOwned::to_owned))
+    }
+
+    /// Returns the read-only `jj config get operation.username` spec.
+    #[must_use]
+    pub fn operation_username_spec(&self) -> JjCommandSpec {
+        let global_options = self
+            .global_options
+            .clone()
+            .with_working_copy(WorkingCopyPolicy::Ignore)
+            .with_output(OutputPolicy {
+                color: ColorPolicy::Never,
+                ..OutputPolicy::default()
+            });
+        JjCommandSpec::render_read_only(["config", "get", OPERATION_USERNAME_KEY])
+            .with_global_options(global_options)
+            .with_title(format!("jj config get {OPERATION_USERNAME_KEY}"))
+    }
+
+    /// Loads a plain-text `jj op diff` summary of everything since `operation`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `jj` cannot be executed or exits unsuccessfully.
+    pub fn load_since_summary_with_runner(
+        &self,
+        operation: &str,
+        runner: &mut impl JjCommandRunner,
+    ) -> Result<String, JjOperationError> {
+        Self::run(runner, &self.since_summary_spec(operation))
+    }
+
+    /// Returns the uncolored `jj op diff --from <operation> --to @` spec used for summaries.
+    #[must_use]
+    pub fn since_summary_spec(&self, operation: &str) -> JjCommandSpec {
+        let global_options = self
+            .global_options
+            .clone()
+            .with_working_copy(WorkingCopyPolicy::Ignore)
+            .with_output(OutputPolicy {
+                color: ColorPolicy::Never,
+                ..OutputPolicy::default()
+            });
+        JjCommandSpec::render_read_only([
+            OP_COMMAND,
+            DIFF_COMMAND,
+            "--from",
+            operation,
+            "--to",
+            "@",
+        ])
+        .with_global_options(global_options)
+        .with_title(format!("jj op diff --from {operation} --to @"))
+    }
+
     /// Returns the command spec for `query`.
     #[must_use]
     pub fn spec_for(&self, query: &OperationQuery) -> JjCommandSpec {
         let argv = match query {
             OperationQuery::Log => vec![OP_COMMAND, LOG_COMMAND],
             OperationQuery::Show { operation } => vec![OP_COMMAND, SHOW_COMMAND, operation],
-            OperationQuery::Diff { operation } => {
-                vec![OP_COMMAND, DIFF_COMMAND, "--operation", operation]
+            OperationQuery::Diff { operation, patch } => {
+                let mut argv = vec![OP_COMMAND, DIFF_COMMAND, "--operation", operation];
+                if *patch {
+                    argv.push(PATCH_FLAG);
+                }
+                argv
+            }
+            OperationQuery::DiffFromTo { from, to, patch } => {
+                let mut argv = vec![OP_COMMAND, DIFF_COMMAND, "--from", from, "--to", to];
+                if *patch {
+                    argv.push(PATCH_FLAG);
+                }
+                argv
             }
         };
 
@@ -146,12 +380,39 @@ pub enum JjOperationError {
     /// `jj op ...` exited unsuccessfully.
     #[error("jj op command failed: {0}")]
     CommandFailed(String),
+
+    /// A JSON operation record could not be decoded.
+    #[error("failed to parse jj op log JSON record on line {line}: {source}")]
+    Parse {
+        /// One-based output line number.
+        line: usize,
+
+        /// JSON parser error.
+        source: serde_json::Error,
+    },
+
+    /// The current operation probe printed no operation id.
+    #[error("jj op log did not report a current operation")]
+    MissingCurrentOperation,
+
+    /// The rendered jj op log rows could not be aligned with semantic records.
+    #[error(
+        "jj rendered {rendered_rows} operation rows, but the semantic op log template emitted {entries} entries"
+    )]
+    RenderedEntryMismatch {
+        /// Number of operation rows detected in the rendered jj output.
+        rendered_rows: usize,
+
+        /// Number of semantic entries emitted by the template pass.
+        entries: usize,
+    },
 }
 
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used)]
 
+    use std::collections::VecDeque;
     use std::io;
     #[cfg(unix)]
     use std::os::unix::process::ExitStatusExt;
@@ -206,6 +467,43 @@ mod tests {
         assert_eq!(spec.refresh_plan(), RefreshPlan::ReRunSpec);
     }
 
+    #[test]
+    fn op_diff_from_to_builds_read_only_render_spec() {
+        let spec = JjOperation::default().spec_for(&OperationQuery::diff_from_to("abc", "def"));
+
+        assert_eq!(
+            strings(spec.argv().to_vec()),
+            vec!["op", "diff", "--from", "abc", "--to", "def"]
+        );
+        assert_eq!(spec.title(), "jj op diff --from abc --to def");
+        assert_eq!(spec.safety(), SafetyClass::ReadOnly);
+        assert_eq!(
+            OperationQuery::diff_from_to("abc", "def").target_label(),
+            "abc..def"
+        );
+    }
+
+    #[test]
+    fn op_diff_patch_toggle_appends_patch_flag() {
+        let single = OperationQuery::diff("abc").with_patch(true);
+        let range = OperationQuery::diff_from_to("abc", "def").with_patch(true);
+
+        assert!(single.patch());
+        assert!(!single.with_patch(false).patch());
+        assert_eq!(
+            strings(JjOperation::default().spec_for(&single).argv().to_vec()),
+            vec!["op", "diff", "--operation", "abc", "--patch"]
+        );
+        assert_eq!(
+            strings(JjOperation::default().spec_for(&range).argv().to_vec()),
+            vec!["op", "diff", "--from", "abc", "--to", "def", "--patch"]
+        );
+        assert_eq!(
+            OperationQuery::show("abc").with_patch(true),
+            OperationQuery::show("abc")
+        );
+    }
+
     #[test]
     fn global_options_render_before_operation_command_family() {
         let global_options = GlobalOptions::default()
@@ -268,30 +566,152 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn load_log_aligns_semantic_operation_records_to_rendered_rows() {
+        let rendered =
+            "@  abc123 me@laptop\n│  describe commit\n○  def456 me@laptop\n│  add workspace\n";
+        let semantic = concat!(
+            "@  {\"id\":\"abc123\",\"parents\":[\"def456\"],\"user\":\"me@laptop\",",
+            "\"time_start\":\"s\",\"time_end\":\"e\",\"description\":\"describe commit\",",
+            "\"tags\":\"\",\"current\":true}\n",
+            "○  {\"id\":\"def456\",\"parents\":[],\"user\":\"me@laptop\",",
+            "\"time_start\":\"s\",\"time_end\":\"e\",\"description\":\"add workspace\",",
+            "\"tags\":\"\",\"current\":false}\n",
+        );
+        let mut runner = FakeRunner::successes(&[(rendered, ""), (semantic, "")]);
+
+        let snapshot = JjOperation::default()
+            .load_log_with_runner(&mut runner)
+            .expect("fake runner succeeds");
+
+        assert_eq!(snapshot.title(), "jj op log");
+        assert_eq!(snapshot.rendered(), rendered);
+        let ids = snapshot
+            .entries()
+            .iter()
+            .map(|entry| (entry.id(), entry.rendered_line(), entry.current()))
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![("abc123", 0, true), ("def456", 2, false)]);
+        assert_eq!(snapshot.entries()[0].user(), "me");
+        assert_eq!(snapshot.entries()[0].host(), "laptop");
+        assert_eq!(runner.argv[0], vec!["op", "log"]);
+        assert_eq!(runner.argv[1][..3], ["op", "log", "-T"]);
+        assert_eq!(
+            JjOperation::default().semantic_log_spec().title(),
+            "jj op log"
+        );
+    }
+
+    #[test]
+    fn load_current_id_reads_first_operation_id_uncolored() {
+        let mut runner = FakeRunner::success("\nabc123def456\n", "");
+
+        let operation_id = JjOperation::default()
+            .load_current_id_with_runner(&mut runner)
+            .expect("fake runner succeeds");
+
+        assert_eq!(operation_id, "abc123def456");
+        assert_eq!(
+            runner.argv,
+            vec![vec![
+                "op",
+                "log",
+                "--no-graph",
+                "-n",
+                "1",
+                "-T",
+                "id ++ \"\\n\""
+            ]]
+        );
+        let spec = JjOperation::default().current_id_spec();
+        let process_argv = strings(spec.process_argv());
+        assert!(!process_argv.contains(&"--ignore-working-copy".to_owned()));
+        assert!(process_argv.contains(&"never".to_owned()));
+    }
+
+    #[test]
+    fn load_current_id_reports_empty_output() {
+        let mut runner = FakeRunner::success("\n", "");
+
+        let error = JjOperation::default()
+            .load_current_id_with_runner(&mut runner)
+            .expect_err("no operation id");
+
+        assert!(matches!(error, JjOperationError::MissingCurrentOperation));
+    }
+
+    #[test]
+    fn load_operation_username_reads_jj_config() {
+        let mut runner = FakeRunner::success("alice\n", "");
+
+        let username = JjOperation::default()
+            .load_operation_username_with_runner(&mut runner)
+            .expect("fake runner succeeds");
+
+        assert_eq!(username.as_deref(), Some("alice"));
+        assert_eq!(
+            runner.argv,
+            vec![vec!["config", "get", "operation.username"]]
+        );
+
+        let mut runner = FakeRunner::failure("Config error: key not found\n");
+        let username = JjOperation::default()
+            .load_operation_username_with_runner(&mut runner)
+            .expect("an unset key is not an error");
+        assert_eq!(username, None);
+    }
+
+    #[test]
+    fn since_summary_diffs_from_checkpoint_to_current_operation() {
+        let mut runner = FakeRunner::success("Changed commits:\n", "");
+
+        let summary = JjOperation::default()
+            .load_since_summary_with_runner("abc123", &mut runner)
+            .expect("fake runner succeeds");
+
+        assert_eq!(summary, "Changed commits:\n");
+        assert_eq!(
+            runner.argv,
+            vec![vec!["op", "diff", "--from", "abc123", "--to", "@"]]
+        );
+        let spec = JjOperation::default().since_summary_spec("abc123");
+        assert_eq!(spec.title(), "jj op diff --from abc123 --to @");
+        assert!(strings(spec.process_argv()).contains(&"--ignore-working-copy".to_owned()));
+    }
+
     struct FakeRunner {
-        result: io::Result<Output>,
+        results: VecDeque<io::Result<Output>>,
         argv: Vec<Vec<String>>,
     }
 
     impl FakeRunner {
         fn success(stdout: &str, stderr: &str) -> Self {
+            Self::successes(&[(stdout, stderr)])
+        }
+
+        fn successes(outputs: &[(&str, &str)]) -> Self {
             Self {
-                result: Ok(Output {
-                    status: exit_status(0),
-                    stdout: stdout.as_bytes().to_vec(),
-                    stderr: stderr.as_bytes().to_vec(),
-                }),
+                results: outputs
+                    .iter()
+                    .map(|(stdout, stderr)| {
+                        Ok(Output {
+                            status: exit_status(0),
+                            stdout: stdout.as_bytes().to_vec(),
+                            stderr: stderr.as_bytes().to_vec(),
+                        })
+                    })
+                    .collect(),
                 argv: Vec::new(),
             }
         }
 
         fn failure(stderr: &str) -> Self {
             Self {
-                result: Ok(Output {
+                results: VecDeque::from([Ok(Output {
                     status: exit_status(1),
                     stdout: Vec::new(),
                     stderr: stderr.as_bytes().to_vec(),
-                }),
+                })]),
                 argv: Vec::new(),
             }
         }
@@ -300,10 +720,9 @@ mod tests {
     impl JjCommandRunner for FakeRunner {
         fn run(&mut self, spec: &JjCommandSpec) -> io::Result<Output> {
             self.argv.push(strings(spec.argv().to_vec()));
-            std::mem::replace(
-                &mut self.result,
-                Err(io::Error::other("fake runner result already consumed")),
-            )
+            self.results
+                .pop_front()
+                .unwrap_or_else(|| Err(io::Error::other("fake runner results already consumed")))
         }
     }
 
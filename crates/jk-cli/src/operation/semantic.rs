@@ -0,0 +1,203 @@
+//! Semantic operation log parsing from a narrow `jj op log` template.
+//!
+//! The template emits one JSON object per operation row. Like the commit log side channel, the
+//! parser tolerates graph prefixes before the object so `jj` can keep its own graph layout.
+
+use jk_core::OperationEntry;
+use serde::Deserialize;
+
+use super::JjOperationError;
+use crate::log::commit_row_lines;
+
+/// `jj op log` template used to emit semantic operation records.
+///
+/// Fields are assembled explicitly from string-valued template methods so the record shape does not
+/// depend on how a given `jj` version serializes operation objects.
+pub(super) const OPERATION_LOG_TEMPLATE: &str = concat!(
+    "\"{\\\"id\\\":\" ++ json(stringify(self.id()))",
+    " ++ \",\\\"parents\\\":[\" ++ self.parents().map(|p| json(stringify(p.id()))).join(\",\") ++ \"]\"",
+    " ++ \",\\\"user\\\":\" ++ json(self.user())",
+    " ++ \",\\\"time_start\\\":\" ++ json(self.time().start().format(\"%Y-%m-%dT%H:%M:%S%:z\"))",
+    " ++ \",\\\"time_end\\\":\" ++ json(self.time().end().format(\"%Y-%m-%dT%H:%M:%S%:z\"))",
+    " ++ \",\\\"description\\\":\" ++ json(self.description())",
+    " ++ \",\\\"tags\\\":\" ++ json(self.tags())",
+    " ++ \",\\\"current\\\":\" ++ if(self.current_operation(), \"true\", \"false\")",
+    " ++ \"}\\n\"",
+);
+
+/// Operation fields emitted by [`OPERATION_LOG_TEMPLATE`].
+#[derive(Debug, Deserialize)]
+struct JjOperationRecord {
+    id: String,
+    parents: Vec<String>,
+    user: String,
+    time_start: String,
+    time_end: String,
+    description: String,
+    tags: String,
+    current: bool,
+}
+
+/// Parses semantic operation records emitted by [`OPERATION_LOG_TEMPLATE`].
+pub(super) fn parse_operation_json_lines(
+    stdout: &str,
+) -> Result<Vec<OperationEntry>, JjOperationError> {
+    let mut entries = Vec::new();
+
+    for (index, line) in stdout.lines().enumerate() {
+        let Some(json_start) = line.find('{') else {
+            continue;
+        };
+
+        let record: JjOperationRecord =
+            serde_json::from_str(&line[json_start..]).map_err(|source| {
+                JjOperationError::Parse {
+                    line: index + 1,
+                    source,
+                }
+            })?;
+        let (user, host) = split_user_host(&record.user);
+        entries.push(
+            OperationEntry::new(record.id, record.description)
+                .with_parents(record.parents)
+                .with_origin(user, host)
+                .with_time_range(record.time_start, record.time_end)
+                .with_tags(parse_tags(&record.tags))
+                .with_current(record.current),
+        );
+    }
+
+    Ok(entries)
+}
+
+/// Assigns rendered `jj op log` line numbers to semantic operation entries.
+pub(super) fn assign_rendered_lines(
+    entries: Vec<OperationEntry>,
+    rendered: &str,
+) -> Result<Vec<OperationEntry>, JjOperationError> {
+    let rendered_lines = commit_row_lines(rendered).collect::<Vec<_>>();
+    if rendered_lines.len() != entries.len() {
+        return Err(JjOperationError::RenderedEntryMismatch {
+            rendered_rows: rendered_lines.len(),
+            entries: entries.len(),
+        });
+    }
+
+    Ok(entries
+        .into_iter()
+        .zip(rendered_lines)
+        .map(|(entry, rendered_line)| entry.with_rendered_line(rendered_line))
+        .collect())
+}
+
+/// Splits jj's `user@host` operation origin.
+fn split_user_host(origin: &str) -> (&str, &str) {
+    origin.rsplit_once('@').unwrap_or((origin, ""))
+}
+
+/// Parses jj's `key: value` operation tag lines.
+fn parse_tags(tags: &str) -> Vec<(String, String)> {
+    tags.lines()
+        .filter_map(|line| line.split_once(": "))
+        .map(|(key, value)| (key.trim().to_owned(), value.to_owned()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_operation_records_behind_graph_prefixes() -> Result<(), JjOperationError> {
+        let output = concat!(
+            "@  {\"id\":\"abc123\",\"parents\":[\"def456\"],\"user\":\"me@laptop\",",
+            "\"time_start\":\"2026-01-02T03:04:05+00:00\",\"time_end\":\"2026-01-02T03:04:06+00:00\",",
+            "\"description\":\"describe commit 111\",\"tags\":\"args: jj describe -m x\",\"current\":true}\n",
+            "○  {\"id\":\"def456\",\"parents\":[],\"user\":\"bot\",\"time_start\":\"a\",\"time_end\":\"b\",",
+            "\"description\":\"\",\"tags\":\"\",\"current\":false}\n",
+        );
+
+        let entries = parse_operation_json_lines(output)?;
+
+        assert_eq!(
+            entries,
+            vec![
+                OperationEntry::new("abc123", "describe commit 111")
+                    .with_parents(vec!["def456".to_owned()])
+                    .with_origin("me", "laptop")
+                    .with_time_range("2026-01-02T03:04:05+00:00", "2026-01-02T03:04:06+00:00")
+                    .with_tags(vec![("args".to_owned(), "jj describe -m x".to_owned())])
+                    .with_current(true),
+                OperationEntry::new("def456", "")
+                    .with_origin("bot", "")
+                    .with_time_range("a", "b"),
+            ]
+        );
+        assert_eq!(entries[0].tag("args"), Some("jj describe -m x"));
+        Ok(())
+    }
+
+    #[test]
+    fn assigns_rendered_lines_from_operation_rows() -> Result<(), JjOperationError> {
+        let rendered = concat!(
+            "\u{1b}[1m@\u{1b}[0m  abc123 me@laptop 1 minute ago\n",
+            "│  describe commit 111\n",
+            "○  def456 bot 2 minutes ago\n",
+            "│  snapshot working copy\n",
+        );
+        let entries = vec![
+            OperationEntry::new("abc123", ""),
+            OperationEntry::new("def456", ""),
+        ];
+
+        let entries = assign_rendered_lines(entries, rendered)?;
+
+        assert_eq!(entries[0].rendered_line(), 0);
+        assert_eq!(entries[1].rendered_line(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn assigns_rendered_lines_to_alternate_node_glyph_rows() -> Result<(), JjOperationError> {
+        let rendered = "@  abc123 me@laptop\n│  describe\n◉  def456 me@laptop\n│  snapshot\n";
+        let entries = vec![
+            OperationEntry::new("abc123", ""),
+            OperationEntry::new("def456", ""),
+        ];
+
+        let entries = assign_rendered_lines(entries, rendered)?;
+
+        assert_eq!(entries[1].rendered_line(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_rendered_output_with_missing_operation_rows() {
+        let entries = vec![
+            OperationEntry::new("abc123", ""),
+            OperationEntry::new("def456", ""),
+        ];
+
+        let error = assign_rendered_lines(entries, "@  abc123\n│  only one row\n").err();
+
+        assert!(matches!(
+            error,
+            Some(JjOperationError::RenderedEntryMismatch {
+                rendered_rows: 1,
+                entries: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn reports_parse_line_number() {
+        let output = "@  {\"id\":\"abc\"}\n";
+
+        let error = parse_operation_json_lines(output).err();
+
+        assert!(matches!(
+            error,
+            Some(JjOperationError::Parse { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,108 @@
+//! `jj rebase` mutation command integration.
+
+use jk_core::{GlobalOptions, JjCommandSpec, RefreshPlan, SafetyClass};
+
+const REBASE_COMMAND: &str = "rebase";
+
+/// Rebase revisions onto a destination.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebaseQuery {
+    revisions: Vec<String>,
+    destination: String,
+}
+
+impl RebaseQuery {
+    /// Creates a `jj rebase -r REVISIONS... -d DESTINATION` query.
+    #[must_use]
+    pub fn new(
+        revisions: impl IntoIterator<Item = impl Into<String>>,
+        destination: impl Into<String>,
+    ) -> Self {
+        Self {
+            revisions: revisions.into_iter().map(Into::into).collect(),
+            destination: destination.into(),
+        }
+    }
+
+    /// Returns the revisions moved by the rebase.
+    #[must_use]
+    pub fn revisions(&self) -> &[String] {
+        &self.revisions
+    }
+
+    /// Returns the destination revision.
+    #[must_use]
+    pub fn destination(&self) -> &str {
+        &self.destination
+    }
+}
+
+/// Builds typed `jj rebase` mutation specs.
+#[derive(Clone, Debug, Default)]
+pub struct JjRebase {
+    global_options: GlobalOptions,
+}
+
+impl JjRebase {
+    /// Sets the repository path passed to `jj --repository`.
+    #[must_use]
+    pub fn with_repository(mut self, repository: impl Into<std::path::PathBuf>) -> Self {
+        self.global_options = self.global_options.with_repository(repository);
+        self
+    }
+
+    /// Returns the command spec for `query`.
+    ///
+    /// Each revision gets its own `-r` so descendants stay where they are, matching how the log
+    /// marks individual revisions rather than whole branches.
+    #[must_use]
+    pub fn spec_for(&self, query: &RebaseQuery) -> JjCommandSpec {
+        let mut argv = Vec::with_capacity(query.revisions.len() * 2 + 3);
+        argv.push(REBASE_COMMAND.to_owned());
+        for revision in &query.revisions {
+            argv.push("-r".to_owned());
+            argv.push(revision.clone());
+        }
+        argv.push("-d".to_owned());
+        argv.push(query.destination.clone());
+
+        JjCommandSpec::confirm_mutation(argv, SafetyClass::LocalRewrite)
+            .with_global_options(self.global_options.clone())
+            .with_title(format!(
+                "jj rebase -r {} -d {}",
+                query.revisions.join(" -r "),
+                query.destination
+            ))
+            .with_refresh_plan(RefreshPlan::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use jk_core::ExecutionMode;
+
+    use super::*;
+
+    fn strings(args: &[OsString]) -> Vec<String> {
+        args.iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn rebase_builds_confirmed_local_rewrite_spec() {
+        let query = RebaseQuery::new(["abc123", "def456"], "trunk");
+        let spec = JjRebase::default().spec_for(&query);
+
+        assert_eq!(
+            strings(spec.argv()),
+            vec!["rebase", "-r", "abc123", "-r", "def456", "-d", "trunk"]
+        );
+        assert_eq!(spec.title(), "jj rebase -r abc123 -r def456 -d trunk");
+        assert_eq!(spec.mode(), ExecutionMode::ConfirmMutation);
+        assert_eq!(spec.safety(), SafetyClass::LocalRewrite);
+        assert_eq!(spec.refresh_plan(), RefreshPlan::None);
+    }
+}
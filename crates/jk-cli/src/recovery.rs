@@ -4,6 +4,8 @@ use jk_core::{GlobalOptions, JjCommandSpec, RefreshPlan, SafetyClass};
 
 const UNDO_COMMAND: &str = "undo";
 const REDO_COMMAND: &str = "redo";
+const OP_COMMAND: &str = "op";
+const RESTORE_COMMAND: &str = "restore";
 
 /// Local recovery command supported by the first undo/redo preview surface.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -52,6 +54,17 @@ impl JjRecovery {
             .with_title(command.title())
             .with_refresh_plan(RefreshPlan::None)
     }
+
+    /// Returns the `jj op restore OPERATION` spec used to return to a checkpoint.
+    #[must_use]
+    pub fn restore_spec(&self, operation: &str) -> JjCommandSpec {
+        JjCommandSpec::confirm_mutation(
+            [OP_COMMAND, RESTORE_COMMAND, operation],
+            SafetyClass::LocalRewrite,
+        )
+        .with_global_options(self.global_options.clone())
+        .with_refresh_plan(RefreshPlan::None)
+    }
 }
 
 #[cfg(test)]
@@ -89,6 +102,17 @@ mod tests {
         assert_eq!(spec.mode(), ExecutionMode::ConfirmMutation);
     }
 
+    #[test]
+    fn restore_builds_confirmed_local_rewrite_spec() {
+        let spec = JjRecovery::default().restore_spec("abc123");
+
+        assert_eq!(strings(spec.argv()), vec!["op", "restore", "abc123"]);
+        assert_eq!(spec.title(), "jj op restore abc123");
+        assert_eq!(spec.safety(), SafetyClass::LocalRewrite);
+        assert_eq!(spec.mode(), ExecutionMode::ConfirmMutation);
+        assert_eq!(spec.refresh_plan(), RefreshPlan::None);
+    }
+
     #[test]
     fn repository_renders_before_recovery_command() {
         let spec = JjRecovery::default()
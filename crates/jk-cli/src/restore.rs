@@ -0,0 +1,162 @@
+//! `jj restore` mutation command integration.
+
+use jk_core::{GlobalOptions, JjCommandSpec, RefreshPlan, SafetyClass};
+
+const RESTORE_COMMAND: &str = "restore";
+
+/// Which revisions a restore reads from and writes into.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum RestoreTarget {
+    /// Undo the changes a revision makes, restoring its parent's content.
+    ChangesIn(String),
+    /// Restore content from one revision into another.
+    FromInto { from: String, into: String },
+}
+
+/// Restore paths to an earlier state.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RestoreQuery {
+    target: RestoreTarget,
+    paths: Vec<String>,
+}
+
+impl RestoreQuery {
+    /// Creates a `jj restore --changes-in REV PATHS...` query.
+    #[must_use]
+    pub fn changes_in(
+        rev: impl Into<String>,
+        paths: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            target: RestoreTarget::ChangesIn(rev.into()),
+            paths: paths.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Creates a `jj restore --from FROM --into INTO PATHS...` query.
+    #[must_use]
+    pub fn from_into(
+        from: impl Into<String>,
+        into: impl Into<String>,
+        paths: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            target: RestoreTarget::FromInto {
+                from: from.into(),
+                into: into.into(),
+            },
+            paths: paths.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns the restored paths.
+    #[must_use]
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+}
+
+/// Builds typed `jj restore` mutation specs.
+#[derive(Clone, Debug, Default)]
+pub struct JjRestore {
+    global_options: GlobalOptions,
+}
+
+impl JjRestore {
+    /// Sets the repository path passed to `jj --repository`.
+    #[must_use]
+    pub fn with_repository(mut self, repository: impl Into<std::path::PathBuf>) -> Self {
+        self.global_options = self.global_options.with_repository(repository);
+        self
+    }
+
+    /// Returns the command spec for `query`.
+    #[must_use]
+    pub fn spec_for(&self, query: &RestoreQuery) -> JjCommandSpec {
+        let mut argv = vec![RESTORE_COMMAND.to_owned()];
+        match &query.target {
+            RestoreTarget::ChangesIn(rev) => {
+                argv.push("--changes-in".to_owned());
+                argv.push(rev.clone());
+            }
+            RestoreTarget::FromInto { from, into } => {
+                argv.push("--from".to_owned());
+                argv.push(from.clone());
+                argv.push("--into".to_owned());
+                argv.push(into.clone());
+            }
+        }
+        argv.extend(query.paths.iter().map(|path| fileset_path(path)));
+        let title = format!("jj {}", argv.join(" "));
+
+        JjCommandSpec::confirm_mutation(argv, SafetyClass::LocalRewrite)
+            .with_global_options(self.global_options.clone())
+            .with_title(title)
+            .with_refresh_plan(RefreshPlan::None)
+    }
+}
+
+/// Returns `path` as a fileset argument that matches exactly that path.
+///
+/// Plain paths pass through as bare strings; anything jj's fileset grammar could read as an
+/// operator is quoted as a `file:` pattern instead.
+pub(crate) fn fileset_path(path: &str) -> String {
+    let bare = !path.is_empty()
+        && path
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || "/._-".contains(character));
+    if bare {
+        return path.to_owned();
+    }
+    let escaped = path.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("file:\"{escaped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use jk_core::ExecutionMode;
+
+    use super::*;
+
+    fn strings(args: &[OsString]) -> Vec<String> {
+        args.iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn restore_changes_in_builds_local_rewrite_spec() {
+        let query = RestoreQuery::changes_in("abc123", ["src/a.rs", "docs/b c.md"]);
+        let spec = JjRestore::default().spec_for(&query);
+
+        assert_eq!(
+            strings(spec.argv()),
+            vec![
+                "restore",
+                "--changes-in",
+                "abc123",
+                "src/a.rs",
+                "file:\"docs/b c.md\""
+            ]
+        );
+        assert_eq!(
+            spec.title(),
+            "jj restore --changes-in abc123 src/a.rs file:\"docs/b c.md\""
+        );
+        assert_eq!(spec.mode(), ExecutionMode::ConfirmMutation);
+        assert_eq!(spec.safety(), SafetyClass::LocalRewrite);
+    }
+
+    #[test]
+    fn restore_from_into_names_both_revisions() {
+        let query = RestoreQuery::from_into("aaa", "bbb", ["src/a.rs"]);
+        let spec = JjRestore::default().spec_for(&query);
+
+        assert_eq!(
+            strings(spec.argv()),
+            vec!["restore", "--from", "aaa", "--into", "bbb", "src/a.rs"]
+        );
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::path::PathBuf;
 
-use jk_core::{InspectionSnapshot, JjCommandSpec};
+use jk_core::{GlobalOptions, InspectionSnapshot, JjCommandSpec, WorkingCopyPolicy};
 use thiserror::Error;
 
 use crate::command::{JjCommandRunner, SystemJjCommandRunner};
@@ -49,6 +49,7 @@ impl From<String> for ShowQuery {
 #[derive(Clone, Debug, Default)]
 pub struct JjShow {
     repository: Option<PathBuf>,
+    working_copy: Option<WorkingCopyPolicy>,
 }
 
 impl JjShow {
@@ -59,6 +60,16 @@ impl JjShow {
         self
     }
 
+    /// Sets how `jj` treats the working copy; unset uses jj's default snapshot.
+    ///
+    /// [`WorkingCopyPolicy::Ignore`] reads the last snapshot, which lets views opened right after a
+    /// log refresh replay cached output.
+    #[must_use]
+    pub const fn with_working_copy(mut self, working_copy: WorkingCopyPolicy) -> Self {
+        self.working_copy = Some(working_copy);
+        self
+    }
+
     /// Loads the rendered show output for `query`.
     ///
     /// # Errors
@@ -90,7 +101,11 @@ impl JjShow {
         argv.push(SHOW_COMMAND);
         argv.extend(query.revs().iter().map(String::as_str));
 
-        let spec = JjCommandSpec::render_read_only(argv);
+        let mut spec = JjCommandSpec::render_read_only(argv);
+        if let Some(working_copy) = self.working_copy {
+            spec =
+                spec.with_global_options(GlobalOptions::default().with_working_copy(working_copy));
+        }
         if let Some(repository) = &self.repository {
             spec.with_repository(repository)
         } else {
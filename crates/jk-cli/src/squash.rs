@@ -0,0 +1,158 @@
+//! `jj squash` mutation command integration.
+
+use jk_core::{GlobalOptions, JjCommandSpec, RefreshPlan, SafetyClass};
+
+use crate::restore::fileset_path;
+
+const SQUASH_COMMAND: &str = "squash";
+
+/// Move changes from one revision into another.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SquashQuery {
+    from: String,
+    into: String,
+    paths: Vec<String>,
+}
+
+impl SquashQuery {
+    /// Creates a `jj squash --from FROM --into INTO` query.
+    #[must_use]
+    pub fn new(from: impl Into<String>, into: impl Into<String>) -> Self {
+        Self {
+            from: from.into(),
+            into: into.into(),
+            paths: Vec::new(),
+        }
+    }
+
+    /// Limits the squash to `paths`; an empty list moves every change.
+    #[must_use]
+    pub fn with_paths(mut self, paths: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Returns the revision whose changes are moved.
+    #[must_use]
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    /// Returns the revision receiving the changes.
+    #[must_use]
+    pub fn into(&self) -> &str {
+        &self.into
+    }
+
+    /// Returns the paths the squash is limited to.
+    #[must_use]
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+}
+
+/// Builds typed `jj squash` mutation specs.
+#[derive(Clone, Debug, Default)]
+pub struct JjSquash {
+    global_options: GlobalOptions,
+}
+
+impl JjSquash {
+    /// Sets the repository path passed to `jj --repository`.
+    #[must_use]
+    pub fn with_repository(mut self, repository: impl Into<std::path::PathBuf>) -> Self {
+        self.global_options = self.global_options.with_repository(repository);
+        self
+    }
+
+    /// Returns the command spec for `query`.
+    ///
+    /// `--use-destination-message` keeps the spec non-interactive; otherwise jj would open an
+    /// editor to combine two non-empty descriptions.
+    #[must_use]
+    pub fn spec_for(&self, query: &SquashQuery) -> JjCommandSpec {
+        let paths = query
+            .paths
+            .iter()
+            .map(|path| fileset_path(path))
+            .collect::<Vec<_>>();
+        let mut argv = vec![
+            SQUASH_COMMAND.to_owned(),
+            "--from".to_owned(),
+            query.from.clone(),
+            "--into".to_owned(),
+            query.into.clone(),
+            "--use-destination-message".to_owned(),
+        ];
+        argv.extend(paths.iter().cloned());
+        let mut title = format!("jj squash --from {} --into {}", query.from, query.into);
+        for path in &paths {
+            title.push(' ');
+            title.push_str(path);
+        }
+
+        JjCommandSpec::confirm_mutation(argv, SafetyClass::LocalRewrite)
+            .with_global_options(self.global_options.clone())
+            .with_title(title)
+            .with_refresh_plan(RefreshPlan::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use jk_core::ExecutionMode;
+
+    use super::*;
+
+    fn strings(args: &[OsString]) -> Vec<String> {
+        args.iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn squash_builds_non_interactive_local_rewrite_spec() {
+        let query = SquashQuery::new("abc123", "def456");
+        let spec = JjSquash::default().spec_for(&query);
+
+        assert_eq!(
+            strings(spec.argv()),
+            vec![
+                "squash",
+                "--from",
+                "abc123",
+                "--into",
+                "def456",
+                "--use-destination-message"
+            ]
+        );
+        assert_eq!(spec.title(), "jj squash --from abc123 --into def456");
+        assert_eq!(spec.mode(), ExecutionMode::ConfirmMutation);
+        assert_eq!(spec.safety(), SafetyClass::LocalRewrite);
+    }
+
+    #[test]
+    fn squash_with_paths_moves_only_those_files() {
+        let query = SquashQuery::new("abc123", "abc123-").with_paths(["src/a.rs"]);
+        let spec = JjSquash::default().spec_for(&query);
+
+        assert_eq!(
+            strings(spec.argv()),
+            vec![
+                "squash",
+                "--from",
+                "abc123",
+                "--into",
+                "abc123-",
+                "--use-destination-message",
+                "src/a.rs"
+            ]
+        );
+        assert_eq!(
+            spec.title(),
+            "jj squash --from abc123 --into abc123- src/a.rs"
+        );
+    }
+}
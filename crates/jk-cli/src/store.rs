@@ -0,0 +1,46 @@
+//! Locating the jj repository store behind a workspace.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Returns the jj repository store for the workspace containing `start`.
+///
+/// Secondary workspaces keep a `.jj/repo` file pointing at the shared store instead of a
+/// directory, so every workspace of a repository resolves to the same path.
+#[must_use]
+pub fn repository_store(start: &Path) -> Option<PathBuf> {
+    let start = fs::canonicalize(start).ok()?;
+    let jj_dir = start
+        .ancestors()
+        .map(|dir| dir.join(".jj"))
+        .find(|dir| dir.is_dir())?;
+    let repo = jj_dir.join("repo");
+    let store = if repo.is_file() {
+        jj_dir.join(fs::read_to_string(&repo).ok()?.trim())
+    } else {
+        repo
+    };
+    fs::canonicalize(&store).ok().or(Some(store))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secondary_workspaces_share_the_main_store() {
+        let dir = std::env::temp_dir().join(format!("jk-repository-store-{}", std::process::id()));
+        let main_store = dir.join("main").join(".jj").join("repo");
+        let secondary = dir.join("secondary");
+        let _ = fs::create_dir_all(&main_store);
+        let _ = fs::create_dir_all(secondary.join(".jj"));
+        let _ = fs::create_dir_all(secondary.join("src"));
+        let _ = fs::write(secondary.join(".jj").join("repo"), "../../main/.jj/repo");
+
+        assert_eq!(
+            repository_store(&secondary.join("src")),
+            fs::canonicalize(&main_store).ok()
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -372,7 +372,12 @@ impl JjWorkspaces {
         }
     }
 
-    fn load_current_root_with_runner(
+    /// Loads the root of the workspace `jj` resolves for this source, via `jj root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `jj` cannot be executed or exits unsuccessfully.
+    pub fn load_current_root_with_runner(
         &self,
         runner: &mut impl JjCommandRunner,
     ) -> Result<PathBuf, JjWorkspacesError> {
@@ -211,6 +211,8 @@ pub struct CommandPreview {
     pub refresh_plan: RefreshPlan,
     /// User-visible warnings inferred from the command spec.
     pub warnings: Vec<CommandPreviewWarning>,
+    /// Extra plain-text context gathered before confirmation, such as a change summary.
+    pub details: Vec<String>,
 }
 
 impl CommandPreview {
@@ -225,9 +227,53 @@ impl CommandPreview {
             safety: spec.safety(),
             refresh_plan: spec.refresh_plan(),
             warnings: CommandPreviewWarning::from_spec(spec),
+            details: Vec::new(),
         }
     }
 
+    /// Adds plain-text context lines shown beside the command summary.
+    #[must_use]
+    pub fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// Adds a warning that needs context the spec alone does not carry.
+    #[must_use]
+    pub fn with_warning(mut self, warning: CommandPreviewWarning) -> Self {
+        self.warnings.push(warning);
+        self
+    }
+
+    /// Rebuilds the preview with a different immutability policy.
+    ///
+    /// The command line and spec-derived warnings follow the new policy; details and warnings
+    /// added with [`Self::with_warning`] are kept, so the preview still names the immutable
+    /// targets that `--ignore-immutable` would rewrite.
+    #[must_use]
+    pub fn with_immutability(self, immutability: ImmutabilityPolicy) -> Self {
+        let spec_warnings = CommandPreviewWarning::from_spec(&self.spec);
+        let global_options = self
+            .spec
+            .global_options()
+            .clone()
+            .with_immutability(immutability);
+        let spec = self.spec.with_global_options(global_options);
+        let mut preview = Self::from_spec(&spec).with_details(self.details);
+        preview.warnings.extend(
+            self.warnings
+                .into_iter()
+                .filter(|warning| !spec_warnings.contains(warning)),
+        );
+        preview
+    }
+
+    /// Returns the immutability policy the previewed command runs with.
+    #[must_use]
+    pub const fn immutability(&self) -> ImmutabilityPolicy {
+        self.spec.global_options.immutability
+    }
+
     /// Returns whether the preview represents a command that needs confirmation.
     #[must_use]
     pub const fn requires_confirmation(&self) -> bool {
@@ -262,6 +308,8 @@ pub enum CommandPreviewWarning {
     DoesNotIntegrateOperation,
     /// The command may rewrite immutable commits.
     IgnoresImmutableCommits,
+    /// The command targets these immutable revisions, which `jj` refuses to rewrite by default.
+    ImmutableTargets(Vec<String>),
 }
 
 impl CommandPreviewWarning {
@@ -383,6 +431,20 @@ impl GlobalOptions {
         self.repository.as_deref()
     }
 
+    /// Returns whether `jj` snapshots the working copy before running.
+    ///
+    /// `--at-operation` implies `--ignore-working-copy`, so only the latest operation snapshots.
+    #[must_use]
+    pub const fn snapshots_working_copy(&self) -> bool {
+        matches!(
+            (&self.working_copy, &self.operation),
+            (
+                WorkingCopyPolicy::SnapshotAndUpdate,
+                OperationLoadPolicy::Latest
+            )
+        )
+    }
+
     /// Returns global `jj` arguments in canonical render order.
     #[must_use]
     pub fn argv(&self) -> Vec<OsString> {
@@ -761,6 +823,9 @@ mod tests {
         let global_options = GlobalOptions::default()
             .with_working_copy(WorkingCopyPolicy::Ignore)
             .with_operation(OperationLoadPolicy::AtOperation("abc123".to_owned()));
+
+        assert!(GlobalOptions::default().snapshots_working_copy());
+        assert!(!global_options.snapshots_working_copy());
         let spec = JjCommandSpec::render_read_only(["status"]).with_global_options(global_options);
 
         assert_eq!(
@@ -939,6 +1004,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn switching_immutability_keeps_added_warnings_and_details() {
+        let preview =
+            JjCommandSpec::confirm_mutation(["abandon", "abc"], SafetyClass::LocalRewrite)
+                .command_preview()
+                .with_details(vec!["abc summary".to_owned()])
+                .with_warning(CommandPreviewWarning::ImmutableTargets(vec![
+                    "abc".to_owned(),
+                ]));
+
+        let ignored = preview
+            .clone()
+            .with_immutability(ImmutabilityPolicy::Ignore);
+
+        assert_eq!(ignored.immutability(), ImmutabilityPolicy::Ignore);
+        assert!(ignored.command_line.contains("--ignore-immutable"));
+        assert_eq!(
+            ignored.warnings,
+            vec![
+                CommandPreviewWarning::LocalRewrite,
+                CommandPreviewWarning::IgnoresImmutableCommits,
+                CommandPreviewWarning::ImmutableTargets(vec!["abc".to_owned()])
+            ]
+        );
+        assert_eq!(ignored.details, ["abc summary"]);
+        assert_eq!(
+            ignored.with_immutability(ImmutabilityPolicy::Enforce),
+            preview
+        );
+    }
+
     #[test]
     fn metadata_builders_preserve_argv() {
         let spec = JjCommandSpec::render_read_only(["diff"])
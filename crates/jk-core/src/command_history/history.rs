@@ -77,6 +77,17 @@ impl CommandHistory {
         id
     }
 
+    /// Moves every record from `other` into this history, oldest first.
+    ///
+    /// Background loads record into their own history and hand it back to the UI thread, which
+    /// merges it here. Moved records get fresh ids from this history.
+    pub fn append_from(&mut self, other: Self) {
+        for mut record in other.records {
+            record.id = self.allocate_id();
+            self.push_record(record);
+        }
+    }
+
     /// Returns retained records from oldest to newest.
     #[must_use]
     pub fn records(&self) -> impl DoubleEndedIterator<Item = &CommandRecord> {
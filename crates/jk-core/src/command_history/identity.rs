@@ -170,6 +170,8 @@ pub enum SourceAction {
     Refresh,
     /// Open a diff.
     OpenDiff,
+    /// Open an interdiff between two marked revisions.
+    OpenInterdiff,
     /// Open a show view.
     OpenShow,
     /// Open a status view.
@@ -184,6 +186,18 @@ pub enum SourceAction {
     NewRevision,
     /// Move the working copy to the selected revision.
     EditRevision,
+    /// Load candidates for the fuzzy revision finder.
+    FindRevision,
+    /// Rebase revisions onto a picked destination.
+    RebaseRevision,
+    /// Squash a revision into a picked target.
+    SquashRevision,
+    /// Restore selected paths in a diff.
+    RestorePaths,
+    /// Find the workspace root to open a diff file in an editor.
+    OpenInEditor,
+    /// Load a diff as a git patch to copy or save.
+    CopyPatch,
     /// List workspaces.
     WorkspaceList,
     /// Show selected workspace status.
@@ -204,6 +218,26 @@ pub enum SourceAction {
     Undo,
     /// Redo the latest undone operation.
     Redo,
+    /// Record a session checkpoint.
+    Checkpoint,
+    /// Restore the repository to a session checkpoint.
+    RestoreCheckpoint,
+    /// Reload the log with a user-entered revset.
+    SetRevset,
+    /// Load `[jk.revsets]` presets for view options.
+    LoadRevsetPresets,
+    /// Load configured diff tools for view options.
+    LoadDiffTools,
+    /// Load jj's `operation.username` for the operation log user filter.
+    LoadOperationUser,
+    /// Reload the log one page longer.
+    LoadMoreLog,
+    /// Load the diff preview beside the log.
+    PreviewDiff,
+    /// Resolve a diff's revisions to change ids for its review notes.
+    ResolveNoteTarget,
+    /// Load repository names for prompt completion.
+    Complete,
     /// Run a user-entered `jj` command.
     UserJjCommand,
     /// A source action not yet modeled.
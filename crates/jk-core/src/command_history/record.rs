@@ -31,6 +31,8 @@ pub struct CommandRecord {
     pub execution_mode: ExecutionMode,
     /// Operation id reported by `jj`, when cheaply available.
     pub operation_id: Option<String>,
+    /// Whether the result was replayed from the output cache instead of a fresh `jj` run.
+    pub cache_hit: bool,
 }
 
 impl CommandRecord {
@@ -51,6 +53,7 @@ impl CommandRecord {
             safety: input.safety,
             execution_mode: input.execution_mode,
             operation_id: None,
+            cache_hit: false,
         }
     }
 
@@ -68,6 +71,7 @@ impl CommandRecord {
         self.timing.duration = Some(duration);
         self.result = finish.result;
         self.operation_id = finish.operation_id;
+        self.cache_hit = finish.cache_hit;
         true
     }
 }
@@ -133,6 +137,8 @@ pub struct CommandRecordFinish {
     pub result: CommandResultSummary,
     /// Operation id reported by `jj`, when cheaply available.
     pub operation_id: Option<String>,
+    /// Whether the result was replayed from the output cache instead of a fresh `jj` run.
+    pub cache_hit: bool,
 }
 
 impl CommandRecordFinish {
@@ -147,9 +153,17 @@ impl CommandRecordFinish {
             ended_at,
             result,
             operation_id,
+            cache_hit: false,
         }
     }
 
+    /// Marks the result as replayed from the output cache.
+    #[must_use]
+    pub const fn with_cache_hit(mut self, cache_hit: bool) -> Self {
+        self.cache_hit = cache_hit;
+        self
+    }
+
     /// Creates finish data for a process exit code and captured output.
     #[must_use]
     pub fn from_exit_code(
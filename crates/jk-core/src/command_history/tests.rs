@@ -116,6 +116,31 @@ fn history_limit_drops_oldest_records_first() {
     );
 }
 
+#[test]
+fn append_from_moves_background_records_with_fresh_ids() {
+    let spec = JjCommandSpec::render_read_only(["diff"]);
+    let mut history = CommandHistory::new(4);
+    history.append(
+        start_from_spec(&spec, source(SourceView::Log, SourceAction::InitialLoad)),
+        CommandRecordFinish::from_exit_code(0, "log", "", finish_at()),
+    );
+    let mut background = CommandHistory::new(4);
+    background.append(
+        start_from_spec(&spec, source(SourceView::Log, SourceAction::PreviewDiff)),
+        CommandRecordFinish::from_exit_code(0, "diff", "", finish_at()),
+    );
+
+    history.append_from(background);
+
+    assert_eq!(
+        history
+            .records()
+            .map(|record| (record.id.get(), record.result.stdout.snippet.as_str()))
+            .collect::<Vec<_>>(),
+        vec![(1, "log"), (2, "diff")]
+    );
+}
+
 #[test]
 fn identity_uses_argv_and_global_options_from_spec() {
     let options = GlobalOptions::default()
@@ -28,6 +28,7 @@ pub struct LogSnapshot {
     title: String,
     rendered: String,
     entries: Vec<LogEntry>,
+    more_available: bool,
 }
 
 impl LogSnapshot {
@@ -41,9 +42,23 @@ impl LogSnapshot {
             title: String::new(),
             rendered: rendered.into(),
             entries,
+            more_available: false,
         }
     }
 
+    /// Marks the snapshot as one page of a longer log that can load more entries.
+    #[must_use]
+    pub const fn with_more_available(mut self, more_available: bool) -> Self {
+        self.more_available = more_available;
+        self
+    }
+
+    /// Returns whether the log was cut off at its page limit.
+    #[must_use]
+    pub const fn more_available(&self) -> bool {
+        self.more_available
+    }
+
     /// Sets the command context shown in the title bar.
     #[must_use]
     pub fn with_title(mut self, title: impl Into<String>) -> Self {
@@ -83,6 +98,7 @@ pub struct DiffSnapshot {
     change_id: String,
     rendered: String,
     file_stats: Vec<DiffFileStat>,
+    range: Option<(String, String)>,
 }
 
 impl DiffSnapshot {
@@ -94,6 +110,7 @@ impl DiffSnapshot {
             change_id: change_id.into(),
             rendered: rendered.into(),
             file_stats: Vec::new(),
+            range: None,
         }
     }
 
@@ -111,12 +128,27 @@ impl DiffSnapshot {
         self
     }
 
+    /// Marks the snapshot as a comparison between two revisions.
+    #[must_use]
+    pub fn with_range(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.range = Some((from.into(), to.into()));
+        self
+    }
+
     /// Returns the human-readable command context for the current view.
     #[must_use]
     pub fn title(&self) -> &str {
         &self.title
     }
 
+    /// Returns the compared revisions when the diff spans two revisions.
+    #[must_use]
+    pub fn range(&self) -> Option<(&str, &str)> {
+        self.range
+            .as_ref()
+            .map(|(from, to)| (from.as_str(), to.as_str()))
+    }
+
     /// Returns the `jj` change identifier being inspected.
     #[must_use]
     pub fn change_id(&self) -> &str {
@@ -247,6 +279,9 @@ impl DiffFileStat {
 }
 
 /// A semantic log item that the TUI can navigate without knowing how `jj` produced it.
+///
+/// Beyond ids and description, entries carry the graph, ref, and state metadata that navigation
+/// and previews need, so those features never have to ask `jj` again for the visible revisions.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LogEntry {
     change_id: String,
@@ -254,6 +289,72 @@ pub struct LogEntry {
     description: String,
     details: String,
     rendered_line: usize,
+    parents: Vec<String>,
+    local_bookmarks: Vec<String>,
+    remote_bookmarks: Vec<String>,
+    tags: Vec<String>,
+    author: Signature,
+    committer: Signature,
+    flags: Vec<ChangeFlag>,
+}
+
+/// Author or committer metadata as emitted by `jj`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Signature {
+    /// Display name.
+    pub name: String,
+    /// Email address.
+    pub email: String,
+    /// RFC 3339 timestamp as rendered by `jj`.
+    pub timestamp: String,
+}
+
+/// Revision state reported by `jj` template keywords.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ChangeFlag {
+    /// The change has no file modifications (`empty`).
+    Empty,
+    /// The change has unresolved conflicts (`conflict`).
+    Conflict,
+    /// The change is in `immutable_heads()` or its ancestors (`immutable`).
+    Immutable,
+    /// The change id is shared by several visible commits (`divergent`).
+    Divergent,
+    /// The commit is not visible in the current view (`hidden`).
+    Hidden,
+    /// The commit is this workspace's working-copy commit `@` (`current_working_copy`).
+    WorkingCopy,
+}
+
+impl ChangeFlag {
+    /// Returns the `jj` template keyword for this flag.
+    #[must_use]
+    pub const fn keyword(self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::Conflict => "conflict",
+            Self::Immutable => "immutable",
+            Self::Divergent => "divergent",
+            Self::Hidden => "hidden",
+            Self::WorkingCopy => "current_working_copy",
+        }
+    }
+
+    /// Parses a `jj` template keyword.
+    #[must_use]
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        [
+            Self::Empty,
+            Self::Conflict,
+            Self::Immutable,
+            Self::Divergent,
+            Self::Hidden,
+            Self::WorkingCopy,
+        ]
+        .into_iter()
+        .find(|flag| flag.keyword() == keyword)
+    }
 }
 
 impl LogEntry {
@@ -273,6 +374,13 @@ impl LogEntry {
             description: description.into(),
             details: String::new(),
             rendered_line: 0,
+            parents: Vec::new(),
+            local_bookmarks: Vec::new(),
+            remote_bookmarks: Vec::new(),
+            tags: Vec::new(),
+            author: Signature::default(),
+            committer: Signature::default(),
+            flags: Vec::new(),
         }
     }
 
@@ -290,6 +398,43 @@ impl LogEntry {
         self
     }
 
+    /// Sets parent commit ids in `jj` order; the first is the first parent.
+    #[must_use]
+    pub fn with_parents(mut self, parents: Vec<String>) -> Self {
+        self.parents = parents;
+        self
+    }
+
+    /// Sets local bookmark names and remote bookmarks as `name@remote`.
+    #[must_use]
+    pub fn with_bookmarks(mut self, local: Vec<String>, remote: Vec<String>) -> Self {
+        self.local_bookmarks = local;
+        self.remote_bookmarks = remote;
+        self
+    }
+
+    /// Sets tag names.
+    #[must_use]
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Sets the author and committer signatures.
+    #[must_use]
+    pub fn with_signatures(mut self, author: Signature, committer: Signature) -> Self {
+        self.author = author;
+        self.committer = committer;
+        self
+    }
+
+    /// Sets the revision state flags.
+    #[must_use]
+    pub fn with_flags(mut self, flags: Vec<ChangeFlag>) -> Self {
+        self.flags = flags;
+        self
+    }
+
     /// Returns the stable `jj` change identifier.
     #[must_use]
     pub fn change_id(&self) -> &str {
@@ -320,6 +465,54 @@ impl LogEntry {
         self.rendered_line
     }
 
+    /// Returns parent commit ids; the first is the first parent.
+    #[must_use]
+    pub fn parents(&self) -> &[String] {
+        &self.parents
+    }
+
+    /// Returns local bookmark names pointing at this revision.
+    #[must_use]
+    pub fn local_bookmarks(&self) -> &[String] {
+        &self.local_bookmarks
+    }
+
+    /// Returns remote bookmarks pointing at this revision as `name@remote`.
+    #[must_use]
+    pub fn remote_bookmarks(&self) -> &[String] {
+        &self.remote_bookmarks
+    }
+
+    /// Returns tag names pointing at this revision.
+    #[must_use]
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Returns the author signature.
+    #[must_use]
+    pub const fn author(&self) -> &Signature {
+        &self.author
+    }
+
+    /// Returns the committer signature.
+    #[must_use]
+    pub const fn committer(&self) -> &Signature {
+        &self.committer
+    }
+
+    /// Returns the revision state flags.
+    #[must_use]
+    pub fn flags(&self) -> &[ChangeFlag] {
+        &self.flags
+    }
+
+    /// Returns whether `jj` reported `flag` for this revision.
+    #[must_use]
+    pub fn has_flag(&self, flag: ChangeFlag) -> bool {
+        self.flags.contains(&flag)
+    }
+
     /// Returns the first line of the commit description, or a placeholder for empty descriptions.
     #[must_use]
     pub fn summary(&self) -> &str {
@@ -331,3 +524,191 @@ impl LogEntry {
         }
     }
 }
+
+/// A rendered `jj op log` view plus semantic operation records.
+///
+/// Like [`LogSnapshot`], the rendered body stays opaque `jj` output. Each [`OperationEntry`] points
+/// at the rendered line containing its graph node through [`OperationEntry::rendered_line`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OperationLogSnapshot {
+    title: String,
+    rendered: String,
+    entries: Vec<OperationEntry>,
+}
+
+impl OperationLogSnapshot {
+    /// Creates an operation log snapshot from rendered terminal text and semantic entries.
+    #[must_use]
+    pub fn new(rendered: impl Into<String>, entries: Vec<OperationEntry>) -> Self {
+        Self {
+            title: String::new(),
+            rendered: rendered.into(),
+            entries,
+        }
+    }
+
+    /// Sets the command context shown in the title bar.
+    #[must_use]
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Returns the human-readable command context for the current view.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Returns the opaque output rendered by `jj op log`.
+    #[must_use]
+    pub fn rendered(&self) -> &str {
+        &self.rendered
+    }
+
+    /// Returns semantic operation records aligned to the rendered body.
+    #[must_use]
+    pub fn entries(&self) -> &[OperationEntry] {
+        &self.entries
+    }
+}
+
+/// A semantic `jj` operation record.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OperationEntry {
+    id: String,
+    parents: Vec<String>,
+    user: String,
+    host: String,
+    time_start: String,
+    time_end: String,
+    description: String,
+    tags: Vec<(String, String)>,
+    current: bool,
+    rendered_line: usize,
+}
+
+impl OperationEntry {
+    /// Creates an operation entry from its full operation id and description.
+    #[must_use]
+    pub fn new(id: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            description: description.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets parent operation ids.
+    #[must_use]
+    pub fn with_parents(mut self, parents: Vec<String>) -> Self {
+        self.parents = parents;
+        self
+    }
+
+    /// Sets the user and host that recorded the operation.
+    #[must_use]
+    pub fn with_origin(mut self, user: impl Into<String>, host: impl Into<String>) -> Self {
+        self.user = user.into();
+        self.host = host.into();
+        self
+    }
+
+    /// Sets the operation start and end timestamps as rendered by `jj`.
+    #[must_use]
+    pub fn with_time_range(mut self, start: impl Into<String>, end: impl Into<String>) -> Self {
+        self.time_start = start.into();
+        self.time_end = end.into();
+        self
+    }
+
+    /// Sets operation tags as ordered key/value pairs.
+    #[must_use]
+    pub fn with_tags(mut self, tags: Vec<(String, String)>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Marks whether this is the current operation.
+    #[must_use]
+    pub const fn with_current(mut self, current: bool) -> Self {
+        self.current = current;
+        self
+    }
+
+    /// Sets the rendered line for this entry.
+    #[must_use]
+    pub const fn with_rendered_line(mut self, rendered_line: usize) -> Self {
+        self.rendered_line = rendered_line;
+        self
+    }
+
+    /// Returns the full operation id.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns parent operation ids.
+    #[must_use]
+    pub fn parents(&self) -> &[String] {
+        &self.parents
+    }
+
+    /// Returns the user name that recorded the operation.
+    #[must_use]
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Returns the host name that recorded the operation.
+    #[must_use]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the operation start timestamp.
+    #[must_use]
+    pub fn time_start(&self) -> &str {
+        &self.time_start
+    }
+
+    /// Returns the operation end timestamp.
+    #[must_use]
+    pub fn time_end(&self) -> &str {
+        &self.time_end
+    }
+
+    /// Returns the operation description.
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns operation tags as ordered key/value pairs.
+    #[must_use]
+    pub fn tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
+
+    /// Returns the value of one operation tag.
+    #[must_use]
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns whether this is the current operation.
+    #[must_use]
+    pub const fn current(&self) -> bool {
+        self.current
+    }
+
+    /// Returns the zero-based line in the rendered `jj op log` output for this entry.
+    #[must_use]
+    pub const fn rendered_line(&self) -> usize {
+        self.rendered_line
+    }
+}
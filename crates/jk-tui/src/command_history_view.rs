@@ -6,7 +6,8 @@
 use std::time::Duration;
 
 use jk_core::{
-    CommandRecord, ExitStatusSummary, InspectionSnapshot, SourceAction, SourceView, StreamSummary,
+    CommandRecord, CommandSource, ExitStatusSummary, InspectionSnapshot, SourceAction, SourceView,
+    StreamSummary,
 };
 use ratatui::Frame;
 use ratatui::layout::Rect;
@@ -165,6 +166,7 @@ pub struct CommandHistoryDetails {
     summary: String,
     operation_id: Option<String>,
     duration: Option<Duration>,
+    cache_hit: bool,
     exit_status: Option<ExitStatusSummary>,
     spawn_error: Option<String>,
     stdout: StreamSummary,
@@ -187,6 +189,7 @@ impl CommandHistoryDetails {
             summary: summary.into(),
             operation_id: None,
             duration: None,
+            cache_hit: false,
             exit_status: None,
             spawn_error: None,
             stdout: StreamSummary::empty(),
@@ -204,6 +207,7 @@ impl CommandHistoryDetails {
             summary: result_summary(record),
             operation_id: record.operation_id.clone(),
             duration: record.timing.duration,
+            cache_hit: record.cache_hit,
             exit_status: record.result.exit_status,
             spawn_error: record.result.spawn_error.clone(),
             stdout: record.result.stdout.clone(),
@@ -247,6 +251,9 @@ impl CommandHistoryDetails {
 
     fn status_label(&self) -> String {
         if let Some(status) = self.exit_status {
+            if status.success && self.cache_hit {
+                return "success (cached output, jj not run)".to_owned();
+            }
             if status.success {
                 return "success".to_owned();
             }
@@ -597,6 +604,7 @@ fn history_line(row: &CommandHistoryRow) -> Line<'_> {
 fn status_color(status: &str) -> Color {
     match status {
         "ok" => Color::Green,
+        "hit" => Color::Cyan,
         "fail" | "err" => Color::Red,
         _ => Color::Yellow,
     }
@@ -607,12 +615,19 @@ const fn status_marker(record: &CommandRecord) -> &'static str {
         return "err";
     }
     match record.result.exit_status {
+        Some(status) if status.success && record.cache_hit => "hit",
         Some(status) if status.success => "ok",
         Some(_) => "fail",
         None => "run",
     }
 }
 
+/// Returns the compact `view action` label used for a command source, such as `log describe`.
+#[must_use]
+pub fn command_source_label(source: &CommandSource) -> String {
+    source_label(source.view.clone(), source.action.clone())
+}
+
 fn source_label(view: SourceView, action: SourceAction) -> String {
     format!("{} {}", view_label(view), action_label(action))
 }
@@ -645,7 +660,14 @@ fn action_label(action: SourceAction) -> String {
         SourceAction::OpenShow => "show".to_owned(),
         SourceAction::OpenStatus | SourceAction::WorkspaceStatus => "status".to_owned(),
         SourceAction::OpenEvolog => "evolog".to_owned(),
+        SourceAction::OpenInterdiff => "interdiff".to_owned(),
         SourceAction::DescribeRevision => "describe".to_owned(),
+        SourceAction::FindRevision => "find revision".to_owned(),
+        SourceAction::RebaseRevision => "rebase".to_owned(),
+        SourceAction::SquashRevision => "squash".to_owned(),
+        SourceAction::RestorePaths => "restore".to_owned(),
+        SourceAction::OpenInEditor => "editor".to_owned(),
+        SourceAction::CopyPatch => "patch".to_owned(),
         SourceAction::WorkspaceList => "list".to_owned(),
         SourceAction::WorkspaceLog => "log".to_owned(),
         SourceAction::WorkspaceUpdateStale => "update-stale".to_owned(),
@@ -654,6 +676,16 @@ fn action_label(action: SourceAction) -> String {
         SourceAction::OperationDiff => "op diff".to_owned(),
         SourceAction::Undo => "undo".to_owned(),
         SourceAction::Redo => "redo".to_owned(),
+        SourceAction::Checkpoint => "checkpoint".to_owned(),
+        SourceAction::RestoreCheckpoint => "restore checkpoint".to_owned(),
+        SourceAction::SetRevset => "set revset".to_owned(),
+        SourceAction::LoadRevsetPresets => "revset presets".to_owned(),
+        SourceAction::LoadDiffTools => "diff tools".to_owned(),
+        SourceAction::LoadOperationUser => "operation user".to_owned(),
+        SourceAction::LoadMoreLog => "load more".to_owned(),
+        SourceAction::PreviewDiff => "preview diff".to_owned(),
+        SourceAction::ResolveNoteTarget => "note target".to_owned(),
+        SourceAction::Complete => "complete".to_owned(),
         SourceAction::UserJjCommand => "command".to_owned(),
         SourceAction::Other(label) => label,
         _ => "unknown".to_owned(),
@@ -1073,6 +1105,31 @@ mod tests {
         assert_eq!(snapshot.rows()[0].summary, "@ current");
     }
 
+    #[test]
+    fn cache_hits_show_their_own_marker_and_status() {
+        let mut history = jk_core::CommandHistory::new(8);
+        let start = CommandRecordStart::from_spec(
+            &JjCommandSpec::render_read_only(["show", "aaa"]),
+            CommandSource::new(SourceView::Log, SourceAction::OpenShow),
+        );
+        let pending = history.start(start);
+        history.finish(
+            &pending,
+            CommandRecordFinish::from_exit_code(0, "show output\n", "", SystemTime::now())
+                .with_cache_hit(true),
+        );
+
+        let snapshot = CommandHistorySnapshot::from_records(history.records());
+
+        assert_eq!(snapshot.rows()[0].status, "hit");
+        assert!(
+            snapshot.rows()[0]
+                .details
+                .rendered()
+                .contains("Status: success (cached output, jj not run)")
+        );
+    }
+
     fn history_with_records() -> jk_core::CommandHistory {
         let mut history = jk_core::CommandHistory::new(8);
         append_record(
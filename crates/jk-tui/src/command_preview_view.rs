@@ -2,7 +2,10 @@
 //!
 //! This view renders [`jk_core::CommandPreview`] data and intentionally owns no execution behavior.
 
-use jk_core::{CommandPreview, CommandPreviewWarning, ExecutionMode, RefreshPlan, SafetyClass};
+use jk_core::{
+    CommandPreview, CommandPreviewWarning, ExecutionMode, ImmutabilityPolicy, RefreshPlan,
+    SafetyClass,
+};
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::prelude::{Color, Line, Modifier, Span, Style, Text};
@@ -83,8 +86,11 @@ impl CommandPreviewView {
             frame.render_widget(paragraph, body_area);
         }
         frame.render_widget(
-            Paragraph::new(footer_line(self.status.as_deref()))
-                .style(Style::new().fg(Color::White).bg(Color::Black)),
+            Paragraph::new(footer_line(
+                self.status.as_deref(),
+                immutability_hint(&self.preview),
+            ))
+            .style(Style::new().fg(Color::White).bg(Color::Black)),
             footer_area,
         );
     }
@@ -120,6 +126,20 @@ impl CommandPreviewView {
             )),
         ];
 
+        if !self.preview.details.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Details",
+                Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            )));
+            lines.extend(
+                self.preview
+                    .details
+                    .iter()
+                    .map(|detail| Line::from(detail.as_str())),
+            );
+        }
+
         lines.push(Line::from(""));
         if self.preview.warnings.is_empty() {
             lines.push(Line::from(Span::styled(
@@ -138,7 +158,22 @@ impl CommandPreviewView {
     }
 }
 
-fn footer_line(status: Option<&str>) -> Line<'static> {
+/// Returns the key hint for switching immutability when the preview targets immutable commits.
+fn immutability_hint(preview: &CommandPreview) -> Option<&'static str> {
+    let targets_immutable = preview
+        .warnings
+        .iter()
+        .any(|warning| matches!(warning, CommandPreviewWarning::ImmutableTargets(_)));
+    if !targets_immutable {
+        return None;
+    }
+    match preview.immutability() {
+        ImmutabilityPolicy::Enforce => Some("i ignore immutable"),
+        _ => Some("i enforce immutable"),
+    }
+}
+
+fn footer_line(status: Option<&str>, immutability_hint: Option<&'static str>) -> Line<'static> {
     if let Some(status) = status {
         return Line::from(vec![
             Span::styled(
@@ -152,7 +187,7 @@ fn footer_line(status: Option<&str>) -> Line<'static> {
         ]);
     }
 
-    Line::from(vec![
+    let mut spans = vec![
         Span::styled(
             "enter run",
             Style::new().fg(Color::Green).add_modifier(Modifier::BOLD),
@@ -163,11 +198,19 @@ fn footer_line(status: Option<&str>) -> Line<'static> {
             Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
         ),
         Span::raw("    "),
-        Span::styled(
-            "esc cancel",
-            Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
-        ),
-    ])
+    ];
+    if let Some(hint) = immutability_hint {
+        spans.push(Span::styled(
+            hint,
+            Style::new().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw("    "));
+    }
+    spans.push(Span::styled(
+        "esc cancel",
+        Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+    ));
+    Line::from(spans)
 }
 
 fn warning_line(warning: &CommandPreviewWarning) -> Line<'_> {
@@ -230,6 +273,10 @@ fn warning_label(warning: &CommandPreviewWarning) -> String {
         CommandPreviewWarning::IgnoresImmutableCommits => {
             "May rewrite immutable commits.".to_owned()
         }
+        CommandPreviewWarning::ImmutableTargets(revisions) => format!(
+            "Targets immutable {}; jj refuses unless --ignore-immutable is set.",
+            revisions.join(", ")
+        ),
         _ => "Review this command before running.".to_owned(),
     }
 }
@@ -304,6 +351,31 @@ mod tests {
         assert!(rendered.contains("cancel"));
     }
 
+    #[test]
+    fn command_preview_renders_details_section() {
+        let preview =
+            JjCommandSpec::confirm_mutation(["op", "restore", "abc123"], SafetyClass::LocalRewrite)
+                .command_preview()
+                .with_details(vec![
+                    "Changes since checkpoint start:".to_owned(),
+                    "Changed commits:".to_owned(),
+                ]);
+        let view = CommandPreviewView::new(preview);
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = match Terminal::new(backend) {
+            Ok(terminal) => terminal,
+            Err(error) => match error {},
+        };
+
+        let draw_result = terminal.draw(|frame| view.render(frame));
+        assert!(draw_result.is_ok());
+
+        let rendered = buffer_to_string(terminal.backend().buffer());
+        assert!(rendered.contains("Details"));
+        assert!(rendered.contains("Changes since checkpoint start:"));
+        assert!(rendered.contains("Changed commits:"));
+    }
+
     #[test]
     fn command_preview_without_warnings_says_so() {
         let preview = JjCommandSpec::render_read_only(["log"])
@@ -331,6 +403,34 @@ mod tests {
         assert!(rendered.contains("esc cancel"));
     }
 
+    #[test]
+    fn immutable_targets_offer_an_ignore_immutable_switch() {
+        let preview =
+            JjCommandSpec::confirm_mutation(["abandon", "abc"], SafetyClass::LocalRewrite)
+                .command_preview()
+                .with_warning(CommandPreviewWarning::ImmutableTargets(vec![
+                    "abc".to_owned(),
+                ]));
+        let backend = TestBackend::new(100, 24);
+        let mut terminal = match Terminal::new(backend) {
+            Ok(terminal) => terminal,
+            Err(error) => match error {},
+        };
+
+        let view = CommandPreviewView::new(preview.clone());
+        assert!(terminal.draw(|frame| view.render(frame)).is_ok());
+        let rendered = buffer_to_string(terminal.backend().buffer());
+        assert!(rendered.contains("Targets immutable abc"));
+        assert!(rendered.contains("i ignore immutable"));
+
+        let view = CommandPreviewView::new(preview.with_immutability(ImmutabilityPolicy::Ignore));
+        assert!(terminal.draw(|frame| view.render(frame)).is_ok());
+        let rendered = buffer_to_string(terminal.backend().buffer());
+        assert!(rendered.contains("--ignore-immutable"));
+        assert!(rendered.contains("May rewrite immutable commits."));
+        assert!(rendered.contains("i enforce immutable"));
+    }
+
     #[test]
     fn command_preview_status_replaces_copy_hint() {
         let preview = JjCommandSpec::render_read_only(["undo"])
@@ -0,0 +1,295 @@
+//! Review notes anchored to lines and hunks of a diff.
+//!
+//! An anchor keeps the ANSI-stripped lines it quotes, so notes can be found again by content after
+//! the diff is reloaded, and exported as markdown that still makes sense outside `jk`.
+
+/// Lines quoted on each side of a line anchor when the diff has no `@@` hunks to quote.
+const LINE_CONTEXT: usize = 3;
+
+/// The part of a diff a review note is attached to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiffAnchor {
+    path: String,
+    context: Vec<String>,
+    line: Option<usize>,
+}
+
+impl DiffAnchor {
+    /// Creates an anchor on `path` quoting `context`, pointing at `context[line]` when `line` is
+    /// set and at the whole quote otherwise.
+    #[must_use]
+    pub fn new(path: impl Into<String>, context: Vec<String>, line: Option<usize>) -> Self {
+        let line = line.filter(|line| *line < context.len());
+        Self {
+            path: path.into(),
+            context,
+            line,
+        }
+    }
+
+    /// Returns the file path the note belongs to.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the quoted lines: a whole `@@` hunk, or the lines around an anchored line.
+    #[must_use]
+    pub fn context(&self) -> &[String] {
+        &self.context
+    }
+
+    /// Returns the anchored line's index into [`Self::context`], or `None` for a hunk or file note.
+    #[must_use]
+    pub const fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Returns the text of the anchored line.
+    #[must_use]
+    pub fn line_text(&self) -> Option<&str> {
+        self.line
+            .and_then(|line| self.context.get(line))
+            .map(String::as_str)
+    }
+
+    /// Returns the new-side line range the anchor covers, when the quote carries line numbers.
+    ///
+    /// Numbers come from a `@@` hunk header or from jj's `old new:` line prefixes.
+    #[must_use]
+    pub fn line_range(&self) -> Option<(usize, usize)> {
+        let first = self.context.first()?;
+        if let Some(start) = hunk_new_start(first) {
+            let Some(line) = self.line else {
+                let count = hunk_new_count(first).unwrap_or(1).max(1);
+                return Some((start, start + count - 1));
+            };
+            // Removed lines report the new-side position they were removed at.
+            let number = start
+                + self
+                    .context
+                    .get(1..line.max(1))?
+                    .iter()
+                    .filter(|text| !text.starts_with('-') && !text.starts_with('\\'))
+                    .count();
+            return Some((number, number));
+        }
+
+        let numbers = match self.line {
+            Some(line) => vec![self.context.get(line)?],
+            None => self.context.iter().collect(),
+        };
+        let mut numbers = numbers
+            .into_iter()
+            .filter_map(|text| color_words_new_line(text));
+        let start = numbers.next()?;
+        let end = numbers.next_back().unwrap_or(start);
+        Some((start, end))
+    }
+}
+
+/// A review comment attached to a diff anchor.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiffNote {
+    anchor: DiffAnchor,
+    comment: String,
+    outdated: bool,
+}
+
+impl DiffNote {
+    /// Creates a note on `anchor`.
+    #[must_use]
+    pub fn new(anchor: DiffAnchor, comment: impl Into<String>) -> Self {
+        Self {
+            anchor,
+            comment: comment.into(),
+            outdated: false,
+        }
+    }
+
+    /// Returns where the note is attached.
+    #[must_use]
+    pub const fn anchor(&self) -> &DiffAnchor {
+        &self.anchor
+    }
+
+    /// Returns the note text.
+    #[must_use]
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    /// Returns whether the anchored content could not be found in the current diff.
+    #[must_use]
+    pub const fn is_outdated(&self) -> bool {
+        self.outdated
+    }
+
+    pub(crate) fn placed(mut self, anchor: Option<DiffAnchor>) -> Self {
+        match anchor {
+            Some(anchor) => {
+                self.anchor = anchor;
+                self.outdated = false;
+            }
+            None => self.outdated = true,
+        }
+        self
+    }
+}
+
+/// Formats notes as markdown with the path, line range, quoted diff and comment for each.
+#[must_use]
+pub fn notes_markdown(title: &str, notes: &[DiffNote]) -> String {
+    let mut markdown = format!("# Review notes: {title}\n");
+    for note in notes {
+        let anchor = note.anchor();
+        let range = match anchor.line_range() {
+            Some((start, end)) if start == end => format!(" line {start}"),
+            Some((start, end)) => format!(" lines {start}-{end}"),
+            None => String::new(),
+        };
+        let heading = format!("\n## `{}`{range}", anchor.path());
+        markdown.push_str(&heading);
+        if note.is_outdated() {
+            markdown.push_str(" (outdated)");
+        }
+        markdown.push('\n');
+        if !anchor.context().is_empty() {
+            markdown.push_str("\n```diff\n");
+            for line in anchor.context() {
+                markdown.push_str(line);
+                markdown.push('\n');
+            }
+            markdown.push_str("```\n");
+        }
+        markdown.push('\n');
+        markdown.push_str(note.comment().trim_end());
+        markdown.push('\n');
+    }
+    markdown
+}
+
+/// Returns the lines around `line` within `body`, and `line`'s index in that window.
+pub fn line_window(body: &[String], line: usize) -> (Vec<String>, usize) {
+    let start = line.saturating_sub(LINE_CONTEXT);
+    let end = (line + LINE_CONTEXT + 1).min(body.len());
+    (body[start..end].to_vec(), line - start)
+}
+
+/// Returns the position where `context` occurs in `lines`, comparing `@@` headers loosely so
+/// a hunk whose line numbers shifted still matches.
+pub fn find_context(lines: &[String], context: &[String]) -> Option<usize> {
+    if context.is_empty() || context.len() > lines.len() {
+        return None;
+    }
+    (0..=lines.len() - context.len()).find(|start| {
+        context.iter().enumerate().all(|(offset, expected)| {
+            let actual = &lines[start + offset];
+            actual == expected
+                || (offset == 0
+                    && hunk_new_start(actual).is_some()
+                    && hunk_new_start(expected).is_some())
+        })
+    })
+}
+
+/// Parses the new-side start from `@@ -a,b +c,d @@`.
+pub fn hunk_new_start(line: &str) -> Option<usize> {
+    hunk_new_side(line)?.0.parse().ok()
+}
+
+/// Parses the new-side line count from `@@ -a,b +c,d @@`, which defaults to 1 when omitted.
+pub fn hunk_new_count(line: &str) -> Option<usize> {
+    hunk_new_side(line)?
+        .1
+        .map_or(Some(1), |count| count.parse().ok())
+}
+
+fn hunk_new_side(line: &str) -> Option<(&str, Option<&str>)> {
+    let rest = line.strip_prefix("@@ ")?;
+    let new_side = rest
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix('+'))?;
+    Some(match new_side.split_once(',') {
+        Some((start, count)) => (start, Some(count)),
+        None => (new_side, None),
+    })
+}
+
+/// Parses the new-side number from jj's default `  old  new: text` line prefix.
+fn color_words_new_line(line: &str) -> Option<usize> {
+    let (numbers, _) = line.split_once(':')?;
+    let mut columns = numbers.split_whitespace();
+    let first = columns.next()?;
+    match columns.next() {
+        Some(second) => second.parse().ok(),
+        // A lone number sits in the new column only when it runs right up to the colon.
+        None if numbers.ends_with(first) => first.parse().ok(),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(ToOwned::to_owned).collect()
+    }
+
+    #[test]
+    fn git_hunk_anchors_report_new_side_ranges() {
+        let hunk = lines("@@ -10,3 +12,4 @@\n context\n-old\n+new\n+added\n context");
+
+        assert_eq!(
+            DiffAnchor::new("a.rs", hunk.clone(), None).line_range(),
+            Some((12, 15))
+        );
+        assert_eq!(
+            DiffAnchor::new("a.rs", hunk.clone(), Some(1)).line_range(),
+            Some((12, 12))
+        );
+        assert_eq!(
+            DiffAnchor::new("a.rs", hunk.clone(), Some(3)).line_range(),
+            Some((13, 13))
+        );
+        assert_eq!(
+            DiffAnchor::new("a.rs", hunk, Some(4)).line_range(),
+            Some((14, 14))
+        );
+    }
+
+    #[test]
+    fn color_words_anchors_read_line_prefixes() {
+        let context = lines("   4    4: same\n   5     : gone\n        5: added");
+
+        assert_eq!(
+            DiffAnchor::new("a.rs", context.clone(), Some(2)).line_range(),
+            Some((5, 5))
+        );
+        assert_eq!(
+            DiffAnchor::new("a.rs", context, None).line_range(),
+            Some((4, 5))
+        );
+    }
+
+    #[test]
+    fn markdown_quotes_hunk_with_path_range_and_comment() {
+        let anchor = DiffAnchor::new("src/a.rs", lines("@@ -1,1 +1,1 @@\n-old\n+new"), Some(2));
+        let markdown = notes_markdown("jj diff -r abc", &[DiffNote::new(anchor, "Rename this.")]);
+
+        assert_eq!(
+            markdown,
+            "# Review notes: jj diff -r abc\n\n## `src/a.rs` line 1\n\n```diff\n@@ -1,1 +1,1 @@\n-old\n+new\n```\n\nRename this.\n"
+        );
+    }
+
+    #[test]
+    fn context_matches_hunks_whose_numbers_shifted() {
+        let current = lines("header\n@@ -5,1 +5,1 @@\n-old\n+new");
+        let context = lines("@@ -1,1 +1,1 @@\n-old\n+new");
+
+        assert_eq!(find_context(&current, &context), Some(1));
+        assert_eq!(find_context(&current, &lines("-gone")), None);
+    }
+}
@@ -0,0 +1,204 @@
+//! Slicing plain git patches into files and hunks for copying.
+//!
+//! The view may render color-words, a stat or an external tool, so callers load the same diff
+//! with `--git` and cut it here by the path and new-side line under the cursor.
+
+use crate::diff_notes::{hunk_new_count, hunk_new_start};
+
+/// One file section of a git patch: its `diff --git` prelude and `@@` hunks.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct PatchFile {
+    header: Vec<String>,
+    hunks: Vec<Vec<String>>,
+}
+
+impl PatchFile {
+    /// Returns whether the `diff --git a/old b/new` header names `path` on either side.
+    fn is_for(&self, path: &str) -> bool {
+        self.header.first().is_some_and(|header| {
+            header.ends_with(&format!(" b/{path}"))
+                || header.starts_with(&format!("diff --git a/{path} "))
+        })
+    }
+
+    /// Returns the hunk covering new-side `line`, or the last hunk starting before it.
+    fn hunk_at(&self, line: usize) -> Option<&[String]> {
+        let starts = self
+            .hunks
+            .iter()
+            .filter_map(|hunk| Some((hunk, hunk_new_start(hunk.first()?)?)));
+        let mut before = None;
+        for (hunk, start) in starts {
+            let count = hunk.first().and_then(|header| hunk_new_count(header));
+            if (start..start + count.unwrap_or(1).max(1)).contains(&line) {
+                return Some(hunk);
+            }
+            if start <= line {
+                before = Some(hunk);
+            }
+        }
+        before.map(Vec::as_slice)
+    }
+}
+
+/// A parsed `jj diff --git` patch.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GitPatch {
+    files: Vec<PatchFile>,
+}
+
+impl GitPatch {
+    /// Splits `text` into file sections and hunks.
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        let mut files = Vec::<PatchFile>::new();
+        for line in text.lines() {
+            if line.starts_with("diff --git ") {
+                files.push(PatchFile {
+                    header: vec![line.to_owned()],
+                    hunks: Vec::new(),
+                });
+                continue;
+            }
+            let Some(file) = files.last_mut() else {
+                continue;
+            };
+            if line.starts_with("@@ ") {
+                file.hunks.push(vec![line.to_owned()]);
+            } else if let Some(hunk) = file.hunks.last_mut() {
+                hunk.push(line.to_owned());
+            } else {
+                file.header.push(line.to_owned());
+            }
+        }
+        Self { files }
+    }
+
+    /// Returns whether the patch has no file sections.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Returns the whole patch for `path`, prelude and all hunks.
+    #[must_use]
+    pub fn file(&self, path: &str) -> Option<String> {
+        let file = self.files.iter().find(|file| file.is_for(path))?;
+        Some(patch_text(
+            file.header.iter().chain(file.hunks.iter().flatten()),
+        ))
+    }
+
+    /// Returns an applicable patch holding only the hunk of `path` at new-side `line`.
+    #[must_use]
+    pub fn hunk(&self, path: &str, line: usize) -> Option<String> {
+        let file = self.files.iter().find(|file| file.is_for(path))?;
+        let hunk = file.hunk_at(line)?;
+        Some(patch_text(file.header.iter().chain(hunk)))
+    }
+
+    /// Returns the hunk of `path` at new-side `line` as a fenced `diff` block under the path.
+    #[must_use]
+    pub fn hunk_markdown(&self, path: &str, line: usize) -> Option<String> {
+        let hunk = self
+            .files
+            .iter()
+            .find(|file| file.is_for(path))?
+            .hunk_at(line)?;
+        let range = hunk.first().and_then(|header| {
+            let start = hunk_new_start(header)?;
+            let end = start + hunk_new_count(header)?.max(1) - 1;
+            Some(if start == end {
+                format!(" line {start}")
+            } else {
+                format!(" lines {start}-{end}")
+            })
+        });
+        Some(format!(
+            "`{path}`{}\n\n```diff\n{}```\n",
+            range.unwrap_or_default(),
+            patch_text(hunk)
+        ))
+    }
+}
+
+fn patch_text<'a>(lines: impl IntoIterator<Item = &'a String>) -> String {
+    lines.into_iter().fold(String::new(), |mut text, line| {
+        text.push_str(line);
+        text.push('\n');
+        text
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = concat!(
+        "diff --git a/src/a.rs b/src/a.rs\n",
+        "index 1111111..2222222 100644\n",
+        "--- a/src/a.rs\n",
+        "+++ b/src/a.rs\n",
+        "@@ -1,2 +1,2 @@\n",
+        " fn a() {}\n",
+        "-fn old() {}\n",
+        "+fn new() {}\n",
+        "@@ -20,1 +20,2 @@\n",
+        " fn tail() {}\n",
+        "+fn more() {}\n",
+        "diff --git a/src/b.rs b/src/b.rs\n",
+        "deleted file mode 100644\n",
+        "index 3333333..0000000\n",
+        "--- a/src/b.rs\n",
+        "+++ /dev/null\n",
+        "@@ -1,1 +0,0 @@\n",
+        "-fn b() {}\n",
+    );
+
+    #[test]
+    fn hunk_patch_keeps_the_file_prelude() {
+        let patch = GitPatch::parse(PATCH);
+
+        assert_eq!(
+            patch.hunk("src/a.rs", 21).as_deref(),
+            Some(concat!(
+                "diff --git a/src/a.rs b/src/a.rs\n",
+                "index 1111111..2222222 100644\n",
+                "--- a/src/a.rs\n",
+                "+++ b/src/a.rs\n",
+                "@@ -20,1 +20,2 @@\n",
+                " fn tail() {}\n",
+                "+fn more() {}\n",
+            ))
+        );
+        assert_eq!(
+            patch.hunk("src/a.rs", 5),
+            patch.hunk("src/a.rs", 1),
+            "lines between hunks fall back to the hunk above"
+        );
+        assert!(patch.hunk("src/b.rs", 0).is_some());
+    }
+
+    #[test]
+    fn file_patch_contains_only_that_file() {
+        let Some(file) = GitPatch::parse(PATCH).file("src/b.rs") else {
+            panic!("src/b.rs has a file patch");
+        };
+
+        assert!(file.starts_with("diff --git a/src/b.rs b/src/b.rs\n"));
+        assert!(file.ends_with("-fn b() {}\n"));
+        assert!(!file.contains("src/a.rs"));
+    }
+
+    #[test]
+    fn hunk_markdown_fences_the_hunk_under_its_path() {
+        let markdown = GitPatch::parse(PATCH).hunk_markdown("src/a.rs", 2);
+
+        assert_eq!(
+            markdown.as_deref(),
+            Some(
+                "`src/a.rs` lines 1-2\n\n```diff\n@@ -1,2 +1,2 @@\n fn a() {}\n-fn old() {}\n+fn new() {}\n```\n"
+            )
+        );
+    }
+}
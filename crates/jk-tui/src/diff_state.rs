@@ -1,11 +1,13 @@
 //! State machine for selected-change diff inspection.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use jk_core::{DiffFileStat, DiffSnapshot};
 
 use crate::ansi_text::strip_ansi;
 use crate::chrome::title_or_default;
+use crate::diff_notes::{DiffAnchor, DiffNote, find_context, line_window};
+use crate::side_by_side::{SIDE_BY_SIDE_MIN_WIDTH, side_by_side};
 
 const HORIZONTAL_SCROLL_STEP: usize = 8;
 const DIFF_HORIZONTAL_STATUS: &str = "</> horizontal scroll";
@@ -16,6 +18,11 @@ const DIFF_FILE_STATUS_HINT: &str = "  f files  [/] file  { } hunk  ? help";
 pub struct DiffState {
     title: String,
     change_id: String,
+    range: Option<(String, String)>,
+    source: String,
+    file_stats: Vec<DiffFileStat>,
+    side_by_side: bool,
+    layout_width: Option<usize>,
     rendered: String,
     sections: Vec<FileSection>,
     hunks: Vec<HunkSection>,
@@ -23,6 +30,9 @@ pub struct DiffState {
     selected_hunk: Option<usize>,
     collapsed_paths: BTreeSet<String>,
     collapsed_hunks: BTreeSet<String>,
+    viewed: BTreeSet<String>,
+    notes: Vec<PlacedNote>,
+    file_filter: Option<FileFilter>,
     search: Option<SearchState>,
     scroll_offset: usize,
     horizontal_offset: usize,
@@ -33,13 +43,20 @@ pub struct DiffState {
 impl DiffState {
     /// Creates state from a freshly loaded diff snapshot.
     pub fn new(snapshot: DiffSnapshot) -> Self {
+        let range = snapshot_range(&snapshot);
         let (title, change_id, rendered, file_stats) = snapshot.into_parts();
-        let sections = file_sections(&rendered, &file_stats);
-        let hunks = hunk_sections(&rendered, &sections);
+        let mut sections = file_sections(&rendered, &file_stats);
+        let mut hunks = hunk_sections(&rendered, &sections);
+        assign_viewed_keys(&rendered, &mut sections, &mut hunks);
         let selected = (!sections.is_empty()).then_some(0);
         Self {
             title: title_or_default(title),
             change_id,
+            range,
+            source: rendered.clone(),
+            file_stats,
+            side_by_side: false,
+            layout_width: None,
             rendered,
             sections,
             hunks,
@@ -47,6 +64,9 @@ impl DiffState {
             selected_hunk: None,
             collapsed_paths: BTreeSet::new(),
             collapsed_hunks: BTreeSet::new(),
+            viewed: BTreeSet::new(),
+            notes: Vec::new(),
+            file_filter: None,
             search: None,
             scroll_offset: 0,
             horizontal_offset: 0,
@@ -56,31 +76,37 @@ impl DiffState {
     }
 
     /// Replaces the diff output while preserving selected and collapsed file paths when possible.
+    ///
+    /// Viewed marks whose content changed are dropped, and the ones that still match fold again.
+    /// Review notes move to wherever their quoted content now appears.
     pub fn refresh(&mut self, snapshot: DiffSnapshot) {
-        let selected_path = self.selected_section().map(|section| section.path.clone());
+        self.range = snapshot_range(&snapshot);
         let (title, change_id, rendered, file_stats) = snapshot.into_parts();
 
         self.title = title_or_default(title);
         self.change_id = change_id;
-        self.rendered = rendered;
-        self.sections = file_sections(&self.rendered, &file_stats);
-        self.hunks = hunk_sections(&self.rendered, &self.sections);
-        self.selected_hunk = None;
-        self.collapsed_paths
-            .retain(|path| self.sections.iter().any(|section| section.path == *path));
-        self.collapsed_hunks
-            .retain(|key| self.hunks.iter().any(|hunk| hunk.key == *key));
-        self.selected = selected_path
-            .and_then(|path| {
-                self.sections
-                    .iter()
-                    .position(|section| section.path == path)
-            })
-            .or_else(|| (!self.sections.is_empty()).then_some(0));
+        self.source = rendered;
+        self.file_stats = file_stats;
+        self.rebuild();
+        self.refilter_files();
+        self.fold_viewed();
+        let notes = self.notes();
+        self.set_notes(notes);
+    }
+
+    /// Lays `--git` output out in old/new columns when the viewport is wide enough.
+    ///
+    /// Narrower viewports, and output that is not `--git` shaped, keep the inline layout.
+    pub fn set_side_by_side(&mut self, enabled: bool) {
+        self.side_by_side = enabled;
+        if self.side_by_side_width() != self.layout_width {
+            self.rebuild();
+        }
+    }
 
-        self.clamp_scroll_offset();
-        self.clamp_horizontal_offset();
-        self.refresh_search_matches();
+    /// Returns whether the diff body is currently laid out in two columns.
+    pub const fn is_side_by_side(&self) -> bool {
+        self.layout_width.is_some()
     }
 
     /// Returns the command context shown in the title bar.
@@ -88,6 +114,15 @@ impl DiffState {
         &self.title
     }
 
+    /// Returns the title with review progress appended when the diff has files.
+    pub fn progress_title(&self) -> String {
+        if self.sections.is_empty() {
+            return self.title.clone();
+        }
+        let (viewed, total) = self.viewed_progress();
+        format!("{}  viewed {viewed}/{total}", self.title)
+    }
+
     /// Returns the target change identifier for refresh requests.
     pub fn change_id(&self) -> &str {
         &self.change_id
@@ -126,6 +161,175 @@ impl DiffState {
             .collect()
     }
 
+    /// Returns per-file line counts loaded with the diff.
+    pub fn file_stats(&self) -> &[DiffFileStat] {
+        &self.file_stats
+    }
+
+    /// Folds every file whose path `matcher` rejects for `query`, remembering both so a refresh
+    /// filters the new file list too.
+    ///
+    /// The filter folds on top of the user's own folds, so clearing it with `None` brings back
+    /// exactly the files that were open before filtering.
+    pub fn set_file_filter(&mut self, filter: Option<(String, FileMatcher)>) {
+        self.file_filter = filter.map(|(query, matcher)| FileFilter {
+            query,
+            matcher,
+            paths: BTreeSet::new(),
+        });
+        self.refilter_files();
+    }
+
+    /// Re-matches the file filter against the current sections, keeping files the user unfolded.
+    fn refilter_files(&mut self) {
+        let Some(filter) = &mut self.file_filter else {
+            return;
+        };
+        filter.paths = self
+            .sections
+            .iter()
+            .map(|section| &section.path)
+            .filter(|path| filter.paths.contains(*path) || (filter.matcher)(path, &filter.query))
+            .cloned()
+            .collect();
+        self.clamp_scroll_offset();
+        self.keep_selected_visible();
+    }
+
+    /// Returns the query behind the active file filter.
+    pub fn file_filter_query(&self) -> Option<&str> {
+        self.file_filter
+            .as_ref()
+            .map(|filter| filter.query.as_str())
+    }
+
+    /// Applies remembered viewed marks that match this diff's content and folds those sections.
+    pub fn set_viewed_marks(&mut self, marks: &BTreeSet<String>) {
+        self.viewed = self
+            .viewed_keys()
+            .filter(|key| marks.contains(*key))
+            .map(ToOwned::to_owned)
+            .collect();
+        self.fold_viewed();
+    }
+
+    /// Toggles the viewed mark on the selected hunk, or on the selected file when no hunk is
+    /// selected, folding what becomes viewed.
+    ///
+    /// Returns the mark's key and whether it is now viewed, so callers can persist the change.
+    pub fn toggle_viewed(&mut self) -> Option<(String, bool)> {
+        if let Some(index) = self.selected_hunk {
+            let hunk = self.hunks.get(index)?;
+            let key = hunk.viewed_key.clone()?;
+            let fold_key = hunk.key.clone();
+            let viewed = !self.viewed.remove(&key);
+            if viewed {
+                self.viewed.insert(key.clone());
+                self.collapsed_hunks.insert(fold_key);
+            } else {
+                self.collapsed_hunks.remove(&fold_key);
+            }
+            self.select_hunk_index(index);
+            self.clamp_scroll_offset();
+            return Some((key, viewed));
+        }
+
+        let section = self.selected_section()?;
+        let key = section.viewed_key.clone();
+        let path = section.path.clone();
+        let viewed = !self.viewed.remove(&key);
+        if viewed {
+            self.viewed.insert(key.clone());
+            self.collapsed_paths.insert(path);
+        } else {
+            self.collapsed_paths.remove(&path);
+        }
+        self.clamp_scroll_offset();
+        self.keep_selected_visible();
+        Some((key, viewed))
+    }
+
+    /// Returns how many files are viewed, counting a file whose hunks are all viewed, and the
+    /// file total.
+    pub fn viewed_progress(&self) -> (usize, usize) {
+        let viewed = (0..self.sections.len())
+            .filter(|index| self.file_is_viewed(*index))
+            .count();
+        (viewed, self.sections.len())
+    }
+
+    /// Returns the review-note anchor for the line at the top of the viewport.
+    ///
+    /// Lines inside a hunk anchor to that line, a hunk header anchors to the whole hunk, and file
+    /// headers anchor to the file. Side-by-side layout anchors to the hunk under the cursor.
+    pub fn anchor_at_cursor(&self) -> Option<DiffAnchor> {
+        let rendered_line = self
+            .visible_line_map()
+            .into_iter()
+            .skip(self.scroll_offset)
+            .flatten()
+            .next()?;
+        let source_line = self.source_line_for_rendered_line(rendered_line)?;
+        SourceDiff::new(&self.source).anchor_for_line(source_line)
+    }
+
+    /// Returns the file at the top of the viewport and, inside a hunk, the new-side line there.
+    ///
+    /// The line number counts from the `@@` header (or jj's line-number prefixes) down to the
+    /// cursor line; a hunk header gives the hunk's first new-side line.
+    pub fn cursor_location(&self) -> Option<(String, Option<usize>)> {
+        self.anchor_at_cursor()
+            .map(|anchor| anchor_location(&anchor))
+    }
+
+    /// Returns the comment of the note attached at `anchor`.
+    pub fn note_for(&self, anchor: &DiffAnchor) -> Option<&str> {
+        self.notes
+            .iter()
+            .find(|placed| placed.note.anchor() == anchor)
+            .map(|placed| placed.note.comment())
+    }
+
+    /// Replaces the note at `anchor` with `comment`, removing it when `comment` is blank.
+    pub fn set_note(&mut self, anchor: DiffAnchor, comment: &str) {
+        let mut notes = self
+            .notes()
+            .into_iter()
+            .filter(|note| *note.anchor() != anchor)
+            .collect::<Vec<_>>();
+        if !comment.trim().is_empty() {
+            notes.push(DiffNote::new(anchor, comment.trim()));
+        }
+        self.set_notes(notes);
+    }
+
+    /// Places `notes` on this diff by content, marking the ones that no longer match as outdated.
+    pub fn set_notes(&mut self, notes: Vec<DiffNote>) {
+        let source = SourceDiff::new(&self.source);
+        self.notes = notes
+            .into_iter()
+            .map(|note| {
+                let placed = source.place(note.anchor());
+                let source_line = placed.as_ref().map(|(line, _)| *line);
+                PlacedNote {
+                    note: note.placed(placed.map(|(_, anchor)| anchor)),
+                    source_line,
+                }
+            })
+            .collect();
+        self.notes
+            .sort_by_key(|placed| placed.source_line.unwrap_or(usize::MAX));
+        self.refresh_search_matches();
+    }
+
+    /// Returns the review notes in diff order, outdated ones last.
+    pub fn notes(&self) -> Vec<DiffNote> {
+        self.notes
+            .iter()
+            .map(|placed| placed.note.clone())
+            .collect()
+    }
+
     /// Selects a file section by index.
     pub fn select_file_index(&mut self, index: usize) {
         self.select_index(index);
@@ -277,12 +481,15 @@ impl DiffState {
         self.keep_selected_visible();
     }
 
-    /// Unfolds the selected file section.
+    /// Unfolds the selected file section, even when the file filter folded it.
     pub fn unfold_selected_file(&mut self) {
         let Some(path) = self.selected_section().map(|section| section.path.clone()) else {
             return;
         };
 
+        if let Some(filter) = &mut self.file_filter {
+            filter.paths.insert(path.clone());
+        }
         self.collapsed_paths.remove(&path);
         self.clamp_scroll_offset();
         self.keep_selected_visible();
@@ -299,9 +506,10 @@ impl DiffState {
         self.keep_selected_visible();
     }
 
-    /// Unfolds every file section.
+    /// Unfolds every file section and drops the file filter.
     pub fn unfold_all_files(&mut self) {
         self.collapsed_paths.clear();
+        self.file_filter = None;
         self.clamp_scroll_offset();
         self.keep_selected_visible();
     }
@@ -315,6 +523,9 @@ impl DiffState {
     /// Updates viewport width and clamps horizontal scrolling to visible content.
     pub fn set_viewport_width(&mut self, width: usize) {
         self.viewport_width = width.max(1);
+        if self.side_by_side_width() != self.layout_width {
+            self.rebuild();
+        }
         self.clamp_horizontal_offset();
     }
 
@@ -326,6 +537,7 @@ impl DiffState {
 
         let lines = self.rendered.split_inclusive('\n').collect::<Vec<_>>();
         let folded_header_width = self.folded_header_width(&lines);
+        let markers = self.note_markers();
         let mut visible = String::new();
         let mut line_index = 0;
 
@@ -337,7 +549,7 @@ impl DiffState {
                 line_index += 1;
             }
 
-            if !self.collapsed_paths.contains(&section.path) {
+            if !self.is_collapsed(&section.path) {
                 let folded_hunks = self
                     .hunks
                     .iter()
@@ -345,15 +557,23 @@ impl DiffState {
                     .filter(|hunk| self.collapsed_hunks.contains(&hunk.key));
                 for hunk in folded_hunks {
                     while line_index < hunk.start_line {
-                        self.push_visible_line(&mut visible, &lines, line_index);
+                        self.push_visible_line(&mut visible, &lines, line_index, &markers);
                         line_index += 1;
                     }
-                    self.push_visible_line(&mut visible, &lines, hunk.start_line);
-                    visible.push_str("  | folded hunk\n");
+                    self.push_visible_line(&mut visible, &lines, hunk.start_line, &markers);
+                    if hunk
+                        .viewed_key
+                        .as_ref()
+                        .is_some_and(|key| self.viewed.contains(key))
+                    {
+                        visible.push_str("  | viewed hunk\n");
+                    } else {
+                        visible.push_str("  | folded hunk\n");
+                    }
                     line_index = hunk.end_line;
                 }
                 while line_index < section.end_line {
-                    self.push_visible_line(&mut visible, &lines, line_index);
+                    self.push_visible_line(&mut visible, &lines, line_index, &markers);
                     line_index += 1;
                 }
                 continue;
@@ -362,10 +582,18 @@ impl DiffState {
             if let Some(header) = lines.get(section.start_line) {
                 let header = header.trim_end_matches('\n');
                 visible.push_str(header);
+                let mut extra = self.file_index_suffix(section_index);
+                if self.file_is_viewed(section_index) {
+                    extra.push_str("  viewed");
+                }
+                let notes = self.file_note_count(&section.path);
+                if notes > 0 {
+                    extra = format!("{extra}  ✎ {notes}");
+                }
                 visible.push_str(&section.folded_suffix(
                     folded_header_width,
                     visible_width(header),
-                    &self.file_index_suffix(section_index),
+                    &extra,
                 ));
                 visible.push('\n');
             }
@@ -380,7 +608,13 @@ impl DiffState {
         visible
     }
 
-    fn push_visible_line(&self, visible: &mut String, lines: &[&str], line_index: usize) {
+    fn push_visible_line(
+        &self,
+        visible: &mut String,
+        lines: &[&str],
+        line_index: usize,
+        markers: &BTreeMap<usize, String>,
+    ) {
         let Some(line) = lines.get(line_index) else {
             return;
         };
@@ -390,7 +624,14 @@ impl DiffState {
             .iter()
             .position(|section| section.start_line == line_index)
         else {
-            visible.push_str(line);
+            match markers.get(&line_index) {
+                Some(marker) => {
+                    visible.push_str(line.trim_end_matches('\n'));
+                    visible.push_str(marker);
+                    visible.push('\n');
+                }
+                None => visible.push_str(line),
+            }
             return;
         };
 
@@ -405,6 +646,9 @@ impl DiffState {
                 &self.file_index_suffix(section_index),
             ));
         }
+        if let Some(marker) = markers.get(&line_index) {
+            visible.push_str(marker);
+        }
         visible.push('\n');
     }
 
@@ -435,6 +679,12 @@ impl DiffState {
             sticky_header.push_str(&suffix);
         }
         sticky_header.push_str(&self.file_index_suffix(selected_index));
+        if let Some((from, to)) = &self.range {
+            sticky_header.push_str("  ");
+            sticky_header.push_str(from);
+            sticky_header.push_str(" → ");
+            sticky_header.push_str(to);
+        }
         Some(sticky_header)
     }
 
@@ -535,7 +785,50 @@ impl DiffState {
     #[cfg(test)]
     pub fn selected_file_is_collapsed(&self) -> bool {
         self.selected_section()
-            .is_some_and(|section| self.collapsed_paths.contains(&section.path))
+            .is_some_and(|section| self.is_collapsed(&section.path))
+    }
+
+    /// Lays out the loaded output again, keeping selected and collapsed paths when they remain.
+    fn rebuild(&mut self) {
+        let selected_path = self.selected_section().map(|section| section.path.clone());
+
+        self.layout_width = self.side_by_side_width();
+        self.rendered = match self.layout_width {
+            Some(width) => side_by_side(&self.source, width),
+            None => self.source.clone(),
+        };
+        self.sections = file_sections(&self.rendered, &self.file_stats);
+        self.hunks = hunk_sections(&self.rendered, &self.sections);
+        assign_viewed_keys(&self.source, &mut self.sections, &mut self.hunks);
+        self.selected_hunk = None;
+        self.collapsed_paths
+            .retain(|path| self.sections.iter().any(|section| section.path == *path));
+        self.collapsed_hunks
+            .retain(|key| self.hunks.iter().any(|hunk| hunk.key == *key));
+        let viewed_keys = self
+            .viewed_keys()
+            .map(ToOwned::to_owned)
+            .collect::<BTreeSet<_>>();
+        self.viewed.retain(|key| viewed_keys.contains(key));
+        self.selected = selected_path
+            .and_then(|path| {
+                self.sections
+                    .iter()
+                    .position(|section| section.path == path)
+            })
+            .or_else(|| (!self.sections.is_empty()).then_some(0));
+
+        self.clamp_scroll_offset();
+        self.clamp_horizontal_offset();
+        self.refresh_search_matches();
+    }
+
+    /// Returns the width to lay out columns for, or `None` when the body stays inline.
+    fn side_by_side_width(&self) -> Option<usize> {
+        (self.side_by_side
+            && self.viewport_width >= SIDE_BY_SIDE_MIN_WIDTH
+            && is_git_diff(&self.source))
+        .then_some(self.viewport_width)
     }
 
     /// Returns the selected file section.
@@ -673,6 +966,195 @@ impl DiffState {
         self.horizontal_offset = self.horizontal_offset.min(max_horizontal_offset);
     }
 
+    /// Maps each visible line to the rendered line it shows, mirroring `visible_rendered`.
+    ///
+    /// Fold placeholders map to `None`.
+    fn visible_line_map(&self) -> Vec<Option<usize>> {
+        let line_count = self.rendered.lines().count();
+        if self.sections.is_empty() {
+            return (0..line_count).map(Some).collect();
+        }
+
+        let mut map = Vec::new();
+        let mut line_index = 0;
+        for (section_index, section) in self.sections.iter().enumerate() {
+            map.extend((line_index..section.start_line).map(Some));
+            line_index = section.start_line;
+            if self.is_collapsed(&section.path) {
+                map.push(Some(section.start_line));
+                line_index = section.end_line;
+                continue;
+            }
+            let folded_hunks = self
+                .hunks
+                .iter()
+                .filter(|hunk| hunk.file_index == section_index)
+                .filter(|hunk| self.collapsed_hunks.contains(&hunk.key));
+            for hunk in folded_hunks {
+                map.extend((line_index..=hunk.start_line).map(Some));
+                map.push(None);
+                line_index = hunk.end_line;
+            }
+            map.extend((line_index..section.end_line).map(Some));
+            line_index = section.end_line;
+        }
+        map.extend((line_index..line_count).map(Some));
+        map
+    }
+
+    /// Maps a rendered line to the `jj diff` output line it came from.
+    ///
+    /// Side-by-side rows have no single source line, so they map to their hunk or file header.
+    fn source_line_for_rendered_line(&self, rendered_line: usize) -> Option<usize> {
+        if self.layout_width.is_none() {
+            return Some(rendered_line);
+        }
+        let source = SourceDiff::new(&self.source);
+        let section_index = self
+            .sections
+            .iter()
+            .rposition(|section| section.start_line <= rendered_line)?;
+        let section = &self.sections[section_index];
+        let source_index = source
+            .sections
+            .iter()
+            .position(|source_section| source_section.path == section.path)?;
+        let ordinal = self
+            .hunks
+            .iter()
+            .filter(|hunk| hunk.file_index == section_index)
+            .take_while(|hunk| hunk.start_line <= rendered_line)
+            .count();
+        if ordinal == 0 {
+            return Some(source.sections[source_index].start_line);
+        }
+        source
+            .hunks
+            .iter()
+            .filter(|hunk| hunk.file_index == source_index)
+            .nth(ordinal - 1)
+            .map(|hunk| hunk.start_line)
+    }
+
+    /// Maps a `jj diff` output line to the rendered line that shows it, the inverse of
+    /// `source_line_for_rendered_line`.
+    fn rendered_line_for_source_line(&self, source_line: usize) -> Option<usize> {
+        if self.layout_width.is_none() {
+            return Some(source_line);
+        }
+        let source = SourceDiff::new(&self.source);
+        let source_index = source
+            .sections
+            .iter()
+            .rposition(|section| section.start_line <= source_line)?;
+        let source_section = &source.sections[source_index];
+        let section_index = self
+            .sections
+            .iter()
+            .position(|section| section.path == source_section.path)?;
+        let ordinal = source
+            .hunks
+            .iter()
+            .filter(|hunk| hunk.file_index == source_index)
+            .take_while(|hunk| hunk.start_line <= source_line)
+            .count();
+        if ordinal == 0 {
+            return Some(self.sections[section_index].start_line);
+        }
+        self.hunks
+            .iter()
+            .filter(|hunk| hunk.file_index == section_index)
+            .nth(ordinal - 1)
+            .map(|hunk| hunk.start_line)
+    }
+
+    /// Returns the note text appended to each rendered line that carries notes.
+    fn note_markers(&self) -> BTreeMap<usize, String> {
+        let mut markers = BTreeMap::<usize, String>::new();
+        for placed in &self.notes {
+            let Some(line) = placed
+                .source_line
+                .and_then(|line| self.rendered_line_for_source_line(line))
+            else {
+                continue;
+            };
+            let comment = placed.note.comment().lines().next().unwrap_or_default();
+            let marker = markers.entry(line).or_default();
+            *marker = format!("{marker}  ✎ {comment}");
+        }
+        markers
+    }
+
+    /// Returns how many notes are attached within the file at `path`.
+    fn file_note_count(&self, path: &str) -> usize {
+        self.notes
+            .iter()
+            .filter(|placed| placed.source_line.is_some() && placed.note.anchor().path() == path)
+            .count()
+    }
+
+    /// Returns the viewed-mark keys for every file and hunk in this diff.
+    fn viewed_keys(&self) -> impl Iterator<Item = &str> {
+        self.sections
+            .iter()
+            .map(|section| section.viewed_key.as_str())
+            .chain(
+                self.hunks
+                    .iter()
+                    .filter_map(|hunk| hunk.viewed_key.as_deref()),
+            )
+    }
+
+    /// Returns whether the file, or every hunk in it, is marked viewed.
+    fn file_is_viewed(&self, section_index: usize) -> bool {
+        let Some(section) = self.sections.get(section_index) else {
+            return false;
+        };
+        if self.viewed.contains(&section.viewed_key) {
+            return true;
+        }
+        let mut hunks = self
+            .hunks
+            .iter()
+            .filter(|hunk| hunk.file_index == section_index)
+            .peekable();
+        hunks.peek().is_some()
+            && hunks.all(|hunk| {
+                hunk.viewed_key
+                    .as_ref()
+                    .is_some_and(|key| self.viewed.contains(key))
+            })
+    }
+
+    /// Folds every viewed file and hunk.
+    fn fold_viewed(&mut self) {
+        for section in &self.sections {
+            if self.viewed.contains(&section.viewed_key) {
+                self.collapsed_paths.insert(section.path.clone());
+            }
+        }
+        for hunk in &self.hunks {
+            if hunk
+                .viewed_key
+                .as_ref()
+                .is_some_and(|key| self.viewed.contains(key))
+            {
+                self.collapsed_hunks.insert(hunk.key.clone());
+            }
+        }
+        self.clamp_scroll_offset();
+        self.keep_selected_visible();
+    }
+
+    /// Returns whether the user or the file filter folded `path`.
+    fn is_collapsed(&self, path: &str) -> bool {
+        self.collapsed_paths.contains(path)
+            || self
+                .file_filter
+                .as_ref()
+                .is_some_and(|filter| !filter.paths.contains(path))
+    }
+
     /// Maps an original rendered line number to its line number after collapsed sections are
     /// hidden.
     fn visible_line_for_rendered_line(&self, rendered_line: usize) -> usize {
@@ -681,7 +1163,7 @@ impl DiffState {
             if section.start_line >= rendered_line {
                 break;
             }
-            if self.collapsed_paths.contains(&section.path) {
+            if self.is_collapsed(&section.path) {
                 hidden_lines += section.end_line.saturating_sub(section.start_line + 1);
             }
         }
@@ -694,9 +1176,7 @@ impl DiffState {
             let Some(section) = self.sections.get(hunk.file_index) else {
                 continue;
             };
-            if !self.collapsed_paths.contains(&section.path)
-                && self.collapsed_hunks.contains(&hunk.key)
-            {
+            if !self.is_collapsed(&section.path) && self.collapsed_hunks.contains(&hunk.key) {
                 hidden_lines += hunk.end_line.saturating_sub(hunk.start_line + 1);
             }
         }
@@ -719,6 +1199,126 @@ impl DiffState {
     }
 }
 
+fn snapshot_range(snapshot: &DiffSnapshot) -> Option<(String, String)> {
+    snapshot
+        .range()
+        .map(|(from, to)| (from.to_owned(), to.to_owned()))
+}
+
+/// A review note and the `jj diff` output line it is attached to, if it still matches.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct PlacedNote {
+    note: DiffNote,
+    source_line: Option<usize>,
+}
+
+/// File and hunk structure of the unlaid-out `jj diff` output, with ANSI-stripped lines.
+struct SourceDiff {
+    lines: Vec<String>,
+    sections: Vec<FileSection>,
+    hunks: Vec<HunkSection>,
+}
+
+impl SourceDiff {
+    fn new(source: &str) -> Self {
+        let sections = file_sections(source, &[]);
+        let hunks = hunk_sections(source, &sections);
+        Self {
+            lines: source
+                .lines()
+                .map(|line| strip_ansi(line).trim_end().to_owned())
+                .collect(),
+            sections,
+            hunks,
+        }
+    }
+
+    /// Builds the anchor for `line`: the line inside its hunk, the whole hunk for a hunk header,
+    /// or the file for headers outside any hunk.
+    fn anchor_for_line(&self, line: usize) -> Option<DiffAnchor> {
+        let section_index = self
+            .sections
+            .iter()
+            .rposition(|section| section.start_line <= line && line < section.end_line)?;
+        let section = &self.sections[section_index];
+        if let Some(hunk) = self
+            .hunks
+            .iter()
+            .find(|hunk| hunk.start_line <= line && line < hunk.end_line)
+        {
+            let context = self.lines[hunk.start_line..hunk.end_line].to_vec();
+            let anchored = (line > hunk.start_line).then(|| line - hunk.start_line);
+            return Some(DiffAnchor::new(&section.path, context, anchored));
+        }
+        let has_hunks = self
+            .hunks
+            .iter()
+            .any(|hunk| hunk.file_index == section_index);
+        if line == section.start_line || has_hunks {
+            return Some(DiffAnchor::new(&section.path, Vec::new(), None));
+        }
+        let body = &self.lines[section.start_line + 1..section.end_line];
+        let (context, anchored) = line_window(body, line - section.start_line - 1);
+        Some(DiffAnchor::new(&section.path, context, Some(anchored)))
+    }
+
+    /// Finds `anchor`'s content in this diff, by its whole quote first and then by the anchored
+    /// line alone, returning the matched line and a fresh anchor there.
+    fn place(&self, anchor: &DiffAnchor) -> Option<(usize, DiffAnchor)> {
+        let section = self
+            .sections
+            .iter()
+            .find(|section| section.path == anchor.path())?;
+        if anchor.context().is_empty() {
+            return Some((section.start_line, anchor.clone()));
+        }
+        let body_start = section.start_line + 1;
+        let body = &self.lines[body_start..section.end_line];
+        let line = find_context(body, anchor.context())
+            .map(|start| body_start + start + anchor.line().unwrap_or_default())
+            .or_else(|| {
+                let text = anchor.line_text()?;
+                body.iter()
+                    .position(|line| line == text)
+                    .map(|index| body_start + index)
+            })?;
+        let placed = self.anchor_for_line(line)?;
+        Some((line, placed))
+    }
+}
+
+/// Returns the file and, inside a hunk, the new-side line at `line` of unlaid-out `jj diff` output.
+pub fn source_location(source: &str, line: usize) -> Option<(String, Option<usize>)> {
+    SourceDiff::new(source)
+        .anchor_for_line(line)
+        .map(|anchor| anchor_location(&anchor))
+}
+
+fn anchor_location(anchor: &DiffAnchor) -> (String, Option<usize>) {
+    let line = anchor.line_range().map(|(start, _)| start);
+    (anchor.path().to_owned(), line)
+}
+
+/// Returns whether a file path matches a file filter query.
+pub type FileMatcher = fn(&str, &str) -> bool;
+
+/// Files kept open by the file list's filter, and the query and matcher that chose them.
+#[derive(Clone, Debug)]
+struct FileFilter {
+    query: String,
+    matcher: FileMatcher,
+    paths: BTreeSet<String>,
+}
+
+/// Filters compare by what they keep open; function pointer addresses are not meaningful.
+impl PartialEq for FileFilter {
+    fn eq(&self, other: &Self) -> bool {
+        self.query == other.query && self.paths == other.paths
+    }
+}
+
+impl Eq for FileFilter {}
+
 /// State for the last submitted diff search.
 #[derive(Clone, Debug, Eq, PartialEq)]
 struct SearchState {
@@ -742,6 +1342,7 @@ struct HunkSection {
     start_line: usize,
     end_line: usize,
     key: String,
+    viewed_key: Option<String>,
 }
 
 /// A file section discovered in `jj diff` output.
@@ -751,6 +1352,7 @@ struct FileSection {
     start_line: usize,
     end_line: usize,
     stat: Option<FileStat>,
+    viewed_key: String,
 }
 
 impl FileSection {
@@ -824,6 +1426,7 @@ fn file_sections(rendered: &str, file_stats: &[DiffFileStat]) -> Vec<FileSection
                     removed: stat.removed(),
                     rendered: stat.rendered().to_owned(),
                 }),
+            viewed_key: String::new(),
         });
     }
 
@@ -847,6 +1450,7 @@ fn hunk_sections(rendered: &str, file_sections: &[FileSection]) -> Vec<HunkSecti
                     start_line: line_index,
                     end_line: section.end_line,
                     key: hunk_key(&section.path, line),
+                    viewed_key: None,
                 });
             }
         }
@@ -865,10 +1469,67 @@ fn hunk_sections(rendered: &str, file_sections: &[FileSection]) -> Vec<HunkSecti
     hunks
 }
 
+/// Keys files and hunks for viewed marks by path and a hash of their content.
+///
+/// Keys come from `source`, the output before side-by-side layout, so relayout keeps them. Hunk
+/// hashes skip the `@@` header, so a hunk keeps its mark when edits above it shift line numbers.
+fn assign_viewed_keys(source: &str, sections: &mut [FileSection], hunks: &mut [HunkSection]) {
+    let source_sections = file_sections(source, &[]);
+    let source_hunks = hunk_sections(source, &source_sections);
+    let lines = source.lines().collect::<Vec<_>>();
+    let body = |start: usize, end: usize| lines.get(start..end).unwrap_or_default();
+
+    for (file_index, section) in sections.iter_mut().enumerate() {
+        let Some(source_index) = source_sections
+            .iter()
+            .position(|source_section| source_section.path == section.path)
+        else {
+            continue;
+        };
+        let source_section = &source_sections[source_index];
+        section.viewed_key = viewed_key(
+            "file",
+            &section.path,
+            body(source_section.start_line + 1, source_section.end_line),
+        );
+
+        let source_file_hunks = source_hunks
+            .iter()
+            .filter(|hunk| hunk.file_index == source_index);
+        let file_hunks = hunks
+            .iter_mut()
+            .filter(|hunk| hunk.file_index == file_index);
+        for (hunk, source_hunk) in file_hunks.zip(source_file_hunks) {
+            hunk.viewed_key = Some(viewed_key(
+                "hunk",
+                &section.path,
+                body(source_hunk.start_line + 1, source_hunk.end_line),
+            ));
+        }
+    }
+}
+
+/// Builds a viewed-mark key from an FNV-1a hash of the ANSI-stripped lines.
+fn viewed_key(kind: &str, path: &str, lines: &[&str]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for line in lines {
+        for byte in strip_ansi(line).trim_end().bytes().chain([b'\n']) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{kind}\t{hash:016x}\t{path}")
+}
+
 /// Extracts a stable file path from a visible `jj diff` file header line.
 fn file_header_path(line: &str) -> Option<String> {
     let line = strip_ansi(line);
     let line = line.trim();
+    if let Some(paths) = line.strip_prefix("diff --git a/") {
+        return paths
+            .rsplit_once(" b/")
+            .map(|(_, new_path)| new_path.to_owned());
+    }
     let path = [
         "Modified regular file ",
         "Added regular file ",
@@ -888,6 +1549,14 @@ fn file_header_path(line: &str) -> Option<String> {
     path.strip_suffix(':').map(ToOwned::to_owned)
 }
 
+/// Returns whether output starts with a `--git` file header that side-by-side layout can parse.
+fn is_git_diff(rendered: &str) -> bool {
+    rendered
+        .lines()
+        .next()
+        .is_some_and(|line| strip_ansi(line).starts_with("diff --git "))
+}
+
 /// Returns whether a rendered line looks like a unified diff hunk header.
 fn hunk_header_line(line: &str) -> bool {
     let line = strip_ansi(line);
@@ -1111,6 +1780,219 @@ mod tests {
         assert_eq!(state.sticky_header(), None);
     }
 
+    #[test]
+    fn file_filter_folds_other_files_and_restores_user_folds() {
+        let mut state = DiffState::new(snapshot(
+            "aaa",
+            "Modified regular file src/a.rs:\n a1\nModified regular file src/b.rs:\n b1\nModified regular file docs/c.md:\n c1\n",
+        ));
+        state.select_file_index(2);
+        state.fold_selected_file();
+
+        state.set_file_filter(Some(("a.rs c.md".to_owned(), |path, query| {
+            query.split(' ').any(|name| path.ends_with(name))
+        })));
+        let visible = strip_ansi(&state.visible_rendered());
+        assert!(visible.contains(" a1"));
+        assert!(!visible.contains(" b1"), "src/b.rs is outside the filter");
+        assert!(!visible.contains(" c1"), "the user's own fold stays");
+        assert_eq!(state.file_filter_query(), Some("a.rs c.md"));
+
+        state.refresh(snapshot(
+            "aaa",
+            "Modified regular file src/a.rs:\n a2\nModified regular file src/b.rs:\n b2\nAdded regular file docs/c.md:\n c2\nAdded regular file src/new/a.rs:\n n2\n",
+        ));
+        let visible = strip_ansi(&state.visible_rendered());
+        assert!(
+            visible.contains(" n2"),
+            "new files that match stay open after a refresh"
+        );
+        assert!(!visible.contains(" b2"));
+
+        state.set_file_filter(None);
+        let visible = strip_ansi(&state.visible_rendered());
+        assert!(visible.contains(" b2"));
+        assert!(!visible.contains(" c2"));
+    }
+
+    #[test]
+    fn viewed_marks_fold_count_progress_and_clear_when_content_changes() {
+        let two_files =
+            "Modified regular file src/a.rs:\n a1\nModified regular file src/b.rs:\n b1\n";
+        let mut state = DiffState::new(snapshot("aaa", two_files));
+
+        let (key, viewed) = state.toggle_viewed().expect("file mark");
+        assert!(viewed);
+        assert_eq!(state.viewed_progress(), (1, 2));
+        assert_eq!(state.progress_title(), "jj diff -r aaa  viewed 1/2");
+        assert!(!strip_ansi(&state.visible_rendered()).contains(" a1"));
+
+        let mut reopened = DiffState::new(snapshot("aaa", two_files));
+        reopened.set_viewed_marks(&BTreeSet::from([key]));
+        assert_eq!(reopened.viewed_progress(), (1, 2));
+        assert!(!strip_ansi(&reopened.visible_rendered()).contains(" a1"));
+
+        reopened.refresh(snapshot(
+            "aaa",
+            "Modified regular file src/a.rs:\n a2\nModified regular file src/b.rs:\n b1\n",
+        ));
+        assert_eq!(reopened.viewed_progress(), (0, 2));
+    }
+
+    #[test]
+    fn viewed_hunks_survive_line_shifts_and_complete_their_file() {
+        let mut state = DiffState::new(snapshot(
+            "aaa",
+            concat!(
+                "Modified regular file src/a.rs:\n",
+                "@@ -1,1 +1,1 @@\n",
+                " first\n",
+                "@@ -8,1 +8,1 @@\n",
+                " second\n",
+            ),
+        ));
+        state.select_next_hunk();
+        let (second, _) = state.toggle_viewed().expect("hunk mark");
+        assert_eq!(state.viewed_progress(), (0, 1));
+        assert!(
+            state
+                .visible_rendered()
+                .ends_with("@@ -8,1 +8,1 @@\n  | viewed hunk\n")
+        );
+
+        state.select_previous_hunk();
+        state.toggle_viewed();
+        assert_eq!(state.viewed_progress(), (1, 1));
+
+        let mut shifted = DiffState::new(snapshot(
+            "aaa",
+            concat!(
+                "Modified regular file src/a.rs:\n",
+                "@@ -1,1 +1,1 @@\n",
+                " changed\n",
+                "@@ -10,1 +10,1 @@\n",
+                " second\n",
+            ),
+        ));
+        shifted.set_viewed_marks(&BTreeSet::from([second]));
+        let visible = strip_ansi(&shifted.visible_rendered());
+        assert!(visible.contains(" changed"));
+        assert!(!visible.contains(" second"));
+        assert_eq!(shifted.viewed_progress(), (0, 1));
+    }
+
+    #[test]
+    fn review_notes_anchor_to_the_top_line_and_follow_content_on_refresh() {
+        let mut state = DiffState::new(snapshot(
+            "aaa",
+            concat!(
+                "diff --git a/src/a.rs b/src/a.rs\n",
+                "@@ -1,2 +1,2 @@\n",
+                " keep\n",
+                "-old\n",
+                "+new\n",
+            ),
+        ));
+        state.keep_selected_in_view(1);
+        state.scroll_next_line();
+        state.scroll_next_line();
+        state.scroll_next_line();
+
+        let anchor = state.anchor_at_cursor().expect("line anchor");
+        assert_eq!(anchor.path(), "src/a.rs");
+        assert_eq!(anchor.line_text(), Some("-old"));
+        state.set_note(anchor.clone(), "Why remove this?");
+        assert_eq!(state.note_for(&anchor), Some("Why remove this?"));
+        assert!(
+            strip_ansi(&state.visible_rendered()).contains("-old  \u{270e} Why remove this?\n")
+        );
+
+        state.refresh(snapshot(
+            "aaa",
+            concat!(
+                "diff --git a/src/a.rs b/src/a.rs\n",
+                "@@ -4,2 +4,2 @@\n",
+                " keep\n",
+                "-old\n",
+                "+newer\n",
+            ),
+        ));
+        let notes = state.notes();
+        assert!(!notes[0].is_outdated());
+        assert_eq!(notes[0].anchor().line_range(), Some((5, 5)));
+        assert_eq!(notes[0].anchor().context()[0], "@@ -4,2 +4,2 @@");
+
+        state.refresh(snapshot(
+            "aaa",
+            "diff --git a/src/a.rs b/src/a.rs\n@@ -1,1 +1,1 @@\n-other\n+lines\n",
+        ));
+        assert!(state.notes()[0].is_outdated());
+    }
+
+    #[test]
+    fn cursor_location_counts_new_side_lines_from_the_hunk_header() {
+        let mut state = DiffState::new(snapshot(
+            "aaa",
+            concat!(
+                "diff --git a/src/a.rs b/src/a.rs\n",
+                "@@ -10,3 +20,3 @@\n",
+                " keep\n",
+                "-old\n",
+                "+new\n",
+                " tail\n",
+            ),
+        ));
+        state.keep_selected_in_view(1);
+        assert_eq!(state.cursor_location(), Some(("src/a.rs".to_owned(), None)));
+
+        state.scroll_next_line();
+        assert_eq!(
+            state.cursor_location(),
+            Some(("src/a.rs".to_owned(), Some(20)))
+        );
+
+        for _ in 0..3 {
+            state.scroll_next_line();
+        }
+        assert_eq!(
+            state.cursor_location(),
+            Some(("src/a.rs".to_owned(), Some(21)))
+        );
+    }
+
+    #[test]
+    fn blank_review_note_removes_it() {
+        let mut state = DiffState::new(snapshot(
+            "aaa",
+            "Modified regular file src/a.rs:\n   1    1: a\n",
+        ));
+        let anchor = state.anchor_at_cursor().expect("file anchor");
+        state.set_note(anchor.clone(), "Looks fine.");
+
+        state.set_note(anchor, "  ");
+
+        assert!(state.notes().is_empty());
+    }
+
+    #[test]
+    fn sticky_header_names_both_ends_of_a_comparison() {
+        let mut state = DiffState::new(
+            snapshot(
+                "main..@",
+                "Modified regular file src/a.rs:\n a1\n a2\nModified regular file src/b.rs:\n b\n",
+            )
+            .with_range("main", "@"),
+        );
+        state.keep_selected_in_view(2);
+
+        state.scroll_next_line();
+
+        assert_eq!(
+            state.sticky_header(),
+            Some("Modified regular file src/a.rs:  [file 1/2]  main → @".to_owned())
+        );
+    }
+
     #[test]
     fn sticky_header_includes_stat_suffix_and_file_index() {
         let mut state = DiffState::new(snapshot_with_stats(
@@ -1381,6 +2263,57 @@ mod tests {
         assert_eq!(state.selected_visible_line(), Some(3));
     }
 
+    #[test]
+    fn side_by_side_layout_follows_width_and_keeps_file_and_hunk_navigation() {
+        let mut state = DiffState::new(snapshot(
+            "aaa",
+            concat!(
+                "diff --git a/src/a.rs b/src/a.rs\n",
+                "@@ -1,2 +1,2 @@\n",
+                "-old a\n",
+                "+new a\n",
+                " same\n",
+                "diff --git a/src/b.rs b/src/b.rs\n",
+                "@@ -4 +4 @@\n",
+                "-old b\n",
+                "+new b\n",
+            ),
+        ));
+        state.keep_selected_in_view(3);
+        state.set_side_by_side(true);
+        assert!(
+            !state.is_side_by_side(),
+            "the default viewport is too narrow"
+        );
+
+        state.set_viewport_width(SIDE_BY_SIDE_MIN_WIDTH);
+        assert!(state.is_side_by_side());
+        assert_eq!(state.file_paths(), vec!["src/a.rs", "src/b.rs"]);
+        let rows = state
+            .visible_rendered()
+            .lines()
+            .map(strip_ansi)
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 7);
+        assert!(rows[2].starts_with("  1 -old a"));
+        assert!(rows[2].contains(" │   1 +new a"));
+
+        state.select_next_file();
+        state.select_next_hunk();
+        assert_eq!(state.scroll_offset(), 4);
+        state.fold_selected_hunk();
+        assert!(
+            state
+                .visible_rendered()
+                .contains("@@ -4 +4 @@\u{1b}[0m\n  | folded hunk\n")
+        );
+
+        state.set_viewport_width(SIDE_BY_SIDE_MIN_WIDTH - 1);
+        assert!(!state.is_side_by_side());
+        assert_eq!(state.selected_file_index(), Some(1));
+        assert!(state.visible_rendered().contains("-old a\n+new a\n"));
+    }
+
     fn snapshot(change_id: &str, rendered: &str) -> DiffSnapshot {
         DiffSnapshot::new(change_id, rendered).with_title(format!("jj diff -r {change_id}"))
     }
@@ -1,10 +1,16 @@
 //! Public selected-change diff view and action contract.
 
-use jk_core::DiffSnapshot;
+use std::collections::BTreeSet;
+
+use jk_core::{DiffFileStat, DiffSnapshot};
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::widgets::Paragraph;
 
+pub use crate::diff_notes::{DiffAnchor, DiffNote, notes_markdown};
+pub use crate::diff_patch::GitPatch;
+pub use crate::diff_state::FileMatcher;
+
 use crate::chrome::{ViewChrome, render_help_overlay};
 use crate::diff_state::DiffState;
 use crate::keymap::{BindingContext, adaptive_hotbar, help_lines, help_title};
@@ -143,12 +149,38 @@ impl DiffView {
         view
     }
 
+    /// Lays the diff out side by side on wide terminals, for `--git` output.
+    #[must_use]
+    pub fn with_side_by_side(mut self, enabled: bool) -> Self {
+        self.set_side_by_side(enabled);
+        self
+    }
+
+    /// Switches between side-by-side and inline layout without reloading.
+    ///
+    /// Side-by-side layout needs `--git` output and falls back to inline below 100 columns.
+    pub fn set_side_by_side(&mut self, enabled: bool) {
+        self.state.set_side_by_side(enabled);
+    }
+
+    /// Returns whether the body is currently laid out in two columns.
+    #[must_use]
+    pub const fn is_side_by_side(&self) -> bool {
+        self.state.is_side_by_side()
+    }
+
     /// Returns the target change identifier for refresh requests.
     #[must_use]
     pub fn change_id(&self) -> &str {
         self.state.change_id()
     }
 
+    /// Returns the command context shown in the title bar.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        self.state.title()
+    }
+
     /// Replaces diff output after a successful refresh.
     pub fn refresh(&mut self, snapshot: DiffSnapshot) {
         self.state.refresh(snapshot);
@@ -167,12 +199,80 @@ impl DiffView {
         self.state.file_paths()
     }
 
+    /// Returns per-file line counts loaded with the diff.
+    #[must_use]
+    pub fn file_stats(&self) -> &[DiffFileStat] {
+        self.state.file_stats()
+    }
+
     /// Returns the currently selected file section index.
     #[must_use]
     pub const fn selected_file_index(&self) -> Option<usize> {
         self.state.selected_file_index()
     }
 
+    /// Folds every file the filter's matcher rejects, including after refreshes; `None` restores
+    /// the user's own folds.
+    pub fn set_file_filter(&mut self, filter: Option<(String, FileMatcher)>) {
+        self.state.set_file_filter(filter);
+    }
+
+    /// Returns the query behind the active file filter.
+    #[must_use]
+    pub fn file_filter_query(&self) -> Option<&str> {
+        self.state.file_filter_query()
+    }
+
+    /// Applies remembered viewed marks that still match this diff and folds those sections.
+    pub fn set_viewed_marks(&mut self, marks: &BTreeSet<String>) {
+        self.state.set_viewed_marks(marks);
+    }
+
+    /// Toggles the viewed mark on the selected hunk or file, returning the key and new state.
+    pub fn toggle_viewed(&mut self) -> Option<(String, bool)> {
+        self.state.toggle_viewed()
+    }
+
+    /// Returns the review-note anchor for the line at the top of the viewport.
+    #[must_use]
+    pub fn anchor_at_cursor(&self) -> Option<DiffAnchor> {
+        self.state.anchor_at_cursor()
+    }
+
+    /// Returns the file and new-side line at the top of the viewport.
+    #[must_use]
+    pub fn cursor_location(&self) -> Option<(String, Option<usize>)> {
+        self.state.cursor_location()
+    }
+
+    /// Returns the comment of the note attached at `anchor`.
+    #[must_use]
+    pub fn note_for(&self, anchor: &DiffAnchor) -> Option<&str> {
+        self.state.note_for(anchor)
+    }
+
+    /// Replaces the note at `anchor`, removing it when `comment` is blank.
+    pub fn set_note(&mut self, anchor: DiffAnchor, comment: &str) {
+        self.state.set_note(anchor, comment);
+    }
+
+    /// Places remembered notes on this diff by content.
+    pub fn set_notes(&mut self, notes: Vec<DiffNote>) {
+        self.state.set_notes(notes);
+    }
+
+    /// Returns the review notes in diff order, outdated ones last.
+    #[must_use]
+    pub fn notes(&self) -> Vec<DiffNote> {
+        self.state.notes()
+    }
+
+    /// Returns viewed and total file counts.
+    #[must_use]
+    pub fn viewed_progress(&self) -> (usize, usize) {
+        self.state.viewed_progress()
+    }
+
     /// Jumps to a file section by index.
     pub fn select_file_index(&mut self, index: usize) {
         self.state.select_file_index(index);
@@ -183,6 +283,11 @@ impl DiffView {
         self.status_message = Some(error.into());
     }
 
+    /// Shows a transient status message in the footer.
+    pub fn show_status(&mut self, status: impl Into<String>) {
+        self.status_message = Some(status.into());
+    }
+
     /// Applies a single input action.
     #[must_use]
     pub fn apply(&mut self, action: DiffAction) -> DiffActionResult {
@@ -292,6 +397,11 @@ impl DiffView {
         self.render_area(frame, area, None);
     }
 
+    /// Renders the diff view into `area`, such as the preview pane beside the log.
+    pub fn render_in(&mut self, frame: &mut Frame<'_>, area: Rect) {
+        self.render_area(frame, area, None);
+    }
+
     /// Renders the diff view with a temporary status-line override.
     pub fn render_with_status(&mut self, frame: &mut Frame<'_>, status: &str) {
         let area = frame.area();
@@ -334,7 +444,8 @@ impl DiffView {
             .or(horizontal_status.as_deref())
             .or(file_status.as_deref())
             .unwrap_or(&fallback_status);
-        let chrome = ViewChrome::new(self.state.title(), status);
+        let title = self.state.progress_title();
+        let chrome = ViewChrome::new(&title, status);
         chrome.render(frame, areas);
 
         let rendered = self.visible_body();
@@ -34,6 +34,7 @@ enum ActionId {
     Collapse,
     OpenShow,
     OpenDiff,
+    OpenInterdiff,
     OpenLog,
     OpenDescribe,
     OpenEvolog,
@@ -47,13 +48,26 @@ enum ActionId {
     NewChange,
     EditChange,
     Abandon,
+    FindRevision,
+    GraphJump,
+    Rebase,
+    Squash,
     Undo,
     Redo,
+    Checkpoint,
+    RestoreCheckpoint,
     UpdateStale,
     ViewOptions,
     Refresh,
     SwitchLogCommand,
+    Revset,
     OpenFileList,
+    ToggleViewed,
+    ReviewNote,
+    ExportNotes,
+    OpenInEditor,
+    CopyPatch,
+    CopyRevision,
     File,
     Hunk,
     FoldFile,
@@ -79,6 +93,8 @@ const fn default_help_group(action: ActionId) -> HelpGroup {
         | ActionId::Collapse
         | ActionId::HorizontalScroll
         | ActionId::Search
+        | ActionId::FindRevision
+        | ActionId::GraphJump
         | ActionId::ReturnToLog
         | ActionId::ReturnBack => HelpGroup::Navigation,
         ActionId::OpenShow
@@ -87,8 +103,15 @@ const fn default_help_group(action: ActionId) -> HelpGroup {
         | ActionId::OpenEvolog
         | ActionId::OpenStatus
         | ActionId::SwitchLogCommand
+        | ActionId::Revset
         | ActionId::ViewOptions
+        | ActionId::OpenInterdiff
         | ActionId::OpenFileList
+        | ActionId::ToggleViewed
+        | ActionId::ReviewNote
+        | ActionId::ExportNotes
+        | ActionId::OpenInEditor
+        | ActionId::CopyPatch
         | ActionId::File
         | ActionId::Hunk
         | ActionId::FoldFile
@@ -98,15 +121,20 @@ const fn default_help_group(action: ActionId) -> HelpGroup {
         | ActionId::NewChange
         | ActionId::EditChange
         | ActionId::Abandon
+        | ActionId::Rebase
+        | ActionId::Squash
         | ActionId::Mark
-        | ActionId::ClearMarks => HelpGroup::Mutations,
+        | ActionId::ClearMarks
+        | ActionId::CopyRevision => HelpGroup::Mutations,
         ActionId::OpenCommandHistory
         | ActionId::OpenCommandDetails
         | ActionId::CopyCommand
         | ActionId::OpenOperation
         | ActionId::OpenOperationLog
         | ActionId::Undo
-        | ActionId::Redo => HelpGroup::Recovery,
+        | ActionId::Redo
+        | ActionId::Checkpoint
+        | ActionId::RestoreCheckpoint => HelpGroup::Recovery,
         ActionId::CommandMode
         | ActionId::Refresh
         | ActionId::UpdateStale
@@ -151,6 +179,7 @@ impl ActionId {
             Self::Collapse => "Collapse change",
             Self::OpenShow => "Open show",
             Self::OpenDiff => "Open diff",
+            Self::OpenInterdiff => "Open interdiff",
             Self::OpenLog => "Open log",
             Self::OpenDescribe => "Describe revision",
             Self::OpenEvolog => "Open evolog",
@@ -164,13 +193,26 @@ impl ActionId {
             Self::NewChange => "New change",
             Self::EditChange => "Edit change",
             Self::Abandon => "Abandon revision",
+            Self::FindRevision => "Find revision",
+            Self::GraphJump => "Graph jump",
+            Self::Rebase => "Rebase revisions",
+            Self::Squash => "Squash revision",
             Self::Undo => "Undo",
             Self::Redo => "Redo",
+            Self::Checkpoint => "Record checkpoint",
+            Self::RestoreCheckpoint => "Restore checkpoint",
             Self::UpdateStale => "Update stale",
             Self::ViewOptions => "View options",
             Self::Refresh => "Refresh",
             Self::SwitchLogCommand => "Switch log command",
+            Self::Revset => "Change revset",
             Self::OpenFileList => "Open file list",
+            Self::ToggleViewed => "Mark viewed",
+            Self::ReviewNote => "Review note",
+            Self::ExportNotes => "Export notes",
+            Self::OpenInEditor => "Open in editor",
+            Self::CopyPatch => "Copy patch",
+            Self::CopyRevision => "Copy revision",
             Self::File => "Move file",
             Self::Hunk => "Move hunk",
             Self::FoldFile => "Fold file",
@@ -304,6 +346,11 @@ impl KeyBinding {
         self
     }
 
+    const fn with_help_group(mut self, help_group: HelpGroup) -> Self {
+        self.help_group = help_group;
+        self
+    }
+
     const fn with_hotbar(mut self, rank: u8, hotbar: &'static str) -> Self {
         self.hotbar = Some(hotbar);
         self.hotbar_rank = Some(rank);
@@ -327,6 +374,7 @@ const LOG_BINDINGS: &[KeyBinding] = &[
         .with_hotbar(4, "enter open"),
     KeyBinding::new(ActionId::OpenDiff, "d", "open selected-change diff")
         .with_family(CommandFamily::JjDiff)
+        .with_aliases(&["compare", "range", "from", "to", "marks"])
         .with_hotbar(5, "d diff"),
     KeyBinding::new(ActionId::OpenEvolog, "v", "open selected-change evolog")
         .with_family(CommandFamily::JjEvolog)
@@ -349,10 +397,21 @@ const LOG_BINDINGS: &[KeyBinding] = &[
         .with_family(CommandFamily::JjDescribe)
         .with_aliases(&["message", "description", "mutation", "preview"])
         .with_hotbar(6, "m describe"),
-    KeyBinding::new(ActionId::NewChange, "n", "preview jj new")
-        .with_family(CommandFamily::JjNew)
-        .with_aliases(&["new", "change", "parent", "mutation", "preview"])
-        .with_hotbar(15, "n new"),
+    KeyBinding::new(
+        ActionId::Search,
+        "/, n, N",
+        "search changes; n/N repeat, esc clears",
+    )
+    .with_family(CommandFamily::Search)
+    .with_aliases(&["find", "filter", "description", "change id", "commit id"]),
+    KeyBinding::new(
+        ActionId::NewChange,
+        "n",
+        "preview jj new (next match while /query shown)",
+    )
+    .with_family(CommandFamily::JjNew)
+    .with_aliases(&["new", "change", "parent", "mutation", "preview"])
+    .with_hotbar(15, "n new"),
     KeyBinding::new(ActionId::EditChange, "e", "preview jj edit")
         .with_family(CommandFamily::JjEdit)
         .with_aliases(&["edit", "checkout", "working copy", "mutation", "preview"])
@@ -361,6 +420,33 @@ const LOG_BINDINGS: &[KeyBinding] = &[
         .with_family(CommandFamily::JjOperation)
         .with_aliases(&["abandon", "delete", "destructive", "mutation", "preview"])
         .with_hotbar(16, "a abandon"),
+    KeyBinding::new(
+        ActionId::FindRevision,
+        "p",
+        "find any revision and jump to it",
+    )
+    .with_family(CommandFamily::JjLog)
+    .with_aliases(&["picker", "fuzzy", "jump", "goto", "author", "bookmark"]),
+    KeyBinding::new(
+        ActionId::CopyRevision,
+        "y",
+        "copy change id, commit id or description",
+    )
+    .with_family(CommandFamily::JjLog)
+    .with_aliases(&[
+        "copy",
+        "clipboard",
+        "yank",
+        "change id",
+        "commit id",
+        "hash",
+    ]),
+    KeyBinding::new(ActionId::Rebase, "B", "pick destination, preview jj rebase")
+        .with_family(CommandFamily::JjOperation)
+        .with_aliases(&["rebase", "move", "destination", "mutation", "preview"]),
+    KeyBinding::new(ActionId::Squash, "S", "pick target, preview jj squash")
+        .with_family(CommandFamily::JjOperation)
+        .with_aliases(&["squash", "fold", "combine", "mutation", "preview"]),
     KeyBinding::new(ActionId::Undo, "u", "preview jj undo")
         .with_family(CommandFamily::JjOperation)
         .with_aliases(&["undo", "operation", "recovery"])
@@ -369,6 +455,26 @@ const LOG_BINDINGS: &[KeyBinding] = &[
         .with_family(CommandFamily::JjOperation)
         .with_aliases(&["redo", "operation", "recovery"])
         .with_hotbar(14, "U redo"),
+    KeyBinding::new(
+        ActionId::Checkpoint,
+        "K",
+        "record named checkpoint at current operation",
+    )
+    .with_family(CommandFamily::JjOperation)
+    .with_aliases(&["checkpoint", "session", "operation", "recovery"]),
+    KeyBinding::new(
+        ActionId::RestoreCheckpoint,
+        "R",
+        "pick a checkpoint and preview jj op restore",
+    )
+    .with_family(CommandFamily::JjOperation)
+    .with_aliases(&[
+        "restore",
+        "checkpoint",
+        "session start",
+        "operation",
+        "recovery",
+    ]),
     KeyBinding::new(ActionId::Mark, "space", "mark/unmark selected revision")
         .with_family(CommandFamily::Mark)
         .with_aliases(&["selected", "revision", "toggle"])
@@ -381,6 +487,13 @@ const LOG_BINDINGS: &[KeyBinding] = &[
     .with_family(CommandFamily::Mark)
     .with_aliases(&["clear", "unmark", "selected", "revision"])
     .with_hotbar(11, "c clear"),
+    KeyBinding::new(
+        ActionId::OpenInterdiff,
+        "D",
+        "interdiff two marks (d diffs them)",
+    )
+    .with_family(CommandFamily::JjDiff)
+    .with_aliases(&["interdiff", "compare", "patch series", "version"]),
     KeyBinding::new(ActionId::OpenCommandHistory, "C", "open command history")
         .with_family(CommandFamily::History)
         .with_aliases(&["commands", "history", "recent"]),
@@ -389,7 +502,7 @@ const LOG_BINDINGS: &[KeyBinding] = &[
         .with_aliases(&["command", "prompt", "colon", "jj"]),
     KeyBinding::new(ActionId::ViewOptions, "V", "open view options")
         .with_family(CommandFamily::ViewOptions)
-        .with_aliases(&["view", "options", "template", "jj log"])
+        .with_aliases(&["view", "options", "template", "revset", "preset", "jj log"])
         .with_hotbar(18, "V options"),
     KeyBinding::new(ActionId::Refresh, "r", "refresh")
         .with_family(CommandFamily::Refresh)
@@ -398,6 +511,13 @@ const LOG_BINDINGS: &[KeyBinding] = &[
         .with_family(CommandFamily::JjLog)
         .with_aliases(&["home", "current screen"])
         .with_hotbar(2, "H home  L log"),
+    KeyBinding::new(
+        ActionId::Revset,
+        "i",
+        "edit log revset; empty resets to default",
+    )
+    .with_family(CommandFamily::JjLog)
+    .with_aliases(&["revset", "filter", "query", "scope", "-r", "history"]),
     KeyBinding::new(ActionId::Move, "↑/↓, j/k", "move selection")
         .with_family(CommandFamily::Navigation)
         .with_aliases(&["selection", "current row"])
@@ -414,6 +534,13 @@ const LOG_BINDINGS: &[KeyBinding] = &[
         .with_family(CommandFamily::Navigation),
     KeyBinding::new(ActionId::JumpBottom, "End, G", "jump to bottom")
         .with_family(CommandFamily::Navigation),
+    KeyBinding::new(
+        ActionId::GraphJump,
+        "P tab @ '",
+        "jump to parent, next child, @, bookmark",
+    )
+    .with_family(CommandFamily::Navigation)
+    .with_aliases(&["graph", "parent", "child", "working copy", "bookmark"]),
     KeyBinding::new(ActionId::Expand, "→, l", "expand change / drill into ~")
         .with_family(CommandFamily::Navigation),
     KeyBinding::new(ActionId::Collapse, "←, h", "collapse selected change")
@@ -432,6 +559,33 @@ const DIFF_BINDINGS: &[KeyBinding] = &[
         .with_family(CommandFamily::File)
         .with_aliases(&["files", "paths", "jump", "file list"])
         .with_hotbar(5, "f files"),
+    KeyBinding::new(ActionId::ToggleViewed, "v", "mark file or hunk viewed")
+        .with_family(CommandFamily::File)
+        .with_aliases(&["viewed", "reviewed", "progress"]),
+    KeyBinding::new(
+        ActionId::ReviewNote,
+        "c",
+        "add or edit a note at the top line",
+    )
+    .with_family(CommandFamily::File)
+    .with_aliases(&["comment", "note", "review"]),
+    KeyBinding::new(ActionId::ExportNotes, "x", "export notes as markdown")
+        .with_family(CommandFamily::File)
+        .with_aliases(&["export", "notes", "markdown", "feedback"]),
+    KeyBinding::new(
+        ActionId::OpenInEditor,
+        "e",
+        "open the file at the top line in $EDITOR",
+    )
+    .with_family(CommandFamily::File)
+    .with_aliases(&["editor", "edit file", "open file"]),
+    KeyBinding::new(
+        ActionId::CopyPatch,
+        "y",
+        "copy or save the hunk, file or diff as a patch",
+    )
+    .with_family(CommandFamily::File)
+    .with_aliases(&["copy", "patch", "yank", "save patch", "markdown"]),
     KeyBinding::new(ActionId::File, "[ / ]", "previous/next file").with_family(CommandFamily::File),
     KeyBinding::new(ActionId::Hunk, "{ / }", "previous/next hunk").with_family(CommandFamily::Hunk),
     KeyBinding::new(ActionId::FoldFile, "h / l", "fold/unfold current file")
@@ -662,8 +816,42 @@ const OPERATION_LOG_BINDINGS: &[KeyBinding] = &[
         .with_hotbar(2, "enter show"),
     KeyBinding::new(ActionId::OpenDiff, "d", "open selected operation diff")
         .with_family(CommandFamily::JjOperation)
-        .with_aliases(&["diff", "operation", "selected"])
+        .with_aliases(&["diff", "operation", "selected", "range", "from", "to"])
         .with_hotbar(3, "d diff"),
+    KeyBinding::new(
+        ActionId::Mark,
+        "space",
+        "mark/unmark operation; two marks diff --from/--to",
+    )
+    .with_family(CommandFamily::Mark)
+    .with_aliases(&["operation", "toggle", "range", "compare"])
+    .with_help_group(HelpGroup::Views)
+    .with_hotbar(5, "space mark"),
+    KeyBinding::new(
+        ActionId::ClearMarks,
+        "c",
+        "clear operation marks when marks exist",
+    )
+    .with_family(CommandFamily::Mark)
+    .with_aliases(&["clear", "unmark", "operation"])
+    .with_help_group(HelpGroup::Views),
+    KeyBinding::new(
+        ActionId::ViewOptions,
+        "V",
+        "filter by user, host, time, or jk origin",
+    )
+    .with_family(CommandFamily::ViewOptions)
+    .with_aliases(&[
+        "view", "options", "filter", "user", "host", "agent", "time", "jk",
+    ])
+    .with_help_group(HelpGroup::Views),
+    KeyBinding::new(
+        ActionId::Checkpoint,
+        "K",
+        "record named checkpoint at current operation",
+    )
+    .with_family(CommandFamily::JjOperation)
+    .with_aliases(&["checkpoint", "session", "operation", "recovery"]),
     KeyBinding::new(ActionId::Refresh, "r", "refresh operation log")
         .with_family(CommandFamily::Refresh)
         .with_aliases(&["reload", "operation"])
@@ -675,10 +863,9 @@ const OPERATION_LOG_BINDINGS: &[KeyBinding] = &[
         .with_family(CommandFamily::Navigation)
         .with_aliases(&["selection", "operation", "current row"])
         .with_hotbar(4, "j/k move"),
-    KeyBinding::new(ActionId::PageDown, "Space, PgDn, Ctrl-f", "page down")
+    KeyBinding::new(ActionId::PageDown, "f, PgDn, Ctrl-f", "page down")
         .with_family(CommandFamily::Navigation)
-        .with_aliases(&["page", "pagedown", "pageup"])
-        .with_hotbar(5, "space page"),
+        .with_aliases(&["page", "pagedown", "pageup"]),
     KeyBinding::new(ActionId::PageUp, "PgUp, Ctrl-b", "page up")
         .with_family(CommandFamily::Navigation)
         .with_aliases(&["page", "pagedown", "pageup"]),
@@ -1122,7 +1309,10 @@ fn discovery_command_display_label(row: DiscoveryRow) -> Option<&'static str> {
         (Some(CommandFamily::JjOperation), ActionId::OpenDiff) => Some("jj op diff"),
         (Some(CommandFamily::JjOperation), ActionId::Undo) => Some("jj undo"),
         (Some(CommandFamily::JjOperation), ActionId::Redo) => Some("jj redo"),
+        (Some(CommandFamily::JjOperation), ActionId::RestoreCheckpoint) => Some("jj op restore"),
         (Some(CommandFamily::JjOperation), ActionId::Abandon) => Some("jj abandon"),
+        (Some(CommandFamily::JjOperation), ActionId::Rebase) => Some("jj rebase"),
+        (Some(CommandFamily::JjOperation), ActionId::Squash) => Some("jj squash"),
         (Some(CommandFamily::JjOperation) | None, _) => None,
         (Some(family), _) => {
             let label = family.label();
@@ -1327,7 +1517,7 @@ mod tests {
     fn operation_log_hotbar_matches_current_status_text() {
         assert_eq!(
             hotbar(BindingContext::OperationLog),
-            "? help  enter show  d diff  j/k move  space page  r refresh  Esc back  q quit"
+            "? help  enter show  d diff  j/k move  space mark  r refresh  Esc back  q quit"
         );
     }
 
@@ -1538,13 +1728,16 @@ mod tests {
             &lines,
             &[
                 "Open and inspect:",
-                "  enter               open selected operation show",
-                "  d                   open selected operation diff",
+                "  enter           open selected operation show",
+                "  d               open selected operation diff",
+                "  space           mark/unmark operation; two marks diff --from/--to",
+                "  c               clear operation marks when marks exist",
                 "Move and find:",
-                "  Backspace, Esc      return to previous view",
+                "  f, PgDn, Ctrl-f page down",
+                "  Backspace, Esc  return to previous view",
                 "Session:",
-                "  r                   refresh operation log",
-                "  :                   run jj command",
+                "  r               refresh operation log",
+                "  :               run jj command",
             ],
         );
     }
@@ -1725,8 +1918,11 @@ mod tests {
                 .any(|line| line.starts_with("showing lines 1-"))
         );
         assert!(lines.iter().any(|line| line.contains("j/k scroll")));
-        assert!(lines.iter().any(|line| line.contains("↑/↓, j/k")));
         assert!(!lines.iter().any(|line| line.contains("...")));
+
+        let scrolled = discovery_lines_for_width(BindingContext::Log, "", 4, 40);
+        assert!(scrolled.iter().any(|line| line.contains("↑/↓, j/k")));
+        assert!(!scrolled.iter().any(|line| line.contains("...")));
     }
 
     #[test]
@@ -15,12 +15,17 @@ pub mod workspaces_view;
 
 mod ansi_text;
 mod chrome;
+mod diff_notes;
+mod diff_patch;
 mod diff_state;
 mod keymap;
 mod log_state;
+mod ordered_marks;
 mod rendered_log;
 mod rendered_state;
+mod search_highlight;
 mod selected_row;
+mod side_by_side;
 
 /// Contextual command-help metadata and popup formatting.
 pub mod command_discovery {
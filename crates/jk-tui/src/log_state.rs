@@ -4,48 +4,67 @@
 //! keeps rendering concerns out, but it knows enough about line positions to preserve selection,
 //! keep the selected change in view, and choose where inline details should be inserted.
 
-use jk_core::{LogEntry, LogSnapshot};
+use std::collections::{HashSet, VecDeque};
+
+use jk_core::{ChangeFlag, LogEntry, LogSnapshot};
 
 use crate::ansi_text::strip_ansi;
 use crate::chrome::title_or_default;
+use crate::ordered_marks::OrderedMarks;
 
 const REVSET_ID_PREFIX_LEN: usize = 8;
+/// Entries from the end of a partial page at which the next page starts loading.
+const LOAD_MORE_MARGIN: usize = 5;
+/// Synthetic elision row shown below a log page that filled its limit.
+const LOAD_MORE_LINE: &str = "~  (more changes: enter or keep scrolling to load)";
+
+/// Graph-aware jumps from the selected change.
+///
+/// Unlike row movement these follow the commit graph, so they stay meaningful in branchy logs where
+/// the next rendered row may be an unrelated head.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum GraphMove {
+    /// Move to the selected change's first parent.
+    FirstParent,
 
-/// Ordered revision marks keyed by stable change id.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
-pub struct OrderedRevisionMarks {
-    change_ids: Vec<String>,
-}
+    /// Cycle through the children of the change the cycle started from.
+    NextChild,
 
-impl OrderedRevisionMarks {
-    fn toggle(&mut self, change_id: &str) {
-        if let Some(index) = self.index_for(change_id) {
-            self.change_ids.remove(index);
-        } else {
-            self.change_ids.push(change_id.to_owned());
-        }
-    }
-
-    fn clear(&mut self) -> bool {
-        let had_marks = !self.change_ids.is_empty();
-        self.change_ids.clear();
-        had_marks
-    }
+    /// Move to the working-copy commit `@`.
+    WorkingCopy,
 
-    fn retain_visible(&mut self, entries: &[LogEntry]) {
-        self.change_ids
-            .retain(|change_id| entries.iter().any(|entry| entry.change_id() == change_id));
-    }
+    /// Move to the nearest ancestor that has a local bookmark.
+    NearestBookmark,
+}
 
-    fn index_for(&self, change_id: &str) -> Option<usize> {
-        self.change_ids
-            .iter()
-            .position(|marked| marked == change_id)
+impl GraphMove {
+    /// Returns the target name used in status messages and prompts.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::FirstParent => "first parent",
+            Self::NextChild => "child",
+            Self::WorkingCopy => "working copy",
+            Self::NearestBookmark => "nearest bookmark",
+        }
     }
+}
 
-    fn change_ids(&self) -> &[String] {
-        &self.change_ids
-    }
+/// Result of applying a [`GraphMove`] to the loaded log.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GraphMoveOutcome {
+    /// The target was loaded and is now selected.
+    Selected,
+
+    /// The target exists but is outside the loaded revset; `revset` would reveal it.
+    Hidden {
+        /// Revset to union with the current one so the move can succeed.
+        revset: String,
+    },
+
+    /// The selection has no such target.
+    NoTarget,
 }
 
 /// Semantic state behind the interactive log view.
@@ -60,7 +79,28 @@ pub struct LogState {
     scroll_offset: usize,
     viewport_height: usize,
     follow_selection: bool,
-    marks: OrderedRevisionMarks,
+    marks: OrderedMarks,
+    search: Option<LogSearch>,
+    child_cycle: Option<ChildCycle>,
+    more_available: bool,
+    /// Entry count when the next page was last requested, so movement inside the margin asks once
+    /// per page rather than on every step, and a failed load is not retried by scrolling.
+    more_requested_at: Option<usize>,
+}
+
+/// Children being cycled by [`GraphMove::NextChild`], remembered so repeated jumps visit siblings
+/// instead of descending into grandchildren.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct ChildCycle {
+    parent_commit_id: String,
+    index: usize,
+}
+
+/// Active log search and the entries it matched, in rendered order.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct LogSearch {
+    query: String,
+    matches: Vec<usize>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -74,13 +114,18 @@ struct LogElision {
     rendered_line: usize,
     before_entry: Option<usize>,
     after_entry: Option<usize>,
+    load_more: bool,
 }
 
 impl LogState {
     /// Creates state from a freshly loaded log snapshot.
     pub fn new(snapshot: LogSnapshot) -> Self {
-        let (title, rendered, entries) = snapshot.into_parts();
-        let elisions = log_elisions(&rendered, &entries);
+        let more_available = snapshot.more_available();
+        let (title, mut rendered, entries) = snapshot.into_parts();
+        let mut elisions = log_elisions(&rendered, &entries);
+        if more_available {
+            append_load_more(&mut rendered, &entries, &mut elisions);
+        }
         let selected = first_selection(&entries, &elisions);
         Self {
             title: title_or_default(title),
@@ -92,7 +137,11 @@ impl LogState {
             scroll_offset: 0,
             viewport_height: 10,
             follow_selection: true,
-            marks: OrderedRevisionMarks::default(),
+            marks: OrderedMarks::default(),
+            search: None,
+            child_cycle: None,
+            more_available,
+            more_requested_at: None,
         }
     }
 
@@ -101,20 +150,41 @@ impl LogState {
         let selected_change_id = self
             .selected_entry()
             .map(|entry| entry.change_id().to_owned());
+        // Leaving "load more" selected should land on the first newly loaded entry.
+        let last_change_id_before_load_more = self
+            .selected_is_load_more()
+            .then(|| {
+                self.entries
+                    .last()
+                    .map(|entry| entry.change_id().to_owned())
+            })
+            .flatten();
+        self.more_available = snapshot.more_available();
         let (title, rendered, entries) = snapshot.into_parts();
 
         self.title = title_or_default(title);
         self.rendered = rendered;
         self.entries = entries;
-        self.marks.retain_visible(&self.entries);
+        let entries = &self.entries;
+        self.marks
+            .retain(|change_id| entries.iter().any(|entry| entry.change_id() == change_id));
         self.elisions = log_elisions(&self.rendered, &self.entries);
+        if self.more_available {
+            append_load_more(&mut self.rendered, &self.entries, &mut self.elisions);
+        }
+        let position = |change_id: &str| {
+            self.entries
+                .iter()
+                .position(|entry| entry.change_id() == change_id)
+        };
         self.selected = selected_change_id
-            .and_then(|change_id| {
-                self.entries
-                    .iter()
-                    .position(|entry| entry.change_id() == change_id)
-                    .map(LogSelection::Entry)
+            .and_then(|change_id| position(&change_id))
+            .or_else(|| {
+                last_change_id_before_load_more
+                    .and_then(|change_id| position(&change_id))
+                    .map(|index| (index + 1).min(self.entries.len().saturating_sub(1)))
             })
+            .map(LogSelection::Entry)
             .or_else(|| first_selection(&self.entries, &self.elisions));
 
         if let Some(expanded_change_id) = &self.expanded_change_id {
@@ -127,6 +197,7 @@ impl LogState {
             }
         }
 
+        self.refresh_search_matches();
         self.clamp_scroll_offset();
     }
 
@@ -140,6 +211,11 @@ impl LogState {
         &self.rendered
     }
 
+    /// Returns the semantic entries in log order.
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
     /// Returns the currently selected semantic entry.
     pub fn selected_entry(&self) -> Option<&LogEntry> {
         let LogSelection::Entry(index) = self.selected? else {
@@ -182,20 +258,199 @@ impl LogState {
         if next_index >= self.entries.len() {
             return false;
         }
-        self.selected = Some(LogSelection::Entry(next_index));
+        self.jump_to_entry(next_index);
+        true
+    }
+
+    /// Applies a graph-aware jump from the selected change.
+    ///
+    /// Targets come from loaded entry parents, so only loaded changes can be selected. A target
+    /// outside the revset is reported with a revset that reveals it.
+    #[must_use]
+    pub fn select_graph_target(&mut self, graph_move: GraphMove) -> GraphMoveOutcome {
+        let target = match graph_move {
+            GraphMove::FirstParent => self.first_parent_target(),
+            GraphMove::NextChild => self.next_child_target(),
+            GraphMove::WorkingCopy => self
+                .entries
+                .iter()
+                .position(|entry| entry.has_flag(ChangeFlag::WorkingCopy))
+                .ok_or_else(|| Some("@".to_owned())),
+            GraphMove::NearestBookmark => self.nearest_bookmark_target(),
+        };
+        match target {
+            Ok(index) => {
+                self.jump_to_entry(index);
+                GraphMoveOutcome::Selected
+            }
+            Err(Some(revset)) => GraphMoveOutcome::Hidden { revset },
+            Err(None) => GraphMoveOutcome::NoTarget,
+        }
+    }
+
+    /// Resolves the first parent as an entry index, or the revset that reveals it.
+    fn first_parent_target(&self) -> Result<usize, Option<String>> {
+        let parent = self
+            .selected_entry()
+            .and_then(|entry| entry.parents().first())
+            .ok_or(None)?;
+        self.entry_index_for_commit_id(parent)
+            .ok_or_else(|| Some(revision_id_prefix(parent).to_owned()))
+    }
+
+    /// Advances the child cycle, starting a new one from the selection when it left the cycle.
+    fn next_child_target(&mut self) -> Result<usize, Option<String>> {
+        let selected_commit_id = self
+            .selected_entry()
+            .map(|entry| entry.commit_id().to_owned())
+            .ok_or(None)?;
+
+        if let Some(cycle) = &self.child_cycle {
+            let children = self.child_indexes(&cycle.parent_commit_id);
+            let in_cycle = children
+                .get(cycle.index)
+                .is_some_and(|&index| self.entries[index].commit_id() == selected_commit_id);
+            if in_cycle {
+                let index = (cycle.index + 1) % children.len();
+                let parent_commit_id = cycle.parent_commit_id.clone();
+                self.child_cycle = Some(ChildCycle {
+                    parent_commit_id,
+                    index,
+                });
+                return Ok(children[index]);
+            }
+        }
+
+        let children = self.child_indexes(&selected_commit_id);
+        let Some(&first_child) = children.first() else {
+            return Err(Some(format!(
+                "children({})",
+                revision_id_prefix(&selected_commit_id)
+            )));
+        };
+        self.child_cycle = Some(ChildCycle {
+            parent_commit_id: selected_commit_id,
+            index: 0,
+        });
+        Ok(first_child)
+    }
+
+    /// Walks loaded ancestors breadth-first for the closest change with a local bookmark.
+    fn nearest_bookmark_target(&self) -> Result<usize, Option<String>> {
+        let selected = self.selected_entry().ok_or(None)?;
+        let mut queue = selected
+            .parents()
+            .iter()
+            .map(String::as_str)
+            .collect::<VecDeque<_>>();
+        let mut seen = HashSet::new();
+        let mut left_loaded_graph = false;
+        while let Some(commit_id) = queue.pop_front() {
+            if !seen.insert(commit_id) {
+                continue;
+            }
+            let Some(index) = self.entry_index_for_commit_id(commit_id) else {
+                left_loaded_graph = true;
+                continue;
+            };
+            let entry = &self.entries[index];
+            if !entry.local_bookmarks().is_empty() {
+                return Ok(index);
+            }
+            queue.extend(entry.parents().iter().map(String::as_str));
+        }
+
+        // Reveal the whole path down from the bookmark so the next walk can reach it.
+        let commit_id = revision_id_prefix(selected.commit_id());
+        Err(left_loaded_graph.then(|| format!("heads(::{commit_id}- & bookmarks())::{commit_id}")))
+    }
+
+    fn entry_index_for_commit_id(&self, commit_id: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.commit_id() == commit_id)
+    }
+
+    /// Returns loaded children of `parent_commit_id` in log order.
+    fn child_indexes(&self, parent_commit_id: &str) -> Vec<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                entry
+                    .parents()
+                    .iter()
+                    .any(|parent| parent == parent_commit_id)
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Selects an entry after a jump and keeps it in view, collapsing inline details.
+    fn jump_to_entry(&mut self, index: usize) {
+        self.selected = Some(LogSelection::Entry(index));
         self.follow_selection = true;
         self.expanded_change_id = None;
         self.keep_selected_visible();
+    }
+
+    /// Selects the visible entry for `change_id`, returning whether it is in the log.
+    #[must_use]
+    pub fn select_change_id(&mut self, change_id: &str) -> bool {
+        let Some(index) = self
+            .entries
+            .iter()
+            .position(|entry| entry.change_id() == change_id)
+        else {
+            return false;
+        };
+        self.select_entry(index);
         true
     }
 
+    /// Returns whether the synthetic "load more" row is selected.
+    #[must_use]
+    pub fn selected_is_load_more(&self) -> bool {
+        let Some(LogSelection::Elision(index)) = self.selected else {
+            return false;
+        };
+        self.elisions
+            .get(index)
+            .is_some_and(|elision| elision.load_more)
+    }
+
+    /// Returns whether the selection is close enough to the end of a partial page to load more.
+    ///
+    /// Once the next page is requested this stays false until a longer page arrives.
+    #[must_use]
+    pub fn wants_more(&self) -> bool {
+        if !self.more_available || self.more_requested_at == Some(self.entries.len()) {
+            return false;
+        }
+        match self.selected {
+            Some(LogSelection::Entry(index)) => {
+                index.saturating_add(LOAD_MORE_MARGIN) >= self.entries.len()
+            }
+            Some(LogSelection::Elision(_)) => self.selected_is_load_more(),
+            None => false,
+        }
+    }
+
+    /// Records that the next page was requested for the entries loaded now.
+    pub const fn mark_more_requested(&mut self) {
+        self.more_requested_at = Some(self.entries.len());
+    }
+
     /// Returns the revset that should reveal the selected graph elision.
     #[must_use]
     pub fn selected_elision_revset(&self) -> Option<String> {
         let LogSelection::Elision(index) = self.selected? else {
             return None;
         };
-        let elision = self.elisions.get(index)?;
+        let elision = self
+            .elisions
+            .get(index)
+            .filter(|elision| !elision.load_more)?;
         let reveal_revset = match (elision.before_entry, elision.after_entry) {
             (Some(before), Some(after)) => {
                 let before = self.entries.get(before)?;
@@ -327,6 +582,110 @@ impl LogState {
         }
     }
 
+    /// Searches entries by id, description, details, and rendered text, then selects the first
+    /// match at or after the current selection.
+    pub fn search(&mut self, query: &str) {
+        let query = query.trim();
+        if query.is_empty() {
+            self.search = None;
+            return;
+        }
+
+        self.search = Some(LogSearch {
+            query: query.to_owned(),
+            matches: matching_entries(&self.rendered, &self.entries, query),
+        });
+        let current_line = self.selected_rendered_line().unwrap_or(0);
+        let target = self
+            .search_matches()
+            .iter()
+            .copied()
+            .find(|index| self.entries[*index].rendered_line() >= current_line)
+            .or_else(|| self.search_matches().first().copied());
+        if let Some(index) = target {
+            self.select_entry(index);
+        }
+    }
+
+    /// Selects the next matching entry below the current selection.
+    pub fn search_next(&mut self) {
+        let Some(current_line) = self.selected_rendered_line() else {
+            return;
+        };
+        let target = self
+            .search_matches()
+            .iter()
+            .copied()
+            .find(|index| self.entries[*index].rendered_line() > current_line);
+        if let Some(index) = target {
+            self.select_entry(index);
+        }
+    }
+
+    /// Selects the previous matching entry above the current selection.
+    pub fn search_previous(&mut self) {
+        let Some(current_line) = self.selected_rendered_line() else {
+            return;
+        };
+        let target = self
+            .search_matches()
+            .iter()
+            .copied()
+            .rfind(|index| self.entries[*index].rendered_line() < current_line);
+        if let Some(index) = target {
+            self.select_entry(index);
+        }
+    }
+
+    /// Clears the active search, returning whether one existed.
+    pub fn clear_search(&mut self) -> bool {
+        self.search.take().is_some()
+    }
+
+    /// Returns the active search query.
+    pub fn search_query(&self) -> Option<&str> {
+        self.search.as_ref().map(|search| search.query.as_str())
+    }
+
+    /// Returns status-line text for the active search, if any.
+    pub fn search_status(&self) -> Option<String> {
+        let search = self.search.as_ref()?;
+        if search.matches.is_empty() {
+            return Some(format!("/{}  no matches", search.query));
+        }
+        let position = match self.selected {
+            Some(LogSelection::Entry(selected)) => search
+                .matches
+                .iter()
+                .position(|index| *index == selected)
+                .map_or_else(|| "-".to_owned(), |position| (position + 1).to_string()),
+            _ => "-".to_owned(),
+        };
+        Some(format!(
+            "/{}  {position}/{}   n/N next/previous   esc clears",
+            search.query,
+            search.matches.len()
+        ))
+    }
+
+    fn search_matches(&self) -> &[usize] {
+        self.search
+            .as_ref()
+            .map_or(&[], |search| search.matches.as_slice())
+    }
+
+    fn refresh_search_matches(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.matches = matching_entries(&self.rendered, &self.entries, &search.query);
+        }
+    }
+
+    fn select_entry(&mut self, index: usize) {
+        if let Some(selectable) = self.selectable_index(LogSelection::Entry(index)) {
+            self.select_selectable_index(selectable);
+        }
+    }
+
     /// Toggles inline details for the selected entry.
     pub fn toggle_expanded(&mut self) {
         let Some(entry) = self.selected_entry() else {
@@ -366,18 +725,18 @@ impl LogState {
 
     /// Returns whether any revision marks are set.
     pub fn has_marks(&self) -> bool {
-        !self.marks.change_ids().is_empty()
+        !self.marks.ids().is_empty()
     }
 
     /// Returns marked change ids in insertion order.
     pub fn marked_change_ids(&self) -> &[String] {
-        self.marks.change_ids()
+        self.marks.ids()
     }
 
     /// Returns marked revision identifiers shortened for follow-up commands.
     pub fn marked_revision_ids(&self) -> Vec<String> {
         self.marks
-            .change_ids()
+            .ids()
             .iter()
             .map(|change_id| revision_id_prefix(change_id).to_owned())
             .collect()
@@ -549,6 +908,40 @@ impl LogState {
     }
 }
 
+/// Returns indices of entries whose ids, description, details, or rendered rows contain `query`.
+///
+/// Matching is case-insensitive. An entry's rendered rows run from its graph node to the next
+/// entry's node, so text `jj` prints under a node, such as bookmarks or the description line,
+/// counts toward that entry.
+fn matching_entries(rendered: &str, entries: &[LogEntry], query: &str) -> Vec<usize> {
+    let query = query.to_lowercase();
+    let lines = strip_ansi(rendered)
+        .lines()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    let contains = |text: &str| text.to_lowercase().contains(&query);
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(index, entry)| {
+            let start = entry.rendered_line();
+            let end = entries
+                .get(index + 1)
+                .map_or(lines.len(), LogEntry::rendered_line)
+                .max(start + 1)
+                .min(lines.len());
+            contains(entry.change_id())
+                || contains(entry.commit_id())
+                || contains(entry.description())
+                || contains(entry.details())
+                || lines
+                    .get(start..end)
+                    .is_some_and(|rows| rows.iter().any(|row| row.contains(&query)))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
 fn revision_id_prefix(id: &str) -> &str {
     id.char_indices()
         .nth(REVSET_ID_PREFIX_LEN)
@@ -635,11 +1028,28 @@ fn log_elisions(rendered: &str, entries: &[LogEntry]) -> Vec<LogElision> {
                 rendered_line,
                 before_entry,
                 after_entry,
+                load_more: false,
             }
         })
         .collect()
 }
 
+/// Appends the synthetic "load more" row below the last rendered line.
+fn append_load_more(rendered: &mut String, entries: &[LogEntry], elisions: &mut Vec<LogElision>) {
+    if !rendered.is_empty() && !rendered.ends_with('\n') {
+        rendered.push('\n');
+    }
+    let rendered_line = rendered.lines().count();
+    rendered.push_str(LOAD_MORE_LINE);
+    rendered.push('\n');
+    elisions.push(LogElision {
+        rendered_line,
+        before_entry: entries.len().checked_sub(1),
+        after_entry: None,
+        load_more: true,
+    });
+}
+
 /// Returns whether a rendered line is jj's hidden-revision graph elision.
 fn is_graph_elision_line(line: &str) -> bool {
     graph_elision_column(line).is_some()
@@ -691,7 +1101,7 @@ const fn is_graph_prefix(character: char) -> bool {
 
 /// Returns whether a graph character marks a rendered commit row.
 const fn is_graph_node(character: char) -> bool {
-    matches!(character, '@' | '○' | '◆' | '×' | '◇')
+    matches!(character, '@' | '○' | '◉' | '◆' | '×' | '◇')
 }
 
 /// Returns whether a graph-prefix character joins or closes lanes.
@@ -1433,6 +1843,254 @@ mod tests {
         assert_eq!(state.expanded_insertion_line(), Some(0));
     }
 
+    #[test]
+    fn search_matches_entry_fields_and_rendered_rows() {
+        let mut state = LogState::new(LogSnapshot::new(
+            "@  aaa first\n│  main\n○  bbb second\n○  ccc third\n",
+            vec![
+                LogEntry::new("aaa", "111", "first").with_rendered_line(0),
+                LogEntry::new("bbb", "222", "second")
+                    .with_details("Author: Ada")
+                    .with_rendered_line(2),
+                LogEntry::new("ccc", "333", "Third").with_rendered_line(3),
+            ],
+        ));
+
+        state.search("MAIN");
+        assert_eq!(state.search_matches(), [0]);
+
+        state.search("ada");
+        assert_eq!(state.search_matches(), [1]);
+        assert_eq!(state.selected_change_id_for_test(), Some("bbb"));
+
+        state.search("333");
+        assert_eq!(state.search_matches(), [2]);
+        assert_eq!(
+            state.search_status().as_deref(),
+            Some("/333  1/1   n/N next/previous   esc clears")
+        );
+
+        state.search("nothing");
+        assert_eq!(
+            state.search_status().as_deref(),
+            Some("/nothing  no matches")
+        );
+        assert!(state.clear_search());
+        assert_eq!(state.search_status(), None);
+    }
+
+    #[test]
+    fn search_next_and_previous_move_between_matching_entries() {
+        let mut state = LogState::new(snapshot(["aaa", "xbb", "ccc", "xdd"]));
+
+        state.search("x");
+        assert_eq!(state.selected_change_id_for_test(), Some("xbb"));
+
+        state.search_next();
+        assert_eq!(state.selected_change_id_for_test(), Some("xdd"));
+        state.search_next();
+        assert_eq!(state.selected_change_id_for_test(), Some("xdd"));
+
+        state.select_first();
+        state.search_next();
+        assert_eq!(state.selected_change_id_for_test(), Some("xbb"));
+        state.search_previous();
+        assert_eq!(state.selected_change_id_for_test(), Some("xbb"));
+    }
+
+    #[test]
+    fn refresh_keeps_search_and_recomputes_matches() {
+        let mut state = LogState::new(snapshot(["aaa", "xbb"]));
+        state.search("x");
+
+        state.refresh(snapshot(["xaa", "bbb", "xcc"]));
+
+        assert_eq!(state.search_query(), Some("x"));
+        assert_eq!(state.search_matches(), [0, 2]);
+    }
+
+    #[test]
+    fn select_change_id_selects_visible_entries_only() {
+        let mut state = LogState::new(snapshot(["aaa", "bbb", "ccc"]));
+
+        assert!(state.select_change_id("ccc"));
+        assert_eq!(state.selected_change_id_for_test(), Some("ccc"));
+        assert!(!state.select_change_id("zzz"));
+        assert_eq!(state.selected_change_id_for_test(), Some("ccc"));
+    }
+
+    fn graph_snapshot(entries: Vec<LogEntry>) -> LogSnapshot {
+        let mut rendered = String::new();
+        let entries = entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                rendered.push_str("○  ");
+                rendered.push_str(entry.summary());
+                rendered.push('\n');
+                entry.with_rendered_line(index)
+            })
+            .collect();
+        LogSnapshot::new(rendered, entries)
+    }
+
+    fn graph_entry(change_id: &str, parents: &[&str]) -> LogEntry {
+        LogEntry::new(change_id, change_id, change_id)
+            .with_parents(parents.iter().map(|&parent| parent.to_owned()).collect())
+    }
+
+    #[test]
+    fn first_parent_and_working_copy_jumps_select_loaded_targets_or_report_revsets() {
+        let mut state = LogState::new(graph_snapshot(vec![
+            graph_entry("merge", &["left", "right"]),
+            graph_entry("right", &["base"]),
+            graph_entry("left", &["gone"]),
+        ]));
+
+        assert_eq!(
+            state.select_graph_target(GraphMove::FirstParent),
+            GraphMoveOutcome::Selected
+        );
+        assert_eq!(state.selected_change_id_for_test(), Some("left"));
+        assert_eq!(
+            state.select_graph_target(GraphMove::FirstParent),
+            GraphMoveOutcome::Hidden {
+                revset: "gone".to_owned()
+            }
+        );
+        assert_eq!(
+            state.select_graph_target(GraphMove::WorkingCopy),
+            GraphMoveOutcome::Hidden {
+                revset: "@".to_owned()
+            }
+        );
+
+        state.refresh(graph_snapshot(vec![
+            graph_entry("wc", &["left"]).with_flags(vec![ChangeFlag::WorkingCopy]),
+            graph_entry("left", &[]),
+        ]));
+        assert_eq!(
+            state.select_graph_target(GraphMove::WorkingCopy),
+            GraphMoveOutcome::Selected
+        );
+        assert_eq!(state.selected_change_id_for_test(), Some("wc"));
+        state.select_last();
+        assert_eq!(
+            state.select_graph_target(GraphMove::FirstParent),
+            GraphMoveOutcome::NoTarget
+        );
+    }
+
+    #[test]
+    fn next_child_cycles_siblings_of_the_starting_change() {
+        let mut state = LogState::new(graph_snapshot(vec![
+            graph_entry("second", &["base"]),
+            graph_entry("first", &["base"]),
+            graph_entry("base", &[]),
+        ]));
+        state.select_last();
+
+        let mut visited = Vec::new();
+        for _ in 0..3 {
+            assert_eq!(
+                state.select_graph_target(GraphMove::NextChild),
+                GraphMoveOutcome::Selected
+            );
+            visited.extend(state.selected_change_id_for_test().map(ToOwned::to_owned));
+        }
+
+        assert_eq!(visited, ["second", "first", "second"]);
+        state.select_first();
+        state.select_next();
+        assert_eq!(
+            state.select_graph_target(GraphMove::NextChild),
+            GraphMoveOutcome::Hidden {
+                revset: "children(first)".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn nearest_bookmark_walks_loaded_ancestors_before_widening() {
+        let mut state = LogState::new(graph_snapshot(vec![
+            graph_entry("top", &["middle"]),
+            graph_entry("middle", &["main"]),
+            graph_entry("main", &["hidden"]).with_bookmarks(vec!["main".to_owned()], Vec::new()),
+        ]));
+
+        assert_eq!(
+            state.select_graph_target(GraphMove::NearestBookmark),
+            GraphMoveOutcome::Selected
+        );
+        assert_eq!(state.selected_change_id_for_test(), Some("main"));
+        assert_eq!(
+            state.select_graph_target(GraphMove::NearestBookmark),
+            GraphMoveOutcome::Hidden {
+                revset: "heads(::main- & bookmarks())::main".to_owned()
+            }
+        );
+    }
+
+    fn paged_snapshot(count: usize) -> LogSnapshot {
+        let change_ids = (0..count)
+            .map(|index| format!("c{index:02}"))
+            .collect::<Vec<_>>();
+        let entries = change_ids
+            .iter()
+            .enumerate()
+            .map(|(index, change_id)| {
+                LogEntry::new(change_id, "commit", change_id.as_str()).with_rendered_line(index)
+            })
+            .collect::<Vec<_>>();
+        let rendered = change_ids
+            .iter()
+            .map(|change_id| format!("○  {change_id}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        LogSnapshot::new(rendered + "\n", entries).with_more_available(true)
+    }
+
+    #[test]
+    fn partial_page_ends_in_load_more_row_and_keeps_marks_and_selection_across_pages() {
+        let mut state = LogState::new(paged_snapshot(10));
+        assert!(state.rendered().ends_with(&format!("{LOAD_MORE_LINE}\n")));
+        state.toggle_selected_mark();
+        assert!(!state.wants_more());
+
+        for _ in 0..5 {
+            state.select_next();
+        }
+        assert_eq!(state.selected_change_id_for_test(), Some("c05"));
+        assert!(state.wants_more());
+        state.mark_more_requested();
+        state.select_next();
+        assert!(
+            !state.wants_more(),
+            "one crossing requests the next page once"
+        );
+
+        state.refresh(paged_snapshot(20));
+        assert_eq!(state.selected_change_id_for_test(), Some("c06"));
+        assert_eq!(state.marked_change_ids(), ["c00"]);
+        assert!(!state.wants_more());
+
+        state.select_last();
+        assert!(state.selected_is_load_more());
+        assert!(state.wants_more(), "a longer page re-arms loading");
+        assert_eq!(state.selected_elision_revset(), None);
+        state.refresh(paged_snapshot(30).with_more_available(false));
+        assert_eq!(state.selected_change_id_for_test(), Some("c20"));
+        assert!(!state.rendered().contains(LOAD_MORE_LINE));
+        state.select_last();
+        assert!(!state.wants_more());
+    }
+
+    impl LogState {
+        fn selected_change_id_for_test(&self) -> Option<&str> {
+            self.selected_entry().map(LogEntry::change_id)
+        }
+    }
+
     fn snapshot<const N: usize>(change_ids: [&str; N]) -> LogSnapshot {
         let entries = change_ids
             .into_iter()
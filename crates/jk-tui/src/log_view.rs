@@ -14,7 +14,10 @@ use ratatui::widgets::Paragraph;
 use crate::chrome::{ViewChrome, render_help_overlay};
 use crate::keymap::{BindingContext, adaptive_hotbar, help_lines, help_title};
 use crate::log_state::LogState;
+pub use crate::log_state::{GraphMove, GraphMoveOutcome};
+pub use crate::ordered_marks::marked_pair;
 use crate::rendered_log::{ExpandedDetails, RenderedLog, rendered_text};
+use crate::search_highlight::paint_search_matches;
 use crate::selected_row::paint_selected_row;
 
 /// The effect requested after applying an input action to the log view.
@@ -39,6 +42,9 @@ pub enum ActionResult {
     /// Drill into the selected graph elision.
     DrillElision,
 
+    /// Load the next page of log entries.
+    LoadMore,
+
     /// Exit the application.
     Quit,
 }
@@ -184,6 +190,14 @@ impl LogView {
             .map(jk_core::LogEntry::change_id)
     }
 
+    /// Returns the selected commit identifier, which pins content-addressed caches.
+    #[must_use]
+    pub fn selected_commit_id(&self) -> Option<&str> {
+        self.state
+            .selected_entry()
+            .map(jk_core::LogEntry::commit_id)
+    }
+
     /// Returns the selected revision identifier for follow-up commands.
     #[must_use]
     pub fn selected_revision_id(&self) -> Option<&str> {
@@ -202,6 +216,30 @@ impl LogView {
         self.state.select_first_entry_after_change_id(change_id)
     }
 
+    /// Selects the visible entry for `change_id`, returning whether it is in the log.
+    #[must_use]
+    pub fn select_change_id(&mut self, change_id: &str) -> bool {
+        self.state.select_change_id(change_id)
+    }
+
+    /// Applies a graph-aware jump from the selected change.
+    #[must_use]
+    pub fn select_graph_target(&mut self, graph_move: GraphMove) -> GraphMoveOutcome {
+        self.state.select_graph_target(graph_move)
+    }
+
+    /// Returns the title shown above the log.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        self.state.title()
+    }
+
+    /// Returns the visible semantic entries in log order.
+    #[must_use]
+    pub fn entries(&self) -> &[jk_core::LogEntry] {
+        self.state.entries()
+    }
+
     /// Returns the selected change's full description for editing commands.
     pub fn selected_description(&self) -> Option<&str> {
         self.state
@@ -233,12 +271,47 @@ impl LogView {
         self.state.selected_mark_index()
     }
 
+    /// Searches the log and selects the first matching change at or after the selection.
+    ///
+    /// Entries match on change id, commit id, description, details, or their rendered rows. An
+    /// empty query clears the search.
+    pub fn search(&mut self, query: &str) {
+        self.state.search(query);
+    }
+
+    /// Selects the next matching change.
+    pub fn search_next(&mut self) {
+        self.state.search_next();
+    }
+
+    /// Selects the previous matching change.
+    pub fn search_previous(&mut self) {
+        self.state.search_previous();
+    }
+
+    /// Clears the active search, returning whether one existed.
+    pub fn clear_search(&mut self) -> bool {
+        self.state.clear_search()
+    }
+
+    /// Returns whether a search is active.
+    #[must_use]
+    pub fn has_search(&self) -> bool {
+        self.state.search_query().is_some()
+    }
+
     /// Returns the revset that reveals the selected graph elision.
     #[must_use]
     pub fn selected_elision_revset(&self) -> Option<String> {
         self.state.selected_elision_revset()
     }
 
+    /// Returns whether the "load more" row below a partial page is selected.
+    #[must_use]
+    pub fn selected_is_load_more(&self) -> bool {
+        self.state.selected_is_load_more()
+    }
+
     /// Applies a single input action.
     ///
     /// [`ActionResult::Refresh`] asks the caller to load a new [`LogSnapshot`]. The view does not
@@ -252,7 +325,7 @@ impl LogView {
             }
             LogAction::Next => {
                 self.state.select_next();
-                ActionResult::Continue
+                self.continue_or_load_more()
             }
             LogAction::ScrollPreviousLine => {
                 self.state.scroll_previous_line();
@@ -268,7 +341,7 @@ impl LogView {
             }
             LogAction::PageNext => {
                 self.state.select_page_next();
-                ActionResult::Continue
+                self.continue_or_load_more()
             }
             LogAction::First => {
                 self.state.select_first();
@@ -276,7 +349,7 @@ impl LogView {
             }
             LogAction::Last => {
                 self.state.select_last();
-                ActionResult::Continue
+                self.continue_or_load_more()
             }
             LogAction::PreviousFile
             | LogAction::NextFile
@@ -289,6 +362,10 @@ impl LogView {
             | LogAction::FoldAll
             | LogAction::UnfoldAll => ActionResult::Continue,
             LogAction::ToggleExpanded => {
+                if self.state.selected_is_load_more() {
+                    self.state.mark_more_requested();
+                    return ActionResult::LoadMore;
+                }
                 if self.selected_elision_revset().is_some() {
                     return ActionResult::DrillElision;
                 }
@@ -329,6 +406,15 @@ impl LogView {
         }
     }
 
+    fn continue_or_load_more(&mut self) -> ActionResult {
+        if self.state.wants_more() {
+            self.state.mark_more_requested();
+            ActionResult::LoadMore
+        } else {
+            ActionResult::Continue
+        }
+    }
+
     /// Renders the log view.
     pub fn render(&mut self, frame: &mut Frame<'_>) {
         let area = frame.area();
@@ -344,11 +430,37 @@ impl LogView {
     /// Renders the log view with a centered selector overlay.
     pub fn render_with_selector(&mut self, frame: &mut Frame<'_>, title: &str, lines: &[String]) {
         let area = frame.area();
+        self.render_with_selector_in(frame, area, title, lines);
+    }
+
+    /// Renders the log view into `area`, such as one pane of a split layout.
+    pub fn render_in(&mut self, frame: &mut Frame<'_>, area: Rect, status: Option<&str>) {
+        self.render_area(frame, area, status);
+    }
+
+    /// Renders the log view into `area` with a selector overlay centered in that area.
+    pub fn render_with_selector_in(
+        &mut self,
+        frame: &mut Frame<'_>,
+        area: Rect,
+        title: &str,
+        lines: &[String],
+    ) {
         self.render_area(frame, area, None);
         let areas = ViewChrome::layout(area);
         render_help_overlay(frame, areas.content, title, lines);
     }
 
+    /// Returns the status message and search state, keeping an active query visible so `n` reads
+    /// as "next match" rather than "new change".
+    fn status_line(&self) -> Option<String> {
+        match (&self.status_message, self.state.search_query()) {
+            (Some(message), Some(query)) => Some(format!("/{query}   {message}")),
+            (Some(message), None) => Some(message.clone()),
+            (None, _) => self.state.search_status(),
+        }
+    }
+
     fn render_area(&mut self, frame: &mut Frame<'_>, area: Rect, status: Option<&str>) {
         let areas = ViewChrome::layout(area);
         let height = usize::from(areas.content.height);
@@ -356,7 +468,7 @@ impl LogView {
 
         let status = status
             .map(ToOwned::to_owned)
-            .or_else(|| self.status_message.clone())
+            .or_else(|| self.status_line())
             .unwrap_or_else(|| adaptive_hotbar(BindingContext::Log, areas.status_width()));
         let chrome = ViewChrome::new(self.state.title(), &status);
         chrome.render(frame, areas);
@@ -379,6 +491,9 @@ impl LogView {
         if let Some(line) = self.state.selected_rendered_line() {
             paint_selected_row(frame, areas.content, line, self.state.scroll_offset());
         }
+        if let Some(query) = self.state.search_query() {
+            paint_search_matches(frame, areas.content, query);
+        }
 
         if self.help_visible {
             render_help_overlay(
@@ -581,6 +696,50 @@ mod tests {
         assert!(rendered.contains("close help"));
     }
 
+    #[test]
+    fn search_highlights_matches_and_reports_status() {
+        let mut view = LogView::new(snapshot(["aaa", "bbb"]));
+        view.search("BBB");
+        let backend = TestBackend::new(48, 5);
+        let mut terminal = match Terminal::new(backend) {
+            Ok(terminal) => terminal,
+            Err(error) => match error {},
+        };
+
+        let draw_result = terminal.draw(|frame| view.render(frame));
+        assert!(draw_result.is_ok());
+
+        let buffer = terminal.backend().buffer();
+        assert_eq!(view.selected_change_id(), Some("bbb"));
+        assert!(buffer_line(buffer, 4).contains("/BBB  1/1"));
+        assert_eq!(buffer[(3, 2)].symbol(), "b");
+        assert_eq!(buffer[(3, 2)].bg, Color::Rgb(229, 192, 123));
+        assert_ne!(buffer[(7, 2)].bg, Color::Rgb(229, 192, 123));
+        assert_ne!(buffer[(3, 1)].bg, Color::Rgb(229, 192, 123));
+
+        assert!(view.clear_search());
+        assert!(!view.has_search());
+    }
+
+    #[test]
+    fn status_messages_keep_the_active_search_query_visible() {
+        let mut view = LogView::new(snapshot(["aaa", "bbb"]));
+        view.search("bbb");
+        view.show_status("copied change id");
+        let backend = TestBackend::new(48, 5);
+        let mut terminal = match Terminal::new(backend) {
+            Ok(terminal) => terminal,
+            Err(error) => match error {},
+        };
+
+        assert!(terminal.draw(|frame| view.render(frame)).is_ok());
+        assert!(buffer_line(terminal.backend().buffer(), 4).contains("/bbb   copied change id"));
+
+        assert!(view.clear_search());
+        assert!(terminal.draw(|frame| view.render(frame)).is_ok());
+        assert!(!buffer_line(terminal.backend().buffer(), 4).contains("/bbb"));
+    }
+
     #[test]
     fn quit_closes_log_help_before_quitting() {
         let mut view = LogView::new(snapshot(["aaa"]));
@@ -3,6 +3,16 @@
 //! Callers map operation history into [`OperationLogSnapshot`] rows, translate terminal input into
 //! [`OperationLogAction`], and handle returned [`OperationLogActionResult`] values for effects such
 //! as refresh, operation show, operation diff, back navigation, and quit.
+//!
+//! Operations can be marked in order, mirroring revision marks in the log view. Two marks turn the
+//! diff action into an operation range diff from the first mark to the second.
+//!
+//! [`OperationLogFilter`] narrows the visible rows by user, host, time window, or operations that
+//! `jk` itself produced. Filtering keeps each matching row's rendered `jj` lines intact and drops the
+//! rest, so the view still shows `jj`'s own formatting.
+//!
+//! Callers can annotate rows with session checkpoint names and the `jk` action that produced them;
+//! both are drawn at the right edge of the operation row next to any mark.
 
 use ratatui::Frame;
 use ratatui::layout::Rect;
@@ -11,10 +21,124 @@ use ratatui::widgets::Paragraph;
 
 use crate::chrome::{ViewChrome, render_help_overlay};
 use crate::keymap::{BindingContext, adaptive_hotbar, help_lines, help_title};
+use crate::ordered_marks::{OrderedMarks, marked_pair};
 use crate::rendered_log::rendered_text;
 use crate::selected_row::paint_subtle_selected_row;
 
 const DEFAULT_TITLE: &str = "jj op log";
+const MARK_OVERLAY_STYLE: Style = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+const JK_ACTION_OVERLAY_STYLE: Style = Style::new().fg(Color::Cyan);
+const CHECKPOINT_OVERLAY_STYLE: Style =
+    Style::new().fg(Color::Magenta).add_modifier(Modifier::BOLD);
+
+/// Recent time windows offered by the operation log filter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OperationTimeWindow {
+    /// Operations from the last hour.
+    LastHour,
+    /// Operations from the last 24 hours.
+    LastDay,
+    /// Operations from the last 7 days.
+    LastWeek,
+}
+
+impl OperationTimeWindow {
+    /// Returns the window length in seconds.
+    #[must_use]
+    pub const fn seconds(self) -> i64 {
+        match self {
+            Self::LastHour => 60 * 60,
+            Self::LastDay => 24 * 60 * 60,
+            Self::LastWeek => 7 * 24 * 60 * 60,
+        }
+    }
+
+    /// Returns the short label shown in filter summaries.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::LastHour => "last hour",
+            Self::LastDay => "last 24h",
+            Self::LastWeek => "last 7d",
+        }
+    }
+
+    const fn next(window: Option<Self>) -> Option<Self> {
+        match window {
+            None => Some(Self::LastHour),
+            Some(Self::LastHour) => Some(Self::LastDay),
+            Some(Self::LastDay) => Some(Self::LastWeek),
+            Some(Self::LastWeek) => None,
+        }
+    }
+}
+
+/// Row filters for the operation log.
+///
+/// Every set field must match for a row to stay visible. An inactive filter shows every row.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OperationLogFilter {
+    /// Only show operations recorded by this user.
+    pub user: Option<String>,
+    /// Only show operations recorded on this host.
+    pub host: Option<String>,
+    /// Only show operations that ended inside this window before [`Self::reference_time`].
+    pub time_window: Option<OperationTimeWindow>,
+    /// Unix timestamp, in seconds, that the time window counts back from.
+    pub reference_time: i64,
+    /// Only show operations produced by commands `jk` ran.
+    pub jk_only: bool,
+}
+
+impl OperationLogFilter {
+    /// Returns whether any filter is set.
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.user.is_some() || self.host.is_some() || self.time_window.is_some() || self.jk_only
+    }
+
+    /// Returns whether `row` passes every set filter.
+    ///
+    /// Rows whose timestamp cannot be parsed stay visible under a time window rather than being
+    /// hidden on a guess.
+    #[must_use]
+    pub fn matches(&self, row: &OperationLogRow) -> bool {
+        if self.user.as_deref().is_some_and(|user| row.user != user) {
+            return false;
+        }
+        if self.host.as_deref().is_some_and(|host| row.host != host) {
+            return false;
+        }
+        if self.jk_only && row.jk_action.is_none() {
+            return false;
+        }
+        if let Some(window) = self.time_window
+            && let Some(ended_at) = parse_timestamp_seconds(&row.time_end)
+        {
+            return ended_at >= self.reference_time - window.seconds();
+        }
+        true
+    }
+
+    /// Returns a compact summary of set filters for the view title.
+    #[must_use]
+    pub fn summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(user) = &self.user {
+            parts.push(format!("user {user}"));
+        }
+        if let Some(host) = &self.host {
+            parts.push(format!("host {host}"));
+        }
+        if let Some(window) = self.time_window {
+            parts.push(window.label().to_owned());
+        }
+        if self.jk_only {
+            parts.push("jk only".to_owned());
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
 
 /// A provider-neutral snapshot of operation log rows.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -90,6 +214,40 @@ impl OperationLogSnapshot {
             .iter()
             .position(|row| row.operation_id == operation_id)
     }
+
+    /// Returns the rows matching `filter`, each carrying its rendered lines through the line before
+    /// the next operation row.
+    fn filtered(&self, filter: &OperationLogFilter) -> Self {
+        if !filter.is_active() {
+            return self.clone();
+        }
+
+        let mut rendered_lines = Vec::new();
+        let mut rows = Vec::new();
+        for (index, row) in self.rows.iter().enumerate() {
+            if !filter.matches(row) {
+                continue;
+            }
+            let end = self
+                .rows
+                .get(index + 1)
+                .map_or(self.rendered_lines.len(), |next| next.rendered_line)
+                .min(self.rendered_lines.len());
+            let start = row.rendered_line.min(end);
+            rows.push(row.clone().with_rendered_line(rendered_lines.len()));
+            rendered_lines.extend_from_slice(&self.rendered_lines[start..end]);
+        }
+
+        let title = filter.summary().map_or_else(
+            || self.title.clone(),
+            |summary| format!("{} ({summary})", self.title),
+        );
+        Self {
+            title,
+            rendered_lines,
+            rows,
+        }
+    }
 }
 
 /// One display row in the operation log.
@@ -105,6 +263,20 @@ pub struct OperationLogRow {
     pub current: bool,
     /// Rendered line containing the operation node in the snapshot output.
     pub rendered_line: usize,
+    /// User name that recorded the operation.
+    pub user: String,
+    /// Host name that recorded the operation.
+    pub host: String,
+    /// Operation start timestamp as reported by `jj`.
+    pub time_start: String,
+    /// Operation end timestamp as reported by `jj`.
+    pub time_end: String,
+    /// Operation tags as ordered key/value pairs.
+    pub tags: Vec<(String, String)>,
+    /// Label of the `jk` action that produced this operation, when command history knows it.
+    pub jk_action: Option<String>,
+    /// Name of the session checkpoint recorded at this operation.
+    pub checkpoint: Option<String>,
 }
 
 impl OperationLogRow {
@@ -122,6 +294,13 @@ impl OperationLogRow {
             title: title.into(),
             current,
             rendered_line: 0,
+            user: String::new(),
+            host: String::new(),
+            time_start: String::new(),
+            time_end: String::new(),
+            tags: Vec::new(),
+            jk_action: None,
+            checkpoint: None,
         }
     }
 
@@ -131,6 +310,43 @@ impl OperationLogRow {
         self.rendered_line = rendered_line;
         self
     }
+
+    /// Sets the user and host that recorded this operation.
+    #[must_use]
+    pub fn with_origin(mut self, user: impl Into<String>, host: impl Into<String>) -> Self {
+        self.user = user.into();
+        self.host = host.into();
+        self
+    }
+
+    /// Sets the operation start and end timestamps.
+    #[must_use]
+    pub fn with_time_range(mut self, start: impl Into<String>, end: impl Into<String>) -> Self {
+        self.time_start = start.into();
+        self.time_end = end.into();
+        self
+    }
+
+    /// Sets operation tags as ordered key/value pairs.
+    #[must_use]
+    pub fn with_tags(mut self, tags: Vec<(String, String)>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Marks this operation as the named session checkpoint.
+    #[must_use]
+    pub fn with_checkpoint(mut self, checkpoint: impl Into<String>) -> Self {
+        self.checkpoint = Some(checkpoint.into());
+        self
+    }
+
+    /// Sets the `jk` action that produced this operation.
+    #[must_use]
+    pub fn with_jk_action(mut self, jk_action: impl Into<String>) -> Self {
+        self.jk_action = Some(jk_action.into());
+        self
+    }
 }
 
 /// The effect requested after applying an input action to the operation log view.
@@ -151,6 +367,13 @@ pub enum OperationLogActionResult {
         /// Stable operation id selected for `jj op diff`.
         operation_id: String,
     },
+    /// Open `jj op diff --from FROM --to TO` for two ordered operation marks.
+    OperationDiffFromTo {
+        /// First marked operation id.
+        from: String,
+        /// Second marked operation id.
+        to: String,
+    },
     /// Return to the previous view.
     ReturnBack,
     /// Exit the application.
@@ -177,8 +400,12 @@ pub enum OperationLogAction {
     Refresh,
     /// Open operation details for the selected operation.
     OpenShow,
-    /// Open operation diff for the selected operation.
+    /// Open operation diff for the selected operation, or between two marked operations.
     OpenDiff,
+    /// Toggle the selected operation in ordered operation marks.
+    ToggleMark,
+    /// Clear ordered operation marks.
+    ClearMarks,
     /// Toggle mode-specific help.
     ToggleHelp,
     /// Return to the previous view.
@@ -190,10 +417,14 @@ pub enum OperationLogAction {
 /// Interactive operation log list view.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct OperationLogView {
+    all: OperationLogSnapshot,
     snapshot: OperationLogSnapshot,
+    filter: OperationLogFilter,
     selected: Option<usize>,
     scroll_offset: usize,
     help_visible: bool,
+    marks: OrderedMarks,
+    status_message: Option<String>,
 }
 
 impl OperationLogView {
@@ -202,21 +433,125 @@ impl OperationLogView {
     pub fn new(snapshot: OperationLogSnapshot) -> Self {
         let selected = initial_selection(&snapshot);
         Self {
+            all: snapshot.clone(),
             snapshot,
+            filter: OperationLogFilter::default(),
             selected,
             scroll_offset: 0,
             help_visible: false,
+            marks: OrderedMarks::default(),
+            status_message: None,
         }
     }
 
     /// Replaces rows after a successful refresh.
     ///
     /// Selection is preserved by stable operation id when possible, then falls back to the current
-    /// operation row, then clamps to the nearest available row.
+    /// operation row, then clamps to the nearest available row. Marks survive when their operation
+    /// ids are still present. The active filter is reapplied to the new rows.
     pub fn refresh(&mut self, snapshot: OperationLogSnapshot) {
+        self.all = snapshot;
+        let rows = &self.all.rows;
+        self.marks
+            .retain(|operation_id| rows.iter().any(|row| row.operation_id == operation_id));
+        self.status_message = None;
+        self.reapply_filter();
+    }
+
+    /// Returns the active row filter.
+    #[must_use]
+    pub const fn filter(&self) -> &OperationLogFilter {
+        &self.filter
+    }
+
+    /// Replaces the active row filter.
+    pub fn set_filter(&mut self, filter: OperationLogFilter) {
+        self.filter = filter;
+        self.reapply_filter();
+    }
+
+    /// Toggles showing only operations recorded by `user`.
+    pub fn toggle_user_filter(&mut self, user: &str) {
+        let mut filter = self.filter.clone();
+        filter.user = if filter.user.as_deref() == Some(user) {
+            None
+        } else {
+            Some(user.to_owned())
+        };
+        self.set_filter(filter);
+    }
+
+    /// Cycles the host filter through hosts present in the unfiltered log, then back to all hosts.
+    pub fn cycle_host_filter(&mut self) {
+        let hosts = self.hosts();
+        let next = self.filter.host.as_ref().map_or_else(
+            || hosts.first().cloned(),
+            |host| {
+                hosts
+                    .iter()
+                    .position(|candidate| candidate == host)
+                    .and_then(|index| hosts.get(index + 1))
+                    .cloned()
+            },
+        );
+        let mut filter = self.filter.clone();
+        filter.host = next;
+        self.set_filter(filter);
+    }
+
+    /// Cycles the time window filter, counting back from `now` in Unix seconds.
+    pub fn cycle_time_window(&mut self, now: i64) {
+        let mut filter = self.filter.clone();
+        filter.time_window = OperationTimeWindow::next(filter.time_window);
+        filter.reference_time = now;
+        self.set_filter(filter);
+    }
+
+    /// Toggles showing only operations produced by `jk`.
+    pub fn toggle_jk_only(&mut self) {
+        let mut filter = self.filter.clone();
+        filter.jk_only = !filter.jk_only;
+        self.set_filter(filter);
+    }
+
+    /// Shows an integration error without replacing the current rows.
+    pub fn show_error(&mut self, error: impl Into<String>) {
+        self.status_message = Some(error.into());
+    }
+
+    /// Labels `operation_id` as a session checkpoint without reloading the log.
+    ///
+    /// Returns whether a loaded row matched the id.
+    pub fn set_checkpoint(&mut self, operation_id: &str, name: impl Into<String>) -> bool {
+        let Some(row) = self
+            .all
+            .rows
+            .iter_mut()
+            .find(|row| row.operation_id == operation_id)
+        else {
+            return false;
+        };
+        row.checkpoint = Some(name.into());
+        self.reapply_filter();
+        true
+    }
+
+    /// Returns distinct hosts in the unfiltered log, newest first.
+    #[must_use]
+    pub fn hosts(&self) -> Vec<String> {
+        let mut hosts: Vec<String> = Vec::new();
+        for row in &self.all.rows {
+            if !row.host.is_empty() && !hosts.contains(&row.host) {
+                hosts.push(row.host.clone());
+            }
+        }
+        hosts
+    }
+
+    fn reapply_filter(&mut self) {
         let previous_operation_id = self.selected_row().map(|row| row.operation_id.clone());
         let previous_selected = self.selected;
-        self.snapshot = snapshot;
+        self.snapshot = self.all.filtered(&self.filter);
         self.selected = previous_operation_id
             .as_deref()
             .and_then(|operation_id| self.snapshot.operation_index(operation_id))
@@ -238,9 +573,28 @@ impl OperationLogView {
         self.selected_row().map(|row| row.operation_id.as_str())
     }
 
+    /// Returns whether any operation marks are set.
+    #[must_use]
+    pub fn has_marks(&self) -> bool {
+        !self.marks.ids().is_empty()
+    }
+
+    /// Returns marked operation ids in insertion order.
+    #[must_use]
+    pub fn marked_operation_ids(&self) -> &[String] {
+        self.marks.ids()
+    }
+
+    /// Returns the zero-based mark index for an operation id.
+    #[must_use]
+    pub fn mark_index_for_operation_id(&self, operation_id: &str) -> Option<usize> {
+        self.marks.index_for(operation_id)
+    }
+
     /// Applies a single input action.
     #[must_use]
     pub fn apply(&mut self, action: OperationLogAction) -> OperationLogActionResult {
+        self.status_message = None;
         match action {
             OperationLogAction::Previous => {
                 self.select_previous();
@@ -277,12 +631,17 @@ impl OperationLogView {
                     operation_id: operation_id.to_owned(),
                 },
             ),
-            OperationLogAction::OpenDiff => self.selected_operation_id().map_or(
-                OperationLogActionResult::Continue,
-                |operation_id| OperationLogActionResult::OperationDiff {
-                    operation_id: operation_id.to_owned(),
-                },
-            ),
+            OperationLogAction::OpenDiff => self.diff_result(),
+            OperationLogAction::ToggleMark => {
+                if let Some(operation_id) = self.selected_operation_id().map(ToOwned::to_owned) {
+                    self.marks.toggle(&operation_id);
+                }
+                OperationLogActionResult::Continue
+            }
+            OperationLogAction::ClearMarks => {
+                self.marks.clear();
+                OperationLogActionResult::Continue
+            }
             OperationLogAction::ToggleHelp => {
                 self.help_visible = !self.help_visible;
                 OperationLogActionResult::Continue
@@ -308,6 +667,29 @@ impl OperationLogView {
         self.render_area(frame, area, Some(status));
     }
 
+    fn diff_result(&mut self) -> OperationLogActionResult {
+        match marked_pair(self.marks.ids(), "operation") {
+            Ok(Some((from, to))) => {
+                return OperationLogActionResult::OperationDiffFromTo {
+                    from: from.clone(),
+                    to: to.clone(),
+                };
+            }
+            Ok(None) => {}
+            Err(status) => {
+                self.status_message = Some(status);
+                return OperationLogActionResult::Continue;
+            }
+        }
+
+        self.selected_operation_id()
+            .map_or(OperationLogActionResult::Continue, |operation_id| {
+                OperationLogActionResult::OperationDiff {
+                    operation_id: operation_id.to_owned(),
+                }
+            })
+    }
+
     const fn select_previous(&mut self) {
         let Some(selected) = self.selected else {
             return;
@@ -358,7 +740,9 @@ impl OperationLogView {
         self.keep_selected_in_view(usize::from(areas.content.height));
 
         let fallback_status = adaptive_hotbar(BindingContext::OperationLog, areas.status_width());
-        let status = status_override.unwrap_or(&fallback_status);
+        let status = status_override
+            .or(self.status_message.as_deref())
+            .unwrap_or(&fallback_status);
         let chrome = ViewChrome::new(self.snapshot.title(), status);
         chrome.render(frame, areas);
 
@@ -368,6 +752,7 @@ impl OperationLogView {
         if let Some(selected_line) = self.selected_rendered_line() {
             paint_subtle_selected_row(frame, areas.content, selected_line, self.scroll_offset);
         }
+        self.paint_row_overlays(frame, areas.content);
 
         if self.help_visible {
             render_help_overlay(
@@ -380,6 +765,16 @@ impl OperationLogView {
     }
 
     fn visible_text(&self) -> Text<'_> {
+        if self.snapshot.rendered_lines.is_empty() && !self.all.rendered_lines.is_empty() {
+            return Text::from(vec![
+                Line::from(Span::styled(
+                    "No operations match the active filters.",
+                    Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                )),
+                Line::from(""),
+                Line::from("Press V to change filters."),
+            ]);
+        }
         if self.snapshot.rendered_lines.is_empty() {
             return Text::from(vec![
                 Line::from(Span::styled(
@@ -405,6 +800,61 @@ impl OperationLogView {
     fn selected_rendered_line(&self) -> Option<usize> {
         self.selected_row().map(|row| row.rendered_line)
     }
+
+    fn paint_row_overlays(&self, frame: &mut Frame<'_>, area: Rect) {
+        if area.is_empty() || area.width < 3 {
+            return;
+        }
+
+        for row in &self.snapshot.rows {
+            let mark = self.marks.index_for(&row.operation_id);
+            if mark.is_none() && row.jk_action.is_none() && row.checkpoint.is_none() {
+                continue;
+            }
+            let Some(visible_line) = row.rendered_line.checked_sub(self.scroll_offset) else {
+                continue;
+            };
+            let Ok(visible_line) = u16::try_from(visible_line) else {
+                continue;
+            };
+            if visible_line >= area.height {
+                continue;
+            }
+
+            let mut spans = Vec::new();
+            if let Some(checkpoint) = &row.checkpoint {
+                spans.push((format!("checkpoint {checkpoint}"), CHECKPOINT_OVERLAY_STYLE));
+            }
+            if let Some(jk_action) = &row.jk_action {
+                if !spans.is_empty() {
+                    spans.push((" ".to_owned(), Style::default()));
+                }
+                spans.push((format!("jk {jk_action}"), JK_ACTION_OVERLAY_STYLE));
+            }
+            if let Some(mark) = mark {
+                if !spans.is_empty() {
+                    spans.push((" ".to_owned(), Style::default()));
+                }
+                spans.push((format!("[{}]", mark + 1), MARK_OVERLAY_STYLE));
+            }
+            let label_width: usize = spans.iter().map(|(text, _)| text.chars().count()).sum();
+            let Ok(label_width) = u16::try_from(label_width) else {
+                continue;
+            };
+            if label_width > area.width {
+                continue;
+            }
+
+            let mut x = area.right() - label_width;
+            for (text, style) in spans {
+                let width = u16::try_from(text.chars().count()).unwrap_or(u16::MAX);
+                frame
+                    .buffer_mut()
+                    .set_string(x, area.y + visible_line, text, style);
+                x = x.saturating_add(width);
+            }
+        }
+    }
 }
 
 fn operation_line_text(row: &OperationLogRow) -> String {
@@ -426,6 +876,44 @@ fn initial_selection(snapshot: &OperationLogSnapshot) -> Option<usize> {
         .or_else(|| clamp_index(Some(0), snapshot.rows.len()))
 }
 
+/// Parses jj's `%Y-%m-%dT%H:%M:%S%:z` operation timestamps into Unix seconds.
+fn parse_timestamp_seconds(timestamp: &str) -> Option<i64> {
+    let (date, time) = timestamp.split_once('T')?;
+    let mut date_parts = date.splitn(3, '-').map(str::parse::<i64>);
+    let (year, month, day) = (
+        date_parts.next()?.ok()?,
+        date_parts.next()?.ok()?,
+        date_parts.next()?.ok()?,
+    );
+    let offset_start = time.find(['+', '-', 'Z'])?;
+    let (clock, offset) = time.split_at(offset_start);
+    let mut clock_parts = clock.splitn(3, ':').map(str::parse::<i64>);
+    let (hour, minute, second) = (
+        clock_parts.next()?.ok()?,
+        clock_parts.next()?.ok()?,
+        clock_parts.next().unwrap_or(Ok(0)).ok()?,
+    );
+    let offset_seconds = match offset.split_at(1) {
+        ("Z", _) => 0,
+        (sign, rest) => {
+            let (offset_hour, offset_minute) = rest.split_once(':').unwrap_or((rest, "0"));
+            let magnitude =
+                offset_hour.parse::<i64>().ok()? * 3600 + offset_minute.parse::<i64>().ok()? * 60;
+            if sign == "-" { -magnitude } else { magnitude }
+        }
+    };
+
+    // Days from the civil date, after Howard Hinnant's `days_from_civil`.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    Some(days * 86_400 + hour * 3600 + minute * 60 + second - offset_seconds)
+}
+
 fn clamp_index(index: Option<usize>, len: usize) -> Option<usize> {
     let index = index?;
     if len == 0 {
@@ -529,6 +1017,287 @@ mod tests {
         );
     }
 
+    #[test]
+    fn two_ordered_marks_turn_diff_into_operation_range() {
+        let mut view = OperationLogView::new(snapshot([
+            row("op1-full", "op1", "newest", true),
+            row("op2-full", "op2", "middle", false),
+            row("op3-full", "op3", "oldest", false),
+        ]));
+        let _ = view.apply(OperationLogAction::Last);
+        let _ = view.apply(OperationLogAction::ToggleMark);
+        let _ = view.apply(OperationLogAction::First);
+        let _ = view.apply(OperationLogAction::ToggleMark);
+
+        assert_eq!(view.marked_operation_ids(), ["op3-full", "op1-full"]);
+        assert_eq!(
+            view.apply(OperationLogAction::OpenDiff),
+            OperationLogActionResult::OperationDiffFromTo {
+                from: "op3-full".to_owned(),
+                to: "op1-full".to_owned(),
+            }
+        );
+
+        let _ = view.apply(OperationLogAction::ToggleMark);
+        assert_eq!(view.marked_operation_ids(), ["op3-full"]);
+        assert_eq!(
+            view.apply(OperationLogAction::OpenDiff),
+            OperationLogActionResult::OperationDiff {
+                operation_id: "op1-full".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn three_marks_report_instead_of_diffing_the_selection() {
+        let mut view = OperationLogView::new(snapshot([
+            row("op1-full", "op1", "newest", true),
+            row("op2-full", "op2", "middle", false),
+            row("op3-full", "op3", "oldest", false),
+        ]));
+        for action in [
+            OperationLogAction::ToggleMark,
+            OperationLogAction::Next,
+            OperationLogAction::ToggleMark,
+            OperationLogAction::Next,
+            OperationLogAction::ToggleMark,
+        ] {
+            let _ = view.apply(action);
+        }
+
+        assert_eq!(
+            view.apply(OperationLogAction::OpenDiff),
+            OperationLogActionResult::Continue
+        );
+        assert_eq!(
+            view.status_message.as_deref(),
+            Some("3 operations marked; mark exactly two to compare")
+        );
+    }
+
+    #[test]
+    fn refresh_drops_marks_for_missing_operations_and_clear_empties_marks() {
+        let mut view = OperationLogView::new(snapshot([
+            row("op1", "op1", "newest", true),
+            row("op2", "op2", "older", false),
+        ]));
+        let _ = view.apply(OperationLogAction::ToggleMark);
+        let _ = view.apply(OperationLogAction::Next);
+        let _ = view.apply(OperationLogAction::ToggleMark);
+
+        view.refresh(snapshot([
+            row("op0", "op0", "new current", true),
+            row("op2", "op2", "older", false),
+        ]));
+
+        assert_eq!(view.marked_operation_ids(), ["op2"]);
+        assert_eq!(view.mark_index_for_operation_id("op2"), Some(0));
+
+        let _ = view.apply(OperationLogAction::ClearMarks);
+        assert!(!view.has_marks());
+    }
+
+    #[test]
+    fn marked_operations_render_ordered_affordances() {
+        let mut view = OperationLogView::new(snapshot([
+            row("op1", "op1", "newest", true),
+            row("op2", "op2", "middle", false),
+            row("op3", "op3", "oldest", false),
+        ]));
+        let _ = view.apply(OperationLogAction::Last);
+        let _ = view.apply(OperationLogAction::ToggleMark);
+        let _ = view.apply(OperationLogAction::First);
+        let _ = view.apply(OperationLogAction::ToggleMark);
+        let backend = TestBackend::new(48, 6);
+        let mut terminal = match Terminal::new(backend) {
+            Ok(terminal) => terminal,
+            Err(error) => match error {},
+        };
+
+        let draw_result = terminal.draw(|frame| view.render(frame));
+        assert!(draw_result.is_ok());
+
+        let rendered = buffer_to_string(terminal.backend().buffer());
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert!(lines[1].ends_with("[2]"));
+        assert!(!lines[2].contains('['));
+        assert!(lines[3].ends_with("[1]"));
+    }
+
+    #[test]
+    fn filters_keep_matching_rendered_blocks_and_title_summary() {
+        let rows = vec![
+            row("op1", "op1", "agent work", true)
+                .with_rendered_line(0)
+                .with_origin("agent", "ci"),
+            row("op2", "op2", "my work", false)
+                .with_rendered_line(2)
+                .with_origin("me", "laptop")
+                .with_jk_action("log describe"),
+            row("op3", "op3", "older work", false)
+                .with_rendered_line(4)
+                .with_origin("me", "laptop"),
+        ];
+        let lines = [
+            "@ op1 agent@ci",
+            "│ agent work",
+            "○ op2 me@laptop",
+            "│ my work",
+            "○ op3 me@laptop",
+            "│ older work",
+        ];
+        let mut view = OperationLogView::new(
+            OperationLogSnapshot::from_rendered(rows, lines).with_title("jj op log"),
+        );
+
+        view.toggle_user_filter("me");
+
+        assert_eq!(view.snapshot.title(), "jj op log (user me)");
+        assert_eq!(
+            view.snapshot.rendered_lines(),
+            [
+                "○ op2 me@laptop",
+                "│ my work",
+                "○ op3 me@laptop",
+                "│ older work"
+            ]
+        );
+        assert_eq!(view.selected_operation_id(), Some("op2"));
+        assert_eq!(view.selected_row().map(|row| row.rendered_line), Some(0));
+
+        view.toggle_jk_only();
+        assert_eq!(view.snapshot.rows().len(), 1);
+        assert_eq!(view.snapshot.title(), "jj op log (user me, jk only)");
+
+        view.set_filter(OperationLogFilter::default());
+        assert_eq!(view.snapshot.rendered_lines().len(), 6);
+        assert_eq!(view.selected_operation_id(), Some("op2"));
+    }
+
+    #[test]
+    fn host_filter_cycles_through_hosts_then_clears() {
+        let mut view = OperationLogView::new(snapshot([
+            row("op1", "op1", "a", true).with_origin("agent", "ci"),
+            row("op2", "op2", "b", false).with_origin("me", "laptop"),
+            row("op3", "op3", "c", false).with_origin("me", "ci"),
+        ]));
+
+        view.cycle_host_filter();
+        assert_eq!(view.filter().host.as_deref(), Some("ci"));
+        assert_eq!(view.snapshot.rows().len(), 2);
+        view.cycle_host_filter();
+        assert_eq!(view.filter().host.as_deref(), Some("laptop"));
+        view.cycle_host_filter();
+        assert_eq!(view.filter().host, None);
+        assert_eq!(view.snapshot.rows().len(), 3);
+    }
+
+    #[test]
+    fn time_window_filter_counts_back_from_reference_time() {
+        // 2026-03-01T12:00:00+00:00
+        let now = 1_772_366_400;
+        let mut view = OperationLogView::new(snapshot([
+            row("recent", "recent", "a", true)
+                .with_time_range("2026-03-01T12:30:00+01:00", "2026-03-01T12:30:00+01:00"),
+            row("old", "old", "b", false)
+                .with_time_range("2026-02-27T12:00:00+00:00", "2026-02-27T12:00:00+00:00"),
+            row("unknown", "unknown", "c", false),
+        ]));
+
+        view.cycle_time_window(now);
+
+        assert_eq!(
+            view.filter().time_window,
+            Some(OperationTimeWindow::LastHour)
+        );
+        let ids = view
+            .snapshot
+            .rows()
+            .iter()
+            .map(|row| row.operation_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["recent", "unknown"]);
+
+        view.cycle_time_window(now);
+        view.cycle_time_window(now);
+        assert_eq!(
+            view.filter().time_window,
+            Some(OperationTimeWindow::LastWeek)
+        );
+        assert_eq!(view.snapshot.rows().len(), 3);
+    }
+
+    #[test]
+    fn parses_jj_operation_timestamps() {
+        assert_eq!(
+            parse_timestamp_seconds("1970-01-01T00:00:00+00:00"),
+            Some(0)
+        );
+        assert_eq!(
+            parse_timestamp_seconds("2000-03-01T01:00:00+01:00"),
+            Some(951_868_800)
+        );
+        assert_eq!(parse_timestamp_seconds("not a time"), None);
+    }
+
+    #[test]
+    fn checkpoints_and_jk_actions_render_beside_marks() {
+        let mut view = OperationLogView::new(snapshot([
+            row("op1", "op1", "newest", true).with_jk_action("log describe"),
+            row("op2", "op2", "middle", false).with_checkpoint("start"),
+        ]));
+        let _ = view.apply(OperationLogAction::ToggleMark);
+        let backend = TestBackend::new(48, 5);
+        let mut terminal = match Terminal::new(backend) {
+            Ok(terminal) => terminal,
+            Err(error) => match error {},
+        };
+
+        let draw_result = terminal.draw(|frame| view.render(frame));
+        assert!(draw_result.is_ok());
+
+        let rendered = buffer_to_string(terminal.backend().buffer());
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert!(lines[1].ends_with("jk log describe [1]"));
+        assert!(!lines[2].contains("jk "));
+        assert!(lines[2].ends_with("checkpoint start"));
+    }
+
+    #[test]
+    fn set_checkpoint_labels_loaded_rows_only() {
+        let mut view = OperationLogView::new(snapshot([
+            row("op1", "op1", "newest", true),
+            row("op2", "op2", "older", false),
+        ]));
+
+        assert!(view.set_checkpoint("op2", "before rebase"));
+        assert!(!view.set_checkpoint("missing", "ignored"));
+
+        assert_eq!(
+            view.snapshot.rows[1].checkpoint.as_deref(),
+            Some("before rebase")
+        );
+        assert_eq!(view.selected_operation_id(), Some("op1"));
+    }
+
+    #[test]
+    fn filtered_out_log_renders_filter_empty_state() {
+        let mut view = OperationLogView::new(snapshot([row("op1", "op1", "a", true)]));
+        view.toggle_jk_only();
+        let backend = TestBackend::new(48, 5);
+        let mut terminal = match Terminal::new(backend) {
+            Ok(terminal) => terminal,
+            Err(error) => match error {},
+        };
+
+        let draw_result = terminal.draw(|frame| view.render(frame));
+        assert!(draw_result.is_ok());
+
+        let rendered = buffer_to_string(terminal.backend().buffer());
+        assert!(rendered.contains("No operations match the active filters."));
+        assert_eq!(view.selected_operation_id(), None);
+    }
+
     #[test]
     fn empty_snapshot_is_safe_and_renders_empty_state() {
         let mut view = OperationLogView::new(OperationLogSnapshot::new(Vec::new()));
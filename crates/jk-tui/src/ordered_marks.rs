@@ -0,0 +1,90 @@
+//! Ordered marks shared by the revision log and the operation log.
+//!
+//! Marks keep the order they were set in, so two marks read as "from the first to the second".
+
+/// Ordered marks keyed by a stable id, such as a change id or an operation id.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OrderedMarks {
+    ids: Vec<String>,
+}
+
+impl OrderedMarks {
+    /// Adds `id` as the newest mark, or removes it when already marked.
+    pub fn toggle(&mut self, id: &str) {
+        if let Some(index) = self.index_for(id) {
+            self.ids.remove(index);
+        } else {
+            self.ids.push(id.to_owned());
+        }
+    }
+
+    /// Removes every mark and returns whether anything was marked.
+    pub fn clear(&mut self) -> bool {
+        let had_marks = !self.ids.is_empty();
+        self.ids.clear();
+        had_marks
+    }
+
+    /// Drops marks whose id no longer passes `visible`.
+    pub fn retain(&mut self, visible: impl Fn(&str) -> bool) {
+        self.ids.retain(|id| visible(id));
+    }
+
+    /// Returns the zero-based mark index for `id`.
+    pub fn index_for(&self, id: &str) -> Option<usize> {
+        self.ids.iter().position(|marked| marked == id)
+    }
+
+    /// Returns marked ids in insertion order.
+    pub fn ids(&self) -> &[String] {
+        &self.ids
+    }
+}
+
+/// Returns the from/to pair that exactly two marks select.
+///
+/// Fewer than two marks return `Ok(None)` so callers act on the selection. `noun` names one marked
+/// item.
+///
+/// # Errors
+///
+/// More than two marks return the status to show, since the range would be ambiguous.
+pub fn marked_pair<'a, T>(marks: &'a [T], noun: &str) -> Result<Option<(&'a T, &'a T)>, String> {
+    match marks {
+        [from, to] => Ok(Some((from, to))),
+        [] | [_] => Ok(None),
+        _ => Err(format!(
+            "{} {noun}s marked; mark exactly two to compare",
+            marks.len()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_toggle_in_order_and_pair_only_when_two_are_set() {
+        let mut marks = OrderedMarks::default();
+        marks.toggle("b");
+        marks.toggle("a");
+
+        assert_eq!(marks.ids(), ["b", "a"]);
+        assert_eq!(
+            marked_pair(marks.ids(), "revision"),
+            Ok(Some((&"b".to_owned(), &"a".to_owned())))
+        );
+
+        marks.toggle("c");
+        assert_eq!(
+            marked_pair(marks.ids(), "revision"),
+            Err("3 revisions marked; mark exactly two to compare".to_owned())
+        );
+
+        marks.toggle("b");
+        marks.retain(|id| id != "c");
+        assert_eq!(marks.ids(), ["a"]);
+        assert_eq!(marked_pair(marks.ids(), "revision"), Ok(None));
+    }
+}
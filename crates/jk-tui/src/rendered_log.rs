@@ -280,7 +280,7 @@ fn body_continuation_prefix(line: &str) -> String {
 
 /// Returns whether a graph item character represents a commit row.
 const fn is_commit_marker(character: char) -> bool {
-    matches!(character, '@' | '○' | '◆' | '×' | '+')
+    matches!(character, '@' | '○' | '◉' | '◆' | '×' | '+')
 }
 
 /// Returns whether a character is part of jj's rendered graph.
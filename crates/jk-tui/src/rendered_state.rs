@@ -45,6 +45,11 @@ impl RenderedState {
         &self.title
     }
 
+    /// Returns the raw output, ANSI styling included.
+    pub fn rendered(&self) -> &str {
+        &self.rendered
+    }
+
     /// Returns the first rendered line currently visible in the viewport.
     pub const fn scroll_offset(&self) -> usize {
         self.scroll_offset
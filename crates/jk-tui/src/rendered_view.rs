@@ -6,6 +6,7 @@ use ratatui::layout::Rect;
 use ratatui::widgets::Paragraph;
 
 use crate::chrome::{ViewChrome, render_help_overlay};
+use crate::diff_state::source_location;
 use crate::keymap::{BindingContext, adaptive_hotbar, help_lines, help_title};
 use crate::rendered_log::rendered_text;
 use crate::rendered_state::RenderedState;
@@ -113,6 +114,12 @@ impl RenderedView {
         self.status_message = Some(error.into());
     }
 
+    /// Returns the file and new-side line at the top of the viewport when the output is a diff.
+    #[must_use]
+    pub fn diff_location(&self) -> Option<(String, Option<usize>)> {
+        source_location(self.state.rendered(), self.state.scroll_offset())
+    }
+
     /// Applies a single input action.
     #[must_use]
     pub fn apply(&mut self, action: RenderedAction) -> RenderedActionResult {
@@ -232,6 +239,29 @@ mod tests {
         assert_eq!(view.apply(RenderedAction::Quit), RenderedActionResult::Quit);
     }
 
+    #[test]
+    fn diff_location_follows_the_top_line_of_diff_output() {
+        let mut view = RenderedView::new(snapshot(
+            "aaa",
+            "diff --git a/src/a.rs b/src/a.rs\n@@ -1,2 +4,2 @@\n-old\n+new\n",
+        ));
+        assert_eq!(view.diff_location(), Some(("src/a.rs".to_owned(), None)));
+        let mut terminal = match Terminal::new(TestBackend::new(48, 3)) {
+            Ok(terminal) => terminal,
+            Err(error) => match error {},
+        };
+        assert!(terminal.draw(|frame| view.render(frame)).is_ok());
+
+        let _ = view.apply(RenderedAction::ScrollNext);
+        let _ = view.apply(RenderedAction::ScrollNext);
+        let _ = view.apply(RenderedAction::ScrollNext);
+        assert_eq!(view.diff_location(), Some(("src/a.rs".to_owned(), Some(4))));
+        assert_eq!(
+            RenderedView::new(snapshot("aaa", "Working copy changes:\n")).diff_location(),
+            None
+        );
+    }
+
     #[test]
     fn refresh_errors_replace_status_without_replacing_body() {
         let mut view = RenderedView::new(snapshot("aaa", "first line\n"));
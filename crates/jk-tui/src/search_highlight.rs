@@ -0,0 +1,44 @@
+//! Search-match highlighting painted over rendered output.
+//!
+//! Like selected-row highlighting, matches are painted into the Ratatui buffer after the body
+//! renders, so `jj`'s own colors stay intact everywhere else. Matching works on buffer cells rather
+//! than source text, which keeps wide characters and inserted detail rows aligned for free.
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::prelude::Color;
+
+const MATCH_BG: Color = Color::Rgb(229, 192, 123);
+const MATCH_FG: Color = Color::Rgb(15, 20, 31);
+
+/// Highlights every case-insensitive occurrence of `query` visible in `area`.
+pub fn paint_search_matches(frame: &mut Frame<'_>, area: Rect, query: &str) {
+    let query = query.to_lowercase();
+    if area.is_empty() || query.is_empty() {
+        return;
+    }
+
+    for y in area.top()..area.bottom() {
+        let mut row = String::new();
+        let mut columns = Vec::new();
+        for x in area.left()..area.right() {
+            let symbol = frame.buffer_mut()[(x, y)].symbol().to_lowercase();
+            columns.extend(std::iter::repeat_n(x, symbol.len()));
+            row.push_str(&symbol);
+        }
+
+        let mut search_from = 0;
+        while let Some(offset) = row[search_from..].find(&query) {
+            let start = search_from + offset;
+            let end = start + query.len();
+            let (Some(first), Some(last)) = (columns.get(start), columns.get(end - 1)) else {
+                break;
+            };
+            for x in *first..=*last {
+                frame.buffer_mut()[(x, y)].set_bg(MATCH_BG);
+                frame.buffer_mut()[(x, y)].set_fg(MATCH_FG);
+            }
+            search_from = end;
+        }
+    }
+}
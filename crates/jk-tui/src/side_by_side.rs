@@ -0,0 +1,267 @@
+//! Side-by-side layout for `jj diff --git` output.
+//!
+//! The layout is a text transformation: file headers, metadata, and hunk headers stay full-width
+//! rows, while each hunk body becomes rows with the old line on the left and the new line on the
+//! right. Keeping the header rows intact lets the diff state find files and hunks in the laid-out
+//! text exactly as it does in inline output, so folding, jumps, and search keep working.
+
+use crate::ansi_text::strip_ansi;
+
+/// Narrowest viewport that still lays `--git` output out in two columns.
+pub const SIDE_BY_SIDE_MIN_WIDTH: usize = 100;
+
+const COLUMN_SEPARATOR: &str = " │ ";
+const COLUMN_SEPARATOR_WIDTH: usize = 3;
+const TAB_WIDTH: usize = 4;
+const RESET: &str = "\u{1b}[0m";
+const BOLD: &str = "\u{1b}[1m";
+const DIM: &str = "\u{1b}[2m";
+const REMOVED: &str = "\u{1b}[38;5;1m";
+const ADDED: &str = "\u{1b}[38;5;2m";
+const HUNK: &str = "\u{1b}[38;5;6m";
+
+/// Lays out unified `--git` output in old/new columns that fill `width`.
+pub fn side_by_side(git: &str, width: usize) -> String {
+    let plain = git.lines().map(strip_ansi).collect::<Vec<_>>();
+    let mut layout = Layout::new(width, line_number_width(&plain));
+    for line in &plain {
+        layout.push(line);
+    }
+    layout.finish()
+}
+
+/// Builds side-by-side rows while pairing runs of removed and added lines.
+struct Layout {
+    rendered: String,
+    column_width: usize,
+    number_width: usize,
+    in_hunk: bool,
+    old_line: usize,
+    new_line: usize,
+    removed: Vec<(usize, String)>,
+    added: Vec<(usize, String)>,
+}
+
+impl Layout {
+    const fn new(width: usize, number_width: usize) -> Self {
+        Self {
+            rendered: String::new(),
+            column_width: width.saturating_sub(COLUMN_SEPARATOR_WIDTH) / 2,
+            number_width,
+            in_hunk: false,
+            old_line: 0,
+            new_line: 0,
+            removed: Vec::new(),
+            added: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, line: &str) {
+        if line.starts_with("diff --git ") {
+            self.flush_changes();
+            self.in_hunk = false;
+            self.push_full_width(BOLD, line);
+            return;
+        }
+        if let Some((old_start, new_start)) = hunk_starts(line) {
+            self.flush_changes();
+            self.in_hunk = true;
+            self.old_line = old_start;
+            self.new_line = new_start;
+            self.push_full_width(HUNK, line);
+            return;
+        }
+        if !self.in_hunk {
+            self.push_full_width(BOLD, line);
+            return;
+        }
+
+        if let Some(text) = line.strip_prefix('-') {
+            self.removed.push((self.old_line, expand_tabs(text)));
+            self.old_line += 1;
+        } else if let Some(text) = line.strip_prefix('+') {
+            self.added.push((self.new_line, expand_tabs(text)));
+            self.new_line += 1;
+        } else if line.starts_with('\\') {
+            self.flush_changes();
+            self.push_full_width(DIM, line);
+        } else {
+            self.flush_changes();
+            let text = expand_tabs(line.strip_prefix(' ').unwrap_or(line));
+            let old = self.cell(Some((self.old_line, &text)), ' ', "");
+            let new = self.cell(Some((self.new_line, &text)), ' ', "");
+            self.push_row(&old, &new);
+            self.old_line += 1;
+            self.new_line += 1;
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.flush_changes();
+        self.rendered
+    }
+
+    /// Pairs pending removed and added lines row by row, leaving the shorter side blank.
+    fn flush_changes(&mut self) {
+        let removed = std::mem::take(&mut self.removed);
+        let added = std::mem::take(&mut self.added);
+        for row in 0..removed.len().max(added.len()) {
+            let old = self.cell(
+                removed.get(row).map(|(line, text)| (*line, text.as_str())),
+                '-',
+                REMOVED,
+            );
+            let new = self.cell(
+                added.get(row).map(|(line, text)| (*line, text.as_str())),
+                '+',
+                ADDED,
+            );
+            self.push_row(&old, &new);
+        }
+    }
+
+    fn push_full_width(&mut self, style: &str, line: &str) {
+        self.rendered.push_str(style);
+        self.rendered.push_str(line);
+        self.rendered.push_str(RESET);
+        self.rendered.push('\n');
+    }
+
+    fn push_row(&mut self, old: &str, new: &str) {
+        self.rendered.push_str(old);
+        self.rendered.push_str(DIM);
+        self.rendered.push_str(COLUMN_SEPARATOR);
+        self.rendered.push_str(RESET);
+        self.rendered.push_str(new);
+        self.rendered.push('\n');
+    }
+
+    /// Renders one column cell padded to the column width: line number, marker, then text.
+    fn cell(&self, line: Option<(usize, &str)>, marker: char, style: &str) -> String {
+        let Some((number, text)) = line else {
+            return " ".repeat(self.column_width);
+        };
+        let text_width = self.column_width.saturating_sub(self.number_width + 2);
+        format!(
+            "{DIM}{number:>width$}{RESET} {style}{marker}{}{RESET}",
+            fit(text, text_width),
+            width = self.number_width,
+        )
+    }
+}
+
+/// Returns the old and new starting line numbers from a `@@ -a,b +c,d @@` header.
+fn hunk_starts(line: &str) -> Option<(usize, usize)> {
+    let ranges = line.strip_prefix("@@ ")?;
+    let (ranges, _) = ranges.split_once(" @@")?;
+    let (old, new) = ranges.split_once(' ')?;
+    let start = |range: &str, sign: char| -> Option<usize> {
+        let range = range.strip_prefix(sign)?;
+        range.split(',').next()?.parse().ok()
+    };
+    Some((start(old, '-')?, start(new, '+')?))
+}
+
+/// Returns the gutter width that fits every line number in the diff.
+fn line_number_width(lines: &[String]) -> usize {
+    let last_line = lines
+        .iter()
+        .filter_map(|line| {
+            let ranges = line.strip_prefix("@@ ")?.split_once(" @@")?.0;
+            ranges
+                .split(' ')
+                .filter_map(|range| {
+                    let mut parts = range.get(1..)?.split(',');
+                    let start = parts.next()?.parse::<usize>().ok()?;
+                    let count = parts.next().map_or(Some(1), |count| count.parse().ok())?;
+                    Some(start + count)
+                })
+                .max()
+        })
+        .max()
+        .unwrap_or_default();
+    last_line.to_string().len().max(3)
+}
+
+/// Pads or truncates `text` to exactly `width` characters, marking cut text with an ellipsis.
+fn fit(text: &str, width: usize) -> String {
+    let count = text.chars().count();
+    if count <= width {
+        return format!("{text}{}", " ".repeat(width - count));
+    }
+    let mut fitted = text
+        .chars()
+        .take(width.saturating_sub(1))
+        .collect::<String>();
+    if width > 0 {
+        fitted.push('…');
+    }
+    fitted
+}
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', &" ".repeat(TAB_WIDTH))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIT: &str = concat!(
+        "diff --git a/src/a.rs b/src/a.rs\n",
+        "index 1111111..2222222 100644\n",
+        "--- a/src/a.rs\n",
+        "+++ b/src/a.rs\n",
+        "@@ -1,3 +1,4 @@ fn main() {\n",
+        " keep\n",
+        "-old one\n",
+        "-old two\n",
+        "+new one\n",
+        "+new two\n",
+        "+new three\n",
+        " tail\n",
+    );
+
+    fn plain_rows(git: &str, width: usize) -> Vec<String> {
+        side_by_side(git, width)
+            .lines()
+            .map(|line| strip_ansi(line).trim_end().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn pairs_removed_and_added_runs_in_columns_and_keeps_headers_whole() {
+        let rows = plain_rows(GIT, 41);
+
+        assert_eq!(
+            rows,
+            [
+                "diff --git a/src/a.rs b/src/a.rs",
+                "index 1111111..2222222 100644",
+                "--- a/src/a.rs",
+                "+++ b/src/a.rs",
+                "@@ -1,3 +1,4 @@ fn main() {",
+                "  1  keep           │   1  keep",
+                "  2 -old one        │   2 +new one",
+                "  3 -old two        │   3 +new two",
+                "                    │   4 +new three",
+                "  4  tail           │   5  tail",
+            ]
+        );
+    }
+
+    #[test]
+    fn long_lines_are_cut_to_their_column() {
+        let git = "diff --git a/a b/a\n@@ -1 +1 @@\n-0123456789abcdef\n+x\n";
+
+        let rows = plain_rows(git, 31);
+
+        assert_eq!(rows[2], "  1 -01234567… │   1 +x");
+        assert!(
+            side_by_side(git, 31)
+                .lines()
+                .skip(2)
+                .all(|line| strip_ansi(line).chars().count() == 31)
+        );
+    }
+}
@@ -5,16 +5,26 @@ use jk_cli::{
 };
 use jk_tui::log_view::LogAction;
 
+use crate::checkpoints::{open_checkpoint_picker, open_checkpoint_prompt};
+use crate::diff_patch::open_diff_patch_menu;
+use crate::editor::request_diff_editor;
+use crate::graph_navigation::jump_along_graph;
 use crate::key::AppKey;
+use crate::log_copy::open_log_copy_menu;
+use crate::review_notes::{open_diff_note_prompt, open_diff_notes_export};
+use crate::revision_finder::{open_jump_finder, open_rebase_finder, open_squash_finder};
+use crate::revset_prompt::open_revset_prompt;
 use crate::state::{AppState, AppView, InputMode};
+use crate::viewed_marks::toggle_diff_viewed;
 use crate::{
     AppLoop, SearchDirection, apply_action, apply_search_action, copy_selected_command,
     edit_command_output, handle_back_with_log_source, open_abandon_preview, open_command_discovery,
     open_command_history, open_command_history_operation, open_diff_file_list, open_edit_preview,
     open_jj_command_mode, open_new_preview, open_operation_log, open_recovery_preview,
-    open_view_options, open_workspaces, push_selected_command_history_details,
-    push_selected_evolog, push_selected_operation_show, push_selected_show,
-    push_selected_workspace_status, push_status, update_selected_workspace_stale,
+    open_view_options, open_workspaces, push_marked_interdiff,
+    push_selected_command_history_details, push_selected_evolog, push_selected_operation_show,
+    push_selected_show, push_selected_workspace_status, push_status,
+    update_selected_workspace_stale,
 };
 
 pub struct AppSources<'a> {
@@ -52,6 +62,13 @@ pub fn dispatch_app_key(
         return DispatchResult::Continue;
     }
 
+    if matches!(key.code, KeyCode::Esc)
+        && let AppView::Log(log) = state.views.active_mut()
+        && log.clear_search()
+    {
+        return DispatchResult::Continue;
+    }
+
     let AppKey::Action(action) = app_key else {
         dispatch_direct_app_key(state, sources, app_key);
         return DispatchResult::Continue;
@@ -118,6 +135,9 @@ fn dispatch_direct_app_key(state: &mut AppState, sources: &mut AppSources<'_>, a
         AppKey::OpenEvolog => {
             push_selected_evolog(state, sources.evolog);
         }
+        AppKey::OpenInterdiff => {
+            push_marked_interdiff(state, sources.diff);
+        }
         AppKey::OpenStatus => {
             if matches!(state.views.active(), AppView::Workspaces { .. }) {
                 push_selected_workspace_status(state, sources.workspaces);
@@ -141,6 +161,12 @@ fn dispatch_direct_app_key(state: &mut AppState, sources: &mut AppSources<'_>, a
         AppKey::CopyCommand => {
             copy_selected_command(state);
         }
+        AppKey::CopyPatch => {
+            open_diff_patch_menu(state);
+        }
+        AppKey::CopyRevision => {
+            open_log_copy_menu(state);
+        }
         AppKey::StartUndo => {
             if matches!(state.views.active(), AppView::Workspaces { .. }) {
                 update_selected_workspace_stale(state, sources.workspaces);
@@ -151,6 +177,27 @@ fn dispatch_direct_app_key(state: &mut AppState, sources: &mut AppSources<'_>, a
         AppKey::StartRedo => {
             open_recovery_preview(state, sources.recovery, RecoveryCommand::Redo);
         }
+        AppKey::StartCheckpoint => {
+            open_checkpoint_prompt(state);
+        }
+        AppKey::StartRestoreCheckpoint => {
+            open_checkpoint_picker(state);
+        }
+        AppKey::StartRevset => {
+            open_revset_prompt(state, sources.log);
+        }
+        AppKey::StartRevisionFinder => {
+            open_jump_finder(state, sources.log);
+        }
+        AppKey::GraphJump(graph_move) => {
+            jump_along_graph(state, sources.log, graph_move);
+        }
+        AppKey::StartRebase => {
+            open_rebase_finder(state, sources.log);
+        }
+        AppKey::StartSquash => {
+            open_squash_finder(state, sources.log);
+        }
         AppKey::StartDescribe => {
             crate::open_describe_message(state);
         }
@@ -159,7 +206,7 @@ fn dispatch_direct_app_key(state: &mut AppState, sources: &mut AppSources<'_>, a
         }
         AppKey::OpenViewOptions => {
             if !matches!(state.views.active(), AppView::CommandHistory { .. }) {
-                open_view_options(state);
+                open_view_options(state, sources.log, sources.diff, sources.operation);
             }
         }
         AppKey::StartCommandMode => {
@@ -168,6 +215,11 @@ fn dispatch_direct_app_key(state: &mut AppState, sources: &mut AppSources<'_>, a
         AppKey::EditCommandOutput => {
             if matches!(state.views.active(), AppView::Log(_)) {
                 open_edit_preview(state, sources.edit);
+            } else if matches!(
+                state.views.active(),
+                AppView::Diff { .. } | AppView::WorkspaceDiff { .. }
+            ) {
+                request_diff_editor(state);
             } else {
                 edit_command_output(state);
             }
@@ -175,9 +227,21 @@ fn dispatch_direct_app_key(state: &mut AppState, sources: &mut AppSources<'_>, a
         AppKey::OpenDiffFileList => {
             open_diff_file_list(state);
         }
+        AppKey::ToggleViewed => {
+            toggle_diff_viewed(state);
+        }
+        AppKey::StartReviewNote => {
+            open_diff_note_prompt(state);
+        }
+        AppKey::ExportDiffNotes => {
+            open_diff_notes_export(state);
+        }
         AppKey::StartSearch if active_view_supports_search(state) => {
             state.modes.push(search_input_mode(state));
         }
+        AppKey::SearchNext if active_log_has_search(state) => {
+            apply_search_action(state, SearchDirection::Next);
+        }
         AppKey::SearchNext if matches!(state.views.active(), AppView::Log(_)) => {
             open_new_preview(state, sources.new_change);
         }
@@ -195,13 +259,18 @@ fn active_log_has_selected_elision(state: &AppState) -> bool {
     let AppView::Log(log) = state.views.active() else {
         return false;
     };
-    log.selected_elision_revset().is_some()
+    log.selected_elision_revset().is_some() || log.selected_is_load_more()
+}
+
+fn active_log_has_search(state: &AppState) -> bool {
+    matches!(state.views.active(), AppView::Log(log) if log.has_search())
 }
 
 fn active_view_supports_search(state: &AppState) -> bool {
     matches!(
         state.views.active(),
-        AppView::Diff { .. }
+        AppView::Log(_)
+            | AppView::Diff { .. }
             | AppView::Show { .. }
             | AppView::Evolog { .. }
             | AppView::Status { .. }
@@ -220,7 +289,8 @@ fn search_input_mode(state: &AppState) -> InputMode {
         AppView::Diff { .. } => InputMode::DiffSearch {
             query: String::new(),
         },
-        AppView::Show { .. }
+        AppView::Log(_)
+        | AppView::Show { .. }
         | AppView::Evolog { .. }
         | AppView::Status { .. }
         | AppView::WorkspaceLog { .. }
@@ -232,8 +302,7 @@ fn search_input_mode(state: &AppState) -> InputMode {
         | AppView::CommandOutput { .. } => InputMode::InspectionSearch {
             query: String::new(),
         },
-        AppView::Log(_)
-        | AppView::Workspaces { .. }
+        AppView::Workspaces { .. }
         | AppView::OperationLog { .. }
         | AppView::CommandHistory { .. } => unreachable!("search support checked before call"),
     }
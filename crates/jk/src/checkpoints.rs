@@ -0,0 +1,595 @@
+//! Session checkpoints for returning the repository to a known operation.
+//!
+//! `jk` records the current operation id at startup and whenever the user names a checkpoint. The
+//! user picks a checkpoint, and the restore preview pairs `jj op restore <checkpoint>` with a plain
+//! `jj op diff` summary so the user can see everything that would be undone before confirming.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use jk_cli::{
+    JjCommandRunner, JjOperation, JjRecovery, RecordingJjCommandRunner, SystemJjCommandRunner,
+};
+use jk_core::{CommandHistory, CommandSource, SourceAction, SourceView};
+
+use crate::menus::{MenuDirection, wrapped_selection};
+use crate::mutation_preview::PendingCommandPreview;
+use crate::state::{AppState, AppView, InputMode, InputModeResult};
+
+/// Name of the checkpoint recorded when `jk` starts.
+pub const SESSION_START_CHECKPOINT: &str = "session start";
+
+const SHORT_ID_LEN: usize = 12;
+const SUMMARY_LINE_LIMIT: usize = 12;
+
+/// One named operation id recorded during this session.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionCheckpoint {
+    pub(crate) name: String,
+    pub(crate) operation_id: String,
+}
+
+/// Checkpoints recorded during this session, oldest first.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SessionCheckpoints {
+    checkpoints: Vec<SessionCheckpoint>,
+}
+
+impl SessionCheckpoints {
+    /// Records `operation_id` under `name`, replacing an older checkpoint with the same name.
+    pub(crate) fn record(&mut self, name: impl Into<String>, operation_id: impl Into<String>) {
+        let name = name.into();
+        self.checkpoints
+            .retain(|checkpoint| checkpoint.name != name);
+        self.checkpoints.push(SessionCheckpoint {
+            name,
+            operation_id: operation_id.into(),
+        });
+    }
+
+    /// Returns the most recently recorded checkpoint.
+    pub(crate) fn latest(&self) -> Option<&SessionCheckpoint> {
+        self.checkpoints.last()
+    }
+
+    /// Returns the checkpoint at `index` in picker order, newest first.
+    pub(crate) fn newest(&self, index: usize) -> Option<&SessionCheckpoint> {
+        self.checkpoints.iter().rev().nth(index)
+    }
+
+    /// Returns the number of recorded checkpoints.
+    pub(crate) const fn len(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// Returns the newest checkpoint name recorded at `operation_id`.
+    ///
+    /// Either id being a prefix of the other counts as a match so short display ids still resolve,
+    /// but only once the shorter id is as long as a display id; shorter ids match nothing.
+    pub(crate) fn name_for(&self, operation_id: &str) -> Option<&str> {
+        self.checkpoints
+            .iter()
+            .rev()
+            .find(|checkpoint| same_operation(operation_id, &checkpoint.operation_id))
+            .map(|checkpoint| checkpoint.name.as_str())
+    }
+
+    /// Returns the name used when the checkpoint prompt is submitted empty.
+    pub(crate) fn next_default_name(&self) -> String {
+        format!("checkpoint {}", self.checkpoints.len() + 1)
+    }
+}
+
+/// Records the current operation as the session-start checkpoint.
+///
+/// Failures are recorded in command history but otherwise ignored so `jk` still starts in
+/// repositories where the probe cannot run.
+pub fn record_session_start(
+    checkpoints: &mut SessionCheckpoints,
+    history: &mut CommandHistory,
+    operation_source: &JjOperation,
+) {
+    record_session_start_with_runner(
+        checkpoints,
+        history,
+        operation_source,
+        SystemJjCommandRunner,
+    );
+}
+
+pub fn record_session_start_with_runner<R: JjCommandRunner>(
+    checkpoints: &mut SessionCheckpoints,
+    history: &mut CommandHistory,
+    operation_source: &JjOperation,
+    runner: R,
+) {
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        history,
+        CommandSource::new(SourceView::Log, SourceAction::Checkpoint),
+    );
+    if let Ok(operation_id) = operation_source.load_current_id_with_runner(&mut runner) {
+        checkpoints.record(SESSION_START_CHECKPOINT, operation_id);
+    }
+}
+
+/// Opens the checkpoint name prompt from the log or operation log.
+pub fn open_checkpoint_prompt(state: &mut AppState) {
+    if matches!(
+        state.views.active(),
+        AppView::Log(_) | AppView::OperationLog { .. }
+    ) {
+        state.modes.push(InputMode::CheckpointName {
+            name: String::new(),
+        });
+    }
+}
+
+/// Returns the checkpoint name prompt lines.
+pub fn checkpoint_name_lines(checkpoints: &SessionCheckpoints, name: &str) -> Vec<String> {
+    vec![
+        format!("Name: {name}"),
+        format!("Empty uses '{}'.", checkpoints.next_default_name()),
+        String::new(),
+        "type name   enter record   Ctrl-u clear   backspace edit   esc cancel".to_owned(),
+    ]
+}
+
+/// Records a named checkpoint at the current operation.
+pub fn record_checkpoint(state: &mut AppState, operation_source: &JjOperation, name: &str) {
+    record_checkpoint_with_runner(state, operation_source, name, SystemJjCommandRunner);
+}
+
+pub fn record_checkpoint_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    operation_source: &JjOperation,
+    name: &str,
+    runner: R,
+) {
+    let name = match name.trim() {
+        "" => state.checkpoints.next_default_name(),
+        name => name.to_owned(),
+    };
+    let source_view = match state.views.active() {
+        AppView::OperationLog { .. } => SourceView::OperationLog,
+        _ => SourceView::Log,
+    };
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(source_view, SourceAction::Checkpoint).with_key("K"),
+    );
+    let result = operation_source.load_current_id_with_runner(&mut runner);
+    drop(runner);
+    match result {
+        Ok(operation_id) => {
+            let status = format!("checkpoint '{name}' at {}", short_id(&operation_id));
+            state.checkpoints.record(name.clone(), operation_id.clone());
+            match state.views.active_mut() {
+                AppView::Log(log) => log.show_status(status),
+                AppView::OperationLog { view } => {
+                    view.set_checkpoint(&operation_id, name);
+                }
+                _ => {}
+            }
+        }
+        Err(error) => {
+            let error = format!("failed to record checkpoint: {error}");
+            match state.views.active_mut() {
+                AppView::Log(log) => log.show_error(error),
+                AppView::OperationLog { view } => view.show_error(error),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Opens the checkpoint picker from the log with the latest checkpoint selected.
+pub fn open_checkpoint_picker(state: &mut AppState) {
+    let AppView::Log(log) = state.views.active_mut() else {
+        return;
+    };
+    if state.checkpoints.latest().is_none() {
+        log.show_error("no session checkpoint recorded; press K to record one");
+        return;
+    }
+    state
+        .modes
+        .push(InputMode::CheckpointPicker { selected: 0 });
+}
+
+/// Returns the checkpoint picker lines, newest checkpoint first.
+pub fn checkpoint_picker_lines(checkpoints: &SessionCheckpoints, selected: usize) -> Vec<String> {
+    (0..checkpoints.len())
+        .filter_map(|index| {
+            checkpoints
+                .newest(index)
+                .map(|checkpoint| (index, checkpoint))
+        })
+        .map(|(index, checkpoint)| {
+            let marker = if index == selected { ">" } else { " " };
+            format!(
+                "{marker} {:<18} {}",
+                checkpoint.name,
+                short_id(&checkpoint.operation_id)
+            )
+        })
+        .chain([
+            String::new(),
+            "j/k or arrows move   enter preview restore   esc cancel".to_owned(),
+        ])
+        .collect()
+}
+
+/// Handles keys while the checkpoint picker is open.
+pub fn handle_checkpoint_picker_mode(
+    state: &mut AppState,
+    operation_source: &JjOperation,
+    recovery_source: &JjRecovery,
+    key: KeyEvent,
+) -> InputModeResult {
+    handle_checkpoint_picker_mode_with_runner(
+        state,
+        operation_source,
+        recovery_source,
+        key,
+        SystemJjCommandRunner,
+    )
+}
+
+pub fn handle_checkpoint_picker_mode_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    operation_source: &JjOperation,
+    recovery_source: &JjRecovery,
+    key: KeyEvent,
+    runner: R,
+) -> InputModeResult {
+    let checkpoint_count = state.checkpoints.len();
+    let Some(InputMode::CheckpointPicker { selected }) = state.modes.active_mut() else {
+        return InputModeResult::Unhandled;
+    };
+    let direction = match key {
+        KeyEvent {
+            code: KeyCode::Esc | KeyCode::Char('q'),
+            ..
+        } => {
+            state.modes.pop();
+            return InputModeResult::Handled;
+        }
+        KeyEvent {
+            code: KeyCode::Enter,
+            ..
+        } => {
+            let checkpoint = state.checkpoints.newest(*selected).cloned();
+            state.modes.pop();
+            if let Some(checkpoint) = checkpoint {
+                open_restore_checkpoint_preview_with_runner(
+                    state,
+                    operation_source,
+                    recovery_source,
+                    &checkpoint,
+                    runner,
+                );
+            }
+            return InputModeResult::Handled;
+        }
+        KeyEvent {
+            code: KeyCode::Up, ..
+        }
+        | KeyEvent {
+            code: KeyCode::Char('k'),
+            modifiers: KeyModifiers::NONE,
+            ..
+        } => MenuDirection::Previous,
+        KeyEvent {
+            code: KeyCode::Down,
+            ..
+        }
+        | KeyEvent {
+            code: KeyCode::Char('j'),
+            modifiers: KeyModifiers::NONE,
+            ..
+        } => MenuDirection::Next,
+        _ => return InputModeResult::Handled,
+    };
+    *selected = wrapped_selection(*selected, checkpoint_count, direction);
+    InputModeResult::Handled
+}
+
+/// Opens a `jj op restore` preview for `checkpoint`.
+pub fn open_restore_checkpoint_preview_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    operation_source: &JjOperation,
+    recovery_source: &JjRecovery,
+    checkpoint: &SessionCheckpoint,
+    runner: R,
+) {
+    if !matches!(state.views.active(), AppView::Log(_)) {
+        return;
+    }
+
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(SourceView::Log, SourceAction::RestoreCheckpoint).with_key("R"),
+    );
+    let summary =
+        operation_source.load_since_summary_with_runner(&checkpoint.operation_id, &mut runner);
+    drop(runner);
+
+    let mut details = vec![format!(
+        "Changes since checkpoint '{}' ({}):",
+        checkpoint.name,
+        short_id(&checkpoint.operation_id)
+    )];
+    match summary {
+        Ok(summary) => details.extend(summary_lines(&summary)),
+        Err(error) => details.push(format!("could not summarize changes: {error}")),
+    }
+    let preview = recovery_source
+        .restore_spec(&checkpoint.operation_id)
+        .command_preview()
+        .with_details(details);
+    state.modes.push(InputMode::CommandPreview {
+        pending: PendingCommandPreview::restore_checkpoint(preview),
+    });
+}
+
+/// Trims an op diff to the preview budget, noting how many lines were left out.
+fn summary_lines(summary: &str) -> Vec<String> {
+    let lines = summary
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return vec!["No operations since this checkpoint.".to_owned()];
+    }
+
+    let mut details = lines
+        .iter()
+        .take(SUMMARY_LINE_LIMIT)
+        .map(|line| (*line).to_owned())
+        .collect::<Vec<_>>();
+    if lines.len() > SUMMARY_LINE_LIMIT {
+        details.push(format!("… {} more lines", lines.len() - SUMMARY_LINE_LIMIT));
+    }
+    details
+}
+
+fn same_operation(left: &str, right: &str) -> bool {
+    let (shorter, longer) = if left.len() <= right.len() {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    shorter.len() >= SHORT_ID_LEN && longer.starts_with(shorter)
+}
+
+fn short_id(operation_id: &str) -> String {
+    operation_id.chars().take(SHORT_ID_LEN).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use jk_tui::operation_log_view::OperationLogView;
+
+    use super::*;
+    use crate::test_support::{SequencedRunner, log_app_view, output};
+
+    #[test]
+    fn checkpoints_replace_same_name_and_resolve_prefixes() {
+        let mut checkpoints = SessionCheckpoints::default();
+        checkpoints.record(SESSION_START_CHECKPOINT, "abcdef1234567890");
+        checkpoints.record("before agent", "0123456789abcdef");
+        checkpoints.record("before agent", "fedcba9876543210");
+
+        assert_eq!(
+            checkpoints
+                .latest()
+                .map(|checkpoint| checkpoint.name.as_str()),
+            Some("before agent")
+        );
+        assert_eq!(
+            checkpoints.name_for("abcdef123456"),
+            Some(SESSION_START_CHECKPOINT)
+        );
+        assert_eq!(checkpoints.name_for("0123456789abcdef"), None);
+        assert_eq!(checkpoints.name_for(""), None);
+        assert_eq!(checkpoints.name_for("f"), None);
+        assert_eq!(checkpoints.name_for("fedcba"), None);
+        assert_eq!(checkpoints.next_default_name(), "checkpoint 3");
+    }
+
+    #[test]
+    fn session_start_records_current_operation() {
+        let mut checkpoints = SessionCheckpoints::default();
+        let mut history = CommandHistory::new(4);
+
+        record_session_start_with_runner(
+            &mut checkpoints,
+            &mut history,
+            &JjOperation::default(),
+            SequencedRunner::successes(vec![output(0, "abcdef1234567890\n", "")]),
+        );
+
+        assert_eq!(
+            checkpoints.name_for("abcdef1234567890"),
+            Some(SESSION_START_CHECKPOINT)
+        );
+        assert_eq!(
+            history
+                .records()
+                .map(|record| record.source.action.clone())
+                .collect::<Vec<_>>(),
+            [SourceAction::Checkpoint]
+        );
+    }
+
+    #[test]
+    fn session_start_failure_leaves_no_checkpoint() {
+        let mut checkpoints = SessionCheckpoints::default();
+        let mut history = CommandHistory::new(4);
+
+        record_session_start_with_runner(
+            &mut checkpoints,
+            &mut history,
+            &JjOperation::default(),
+            SequencedRunner::successes(vec![output(1, "", "not a repo")]),
+        );
+
+        assert_eq!(checkpoints.latest(), None);
+    }
+
+    #[test]
+    fn empty_checkpoint_name_uses_default_and_reports_status() {
+        let mut state = AppState::new(log_app_view("abc"));
+        let AppView::Log(mut expected) = log_app_view("abc") else {
+            panic!("log fixture");
+        };
+        expected.show_status("checkpoint 'checkpoint 1' at abcdef123456");
+
+        record_checkpoint_with_runner(
+            &mut state,
+            &JjOperation::default(),
+            "  ",
+            SequencedRunner::successes(vec![output(0, "abcdef1234567890\n", "")]),
+        );
+
+        assert_eq!(
+            state.checkpoints.latest(),
+            Some(&SessionCheckpoint {
+                name: "checkpoint 1".to_owned(),
+                operation_id: "abcdef1234567890".to_owned(),
+            })
+        );
+        assert_eq!(state.views.active(), &AppView::Log(expected));
+    }
+
+    #[test]
+    fn checkpoint_failure_shows_on_the_operation_log() {
+        let mut state = AppState::new(AppView::OperationLog {
+            view: OperationLogView::default(),
+        });
+        let mut expected = OperationLogView::default();
+        expected.show_error("failed to record checkpoint: jj op command failed: not a repo");
+
+        record_checkpoint_with_runner(
+            &mut state,
+            &JjOperation::default(),
+            "before agent",
+            SequencedRunner::successes(vec![output(1, "", "not a repo")]),
+        );
+
+        assert_eq!(state.checkpoints.latest(), None);
+        assert_eq!(
+            state.views.active(),
+            &AppView::OperationLog { view: expected }
+        );
+    }
+
+    #[test]
+    fn restore_preview_summarizes_operations_since_checkpoint() {
+        let mut state = AppState::new(log_app_view("abc"));
+        state
+            .checkpoints
+            .record(SESSION_START_CHECKPOINT, "abcdef1234567890");
+        let summary = (1..=14)
+            .map(|index| format!("line {index}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        open_checkpoint_picker(&mut state);
+        handle_checkpoint_picker_mode_with_runner(
+            &mut state,
+            &JjOperation::default(),
+            &JjRecovery::default(),
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            SequencedRunner::successes(vec![output(0, &summary, "")]),
+        );
+
+        let Some(InputMode::CommandPreview { pending }) = state.modes.active() else {
+            panic!("restore preview opens");
+        };
+        assert_eq!(pending.source_action, SourceAction::RestoreCheckpoint);
+        assert_eq!(
+            pending.preview.spec.argv(),
+            ["op", "restore", "abcdef1234567890"]
+        );
+        assert_eq!(
+            pending.preview.details[0],
+            "Changes since checkpoint 'session start' (abcdef123456):"
+        );
+        assert_eq!(pending.preview.details[1], "line 1");
+        assert_eq!(
+            pending.preview.details.last().map(String::as_str),
+            Some("… 2 more lines")
+        );
+        assert_eq!(
+            state
+                .command_history()
+                .records()
+                .last()
+                .map(|record| record.source.action.clone()),
+            Some(SourceAction::RestoreCheckpoint)
+        );
+    }
+
+    #[test]
+    fn picker_restores_an_older_checkpoint() {
+        let mut state = AppState::new(log_app_view("abc"));
+        state
+            .checkpoints
+            .record(SESSION_START_CHECKPOINT, "abcdef1234567890");
+        state.checkpoints.record("before agent", "0123456789abcdef");
+
+        open_checkpoint_picker(&mut state);
+        assert_eq!(
+            checkpoint_picker_lines(&state.checkpoints, 0)[..2],
+            [
+                "> before agent       0123456789ab",
+                "  session start      abcdef123456"
+            ]
+        );
+        for code in [KeyCode::Char('j'), KeyCode::Enter] {
+            handle_checkpoint_picker_mode_with_runner(
+                &mut state,
+                &JjOperation::default(),
+                &JjRecovery::default(),
+                KeyEvent::new(code, KeyModifiers::NONE),
+                SequencedRunner::successes(vec![output(0, "", "")]),
+            );
+        }
+
+        let Some(InputMode::CommandPreview { pending }) = state.modes.active() else {
+            panic!("restore preview opens");
+        };
+        assert_eq!(
+            pending.preview.spec.argv(),
+            ["op", "restore", "abcdef1234567890"]
+        );
+        assert_eq!(
+            pending.preview.details[0],
+            "Changes since checkpoint 'session start' (abcdef123456):"
+        );
+    }
+
+    #[test]
+    fn picker_without_checkpoint_reports_error() {
+        let mut state = AppState::new(log_app_view("abc"));
+        let AppView::Log(mut expected) = log_app_view("abc") else {
+            panic!("log fixture");
+        };
+        expected.show_error("no session checkpoint recorded; press K to record one");
+
+        open_checkpoint_picker(&mut state);
+
+        assert_eq!(state.modes.active(), None);
+        assert_eq!(state.views.active(), &AppView::Log(expected));
+    }
+
+    #[test]
+    fn summary_of_unchanged_repository_says_so() {
+        assert_eq!(
+            summary_lines("\n\n"),
+            ["No operations since this checkpoint."]
+        );
+    }
+}
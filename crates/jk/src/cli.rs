@@ -3,11 +3,14 @@ use std::process::Command as ProcessCommand;
 
 use clap::{Parser, Subcommand};
 use jk_cli::{
-    DiffFormat, DiffQuery, JjAbandon, JjDescribe, JjDiff, JjEdit, JjEvolog, JjLog, JjLogCommand,
-    JjNew, JjOperation, JjRecovery, JjShow, JjStatus, JjWorkspaces, LogTemplateSelection,
-    ShowQuery, StatusQuery,
+    DiffFormat, DiffOptions, DiffQuery, DiffWhitespace, JjAbandon, JjDescribe, JjDiff, JjEdit,
+    JjEvolog, JjLog, JjLogCommand, JjNew, JjOperation, JjRecovery, JjShow, JjStatus, JjWorkspaces,
+    LogTemplateSelection, ShowQuery, StatusQuery,
 };
 
+/// Log entries loaded per page when no explicit `-n` limit is given.
+const LOG_PAGE_SIZE: usize = 200;
+
 /// Command-line options for the first log-oriented `jk` surface.
 #[derive(Debug, Parser)]
 #[command(version, about)]
@@ -39,7 +42,7 @@ impl Args {
     ///
     /// Bare `jk` intentionally starts from jj's configured default command, while `jk log` forces
     /// the explicit log command. The top-level limit applies to both forms unless the subcommand
-    /// provides a narrower value.
+    /// provides a narrower value. Without a limit the log loads in pages of [`LOG_PAGE_SIZE`].
     pub(crate) fn log_source(&self) -> JjLog {
         let (command, limit, template) = match &self.command {
             Some(Command::Log(log_args)) => (
@@ -63,6 +66,7 @@ impl Args {
         let source = JjLog::default()
             .with_command(command)
             .with_limit(limit)
+            .with_page_size(limit.is_none().then_some(LOG_PAGE_SIZE))
             .with_template(template);
         self.with_repository(source)
     }
@@ -390,6 +394,10 @@ pub struct DiffArgs {
     )]
     to: Option<String>,
 
+    /// Compare the two revisions with `jj interdiff --from FROM --to TO`.
+    #[arg(long, requires = "from")]
+    interdiff: bool,
+
     /// Render `jj diff --stat`.
     #[arg(long, conflicts_with_all = ["summary", "types", "name_only", "git", "color_words"])]
     stat: bool,
@@ -413,17 +421,44 @@ pub struct DiffArgs {
     /// Render `jj diff --color-words`.
     #[arg(long, conflicts_with_all = ["stat", "summary", "types", "name_only", "git"])]
     color_words: bool,
+
+    /// Render `jj diff --git` in old/new columns on wide terminals.
+    #[arg(long, conflicts_with_all = ["stat", "summary", "types", "name_only", "git", "color_words"])]
+    side_by_side: bool,
+
+    /// Lines of context around each change, passed as `jj diff --context`.
+    #[arg(long, value_name = "LINES")]
+    context: Option<usize>,
+
+    /// Render `jj diff --ignore-all-space`.
+    #[arg(short = 'w', long, conflicts_with = "ignore_space_change")]
+    ignore_all_space: bool,
+
+    /// Render `jj diff --ignore-space-change`.
+    #[arg(short = 'b', long)]
+    ignore_space_change: bool,
+
+    /// External diff formatter from `[merge-tools]`, passed as `jj diff --tool`.
+    #[arg(long, value_name = "TOOL")]
+    tool: Option<String>,
 }
 
 impl DiffArgs {
     pub(crate) fn query(&self) -> DiffQuery {
-        let format = self.format();
+        let options = self.options();
 
         if let (Some(from), Some(to)) = (&self.from, &self.to) {
+            if self.interdiff {
+                return DiffQuery::Interdiff {
+                    from: from.clone(),
+                    to: to.clone(),
+                    options,
+                };
+            }
             return DiffQuery::FromTo {
                 from: from.clone(),
                 to: to.clone(),
-                format,
+                options,
             };
         }
 
@@ -433,7 +468,21 @@ impl DiffArgs {
             .or(self.compatibility_revision.as_ref())
             .cloned()
             .unwrap_or_else(|| "@".to_owned());
-        DiffQuery::Revision { rev, format }
+        DiffQuery::Revision { rev, options }
+    }
+
+    fn options(&self) -> DiffOptions {
+        let whitespace = if self.ignore_all_space {
+            DiffWhitespace::IgnoreAll
+        } else if self.ignore_space_change {
+            DiffWhitespace::IgnoreChange
+        } else {
+            DiffWhitespace::Exact
+        };
+        DiffOptions::from(self.format())
+            .with_context(self.context)
+            .with_whitespace(whitespace)
+            .with_tool(self.tool.clone())
     }
 
     const fn format(&self) -> DiffFormat {
@@ -449,6 +498,8 @@ impl DiffArgs {
             DiffFormat::Git
         } else if self.color_words {
             DiffFormat::ColorWords
+        } else if self.side_by_side {
+            DiffFormat::SideBySide
         } else {
             DiffFormat::Patch
         }
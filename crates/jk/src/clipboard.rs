@@ -1,14 +1,157 @@
+//! Copying text to the clipboard.
+//!
+//! OSC 52 reaches the clipboard of the terminal the user is looking at, even over SSH, but many
+//! terminals and multiplexers drop it without a word. Every copy therefore also hands the text to
+//! the first system clipboard tool that works, and the status names that tool.
+
 use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// Bytes of escape sequence per chunk when passing OSC 52 through GNU screen, which truncates
+/// longer device control strings.
+const SCREEN_CHUNK: usize = 76;
+
+/// A way of putting text on the clipboard.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClipboardBackend {
+    Osc52,
+    WlCopy,
+    Xclip,
+    Xsel,
+    Pbcopy,
+}
+
+impl ClipboardBackend {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Osc52 => "OSC 52",
+            Self::WlCopy => "wl-copy",
+            Self::Xclip => "xclip",
+            Self::Xsel => "xsel",
+            Self::Pbcopy => "pbcopy",
+        }
+    }
+
+    const fn args(self) -> &'static [&'static str] {
+        match self {
+            Self::Osc52 | Self::WlCopy | Self::Pbcopy => &[],
+            Self::Xclip => &["-selection", "clipboard"],
+            Self::Xsel => &["--clipboard", "--input"],
+        }
+    }
+}
+
+/// The terminal multiplexer OSC 52 has to be smuggled through.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Multiplexer {
+    None,
+    Tmux,
+    Screen,
+}
 
 pub fn copy_command_line(command_line: &str) -> String {
-    match write_terminal_clipboard(command_line) {
-        Ok(()) => "copied command".to_owned(),
+    copy_text(command_line, "command")
+}
+
+/// A place copied text goes.
+pub trait Clipboard {
+    /// Copies `text` and returns the backend that took it.
+    fn copy(&mut self, text: &str) -> io::Result<ClipboardBackend>;
+}
+
+/// The terminal's OSC 52 clipboard plus the first system clipboard tool that works.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClipboard;
+
+impl Clipboard for SystemClipboard {
+    fn copy(&mut self, text: &str) -> io::Result<ClipboardBackend> {
+        copy_to_clipboard(text)
+    }
+}
+
+/// Copies `text` and returns a status naming what was copied as `label` and how.
+pub fn copy_text(text: &str, label: &str) -> String {
+    copy_text_with(&mut SystemClipboard, text, label)
+}
+
+/// Copies `text` through `clipboard`, returning the same status as [`copy_text`].
+pub fn copy_text_with(clipboard: &mut impl Clipboard, text: &str, label: &str) -> String {
+    match clipboard.copy(text) {
+        Ok(backend) => format!("copied {label} via {}", backend.name()),
         Err(error) => format!("copy failed: {error}"),
     }
 }
 
-fn write_terminal_clipboard(text: &str) -> io::Result<()> {
-    let sequence = osc52_sequence(text);
+/// Writes `text` as OSC 52, then to the first system tool that succeeds, and returns the backend
+/// to report: the tool, or OSC 52 when no tool worked.
+///
+/// OSC 52 cannot confirm delivery, so it is always sent and only reported as a last resort.
+fn copy_to_clipboard(text: &str) -> io::Result<ClipboardBackend> {
+    let env = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+    let osc52 = write_terminal_clipboard(text, multiplexer(env));
+    for backend in system_backends(env) {
+        match run_clipboard_tool(backend, text) {
+            Ok(()) => return Ok(backend),
+            Err(error) => tracing::debug!(%error, tool = backend.name(), "clipboard tool failed"),
+        }
+    }
+    osc52.map(|()| ClipboardBackend::Osc52)
+}
+
+/// Returns the system clipboard tools worth trying in this environment, in preference order.
+fn system_backends(env: impl Fn(&str) -> Option<String>) -> Vec<ClipboardBackend> {
+    let mut backends = Vec::new();
+    if env("WAYLAND_DISPLAY").is_some() {
+        backends.push(ClipboardBackend::WlCopy);
+    }
+    if env("DISPLAY").is_some() {
+        backends.extend([ClipboardBackend::Xclip, ClipboardBackend::Xsel]);
+    }
+    if cfg!(target_os = "macos") {
+        backends.push(ClipboardBackend::Pbcopy);
+    }
+    backends
+}
+
+/// Pipes `text` into the tool behind `backend`.
+///
+/// Output is discarded rather than captured: `xclip` and `wl-copy` leave a child serving the
+/// selection, and waiting for its output would block until something else is copied.
+fn run_clipboard_tool(backend: ClipboardBackend, text: &str) -> io::Result<()> {
+    let mut child = Command::new(backend.name())
+        .args(backend.args())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let written = child
+        .stdin
+        .take()
+        .map_or(Ok(()), |mut stdin| stdin.write_all(text.as_bytes()));
+    let status = child.wait()?;
+    written?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "{} exited with {status}",
+            backend.name()
+        )))
+    }
+}
+
+fn multiplexer(env: impl Fn(&str) -> Option<String>) -> Multiplexer {
+    if env("TMUX").is_some() {
+        Multiplexer::Tmux
+    } else if env("STY").is_some() || env("TERM").is_some_and(|term| term.starts_with("screen")) {
+        Multiplexer::Screen
+    } else {
+        Multiplexer::None
+    }
+}
+
+fn write_terminal_clipboard(text: &str, multiplexer: Multiplexer) -> io::Result<()> {
+    let sequence = passthrough(&osc52_sequence(text), multiplexer);
     let mut stdout = io::stdout();
     stdout.write_all(sequence.as_bytes())?;
     stdout.flush()
@@ -18,6 +161,24 @@ fn osc52_sequence(text: &str) -> String {
     format!("\u{1b}]52;c;{}\u{7}", base64_encode(text.as_bytes()))
 }
 
+/// Wraps `sequence` in the device control string a multiplexer forwards to the outer terminal.
+///
+/// tmux needs `allow-passthrough` and doubled escapes; screen forwards short chunks.
+fn passthrough(sequence: &str, multiplexer: Multiplexer) -> String {
+    match multiplexer {
+        Multiplexer::None => sequence.to_owned(),
+        Multiplexer::Tmux => format!(
+            "\u{1b}Ptmux;{}\u{1b}\\",
+            sequence.replace('\u{1b}', "\u{1b}\u{1b}")
+        ),
+        Multiplexer::Screen => sequence
+            .as_bytes()
+            .chunks(SCREEN_CHUNK)
+            .map(|chunk| format!("\u{1b}P{}\u{1b}\\", String::from_utf8_lossy(chunk)))
+            .collect(),
+    }
+}
+
 fn base64_encode(bytes: &[u8]) -> String {
     const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
     let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
@@ -49,6 +210,18 @@ fn base64_encode(bytes: &[u8]) -> String {
 mod tests {
     use super::*;
 
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars = vars
+            .iter()
+            .map(|(name, value)| ((*name).to_owned(), (*value).to_owned()))
+            .collect::<Vec<_>>();
+        move |name| {
+            vars.iter()
+                .find(|(var, _)| var == name)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
     #[test]
     fn base64_encode_handles_padding() {
         assert_eq!(base64_encode(b""), "");
@@ -61,4 +234,53 @@ mod tests {
     fn osc52_sequence_wraps_encoded_clipboard_payload() {
         assert_eq!(osc52_sequence("jj undo"), "\u{1b}]52;c;amogdW5kbw==\u{7}");
     }
+
+    #[test]
+    fn multiplexers_get_passthrough_wrapping() {
+        let sequence = osc52_sequence("jj undo");
+
+        assert_eq!(passthrough(&sequence, Multiplexer::None), sequence);
+        assert_eq!(
+            passthrough(&sequence, Multiplexer::Tmux),
+            "\u{1b}Ptmux;\u{1b}\u{1b}]52;c;amogdW5kbw==\u{7}\u{1b}\\"
+        );
+        let long = osc52_sequence(&"x".repeat(100));
+        let screen = passthrough(&long, Multiplexer::Screen);
+        assert_eq!(
+            screen.matches("\u{1b}P").count(),
+            long.len().div_ceil(SCREEN_CHUNK)
+        );
+        assert_eq!(screen.replace("\u{1b}P", "").replace("\u{1b}\\", ""), long);
+    }
+
+    #[test]
+    fn multiplexer_prefers_tmux_over_its_screen_term() {
+        assert_eq!(
+            multiplexer(env(&[("TMUX", "/tmp/tmux"), ("TERM", "screen-256color")])),
+            Multiplexer::Tmux
+        );
+        assert_eq!(multiplexer(env(&[("TERM", "screen")])), Multiplexer::Screen);
+        assert_eq!(
+            multiplexer(env(&[("TERM", "xterm-256color")])),
+            Multiplexer::None
+        );
+    }
+
+    #[test]
+    fn system_backends_follow_the_display_server() {
+        let macos = cfg!(target_os = "macos").then_some(ClipboardBackend::Pbcopy);
+        let expected = |backends: &[ClipboardBackend]| {
+            backends.iter().copied().chain(macos).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            system_backends(env(&[("WAYLAND_DISPLAY", "wayland-0"), ("DISPLAY", ":0")])),
+            expected(&[
+                ClipboardBackend::WlCopy,
+                ClipboardBackend::Xclip,
+                ClipboardBackend::Xsel
+            ])
+        );
+        assert_eq!(system_backends(env(&[])), expected(&[]));
+    }
 }
@@ -115,20 +115,27 @@ pub fn open_operation_log_from_with_runner<R: JjCommandRunner>(
     runner: R,
 ) {
     if matches!(state.views.active(), AppView::OperationLog { .. }) {
-        let AppState { views, history, .. } = state;
+        let AppState {
+            views,
+            history,
+            checkpoints,
+            ..
+        } = state;
         if let AppView::OperationLog { view } = views.active_mut() {
-            refresh_operation_log(view, history, operation_source);
+            refresh_operation_log(view, history, checkpoints, operation_source);
         }
         return;
     }
 
     let mut runner = RecordingJjCommandRunner::new(runner, &mut state.history, source);
-    let query = OperationQuery::log();
-    match operation_source.load_query_with_runner(&query, &mut runner) {
+    let result = operation_source.load_log_with_runner(&mut runner);
+    drop(runner);
+    match result {
         Ok(snapshot) => {
             let view = jk_tui::operation_log_view::OperationLogView::new(operation_log_snapshot(
-                snapshot.title(),
-                snapshot.rendered(),
+                &snapshot,
+                &state.history,
+                &state.checkpoints,
             ));
             state.views.push(AppView::OperationLog { view });
         }
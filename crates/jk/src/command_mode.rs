@@ -22,7 +22,7 @@ pub fn jj_command_lines(input: &str, error: Option<&str>) -> Vec<String> {
         lines.push(format!("error: {error}"));
     }
     lines.push(String::new());
-    lines.push("enter run   Ctrl-u clear   backspace edit   esc cancel".to_owned());
+    lines.push("enter run   tab complete   Ctrl-u clear   backspace edit   esc cancel".to_owned());
     lines
 }
 
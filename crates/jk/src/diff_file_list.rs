@@ -0,0 +1,757 @@
+//! Diff file list overlay: a foldable directory tree with fuzzy filtering and multi-select.
+//!
+//! The list is built from the file sections the diff view already parsed, so opening it runs no
+//! `jj` command. Directories that only contain one subdirectory are merged into one row, and every
+//! directory row sums the `DiffFileStat` counts of the files below it. Typing filters files with
+//! the revision finder's subsequence match and folds non-matching files in the diff body too; the
+//! query stays on the diff view after the list closes so reopening shows the same files. Marked
+//! files, or the selected row when nothing is marked, feed restore and squash previews and copy.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use jk_cli::{DiffQuery, JjRestore, JjSquash, RestoreQuery, SquashQuery};
+use jk_tui::diff_view::{DiffView, FileMatcher};
+
+use crate::clipboard::copy_text;
+use crate::mutation_preview::PendingCommandPreview;
+use crate::revision_finder::fuzzy_score;
+use crate::state::{AppState, AppView, InputMode, InputModeResult};
+
+const FILE_LIST_ROWS: usize = 16;
+
+/// One visible row of the file tree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum FileRow {
+    /// A directory, possibly merged with its only subdirectories.
+    Directory {
+        path: String,
+        label: String,
+        depth: usize,
+        files: Vec<usize>,
+    },
+    /// A file section, by index into the diff view's files.
+    File { index: usize, depth: usize },
+}
+
+impl FileRow {
+    fn files(&self) -> Vec<usize> {
+        match self {
+            Self::Directory { files, .. } => files.clone(),
+            Self::File { index, .. } => vec![*index],
+        }
+    }
+}
+
+/// Directory tree node used while laying out rows.
+#[derive(Default)]
+struct DirectoryNode<'a> {
+    directories: BTreeMap<&'a str, Self>,
+    files: Vec<usize>,
+}
+
+impl DirectoryNode<'_> {
+    fn all_files(&self) -> Vec<usize> {
+        let mut files = self
+            .directories
+            .values()
+            .flat_map(DirectoryNode::all_files)
+            .collect::<Vec<_>>();
+        files.extend(&self.files);
+        files
+    }
+}
+
+/// File tree, filter and marks for the open file list.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiffFileList {
+    paths: Vec<String>,
+    stats: Vec<Option<(usize, usize)>>,
+    query: String,
+    matches: Vec<usize>,
+    collapsed: BTreeSet<String>,
+    marked: BTreeSet<usize>,
+    rows: Vec<FileRow>,
+    selected: usize,
+}
+
+impl DiffFileList {
+    fn new(view: &DiffView) -> Self {
+        let paths = view
+            .file_paths()
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+        let stats = paths
+            .iter()
+            .map(|path| {
+                view.file_stats()
+                    .iter()
+                    .find(|stat| stat.path() == path)
+                    .map(|stat| (stat.added(), stat.removed()))
+            })
+            .collect();
+        let mut list = Self {
+            paths,
+            stats,
+            query: view.file_filter_query().unwrap_or_default().to_owned(),
+            matches: Vec::new(),
+            collapsed: BTreeSet::new(),
+            marked: BTreeSet::new(),
+            rows: Vec::new(),
+            selected: 0,
+        };
+        list.refilter();
+        if let Some(index) = view.selected_file_index() {
+            list.select_file(index);
+        }
+        list
+    }
+
+    /// Recomputes matching files from the query and selects the first file row.
+    fn refilter(&mut self) {
+        self.matches = (0..self.paths.len())
+            .filter(|index| path_matches(&self.paths[*index], &self.query))
+            .collect();
+        self.rebuild_rows();
+        self.selected = self
+            .rows
+            .iter()
+            .position(|row| matches!(row, FileRow::File { .. }))
+            .unwrap_or_default();
+    }
+
+    fn rebuild_rows(&mut self) {
+        let mut root = DirectoryNode::default();
+        for index in &self.matches {
+            let mut node = &mut root;
+            let mut components = self.paths[*index].split('/').peekable();
+            while let Some(component) = components.next() {
+                if components.peek().is_none() {
+                    node.files.push(*index);
+                } else {
+                    node = node.directories.entry(component).or_default();
+                }
+            }
+        }
+        let mut rows = Vec::new();
+        self.push_rows(&root, "", 0, &mut rows);
+        self.rows = rows;
+    }
+
+    fn push_rows(
+        &self,
+        node: &DirectoryNode<'_>,
+        parent: &str,
+        depth: usize,
+        rows: &mut Vec<FileRow>,
+    ) {
+        for (name, mut child) in &node.directories {
+            let mut label = (*name).to_owned();
+            while child.files.is_empty() && child.directories.len() == 1 {
+                let Some((name, only)) = child.directories.iter().next() else {
+                    break;
+                };
+                label = format!("{label}/{name}");
+                child = only;
+            }
+            let path = if parent.is_empty() {
+                label.clone()
+            } else {
+                format!("{parent}/{label}")
+            };
+            rows.push(FileRow::Directory {
+                path: path.clone(),
+                label,
+                depth,
+                files: child.all_files(),
+            });
+            // Filtering shows every match, so folds only apply to the full tree.
+            if self.query.is_empty() && self.collapsed.contains(&path) {
+                continue;
+            }
+            self.push_rows(child, &path, depth + 1, rows);
+        }
+        rows.extend(node.files.iter().map(|index| FileRow::File {
+            index: *index,
+            depth,
+        }));
+    }
+
+    fn select_file(&mut self, index: usize) {
+        if let Some(position) = self.rows.iter().position(
+            |row| matches!(row, FileRow::File { index: row_index, .. } if *row_index == index),
+        ) {
+            self.selected = position;
+        }
+    }
+
+    fn select_directory(&mut self, path: &str) {
+        if let Some(position) = self.rows.iter().position(
+            |row| matches!(row, FileRow::Directory { path: row_path, .. } if row_path == path),
+        ) {
+            self.selected = position;
+        }
+    }
+
+    fn selected_row(&self) -> Option<&FileRow> {
+        self.rows.get(self.selected)
+    }
+
+    fn move_selection(&mut self, forward: bool) {
+        let count = self.rows.len();
+        if count == 0 {
+            return;
+        }
+        self.selected = if forward {
+            (self.selected + 1) % count
+        } else {
+            (self.selected + count - 1) % count
+        };
+    }
+
+    /// Folds or unfolds the selected directory; `None` toggles.
+    fn fold_selected(&mut self, fold: Option<bool>) {
+        let Some(FileRow::Directory { path, .. }) = self.selected_row() else {
+            return;
+        };
+        let path = path.clone();
+        let fold = fold.unwrap_or_else(|| !self.collapsed.contains(&path));
+        if fold {
+            self.collapsed.insert(path.clone());
+        } else {
+            self.collapsed.remove(&path);
+        }
+        self.rebuild_rows();
+        self.select_directory(&path);
+    }
+
+    /// Moves from a file to the directory row that holds it.
+    fn select_parent(&mut self) {
+        let Some(FileRow::File { depth, .. }) = self.selected_row() else {
+            return;
+        };
+        let depth = *depth;
+        if let Some(position) = self.rows[..self.selected].iter().rposition(
+            |row| matches!(row, FileRow::Directory { depth: row_depth, .. } if *row_depth < depth),
+        ) {
+            self.selected = position;
+        }
+    }
+
+    /// Marks the selected row's files, or unmarks them when all are already marked.
+    fn toggle_mark(&mut self) {
+        let Some(files) = self.selected_row().map(FileRow::files) else {
+            return;
+        };
+        if files.iter().all(|index| self.marked.contains(index)) {
+            for index in &files {
+                self.marked.remove(index);
+            }
+        } else {
+            self.marked.extend(files);
+        }
+    }
+
+    /// Returns the marked paths, or the selected row's paths when nothing is marked.
+    fn target_paths(&self) -> Vec<String> {
+        let files = if self.marked.is_empty() {
+            self.selected_row().map(FileRow::files).unwrap_or_default()
+        } else {
+            self.marked.iter().copied().collect()
+        };
+        files
+            .into_iter()
+            .map(|index| self.paths[index].clone())
+            .collect()
+    }
+
+    /// Returns the filter to apply to the diff body, which re-matches files on refresh.
+    fn body_filter(&self) -> Option<(String, FileMatcher)> {
+        if self.query.trim().is_empty() {
+            return None;
+        }
+        Some((self.query.clone(), path_matches))
+    }
+
+    fn stat_total(&self, files: &[usize]) -> Option<(usize, usize)> {
+        files.iter().filter_map(|index| self.stats[*index]).reduce(
+            |(added, removed), (more_added, more_removed)| {
+                (added + more_added, removed + more_removed)
+            },
+        )
+    }
+
+    fn row_line(&self, position: usize, row: &FileRow) -> String {
+        let marker = if position == self.selected { ">" } else { " " };
+        let files = row.files();
+        let mark = if files.iter().all(|index| self.marked.contains(index)) {
+            "*"
+        } else if files.iter().any(|index| self.marked.contains(index)) {
+            "-"
+        } else {
+            " "
+        };
+        let mut line = match row {
+            FileRow::Directory {
+                path, label, depth, ..
+            } => {
+                let fold = if self.query.is_empty() && self.collapsed.contains(path) {
+                    "▸"
+                } else {
+                    "▾"
+                };
+                format!("{marker}{mark} {}{fold} {label}/", "  ".repeat(*depth))
+            }
+            FileRow::File { index, depth } => {
+                let path = &self.paths[*index];
+                let name = path.rsplit('/').next().unwrap_or(path);
+                format!("{marker}{mark} {}  {name}", "  ".repeat(*depth))
+            }
+        };
+        if let Some((added, removed)) = self.stat_total(&files) {
+            line.push_str(&format!("  +{added} -{removed}"));
+        }
+        line
+    }
+}
+
+/// Returns whether `path` fuzzy-matches the file list `query`; an empty query matches everything.
+fn path_matches(path: &str, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    query.is_empty() || fuzzy_score(&path.to_lowercase(), &query).is_some()
+}
+
+/// Opens the file list over the active diff.
+pub fn open_diff_file_list(state: &mut AppState) {
+    let AppView::Diff { view, .. } = state.views.active() else {
+        return;
+    };
+    state.modes.push(InputMode::DiffFileList {
+        list: DiffFileList::new(view),
+    });
+}
+
+/// Handles key input while the diff file list is open.
+pub fn handle_diff_file_list_mode(
+    state: &mut AppState,
+    repository: Option<&Path>,
+    key: KeyEvent,
+) -> InputModeResult {
+    let Some(InputMode::DiffFileList { list }) = state.modes.active_mut() else {
+        return InputModeResult::Unhandled;
+    };
+    let mut query_changed = false;
+    match key {
+        KeyEvent {
+            code: KeyCode::Esc, ..
+        } => {
+            state.modes.pop();
+            return InputModeResult::Handled;
+        }
+        KeyEvent {
+            code: KeyCode::Enter,
+            ..
+        } => {
+            if let Some(FileRow::File { index, .. }) = list.selected_row() {
+                let index = *index;
+                state.modes.pop();
+                if let AppView::Diff { view, .. } = state.views.active_mut() {
+                    view.select_file_index(index);
+                }
+                return InputModeResult::Handled;
+            }
+            list.fold_selected(None);
+        }
+        KeyEvent {
+            code: KeyCode::Up, ..
+        } => list.move_selection(false),
+        KeyEvent {
+            code: KeyCode::Down,
+            ..
+        } => list.move_selection(true),
+        KeyEvent {
+            code: KeyCode::Left,
+            ..
+        } => {
+            if matches!(list.selected_row(), Some(FileRow::File { .. })) {
+                list.select_parent();
+            } else {
+                list.fold_selected(Some(true));
+            }
+        }
+        KeyEvent {
+            code: KeyCode::Right,
+            ..
+        } => list.fold_selected(Some(false)),
+        KeyEvent {
+            code: KeyCode::Tab, ..
+        } => list.toggle_mark(),
+        KeyEvent {
+            code: KeyCode::Char(character),
+            modifiers: KeyModifiers::CONTROL,
+            ..
+        } => match character {
+            'p' => list.move_selection(false),
+            'n' => list.move_selection(true),
+            'u' => {
+                list.query.clear();
+                query_changed = true;
+            }
+            'r' => open_restore_preview(state, repository),
+            's' => open_squash_preview(state, repository),
+            'y' => copy_selected_paths(state),
+            _ => {}
+        },
+        KeyEvent {
+            code: KeyCode::Backspace,
+            ..
+        } => {
+            query_changed = list.query.pop().is_some();
+        }
+        KeyEvent {
+            code: KeyCode::Char(character),
+            modifiers,
+            ..
+        } if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+            list.query.push(character);
+            query_changed = true;
+        }
+        _ => {}
+    }
+    if query_changed {
+        apply_query(state);
+    }
+    InputModeResult::Handled
+}
+
+/// Refilters the list and folds the diff body to match.
+fn apply_query(state: &mut AppState) {
+    let Some(InputMode::DiffFileList { list }) = state.modes.active_mut() else {
+        return;
+    };
+    list.refilter();
+    let filter = list.body_filter();
+    if let AppView::Diff { view, .. } = state.views.active_mut() {
+        view.set_file_filter(filter);
+    }
+}
+
+/// Replaces the list with a `jj restore` preview for the target paths.
+fn open_restore_preview(state: &mut AppState, repository: Option<&Path>) {
+    let Some((paths, query)) = list_targets(state) else {
+        return;
+    };
+    let restore = match &query {
+        DiffQuery::Revision { rev, .. } => RestoreQuery::changes_in(rev.clone(), paths),
+        DiffQuery::FromTo { from, to, .. } => {
+            RestoreQuery::from_into(from.clone(), to.clone(), paths)
+        }
+        DiffQuery::Interdiff { .. } => {
+            close_with_status(state, "restore is not available for an interdiff");
+            return;
+        }
+    };
+    let mut source = JjRestore::default();
+    if let Some(repository) = repository {
+        source = source.with_repository(repository);
+    }
+    let preview = source.spec_for(&restore).command_preview();
+    state.modes.pop();
+    state.modes.push(InputMode::CommandPreview {
+        pending: PendingCommandPreview::restore(preview),
+    });
+}
+
+/// Replaces the list with a `jj squash` preview moving the target paths into the parent.
+fn open_squash_preview(state: &mut AppState, repository: Option<&Path>) {
+    let Some((paths, query)) = list_targets(state) else {
+        return;
+    };
+    let DiffQuery::Revision { rev, .. } = &query else {
+        close_with_status(state, "squash needs a single-revision diff");
+        return;
+    };
+    let mut source = JjSquash::default();
+    if let Some(repository) = repository {
+        source = source.with_repository(repository);
+    }
+    let preview = source
+        .spec_for(&SquashQuery::new(rev.clone(), format!("{rev}-")).with_paths(paths))
+        .command_preview();
+    state.modes.pop();
+    state.modes.push(InputMode::CommandPreview {
+        pending: PendingCommandPreview::squash(preview),
+    });
+}
+
+/// Copies the target paths, one per line, and closes the list.
+fn copy_selected_paths(state: &mut AppState) {
+    let Some((paths, _)) = list_targets(state) else {
+        return;
+    };
+    let label = if paths.len() == 1 {
+        "path".to_owned()
+    } else {
+        format!("{} paths", paths.len())
+    };
+    let status = copy_text(&paths.join("\n"), &label);
+    close_with_status(state, status);
+}
+
+fn list_targets(state: &AppState) -> Option<(Vec<String>, DiffQuery)> {
+    let Some(InputMode::DiffFileList { list }) = state.modes.active() else {
+        return None;
+    };
+    let AppView::Diff { query, .. } = state.views.active() else {
+        return None;
+    };
+    let paths = list.target_paths();
+    (!paths.is_empty()).then(|| (paths, query.clone()))
+}
+
+fn close_with_status(state: &mut AppState, status: impl Into<String>) {
+    state.modes.pop();
+    if let AppView::Diff { view, .. } = state.views.active_mut() {
+        view.show_status(status);
+    }
+}
+
+/// Returns overlay lines: the filter, the visible window of rows, and a footer.
+pub fn diff_file_list_lines(list: &DiffFileList) -> Vec<String> {
+    let mut lines = vec![format!("filter {}", list.query), String::new()];
+    if list.paths.is_empty() {
+        lines.push("  no files in this diff".to_owned());
+    } else if list.rows.is_empty() {
+        lines.push("  no matching files".to_owned());
+    }
+    let first = list
+        .selected
+        .saturating_sub(FILE_LIST_ROWS - 1)
+        .min(list.rows.len().saturating_sub(FILE_LIST_ROWS));
+    for (position, row) in list
+        .rows
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(FILE_LIST_ROWS)
+    {
+        lines.push(list.row_line(position, row));
+    }
+    lines.push(String::new());
+    let mut summary = format!("{}/{} files", list.matches.len(), list.paths.len());
+    if !list.marked.is_empty() {
+        summary.push_str(&format!("   {} marked", list.marked.len()));
+    }
+    lines.push(summary);
+    lines.push("↑/↓ move   enter jump or fold   ←/→ fold   tab mark   esc close".to_owned());
+    lines.push("Ctrl-r restore   Ctrl-s squash into parent   Ctrl-y copy paths".to_owned());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use jk_core::{DiffFileStat, DiffSnapshot, SourceAction};
+
+    use super::*;
+    use crate::test_support::diff_query;
+
+    fn tree_view() -> DiffView {
+        DiffView::new(
+            DiffSnapshot::new(
+                "aaa",
+                concat!(
+                    "Modified regular file crates/jk/src/main.rs:\n m\n",
+                    "Modified regular file crates/jk/src/state.rs:\n s\n",
+                    "Modified regular file docs/guide.md:\n g\n",
+                    "Modified regular file README.md:\n r\n",
+                ),
+            )
+            .with_file_stats(vec![
+                DiffFileStat::new("crates/jk/src/main.rs", 10, 2),
+                DiffFileStat::new("crates/jk/src/state.rs", 3, 1),
+                DiffFileStat::new("docs/guide.md", 4, 0),
+                DiffFileStat::new("README.md", 1, 1),
+            ]),
+        )
+    }
+
+    fn diff_state() -> AppState {
+        AppState::new(AppView::Diff {
+            view: tree_view(),
+            query: diff_query("aaa"),
+        })
+    }
+
+    fn press(state: &mut AppState, code: KeyCode, modifiers: KeyModifiers) {
+        let _ = handle_diff_file_list_mode(state, None, KeyEvent::new(code, modifiers));
+    }
+
+    fn type_query(state: &mut AppState, query: &str) {
+        for character in query.chars() {
+            press(state, KeyCode::Char(character), KeyModifiers::NONE);
+        }
+    }
+
+    fn list(state: &AppState) -> &DiffFileList {
+        match state.modes.active() {
+            Some(InputMode::DiffFileList { list }) => list,
+            other => panic!("expected diff file list, got {other:?}"),
+        }
+    }
+
+    fn rows(state: &AppState) -> Vec<String> {
+        let lines = diff_file_list_lines(list(state));
+        lines[2..lines.len() - 4].to_vec()
+    }
+
+    #[test]
+    fn tree_merges_single_child_directories_and_sums_stats() {
+        let mut state = diff_state();
+        open_diff_file_list(&mut state);
+
+        assert_eq!(
+            rows(&state),
+            [
+                "   ▾ crates/jk/src/  +13 -3",
+                ">      main.rs  +10 -2",
+                "       state.rs  +3 -1",
+                "   ▾ docs/  +4 -0",
+                "       guide.md  +4 -0",
+                "     README.md  +1 -1",
+            ]
+        );
+
+        press(&mut state, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(rows(&state)[0], ">  ▸ crates/jk/src/  +13 -3");
+        assert_eq!(rows(&state)[1], "   ▾ docs/  +4 -0");
+
+        press(&mut state, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(rows(&state)[1], "       main.rs  +10 -2");
+    }
+
+    #[test]
+    fn filter_folds_non_matching_files_in_the_diff_body_and_persists() {
+        let mut state = diff_state();
+        open_diff_file_list(&mut state);
+
+        type_query(&mut state, "stat");
+        assert_eq!(
+            rows(&state),
+            ["   ▾ crates/jk/src/  +3 -1", ">      state.rs  +3 -1"]
+        );
+        press(&mut state, KeyCode::Esc, KeyModifiers::NONE);
+
+        let AppView::Diff { view, .. } = state.views.active() else {
+            panic!("diff stays active");
+        };
+        assert_eq!(view.file_filter_query(), Some("stat"));
+
+        open_diff_file_list(&mut state);
+        assert_eq!(list(&state).query, "stat");
+        assert_eq!(list(&state).matches, [1]);
+
+        press(&mut state, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        let AppView::Diff { view, .. } = state.views.active() else {
+            panic!("diff stays active");
+        };
+        assert_eq!(view.file_filter_query(), None);
+    }
+
+    #[test]
+    fn diff_file_list_navigation_wraps_between_edges() {
+        let mut state = diff_state();
+        open_diff_file_list(&mut state);
+        type_query(&mut state, "rs");
+        assert_eq!(
+            list(&state).selected,
+            1,
+            "the first file row starts selected"
+        );
+
+        press(&mut state, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(
+            list(&state).selected,
+            2,
+            "moving up from the top wraps to the last file"
+        );
+        assert_eq!(rows(&state)[2], ">      state.rs  +3 -1");
+
+        press(&mut state, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(
+            list(&state).selected,
+            0,
+            "moving down from the bottom wraps to the top"
+        );
+    }
+
+    #[test]
+    fn enter_on_a_file_jumps_to_its_section() {
+        let mut state = diff_state();
+        open_diff_file_list(&mut state);
+        type_query(&mut state, "guide");
+
+        press(&mut state, KeyCode::Enter, KeyModifiers::NONE);
+
+        assert_eq!(state.modes.active(), None);
+        let AppView::Diff { view, .. } = state.views.active() else {
+            panic!("diff stays active");
+        };
+        assert_eq!(view.selected_file_index(), Some(2));
+    }
+
+    #[test]
+    fn marked_files_feed_restore_and_squash_previews() {
+        let mut state = diff_state();
+        open_diff_file_list(&mut state);
+        press(&mut state, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(rows(&state)[0], ">* ▾ crates/jk/src/  +13 -3");
+        press(&mut state, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Tab, KeyModifiers::NONE);
+        assert!(diff_file_list_lines(list(&state)).contains(&"4/4 files   3 marked".to_owned()));
+
+        press(&mut state, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        let Some(InputMode::CommandPreview { pending }) = state.modes.active() else {
+            panic!("restore preview should open");
+        };
+        assert_eq!(pending.source_action, SourceAction::RestorePaths);
+        assert_eq!(
+            pending.preview.title,
+            "jj restore --changes-in aaa crates/jk/src/main.rs crates/jk/src/state.rs README.md"
+        );
+
+        state.modes.pop();
+        open_diff_file_list(&mut state);
+        type_query(&mut state, "guide");
+        press(&mut state, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        let Some(InputMode::CommandPreview { pending }) = state.modes.active() else {
+            panic!("squash preview should open");
+        };
+        assert_eq!(pending.source_action, SourceAction::SquashRevision);
+        assert_eq!(
+            pending.preview.title,
+            "jj squash --from aaa --into aaa- docs/guide.md"
+        );
+    }
+
+    #[test]
+    fn squash_is_refused_for_a_range_diff() {
+        let mut state = AppState::new(AppView::Diff {
+            view: tree_view(),
+            query: DiffQuery::FromTo {
+                from: "aaa".to_owned(),
+                to: "bbb".to_owned(),
+                options: jk_cli::DiffOptions::default(),
+            },
+        });
+        open_diff_file_list(&mut state);
+
+        press(&mut state, KeyCode::Char('s'), KeyModifiers::CONTROL);
+
+        assert_eq!(state.modes.active(), None);
+    }
+}
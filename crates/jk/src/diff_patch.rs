@@ -0,0 +1,365 @@
+//! Copying and saving the active diff as a git patch.
+//!
+//! `y` in a diff opens a small menu. Whatever format the view renders, the patch is loaded again
+//! with `--git` and cut down to the hunk or file at the top line, so the copy applies with
+//! `git apply` or pastes cleanly into an agent prompt.
+
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use jk_cli::{DiffQuery, JjCommandRunner, JjDiff, RecordingJjCommandRunner, SystemJjCommandRunner};
+use jk_core::{CommandSource, SourceAction, SourceView};
+use jk_tui::diff_view::{DiffView, GitPatch};
+
+use crate::clipboard::{Clipboard, SystemClipboard, copy_text_with};
+use crate::review_notes::{temp_export_path, write_export};
+use crate::state::{AppState, AppView, InputMode, InputModeResult};
+
+/// How much of the diff the patch menu copies or saves, and in what shape.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PatchScope {
+    Hunk,
+    File,
+    Diff,
+    HunkMarkdown,
+}
+
+impl PatchScope {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Hunk => "hunk patch",
+            Self::File => "file patch",
+            Self::Diff => "diff patch",
+            Self::HunkMarkdown => "hunk as markdown",
+        }
+    }
+
+    const fn key(self) -> &'static str {
+        match self {
+            Self::Hunk => "y h",
+            Self::File => "y f",
+            Self::Diff => "y a",
+            Self::HunkMarkdown => "y m",
+        }
+    }
+}
+
+/// Opens the copy/save patch menu for the active diff.
+pub fn open_diff_patch_menu(state: &mut AppState) {
+    if matches!(state.views.active(), AppView::Diff { .. }) {
+        state.modes.push(InputMode::DiffPatch);
+    }
+}
+
+/// Returns the patch menu lines, naming what the top line points at.
+pub fn diff_patch_lines(view: &DiffView) -> Vec<String> {
+    let at = match view.cursor_location() {
+        Some((path, Some(line))) => format!("At: {path}:{line}"),
+        Some((path, None)) => format!("At: {path}"),
+        None => "At: no file".to_owned(),
+    };
+    vec![
+        at,
+        String::new(),
+        "h copy hunk   f copy file   a copy whole diff".to_owned(),
+        "m copy hunk as markdown   s save whole diff as .patch".to_owned(),
+        "esc cancel".to_owned(),
+    ]
+}
+
+/// Returns the save prompt lines.
+pub fn diff_patch_save_lines(path: &str) -> Vec<String> {
+    vec![
+        "Whole diff as a git patch".to_owned(),
+        format!("Write to: {path}"),
+        String::new(),
+        "enter write file   Ctrl-u clear   esc cancel".to_owned(),
+    ]
+}
+
+/// Handles keys while the patch menu is open.
+pub fn handle_diff_patch_mode(
+    state: &mut AppState,
+    diff_source: &JjDiff,
+    key: KeyEvent,
+) -> InputModeResult {
+    handle_diff_patch_mode_with(
+        state,
+        diff_source,
+        key,
+        &mut SystemClipboard,
+        SystemJjCommandRunner,
+    )
+}
+
+fn handle_diff_patch_mode_with<R: JjCommandRunner>(
+    state: &mut AppState,
+    diff_source: &JjDiff,
+    key: KeyEvent,
+    clipboard: &mut impl Clipboard,
+    runner: R,
+) -> InputModeResult {
+    let scope = match key.code {
+        KeyCode::Esc => {
+            state.modes.pop();
+            return InputModeResult::Handled;
+        }
+        KeyCode::Char('h') => PatchScope::Hunk,
+        KeyCode::Char('f') => PatchScope::File,
+        KeyCode::Char('a') => PatchScope::Diff,
+        KeyCode::Char('m') => PatchScope::HunkMarkdown,
+        KeyCode::Char('s') => {
+            state.modes.pop();
+            if let AppView::Diff { view, .. } = state.views.active() {
+                let path = temp_export_path("patch", view.change_id(), "patch");
+                state.modes.push(InputMode::DiffPatchSave {
+                    path: path.display().to_string(),
+                });
+            }
+            return InputModeResult::Handled;
+        }
+        _ => return InputModeResult::Handled,
+    };
+    state.modes.pop();
+    deliver_patch(state, diff_source, scope, runner, |patch| {
+        copy_text_with(clipboard, patch, scope.label())
+    });
+    InputModeResult::Handled
+}
+
+/// Handles keys while the save prompt is open.
+pub fn handle_diff_patch_save_mode(
+    state: &mut AppState,
+    diff_source: &JjDiff,
+    key: KeyEvent,
+) -> InputModeResult {
+    let Some(InputMode::DiffPatchSave { path }) = state.modes.active_mut() else {
+        return InputModeResult::Unhandled;
+    };
+    match key {
+        KeyEvent {
+            code: KeyCode::Esc, ..
+        } => {
+            state.modes.pop();
+        }
+        KeyEvent {
+            code: KeyCode::Enter,
+            ..
+        } => {
+            let path = PathBuf::from(path.trim());
+            if path.as_os_str().is_empty() {
+                return InputModeResult::Handled;
+            }
+            state.modes.pop();
+            deliver_patch(
+                state,
+                diff_source,
+                PatchScope::Diff,
+                SystemJjCommandRunner,
+                |patch| match write_export(&path, patch) {
+                    Ok(()) => format!("wrote patch to {}", path.display()),
+                    Err(error) => format!("write failed: {error}"),
+                },
+            );
+        }
+        KeyEvent {
+            code: KeyCode::Backspace,
+            ..
+        } => {
+            path.pop();
+        }
+        KeyEvent {
+            code: KeyCode::Char('u'),
+            modifiers,
+            ..
+        } if modifiers == KeyModifiers::CONTROL => path.clear(),
+        KeyEvent {
+            code: KeyCode::Char(character),
+            modifiers,
+            ..
+        } if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+            path.push(character);
+        }
+        _ => {}
+    }
+    InputModeResult::Handled
+}
+
+/// Loads the active diff's patch for `scope` and shows the status `deliver` returns for it.
+fn deliver_patch<R: JjCommandRunner>(
+    state: &mut AppState,
+    diff_source: &JjDiff,
+    scope: PatchScope,
+    runner: R,
+    deliver: impl FnOnce(&str) -> String,
+) {
+    let AppView::Diff { view, query } = state.views.active_mut() else {
+        return;
+    };
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(SourceView::Diff, SourceAction::CopyPatch).with_key(scope.key()),
+    );
+    let status = match scoped_patch(view, query, diff_source, scope, &mut runner) {
+        Ok(patch) => deliver(&patch),
+        Err(error) => error,
+    };
+    view.show_status(status);
+}
+
+/// Returns the git patch text for `scope` at the top line of `view`.
+fn scoped_patch(
+    view: &DiffView,
+    query: &DiffQuery,
+    diff_source: &JjDiff,
+    scope: PatchScope,
+    runner: &mut impl JjCommandRunner,
+) -> Result<String, String> {
+    let location = view.cursor_location();
+    match (scope, &location) {
+        (PatchScope::File, None) => return Err("no file at the top line".to_owned()),
+        (PatchScope::Hunk | PatchScope::HunkMarkdown, None | Some((_, None))) => {
+            return Err("scroll into a hunk to copy it".to_owned());
+        }
+        _ => {}
+    }
+    let text = diff_source
+        .load_patch_with_runner(query, runner)
+        .map_err(|error| error.to_string())?;
+    let patch = GitPatch::parse(&text);
+    if patch.is_empty() {
+        return Err("no changes to copy".to_owned());
+    }
+    let Some((path, line)) = location else {
+        return Ok(text);
+    };
+    let sliced = match (scope, line) {
+        (PatchScope::Diff, _) => return Ok(text),
+        (PatchScope::File, _) => patch.file(&path),
+        (PatchScope::Hunk, Some(line)) => patch.hunk(&path, line),
+        (PatchScope::HunkMarkdown, Some(line)) => patch.hunk_markdown(&path, line),
+        (PatchScope::Hunk | PatchScope::HunkMarkdown, None) => None,
+    };
+    sliced.ok_or_else(|| format!("no git patch for {path}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    use crate::test_support::{
+        RecordingClipboard, SequencedRunner, buffer_line, diff_query, output, real_diff_view,
+    };
+
+    const PATCH: &str = concat!(
+        "\u{1b}[1mdiff --git a/src/a.rs b/src/a.rs\u{1b}[0m\n",
+        "--- a/src/a.rs\n",
+        "+++ b/src/a.rs\n",
+        "@@ -1,1 +1,1 @@\n",
+        "\u{1b}[38;5;1m-a\u{1b}[39m\n",
+        "\u{1b}[38;5;2m+A\u{1b}[39m\n",
+        "diff --git a/src/b.rs b/src/b.rs\n",
+        "--- a/src/b.rs\n",
+        "+++ b/src/b.rs\n",
+        "@@ -1,1 +1,1 @@\n",
+        "-b\n",
+        "+B\n",
+    );
+
+    #[test]
+    fn file_scope_copies_the_top_file_without_color() {
+        let view = real_diff_view("abc");
+        let mut runner = SequencedRunner::successes(vec![output(0, PATCH, "")]);
+
+        let patch = scoped_patch(
+            &view,
+            &diff_query("abc"),
+            &JjDiff::default(),
+            PatchScope::File,
+            &mut runner,
+        );
+
+        assert_eq!(
+            patch.as_deref(),
+            Ok(
+                "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1,1 +1,1 @@\n-a\n+A\n"
+            )
+        );
+    }
+
+    #[test]
+    fn hunk_scope_needs_a_line_inside_a_hunk() {
+        let view = real_diff_view("abc");
+        let mut runner = SequencedRunner::successes(Vec::new());
+
+        let patch = scoped_patch(
+            &view,
+            &diff_query("abc"),
+            &JjDiff::default(),
+            PatchScope::HunkMarkdown,
+            &mut runner,
+        );
+
+        assert_eq!(patch, Err("scroll into a hunk to copy it".to_owned()));
+    }
+
+    #[test]
+    fn copy_keys_send_the_scoped_patch_to_the_clipboard() {
+        let mut state = AppState::new(AppView::Diff {
+            view: real_diff_view("abc"),
+            query: diff_query("abc"),
+        });
+        let mut clipboard = RecordingClipboard::default();
+
+        open_diff_patch_menu(&mut state);
+        handle_diff_patch_mode_with(
+            &mut state,
+            &JjDiff::default(),
+            KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE),
+            &mut clipboard,
+            SequencedRunner::successes(vec![output(0, PATCH, "")]),
+        );
+
+        assert_eq!(
+            clipboard.copied,
+            [
+                "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1,1 +1,1 @@\n-a\n+A\n"
+            ]
+        );
+        assert_eq!(state.modes.active(), None);
+        let AppView::Diff { view, .. } = state.views.active_mut() else {
+            panic!("diff stays active");
+        };
+        let mut terminal = match Terminal::new(TestBackend::new(100, 4)) {
+            Ok(terminal) => terminal,
+            Err(error) => match error {},
+        };
+        assert!(terminal.draw(|frame| view.render(frame)).is_ok());
+        assert!(
+            buffer_line(terminal.backend().buffer(), 3).contains("copied file patch via OSC 52")
+        );
+    }
+
+    #[test]
+    fn save_key_opens_a_patch_path_prompt() {
+        let mut state = AppState::new(AppView::Diff {
+            view: real_diff_view("abc"),
+            query: diff_query("abc"),
+        });
+
+        open_diff_patch_menu(&mut state);
+        handle_diff_patch_mode(
+            &mut state,
+            &JjDiff::default(),
+            KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE),
+        );
+
+        let Some(InputMode::DiffPatchSave { path }) = state.modes.active() else {
+            panic!("save prompt is open");
+        };
+        assert!(path.ends_with("jk-patch-abc.patch"));
+    }
+}
@@ -0,0 +1,511 @@
+//! Diff preview beside the log in the split layout.
+//!
+//! The preview follows the log selection. A load waits for the selection to settle, runs `jj diff`
+//! on a background thread, and is cancelled, killing its `jj` child, when the selection moves to
+//! another change first. Finished previews are cached by commit id and format: rewriting a change
+//! gives it a new commit id, so a cached preview never goes stale, and moving back to a change shows
+//! it without running `jj` again. Terminals narrower than [`SPLIT_MIN_WIDTH`] keep the single-pane
+//! log.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use jk_cli::{
+    CancellableJjCommandRunner, DiffFormat, DiffQuery, JjCommandRunner, JjDiff,
+    RecordingJjCommandRunner,
+};
+use jk_core::{CommandHistory, CommandSource, DiffSnapshot, SourceAction, SourceView};
+use jk_tui::diff_view::DiffView;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::state::{AppState, AppView};
+
+/// Narrowest terminal that shows the log and the preview side by side.
+pub const SPLIT_MIN_WIDTH: u16 = 120;
+/// How long the selection must rest on a change before its preview starts loading.
+const PREVIEW_DEBOUNCE: Duration = Duration::from_millis(150);
+/// How often the event loop checks for a finished background load.
+const RESULT_POLL_INTERVAL: Duration = Duration::from_millis(25);
+/// Previews kept in memory; the oldest is dropped first.
+const PREVIEW_CACHE_LIMIT: usize = 32;
+
+/// What the preview pane beside the log shows.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PreviewMode {
+    /// No split layout; the log uses the whole screen.
+    #[default]
+    Off,
+    /// `jj diff -r <selected>` patch output.
+    Diff,
+    /// `jj diff --stat -r <selected>` output.
+    Stat,
+}
+
+impl PreviewMode {
+    /// Returns the label shown in view options.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Diff => "diff",
+            Self::Stat => "stat",
+        }
+    }
+
+    /// Returns the mode that view options switch to next.
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Diff,
+            Self::Diff => Self::Stat,
+            Self::Stat => Self::Off,
+        }
+    }
+
+    const fn format(self) -> Option<DiffFormat> {
+        match self {
+            Self::Off => None,
+            Self::Diff => Some(DiffFormat::Patch),
+            Self::Stat => Some(DiffFormat::Stat),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct PreviewKey {
+    commit_id: String,
+    format: DiffFormat,
+}
+
+#[derive(Debug)]
+enum PreviewPane {
+    Loaded(DiffView),
+    Failed(String),
+}
+
+#[derive(Debug)]
+struct PreviewTarget {
+    key: PreviewKey,
+    change_id: String,
+    selected_at: Instant,
+}
+
+#[derive(Debug)]
+struct InFlightPreview {
+    key: PreviewKey,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// A finished background load, with the history it recorded off the UI thread.
+#[derive(Debug)]
+struct PreviewResult {
+    key: PreviewKey,
+    result: Result<DiffSnapshot, String>,
+    cancelled: bool,
+    history: CommandHistory,
+}
+
+/// Split-layout preview state owned by the terminal loop.
+#[derive(Debug)]
+pub struct DiffPreview {
+    mode: PreviewMode,
+    target: Option<PreviewTarget>,
+    in_flight: Option<InFlightPreview>,
+    cache: VecDeque<(PreviewKey, PreviewPane)>,
+    sender: Sender<PreviewResult>,
+    receiver: Receiver<PreviewResult>,
+}
+
+impl Default for DiffPreview {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            mode: PreviewMode::Off,
+            target: None,
+            in_flight: None,
+            cache: VecDeque::new(),
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl DiffPreview {
+    pub(crate) const fn mode(&self) -> PreviewMode {
+        self.mode
+    }
+
+    /// Switches the preview mode; turning it off cancels any load in flight.
+    pub(crate) fn set_mode(&mut self, mode: PreviewMode) {
+        self.mode = mode;
+        if mode == PreviewMode::Off {
+            self.target = None;
+            self.cancel_in_flight();
+        }
+    }
+
+    /// Returns the log and preview areas when the split layout fits in `area`.
+    pub(crate) fn split_areas(&self, area: Rect) -> Option<(Rect, Rect)> {
+        if self.mode == PreviewMode::Off || area.width < SPLIT_MIN_WIDTH {
+            return None;
+        }
+        let [log, preview] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(area);
+        Some((log, preview))
+    }
+
+    /// Points the preview at `selection` (change id, commit id), restarting the debounce on change.
+    pub(crate) fn follow(&mut self, selection: Option<(&str, &str)>, now: Instant) {
+        let Some(format) = self.mode.format() else {
+            return;
+        };
+        let Some((change_id, commit_id)) = selection else {
+            self.target = None;
+            self.cancel_in_flight();
+            return;
+        };
+        let key = PreviewKey {
+            commit_id: commit_id.to_owned(),
+            format,
+        };
+        if self.target.as_ref().is_some_and(|target| target.key == key) {
+            return;
+        }
+        if self
+            .in_flight
+            .as_ref()
+            .is_some_and(|in_flight| in_flight.key != key)
+        {
+            self.cancel_in_flight();
+        }
+        self.target = Some(PreviewTarget {
+            key,
+            change_id: change_id.to_owned(),
+            selected_at: now,
+        });
+    }
+
+    /// Returns how long the event loop may block before [`Self::tick`] has work to do.
+    pub(crate) fn poll_timeout(&self, now: Instant) -> Option<Duration> {
+        if self.in_flight.is_some() {
+            return Some(RESULT_POLL_INTERVAL);
+        }
+        let target = self.target.as_ref()?;
+        if self.cached(&target.key).is_some() {
+            return None;
+        }
+        Some((target.selected_at + PREVIEW_DEBOUNCE).saturating_duration_since(now))
+    }
+
+    /// Collects finished loads and starts a settled one, returning whether the pane changed.
+    pub(crate) fn tick(
+        &mut self,
+        now: Instant,
+        history: &mut CommandHistory,
+        source: &JjDiff,
+    ) -> bool {
+        self.tick_with_runner(now, history, source, CancellableJjCommandRunner::new)
+    }
+
+    pub(crate) fn tick_with_runner<R, F>(
+        &mut self,
+        now: Instant,
+        history: &mut CommandHistory,
+        source: &JjDiff,
+        make_runner: F,
+    ) -> bool
+    where
+        R: JjCommandRunner + Send + 'static,
+        F: FnOnce(Arc<AtomicBool>) -> R,
+    {
+        let mut changed = false;
+        while let Ok(finished) = self.receiver.try_recv() {
+            changed = true;
+            history.append_from(finished.history);
+            if self
+                .in_flight
+                .as_ref()
+                .is_some_and(|in_flight| in_flight.key == finished.key)
+            {
+                self.in_flight = None;
+            }
+            if finished.cancelled {
+                continue;
+            }
+            let pane = match finished.result {
+                Ok(snapshot) => PreviewPane::Loaded(DiffView::new(snapshot)),
+                Err(error) => PreviewPane::Failed(error),
+            };
+            self.insert(finished.key, pane);
+        }
+
+        let Some(target) = &self.target else {
+            return changed;
+        };
+        if self.in_flight.is_some()
+            || self.cached(&target.key).is_some()
+            || now < target.selected_at + PREVIEW_DEBOUNCE
+        {
+            return changed;
+        }
+        let key = target.key.clone();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let runner = make_runner(Arc::clone(&cancelled));
+        self.spawn_load(key.clone(), source.clone(), runner, Arc::clone(&cancelled));
+        self.in_flight = Some(InFlightPreview { key, cancelled });
+        true
+    }
+
+    /// Renders the preview pane for the current selection.
+    pub(crate) fn render(&mut self, frame: &mut Frame<'_>, area: Rect) {
+        let block = Block::default().borders(Borders::LEFT);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let Some(target) = &self.target else {
+            frame.render_widget(Paragraph::new("No change selected."), inner);
+            return;
+        };
+        let change_id = target.change_id.clone();
+        let key = target.key.clone();
+        match self.cached_mut(&key) {
+            Some(PreviewPane::Loaded(view)) => view.render_in(frame, inner),
+            Some(PreviewPane::Failed(error)) => {
+                let text = format!("Unable to load diff for {change_id}.\n\n{error}");
+                frame.render_widget(Paragraph::new(text).wrap(Wrap { trim: false }), inner);
+            }
+            None => {
+                let text = format!("Loading diff for {change_id}...");
+                frame.render_widget(Paragraph::new(text), inner);
+            }
+        }
+    }
+
+    fn spawn_load<R>(&self, key: PreviewKey, source: JjDiff, runner: R, cancelled: Arc<AtomicBool>)
+    where
+        R: JjCommandRunner + Send + 'static,
+    {
+        let sender = self.sender.clone();
+        let query = DiffQuery::Revision {
+            rev: key.commit_id.clone(),
+            options: key.format.into(),
+        };
+        thread::spawn(move || {
+            let mut history = CommandHistory::default();
+            let mut runner = RecordingJjCommandRunner::new(
+                runner,
+                &mut history,
+                CommandSource::new(SourceView::Log, SourceAction::PreviewDiff),
+            );
+            let result = source
+                .load_query_with_runner(&query, &mut runner)
+                .map_err(|error| error.to_string());
+            drop(runner);
+            let cancelled = cancelled.load(Ordering::Relaxed);
+            // The receiver only goes away when the app exits, so a failed send needs no handling.
+            let _ = sender.send(PreviewResult {
+                key,
+                result,
+                cancelled,
+                history,
+            });
+        });
+    }
+
+    fn cancel_in_flight(&mut self) {
+        if let Some(in_flight) = self.in_flight.take() {
+            in_flight.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn cached(&self, key: &PreviewKey) -> Option<&PreviewPane> {
+        self.cache
+            .iter()
+            .find(|(cached, _)| cached == key)
+            .map(|(_, pane)| pane)
+    }
+
+    fn cached_mut(&mut self, key: &PreviewKey) -> Option<&mut PreviewPane> {
+        self.cache
+            .iter_mut()
+            .find(|(cached, _)| cached == key)
+            .map(|(_, pane)| pane)
+    }
+
+    fn insert(&mut self, key: PreviewKey, pane: PreviewPane) {
+        self.cache.retain(|(cached, _)| *cached != key);
+        while self.cache.len() >= PREVIEW_CACHE_LIMIT {
+            self.cache.pop_front();
+        }
+        self.cache.push_back((key, pane));
+    }
+}
+
+/// Points the preview at the active log's selection; other views leave it without a target.
+pub fn follow_log_selection(state: &mut AppState, now: Instant) {
+    let selection = match state.views.active() {
+        AppView::Log(log) => log.selected_change_id().zip(log.selected_commit_id()),
+        _ => None,
+    };
+    state.diff_preview.follow(selection, now);
+}
+
+/// Switches to the next preview mode from view options and reports it in the log status line.
+pub fn cycle_preview_mode(state: &mut AppState) {
+    let mode = state.diff_preview.mode().next();
+    state.diff_preview.set_mode(mode);
+    if let AppView::Log(log) = state.views.active_mut() {
+        log.show_status(match mode {
+            PreviewMode::Off => "preview: off".to_owned(),
+            _ => format!(
+                "preview: {} (split from {SPLIT_MIN_WIDTH} columns)",
+                mode.label()
+            ),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::process::Output;
+
+    use super::*;
+    use crate::test_support::{SequencedRunner, output};
+
+    /// Blocks like a slow `jj` until the preview cancels it.
+    struct RunUntilCancelled(Arc<AtomicBool>);
+
+    impl JjCommandRunner for RunUntilCancelled {
+        fn run(&mut self, _spec: &jk_core::JjCommandSpec) -> io::Result<Output> {
+            while !self.0.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+            }
+            Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "jj run cancelled",
+            ))
+        }
+    }
+
+    fn diff_outputs() -> SequencedRunner {
+        SequencedRunner::successes(vec![
+            output(0, "Modified regular file a.rs:\n    1    1: a\n", ""),
+            output(0, "a.rs | 1 +\n1 file changed, 1 insertion(+)\n", ""),
+        ])
+    }
+
+    /// Ticks without starting new loads until a background result arrives.
+    fn wait_for_result(preview: &mut DiffPreview, history: &mut CommandHistory, at: Instant) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !preview.tick_with_runner(at, history, &JjDiff::default(), |_| {
+            SequencedRunner::successes(Vec::new())
+        }) {
+            assert!(
+                Instant::now() < deadline,
+                "background preview never finished"
+            );
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn actions(history: &CommandHistory) -> Vec<SourceAction> {
+        history
+            .records()
+            .map(|record| record.source.action.clone())
+            .collect()
+    }
+
+    #[test]
+    fn preview_waits_for_the_selection_to_settle_then_caches_the_diff() {
+        let mut preview = DiffPreview::default();
+        let mut history = CommandHistory::default();
+        let start = Instant::now();
+        preview.set_mode(PreviewMode::Diff);
+
+        preview.follow(Some(("aaa", "111")), start);
+        assert_eq!(preview.poll_timeout(start), Some(PREVIEW_DEBOUNCE));
+        assert!(
+            !preview.tick_with_runner(start, &mut history, &JjDiff::default(), |_| {
+                diff_outputs()
+            })
+        );
+        assert!(preview.in_flight.is_none());
+
+        let settled = start + PREVIEW_DEBOUNCE;
+        assert!(
+            preview.tick_with_runner(settled, &mut history, &JjDiff::default(), |_| {
+                diff_outputs()
+            })
+        );
+        assert_eq!(preview.poll_timeout(settled), Some(RESULT_POLL_INTERVAL));
+        wait_for_result(&mut preview, &mut history, start);
+
+        assert!(matches!(
+            preview.cached(&PreviewKey {
+                commit_id: "111".to_owned(),
+                format: DiffFormat::Patch,
+            }),
+            Some(PreviewPane::Loaded(_))
+        ));
+        assert_eq!(preview.poll_timeout(settled), None);
+        assert_eq!(
+            actions(&history),
+            [SourceAction::PreviewDiff, SourceAction::PreviewDiff]
+        );
+
+        preview.follow(Some(("bbb", "222")), settled);
+        preview.follow(Some(("aaa", "111")), settled);
+        assert_eq!(preview.poll_timeout(settled), None);
+    }
+
+    #[test]
+    fn moving_on_cancels_the_load_in_flight_without_caching_it() {
+        let mut preview = DiffPreview::default();
+        let mut history = CommandHistory::default();
+        let start = Instant::now();
+        let settled = start + PREVIEW_DEBOUNCE;
+        preview.set_mode(PreviewMode::Stat);
+        preview.follow(Some(("aaa", "111")), start);
+        assert!(preview.tick_with_runner(
+            settled,
+            &mut history,
+            &JjDiff::default(),
+            RunUntilCancelled
+        ));
+
+        preview.follow(Some(("bbb", "222")), settled);
+        wait_for_result(&mut preview, &mut history, settled);
+
+        assert!(preview.cache.is_empty());
+        assert!(preview.in_flight.is_none());
+        assert_eq!(actions(&history), [SourceAction::PreviewDiff]);
+        assert_eq!(
+            preview.poll_timeout(settled),
+            Some(PREVIEW_DEBOUNCE),
+            "the new selection still loads after its own debounce"
+        );
+    }
+
+    #[test]
+    fn split_layout_needs_a_mode_and_a_wide_terminal() {
+        let mut preview = DiffPreview::default();
+        let wide = Rect::new(0, 0, SPLIT_MIN_WIDTH, 40);
+        assert_eq!(preview.split_areas(wide), None);
+
+        preview.set_mode(PreviewMode::Diff);
+        assert_eq!(
+            preview.split_areas(wide),
+            Some((Rect::new(0, 0, 60, 40), Rect::new(60, 0, 60, 40)))
+        );
+        assert_eq!(
+            preview.split_areas(Rect::new(0, 0, SPLIT_MIN_WIDTH - 1, 40)),
+            None
+        );
+    }
+}
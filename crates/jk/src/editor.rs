@@ -0,0 +1,306 @@
+//! Opening the file under the diff cursor in `$EDITOR`.
+//!
+//! Key handling only records an [`EditorRequest`]; the terminal loop owns the terminal, so it
+//! suspends the TUI, runs the editor from the workspace root and reloads the diff afterwards.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use jk_cli::{
+    JjCommandRunner, JjDiff, JjWorkspaces, RecordingJjCommandRunner, SystemJjCommandRunner,
+};
+use jk_core::{CommandHistory, CommandSource, SourceAction, SourceView};
+
+use crate::WorkspaceInspectionKind;
+use crate::refresh::{refresh_diff, refresh_workspace_inspection};
+use crate::state::{AppState, AppView};
+
+const EDITOR_UNSET: &str = "set $EDITOR to open files";
+
+/// A workspace-relative file, and optionally a line, to open in the editor.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EditorRequest {
+    path: String,
+    line: Option<usize>,
+    /// The workspace a workspace diff came from; `None` uses the root `jj root` reports.
+    root: Option<PathBuf>,
+}
+
+impl EditorRequest {
+    /// Returns `path:line`, or just the path without a line.
+    fn label(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{line}", self.path),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// Queues the file and line at the top of the active diff for the terminal loop to open.
+pub fn request_diff_editor(state: &mut AppState) {
+    let (location, root) = match state.views.active() {
+        AppView::Diff { view, .. } => (view.cursor_location(), None),
+        AppView::WorkspaceDiff { view, query } => (
+            view.diff_location(),
+            Some(query.workspace_root().to_path_buf()),
+        ),
+        _ => return,
+    };
+    let Some((path, line)) = location else {
+        show_diff_status(state, "no file here to open".to_owned());
+        return;
+    };
+    state.editor_request = Some(EditorRequest { path, line, root });
+}
+
+/// Builds `$EDITOR +line path` run from `root`, splitting arguments configured in `editor`.
+pub fn editor_command(editor: &str, root: &Path, request: &EditorRequest) -> Option<Command> {
+    let mut words = editor.split_whitespace();
+    let mut command = Command::new(words.next()?);
+    command.args(words);
+    if let Some(line) = request.line {
+        command.arg(format!("+{line}"));
+    }
+    command.arg(&request.path).current_dir(root);
+    Some(command)
+}
+
+/// Suspends the terminal, runs the editor for `request` and reloads the active diff.
+///
+/// # Errors
+///
+/// Returns an error only when the terminal cannot be restored; editor failures become a status.
+pub fn open_in_editor(
+    terminal: &mut ratatui::DefaultTerminal,
+    state: &mut AppState,
+    request: &EditorRequest,
+    workspaces_source: &JjWorkspaces,
+    diff_source: &JjDiff,
+) -> std::io::Result<()> {
+    let editor = std::env::var("EDITOR").unwrap_or_default();
+    let mut command = match prepare_editor_with_runner(
+        &mut state.history,
+        &editor,
+        request,
+        workspaces_source,
+        SystemJjCommandRunner,
+    ) {
+        Ok(command) => command,
+        Err(status) => {
+            show_diff_status(state, status);
+            return Ok(());
+        }
+    };
+
+    ratatui::restore();
+    let result = command.status();
+    *terminal = ratatui::try_init()?;
+    terminal.clear()?;
+
+    match state.views.active_mut() {
+        AppView::Diff { view, query } => {
+            refresh_diff(view, query, &mut state.history, diff_source);
+        }
+        AppView::WorkspaceDiff { view, query } => refresh_workspace_inspection(
+            view,
+            query,
+            &mut state.history,
+            workspaces_source,
+            WorkspaceInspectionKind::Diff,
+        ),
+        _ => {}
+    }
+    let status = match result {
+        Ok(status) if status.success() => format!("edited {}", request.label()),
+        Ok(status) => format!("{editor} exited with {status}"),
+        Err(error) => format!("failed to run {editor}: {error}"),
+    };
+    show_diff_status(state, status);
+    Ok(())
+}
+
+/// Builds the editor command for `request` from its workspace, or from the root `jj root` reports.
+///
+/// Returns the status to show instead when `$EDITOR` is blank or the root cannot be loaded.
+fn prepare_editor_with_runner<R: JjCommandRunner>(
+    history: &mut CommandHistory,
+    editor: &str,
+    request: &EditorRequest,
+    workspaces_source: &JjWorkspaces,
+    runner: R,
+) -> Result<Command, String> {
+    if editor.trim().is_empty() {
+        return Err(EDITOR_UNSET.to_owned());
+    }
+    let root = match &request.root {
+        Some(root) => root.clone(),
+        None => {
+            let mut runner = RecordingJjCommandRunner::new(
+                runner,
+                history,
+                CommandSource::new(SourceView::Diff, SourceAction::OpenInEditor).with_key("e"),
+            );
+            workspaces_source
+                .load_current_root_with_runner(&mut runner)
+                .map_err(|error| error.to_string())?
+        }
+    };
+    editor_command(editor, &root, request).ok_or_else(|| EDITOR_UNSET.to_owned())
+}
+
+fn show_diff_status(state: &mut AppState, status: String) {
+    match state.views.active_mut() {
+        AppView::Diff { view, .. } => view.show_status(status),
+        AppView::WorkspaceDiff { view, .. } => view.show_error(status),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jk_cli::WorkspaceInspectionQuery;
+    use jk_core::InspectionSnapshot;
+    use jk_tui::rendered_view::RenderedView;
+
+    use super::*;
+    use crate::test_support::{SequencedRunner, diff_query, output, real_diff_view};
+
+    #[test]
+    fn diff_request_names_the_file_under_the_cursor() {
+        let mut state = AppState::new(AppView::Diff {
+            view: real_diff_view("abc"),
+            query: diff_query("abc"),
+        });
+
+        request_diff_editor(&mut state);
+
+        assert_eq!(
+            state.editor_request,
+            Some(EditorRequest {
+                path: "src/a.rs".to_owned(),
+                line: None,
+                root: None,
+            })
+        );
+    }
+
+    #[test]
+    fn workspace_diff_request_records_the_workspace_root() {
+        let mut state = AppState::new(AppView::WorkspaceDiff {
+            view: RenderedView::new(InspectionSnapshot::new(
+                "/ws",
+                "diff --git a/src/a.rs b/src/a.rs\n",
+            )),
+            query: WorkspaceInspectionQuery::new("/ws"),
+        });
+
+        request_diff_editor(&mut state);
+
+        assert_eq!(
+            state.editor_request,
+            Some(EditorRequest {
+                path: "src/a.rs".to_owned(),
+                line: None,
+                root: Some(PathBuf::from("/ws")),
+            })
+        );
+    }
+
+    #[test]
+    fn workspace_requests_open_from_their_root_without_asking_jj() {
+        let request = EditorRequest {
+            path: "src/a.rs".to_owned(),
+            line: None,
+            root: Some(PathBuf::from("/ws")),
+        };
+        let mut history = CommandHistory::new(4);
+
+        let command = prepare_editor_with_runner(
+            &mut history,
+            "vi",
+            &request,
+            &JjWorkspaces::default(),
+            SequencedRunner::successes(vec![]),
+        )
+        .expect("editor command");
+
+        assert_eq!(command.get_current_dir(), Some(Path::new("/ws")));
+        assert_eq!(history.records().count(), 0);
+    }
+
+    #[test]
+    fn other_requests_open_from_the_current_root() {
+        let request = EditorRequest {
+            path: "src/a.rs".to_owned(),
+            line: Some(3),
+            root: None,
+        };
+        let mut history = CommandHistory::new(4);
+
+        let command = prepare_editor_with_runner(
+            &mut history,
+            "vi",
+            &request,
+            &JjWorkspaces::default(),
+            SequencedRunner::successes(vec![output(0, "/nonexistent-jk-root\n", "")]),
+        )
+        .expect("editor command");
+
+        assert_eq!(
+            command.get_current_dir(),
+            Some(Path::new("/nonexistent-jk-root"))
+        );
+        assert_eq!(history.records().count(), 1);
+    }
+
+    #[test]
+    fn blank_editor_asks_for_editor() {
+        let request = EditorRequest {
+            path: "src/a.rs".to_owned(),
+            line: None,
+            root: Some(PathBuf::from("/ws")),
+        };
+
+        let result = prepare_editor_with_runner(
+            &mut CommandHistory::new(4),
+            "   ",
+            &request,
+            &JjWorkspaces::default(),
+            SequencedRunner::successes(vec![]),
+        );
+
+        assert_eq!(result.err().as_deref(), Some(EDITOR_UNSET));
+    }
+
+    #[test]
+    fn editor_command_passes_line_and_path_from_the_root() {
+        let request = EditorRequest {
+            path: "src/a.rs".to_owned(),
+            line: Some(12),
+            root: None,
+        };
+
+        let command = editor_command("code --wait", Path::new("/repo"), &request);
+
+        let command = command.expect("editor command");
+        assert_eq!(command.get_program(), "code");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["--wait", "+12", "src/a.rs"]
+        );
+        assert_eq!(command.get_current_dir(), Some(Path::new("/repo")));
+    }
+
+    #[test]
+    fn editor_command_skips_the_line_for_file_headers() {
+        let request = EditorRequest {
+            path: "src/a.rs".to_owned(),
+            line: None,
+            root: None,
+        };
+
+        let command = editor_command("vi", Path::new("/repo"), &request).expect("editor command");
+
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["src/a.rs"]);
+    }
+}
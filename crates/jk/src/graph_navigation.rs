@@ -0,0 +1,207 @@
+//! Graph-aware jumps in the log and the prompt that widens the revset when a target is hidden.
+//!
+//! The moves themselves are resolved by `LogState` from the parents carried on each semantic entry,
+//! so jumping inside the loaded log never runs `jj`. When the target sits outside the current
+//! revset, this module asks before reloading the log with a wider revset and then retries the same
+//! move from the same selection.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use jk_cli::{JjCommandRunner, JjLog, RecordingJjCommandRunner, SystemJjCommandRunner};
+use jk_core::{CommandSource, SourceAction, SourceView};
+use jk_tui::log_view::{GraphMove, GraphMoveOutcome};
+
+use crate::state::{AppState, AppView, InputMode, InputModeResult};
+
+/// Applies `graph_move` in the active log, offering a wider revset when the target is hidden.
+pub fn jump_along_graph(state: &mut AppState, source: &JjLog, graph_move: GraphMove) {
+    jump_along_graph_with_runner(state, source, graph_move, SystemJjCommandRunner);
+}
+
+pub fn jump_along_graph_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    source: &JjLog,
+    graph_move: GraphMove,
+    runner: R,
+) {
+    let AppView::Log(log) = state.views.active_mut() else {
+        return;
+    };
+    match log.select_graph_target(graph_move) {
+        GraphMoveOutcome::Selected => {}
+        GraphMoveOutcome::NoTarget => log.show_status(no_target_status(graph_move)),
+        GraphMoveOutcome::Hidden { revset } => {
+            let mut runner = RecordingJjCommandRunner::new(
+                runner,
+                &mut state.history,
+                CommandSource::new(SourceView::Log, SourceAction::SetRevset),
+            );
+            let scope = current_revset(source, &mut runner);
+            drop(runner);
+            state.modes.push(InputMode::WidenRevset {
+                graph_move,
+                revset: widened_revset(&scope, &revset),
+            });
+        }
+    }
+}
+
+/// Handles key input while the widen-revset prompt is open.
+pub fn handle_widen_revset_mode(
+    state: &mut AppState,
+    source: &mut JjLog,
+    key: KeyEvent,
+) -> InputModeResult {
+    match key.code {
+        KeyCode::Enter | KeyCode::Char('y') => {
+            accept_widen_revset_with_runner(state, source, SystemJjCommandRunner);
+        }
+        KeyCode::Esc | KeyCode::Char('n') => {
+            state.modes.pop();
+        }
+        _ => {}
+    }
+    InputModeResult::Handled
+}
+
+/// Reloads the log with the offered revset and retries the pending move.
+pub fn accept_widen_revset_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    source: &mut JjLog,
+    runner: R,
+) {
+    let Some(InputMode::WidenRevset { graph_move, revset }) = state.modes.pop() else {
+        return;
+    };
+    let next_source = source.clone().with_revset(revset.clone());
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(SourceView::Log, SourceAction::SetRevset).with_key("enter"),
+    );
+    let result = next_source.load_with_runner(&mut runner);
+    drop(runner);
+
+    let AppView::Log(log) = state.views.active_mut() else {
+        return;
+    };
+    match result {
+        Ok(snapshot) => {
+            log.refresh(snapshot);
+            *source = next_source;
+            // A second miss means the target does not exist, so do not offer to widen again.
+            if log.select_graph_target(graph_move) == GraphMoveOutcome::Selected {
+                log.show_status(format!("revset: {revset}"));
+            } else {
+                log.show_status(no_target_status(graph_move));
+            }
+        }
+        Err(error) => log.show_error(format!("could not widen revset: {error}")),
+    }
+}
+
+/// Returns the revset the log shows: the explicit one, or jj's configured `revsets.log` default.
+pub fn current_revset(source: &JjLog, runner: &mut impl JjCommandRunner) -> String {
+    source.revset().map_or_else(
+        || source.load_default_revset_with_runner(runner),
+        ToOwned::to_owned,
+    )
+}
+
+/// Unions `addition` into `scope`, the revset the log currently shows.
+pub fn widened_revset(scope: &str, addition: &str) -> String {
+    format!("({scope}) | {addition}")
+}
+
+fn no_target_status(graph_move: GraphMove) -> String {
+    format!("no {} from here", graph_move.label())
+}
+
+/// Returns overlay lines describing the revset the log would switch to.
+pub fn widen_revset_lines(graph_move: GraphMove, revset: &str) -> Vec<String> {
+    vec![
+        format!("The {} is outside the current revset.", graph_move.label()),
+        String::new(),
+        format!("> jj log -r '{revset}'"),
+        String::new(),
+        "enter/y widen  esc/n stay".to_owned(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{SequencedRunner, log_app_view_with_changes, output};
+
+    #[test]
+    fn hidden_working_copy_offers_a_wider_revset_and_selects_it_after_reload() {
+        let mut state = AppState::new(log_app_view_with_changes(["aaa"]));
+        let mut source = JjLog::default().with_revset("mine()");
+
+        jump_along_graph(&mut state, &source, GraphMove::WorkingCopy);
+
+        assert_eq!(
+            state.modes.active(),
+            Some(&InputMode::WidenRevset {
+                graph_move: GraphMove::WorkingCopy,
+                revset: "(mine()) | @".to_owned(),
+            })
+        );
+
+        accept_widen_revset_with_runner(
+            &mut state,
+            &mut source,
+            SequencedRunner::successes(vec![
+                output(0, "@  www\n○  aaa\n", ""),
+                output(
+                    0,
+                    concat!(
+                        "{\"change_id\":\"www\",\"commit_id\":\"2\",\"description\":\"w\",\"parents\":[\"1\"]}",
+                        "\t\"\"\t\"\\n\\n\\ncurrent_working_copy\"\n",
+                        "{\"change_id\":\"aaa\",\"commit_id\":\"1\",\"description\":\"a\"}\t\"\"\n",
+                    ),
+                    "",
+                ),
+            ]),
+        );
+
+        assert_eq!(state.modes.active(), None);
+        assert_eq!(source.revset(), Some("(mine()) | @"));
+        let AppView::Log(log) = state.views.active() else {
+            panic!("log stays active");
+        };
+        assert_eq!(log.selected_change_id(), Some("www"));
+        assert_eq!(
+            state
+                .command_history()
+                .records()
+                .map(|record| record.source.action.clone())
+                .collect::<Vec<_>>(),
+            [SourceAction::SetRevset, SourceAction::SetRevset]
+        );
+    }
+
+    #[test]
+    fn widened_revset_keeps_the_current_scope() {
+        assert_eq!(widened_revset("mine()", "abc"), "(mine()) | abc");
+    }
+
+    #[test]
+    fn default_log_widens_from_the_configured_revset() {
+        let mut state = AppState::new(log_app_view_with_changes(["aaa"]));
+
+        jump_along_graph_with_runner(
+            &mut state,
+            &JjLog::default(),
+            GraphMove::WorkingCopy,
+            SequencedRunner::successes(vec![output(0, "trunk()..@\n", "")]),
+        );
+
+        assert_eq!(
+            state.modes.active(),
+            Some(&InputMode::WidenRevset {
+                graph_move: GraphMove::WorkingCopy,
+                revset: "(trunk()..@) | @".to_owned(),
+            })
+        );
+    }
+}
@@ -5,7 +5,8 @@
 //! and keeps key binding tests close to the binary surface users exercise.
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use jk_tui::log_view::LogAction;
+use jk_tui::command_discovery::BindingContext;
+use jk_tui::log_view::{GraphMove, LogAction};
 
 /// Result of interpreting one terminal key event.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -19,6 +20,9 @@ pub enum AppKey {
     /// Open the selected revision evolution log.
     OpenEvolog,
 
+    /// Open `jj interdiff` between the two marked revisions.
+    OpenInterdiff,
+
     /// Open repository status.
     OpenStatus,
 
@@ -34,12 +38,39 @@ pub enum AppKey {
     /// Copy the current command line.
     CopyCommand,
 
+    /// Open the diff's copy/save patch menu.
+    CopyPatch,
+
+    /// Open the log's copy menu for the selected revision.
+    CopyRevision,
+
     /// Preview and run `jj undo`.
     StartUndo,
 
     /// Preview and run `jj redo`.
     StartRedo,
 
+    /// Start naming a session checkpoint at the current operation.
+    StartCheckpoint,
+
+    /// Preview `jj op restore` back to the latest session checkpoint.
+    StartRestoreCheckpoint,
+
+    /// Start the log revset prompt.
+    StartRevset,
+
+    /// Open the fuzzy revision finder to jump to a change.
+    StartRevisionFinder,
+
+    /// Jump along the commit graph from the selected change.
+    GraphJump(GraphMove),
+
+    /// Pick a rebase destination for the selected or marked revisions.
+    StartRebase,
+
+    /// Pick a squash target for the selected revision.
+    StartSquash,
+
     /// Start an inline describe mutation for the selected revision.
     StartDescribe,
 
@@ -58,6 +89,15 @@ pub enum AppKey {
     /// Open the current diff file list.
     OpenDiffFileList,
 
+    /// Toggle the viewed mark on the diff's current hunk or file.
+    ToggleViewed,
+
+    /// Add or edit the review note at the diff's top line.
+    StartReviewNote,
+
+    /// Export the current diff's review notes.
+    ExportDiffNotes,
+
     /// Close the active mode or return to the previous view.
     Back,
 
@@ -75,6 +115,23 @@ pub enum AppKey {
 }
 
 impl AppKey {
+    /// Converts a crossterm key event for the view bound to `context`.
+    ///
+    /// Views that rebind a shared character key take it first; everything else falls through to
+    /// [`AppKey::from_crossterm`].
+    pub const fn from_crossterm_in(key: KeyEvent, context: BindingContext) -> Self {
+        if !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL.union(KeyModifiers::ALT))
+            && let KeyCode::Char(character) = key.code
+            && let Some(action) = action_for_context_key(context, character)
+        {
+            return action;
+        }
+
+        Self::from_crossterm(key)
+    }
+
     /// Converts a crossterm key event into the current action surface.
     pub const fn from_crossterm(key: KeyEvent) -> Self {
         if key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -104,6 +161,9 @@ impl AppKey {
                 code: KeyCode::Right,
                 ..
             } => Self::Action(LogAction::ToggleExpanded),
+            KeyEvent {
+                code: KeyCode::Tab, ..
+            } => Self::GraphJump(GraphMove::NextChild),
             KeyEvent {
                 code: KeyCode::Up, ..
             } => Self::Action(LogAction::Previous),
@@ -157,15 +217,26 @@ const fn action_for_character_key(character: char) -> Option<AppKey> {
         'C' => Some(AppKey::OpenCommandHistory),
         'e' => Some(AppKey::EditCommandOutput),
         'f' => Some(AppKey::OpenDiffFileList),
+        'x' => Some(AppKey::ExportDiffNotes),
         'o' => Some(AppKey::OpenOperationLog),
         'y' => Some(AppKey::CopyCommand),
         'u' => Some(AppKey::StartUndo),
         'U' => Some(AppKey::StartRedo),
+        'K' => Some(AppKey::StartCheckpoint),
+        'R' => Some(AppKey::StartRestoreCheckpoint),
+        'i' => Some(AppKey::StartRevset),
+        'p' => Some(AppKey::StartRevisionFinder),
+        'P' => Some(AppKey::GraphJump(GraphMove::FirstParent)),
+        '@' => Some(AppKey::GraphJump(GraphMove::WorkingCopy)),
+        '\'' => Some(AppKey::GraphJump(GraphMove::NearestBookmark)),
+        'B' => Some(AppKey::StartRebase),
+        'S' => Some(AppKey::StartSquash),
         'm' => Some(AppKey::StartDescribe),
         'a' => Some(AppKey::StartAbandon),
         'v' => Some(AppKey::OpenEvolog),
         'l' => Some(AppKey::Action(LogAction::ToggleExpanded)),
         'd' => Some(AppKey::Action(LogAction::OpenDiff)),
+        'D' => Some(AppKey::OpenInterdiff),
         'c' => Some(AppKey::Action(LogAction::ClearMarks)),
         's' => Some(AppKey::OpenStatus),
         '?' => Some(AppKey::Action(LogAction::ToggleHelp)),
@@ -189,6 +260,18 @@ const fn action_for_character_key(character: char) -> Option<AppKey> {
     }
 }
 
+/// Interprets character keys a view binds differently from the log.
+const fn action_for_context_key(context: BindingContext, character: char) -> Option<AppKey> {
+    match (context, character) {
+        (BindingContext::Diff, 'v') => Some(AppKey::ToggleViewed),
+        (BindingContext::Diff, 'c') => Some(AppKey::StartReviewNote),
+        (BindingContext::Diff, 'y') => Some(AppKey::CopyPatch),
+        (BindingContext::Log, 'y') => Some(AppKey::CopyRevision),
+        (BindingContext::OperationLog, 'f') => Some(AppKey::Action(LogAction::PageNext)),
+        _ => None,
+    }
+}
+
 /// Interprets Ctrl-key bindings that should override ordinary character keys.
 const fn action_for_control_key(code: KeyCode) -> AppKey {
     match code {
@@ -314,6 +397,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lowercase_x_exports_diff_notes() {
+        assert_eq!(
+            AppKey::from_crossterm(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE)),
+            AppKey::ExportDiffNotes
+        );
+    }
+
     #[test]
     fn lowercase_u_starts_undo_preview() {
         assert_eq!(
@@ -330,6 +421,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn uppercase_k_and_r_manage_session_checkpoints() {
+        assert_eq!(
+            AppKey::from_crossterm(KeyEvent::new(KeyCode::Char('K'), KeyModifiers::NONE)),
+            AppKey::StartCheckpoint
+        );
+        assert_eq!(
+            AppKey::from_crossterm(KeyEvent::new(KeyCode::Char('R'), KeyModifiers::NONE)),
+            AppKey::StartRestoreCheckpoint
+        );
+    }
+
+    #[test]
+    fn lowercase_i_starts_revset_prompt() {
+        assert_eq!(
+            AppKey::from_crossterm(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE)),
+            AppKey::StartRevset
+        );
+    }
+
+    #[test]
+    fn revision_finder_keys_pick_jump_rebase_and_squash_targets() {
+        assert_eq!(
+            AppKey::from_crossterm(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE)),
+            AppKey::StartRevisionFinder
+        );
+        assert_eq!(
+            AppKey::from_crossterm(KeyEvent::new(KeyCode::Char('B'), KeyModifiers::NONE)),
+            AppKey::StartRebase
+        );
+        assert_eq!(
+            AppKey::from_crossterm(KeyEvent::new(KeyCode::Char('S'), KeyModifiers::NONE)),
+            AppKey::StartSquash
+        );
+    }
+
+    #[test]
+    fn graph_jump_keys_follow_parents_children_working_copy_and_bookmarks() {
+        for (code, graph_move) in [
+            (KeyCode::Char('P'), GraphMove::FirstParent),
+            (KeyCode::Tab, GraphMove::NextChild),
+            (KeyCode::Char('@'), GraphMove::WorkingCopy),
+            (KeyCode::Char('\''), GraphMove::NearestBookmark),
+        ] {
+            assert_eq!(
+                AppKey::from_crossterm(KeyEvent::new(code, KeyModifiers::NONE)),
+                AppKey::GraphJump(graph_move)
+            );
+        }
+    }
+
     #[test]
     fn uppercase_t_is_unbound_after_view_options_migration() {
         assert_eq!(
@@ -346,6 +488,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lowercase_v_toggles_viewed_in_diffs() {
+        let key = KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE);
+
+        assert_eq!(
+            AppKey::from_crossterm_in(key, BindingContext::Diff),
+            AppKey::ToggleViewed
+        );
+        assert_eq!(
+            AppKey::from_crossterm_in(key, BindingContext::Log),
+            AppKey::OpenEvolog
+        );
+    }
+
+    #[test]
+    fn lowercase_c_starts_a_review_note_in_diffs() {
+        let key = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE);
+
+        assert_eq!(
+            AppKey::from_crossterm_in(key, BindingContext::Diff),
+            AppKey::StartReviewNote
+        );
+        assert_eq!(
+            AppKey::from_crossterm_in(key, BindingContext::Log),
+            AppKey::Action(LogAction::ClearMarks)
+        );
+    }
+
+    #[test]
+    fn lowercase_y_opens_each_views_copy_menu() {
+        let key = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+
+        assert_eq!(
+            AppKey::from_crossterm_in(key, BindingContext::Diff),
+            AppKey::CopyPatch
+        );
+        assert_eq!(
+            AppKey::from_crossterm_in(key, BindingContext::Log),
+            AppKey::CopyRevision
+        );
+        assert_eq!(
+            AppKey::from_crossterm_in(key, BindingContext::CommandHistory),
+            AppKey::CopyCommand
+        );
+    }
+
+    #[test]
+    fn lowercase_f_pages_down_in_the_operation_log() {
+        let key = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE);
+
+        assert_eq!(
+            AppKey::from_crossterm_in(key, BindingContext::OperationLog),
+            AppKey::Action(LogAction::PageNext)
+        );
+        assert_eq!(
+            AppKey::from_crossterm_in(key, BindingContext::Diff),
+            AppKey::OpenDiffFileList
+        );
+    }
+
     #[test]
     fn lowercase_m_starts_describe() {
         assert_eq!(
@@ -370,6 +572,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn uppercase_d_opens_marked_interdiff() {
+        assert_eq!(
+            AppKey::from_crossterm(KeyEvent::new(KeyCode::Char('D'), KeyModifiers::NONE)),
+            AppKey::OpenInterdiff
+        );
+    }
+
     #[test]
     fn lowercase_c_clears_log_marks() {
         assert_eq!(
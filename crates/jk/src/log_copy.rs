@@ -0,0 +1,148 @@
+//! Copying the selected revision's change id, commit id or description from the log.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use jk_tui::log_view::LogView;
+
+use crate::clipboard::{Clipboard, SystemClipboard, copy_text_with};
+use crate::state::{AppState, AppView, InputMode, InputModeResult};
+
+/// Opens the copy menu for the selected log revision.
+pub fn open_log_copy_menu(state: &mut AppState) {
+    let AppView::Log(log) = state.views.active_mut() else {
+        return;
+    };
+    if log.selected_change_id().is_none() {
+        log.show_status("select a revision to copy from");
+        return;
+    }
+    state.modes.push(InputMode::LogCopy);
+}
+
+/// Returns the copy menu lines with the values each key copies.
+pub fn log_copy_lines(log: &LogView) -> Vec<String> {
+    let description = log
+        .selected_description()
+        .and_then(|description| description.lines().next())
+        .filter(|line| !line.trim().is_empty())
+        .unwrap_or("(no description set)");
+    vec![
+        format!("c change id     {}", log.selected_change_id().unwrap_or("")),
+        format!("i commit id     {}", log.selected_commit_id().unwrap_or("")),
+        format!("d description   {description}"),
+        String::new(),
+        "esc cancel".to_owned(),
+    ]
+}
+
+/// Handles keys while the copy menu is open.
+pub fn handle_log_copy_mode(state: &mut AppState, key: KeyEvent) -> InputModeResult {
+    handle_log_copy_mode_with_clipboard(state, key, &mut SystemClipboard)
+}
+
+fn handle_log_copy_mode_with_clipboard(
+    state: &mut AppState,
+    key: KeyEvent,
+    clipboard: &mut impl Clipboard,
+) -> InputModeResult {
+    let AppView::Log(log) = state.views.active_mut() else {
+        state.modes.pop();
+        return InputModeResult::Handled;
+    };
+    let (field, text) = match key.code {
+        KeyCode::Char('c') => ("change id", log.selected_change_id()),
+        KeyCode::Char('i') => ("commit id", log.selected_commit_id()),
+        KeyCode::Char('d') => ("description", log.selected_description().map(str::trim_end)),
+        KeyCode::Esc => {
+            state.modes.pop();
+            return InputModeResult::Handled;
+        }
+        _ => return InputModeResult::Handled,
+    };
+    let status = match text {
+        Some(text) if !text.is_empty() => copy_text_with(clipboard, text, field),
+        _ => format!("no {field} to copy"),
+    };
+    log.show_status(status);
+    state.modes.pop();
+    InputModeResult::Handled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    use crate::test_support::{RecordingClipboard, buffer_line, log_app_view_with_description};
+
+    fn copy_with(description: &str, character: char) -> (AppState, RecordingClipboard) {
+        let mut state = AppState::new(log_app_view_with_description("abc", description));
+        let mut clipboard = RecordingClipboard::default();
+        open_log_copy_menu(&mut state);
+        handle_log_copy_mode_with_clipboard(
+            &mut state,
+            KeyEvent::new(KeyCode::Char(character), KeyModifiers::NONE),
+            &mut clipboard,
+        );
+        (state, clipboard)
+    }
+
+    fn status_line(state: &mut AppState) -> String {
+        let AppView::Log(log) = state.views.active_mut() else {
+            panic!("log stays active");
+        };
+        let mut terminal = match Terminal::new(TestBackend::new(100, 4)) {
+            Ok(terminal) => terminal,
+            Err(error) => match error {},
+        };
+        assert!(terminal.draw(|frame| log.render(frame)).is_ok());
+        buffer_line(terminal.backend().buffer(), 3)
+    }
+
+    #[test]
+    fn copy_menu_lists_the_selected_revision() {
+        let mut state = AppState::new(log_app_view_with_description(
+            "abc",
+            "Fix the parser\n\nLonger body.\n",
+        ));
+
+        open_log_copy_menu(&mut state);
+
+        let AppView::Log(log) = state.views.active() else {
+            panic!("log stays active");
+        };
+        assert_eq!(state.modes.active(), Some(&InputMode::LogCopy));
+        let lines = log_copy_lines(log);
+        assert!(lines[0].ends_with("abc"));
+        assert_eq!(lines[2], "d description   Fix the parser");
+    }
+
+    #[test]
+    fn copy_keys_copy_each_field_and_close_the_menu() {
+        for (character, copied, status) in [
+            ('c', "abc", "copied change id via OSC 52"),
+            ('i', "commit", "copied commit id via OSC 52"),
+            (
+                'd',
+                "Fix the parser\n\nLonger body.",
+                "copied description via OSC 52",
+            ),
+        ] {
+            let (mut state, clipboard) = copy_with("Fix the parser\n\nLonger body.\n", character);
+
+            assert_eq!(clipboard.copied, [copied], "{character}");
+            assert!(status_line(&mut state).contains(status), "{character}");
+            assert!(state.modes.active().is_none(), "{character}");
+        }
+    }
+
+    #[test]
+    fn empty_description_is_reported_instead_of_copied() {
+        let (mut state, clipboard) = copy_with("", 'd');
+
+        assert!(clipboard.copied.is_empty());
+        assert!(status_line(&mut state).contains("no description to copy"));
+        assert!(state.modes.active().is_none());
+    }
+}
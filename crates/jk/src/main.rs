@@ -14,6 +14,8 @@
 
 use std::io::{self, IsTerminal};
 use std::path::{Path, PathBuf};
+use std::slice;
+use std::time::Instant;
 
 use clap::Parser;
 use color_eyre::Result;
@@ -23,13 +25,13 @@ use crossterm::style::force_color_output;
 #[cfg(test)]
 use jk_cli::RecoveryCommand;
 use jk_cli::{
-    AbandonQuery, DescribeQuery, DiffFormat, DiffQuery, EditQuery, EvologQuery, JjAbandon,
-    JjCommandRunner, JjDescribe, JjDiff, JjEdit, JjEvolog, JjLog, JjLogCommand, JjNew, JjOperation,
-    JjRecovery, JjShow, JjStatus, JjWorkspaces, LogTemplateSelection, NewQuery, OperationQuery,
-    RecordingJjCommandRunner, ShowQuery, StatusQuery, SystemJjCommandRunner,
-    WorkspaceInspectionQuery,
+    AbandonQuery, CachingJjCommandRunner, DescribeQuery, DiffFormat, DiffOptions, DiffQuery,
+    EditQuery, EvologQuery, JjAbandon, JjCommandRunner, JjDescribe, JjDiff, JjEdit, JjEvolog,
+    JjLog, JjLogCommand, JjNew, JjOperation, JjOutputCache, JjRecovery, JjShow, JjStatus,
+    JjWorkspaces, LogTemplateSelection, NewQuery, OperationQuery, RecordingJjCommandRunner,
+    ShowQuery, StatusQuery, SystemJjCommandRunner, WorkspaceInspectionQuery,
 };
-use jk_core::{CommandHistory, CommandSource, SourceAction, SourceView};
+use jk_core::{CommandHistory, CommandSource, SourceAction, SourceView, WorkingCopyPolicy};
 use jk_tui::command_discovery::{BindingContext, discovery_scroll_limit};
 use jk_tui::command_history_view::{CommandHistoryAction, CommandHistoryActionResult};
 #[cfg(test)]
@@ -37,7 +39,7 @@ use jk_tui::command_history_view::{CommandHistorySnapshot, CommandHistoryView};
 use jk_tui::diff_view::{DiffAction, DiffActionResult, DiffView};
 #[cfg(test)]
 use jk_tui::log_view::LogAction;
-use jk_tui::log_view::{ActionResult, LogView};
+use jk_tui::log_view::{ActionResult, LogView, marked_pair};
 use jk_tui::operation_log_view::{OperationLogAction, OperationLogActionResult, OperationLogView};
 use jk_tui::rendered_view::{RenderedAction, RenderedActionResult, RenderedView};
 #[cfg(test)]
@@ -45,26 +47,42 @@ use jk_tui::workspaces_view::WorkspaceViewSnapshot;
 use jk_tui::workspaces_view::{WorkspacesActionResult, WorkspacesView};
 
 mod actions;
+mod checkpoints;
 mod cli;
 mod clipboard;
 mod command_history;
 mod command_mode;
+mod diff_file_list;
+mod diff_patch;
+mod diff_preview;
+mod editor;
+mod graph_navigation;
 mod key;
+mod log_copy;
 mod menus;
 mod mutation_preview;
 mod mutations;
 mod operation_log;
+mod persistence;
+mod prompt_completion;
 mod refresh;
 mod rendering;
+mod review_notes;
+mod revision_finder;
+mod revset_prompt;
 mod root_views;
 mod runner;
 mod state;
 #[cfg(test)]
 mod test_support;
+mod viewed_marks;
 mod workspace_routes;
 mod workspaces;
 
 use actions::{AppSources, DispatchResult, dispatch_app_key};
+use checkpoints::{
+    SessionCheckpoints, handle_checkpoint_picker_mode, record_checkpoint, record_session_start,
+};
 use cli::{Args, Command};
 use clipboard::copy_command_line;
 use command_history::{apply_command_history_action, open_command_history};
@@ -76,30 +94,51 @@ pub(crate) use command_history::{
     open_command_history_operation, open_operation_log, push_selected_command_history_details,
 };
 use command_mode::{command_mode_snapshot, command_mode_spec, parse_jj_command_args};
+use diff_file_list::{handle_diff_file_list_mode, open_diff_file_list};
+use diff_patch::{handle_diff_patch_mode, handle_diff_patch_save_mode};
+use diff_preview::{cycle_preview_mode, follow_log_selection};
+use editor::open_in_editor;
+use graph_navigation::handle_widen_revset_mode;
 use key::AppKey;
-use menus::{MenuDirection, ViewOptionRow, view_option_rows, wrapped_selection};
+use log_copy::handle_log_copy_mode;
+use menus::{
+    DiffViewOptionRow, LogViewOptionRow, MenuDirection, OPERATION_DIFF_VIEW_OPTION_ROWS,
+    OperationLogFilterRow, ViewOptionRow, diff_view_option_rows, next_diff_context,
+    view_option_rows, wrapped_selection,
+};
 #[cfg(test)]
-use menus::{diff_file_list_lines, view_options_lines};
-use mutation_preview::{PendingCommandPreview, selected_new_parents};
+use menus::{diff_view_options_lines, view_options_lines};
+use mutation_preview::{
+    PendingCommandPreview, selected_new_parents, with_immutable_target_warning,
+};
 #[cfg(test)]
 use mutations::confirm_command_preview_with_runner;
 use mutations::{confirm_command_preview, open_recovery_preview};
+use prompt_completion::{
+    complete_prompt, completion_direction, dismiss_completion_popup, reset_prompt_completion,
+};
 #[cfg(test)]
 use refresh::show_log_template_load_error;
 use refresh::{
-    OperationRenderedKind, apply_log_template_selection, operation_rendered_transition,
-    refresh_diff, refresh_evolog, refresh_log, refresh_operation_log, refresh_operation_rendered,
-    refresh_show, refresh_status, refresh_workspace_inspection, refresh_workspaces,
-    switch_log_command,
+    OperationRenderedKind, apply_log_template_selection, load_more_log,
+    operation_rendered_transition, refresh_diff, refresh_evolog, refresh_log,
+    refresh_operation_log, refresh_operation_rendered, refresh_show, refresh_status,
+    refresh_workspace_inspection, refresh_workspaces, switch_log_command,
 };
 use rendering::render_app;
+use review_notes::{
+    ReviewNotes, handle_diff_note_mode, handle_diff_notes_export_mode, show_review_notes,
+};
+use revision_finder::handle_revision_finder_mode;
+use revset_prompt::{RevsetHistory, handle_log_revset_mode};
 use root_views::{
     root_diff_view, root_log_view, root_show_view, root_status_view, root_workspaces_view,
 };
-pub(crate) use runner::recording_runner;
+pub(crate) use runner::{cached_recording_runner, recording_runner};
 #[cfg(test)]
 use state::ViewStack;
 use state::{AppState, AppView, InputMode, InputModeResult, ModeStack};
+use viewed_marks::ViewedMarks;
 use workspace_routes::{
     WorkspaceInspectionKind, open_workspaces, push_selected_workspace_diff,
     push_selected_workspace_log, push_selected_workspace_status, push_status,
@@ -146,6 +185,8 @@ fn main() -> Result<()> {
         Some(Command::Workspaces) => root_workspaces_view(&workspaces_source, &mut history),
         Some(Command::Log(_)) | None => root_log_view(&source, &mut history)?,
     };
+    let mut checkpoints = SessionCheckpoints::default();
+    record_session_start(&mut checkpoints, &mut history, &operation_source);
 
     run_terminal(
         app,
@@ -163,6 +204,7 @@ fn main() -> Result<()> {
         &workspaces_source,
         args.repository,
         history,
+        checkpoints,
     )?;
     Ok(())
 }
@@ -188,6 +230,7 @@ fn run_terminal(
     workspaces_source: &JjWorkspaces,
     command_repository: Option<PathBuf>,
     history: CommandHistory,
+    checkpoints: SessionCheckpoints,
 ) -> Result<()> {
     if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
         return Err(eyre!("jk requires an interactive terminal"));
@@ -199,14 +242,33 @@ fn run_terminal(
     let mut terminal = ratatui::try_init()?;
     let _terminal_restore = TerminalRestore;
     let mut needs_redraw = true;
-    let mut state = AppState::with_history(app, history);
+    let mut state = AppState::with_history(app, history)
+        .with_checkpoints(checkpoints)
+        .with_revset_history(RevsetHistory::load())
+        .with_viewed_marks(ViewedMarks::load(command_repository.as_deref()))
+        .with_review_notes(ReviewNotes::load(command_repository.as_deref()));
+    show_review_notes(&mut state, diff_source);
 
     loop {
+        let now = Instant::now();
+        follow_log_selection(&mut state, now);
+        if state
+            .diff_preview
+            .tick(now, &mut state.history, diff_source)
+        {
+            needs_redraw = true;
+        }
         if needs_redraw {
-            terminal.draw(|frame| render_app(frame, &mut state, source.template()))?;
+            terminal.draw(|frame| render_app(frame, &mut state, &source))?;
             needs_redraw = false;
         }
 
+        // Block on input unless a debounced preview load or a background result needs a tick.
+        if let Some(timeout) = state.diff_preview.poll_timeout(now)
+            && !event::poll(timeout)?
+        {
+            continue;
+        }
         match event::read()? {
             Event::Key(key) => {
                 if handle_input_mode(
@@ -214,6 +276,8 @@ fn run_terminal(
                     &mut source,
                     diff_source,
                     describe_source,
+                    operation_source,
+                    recovery_source,
                     command_repository.as_deref(),
                     key,
                 ) == InputModeResult::Handled
@@ -222,7 +286,7 @@ fn run_terminal(
                     continue;
                 }
 
-                let app_key = AppKey::from_crossterm(key);
+                let app_key = AppKey::from_crossterm_in(key, active_binding_context(&state));
                 let mut sources = AppSources {
                     log: &mut source,
                     diff: diff_source,
@@ -240,6 +304,15 @@ fn run_terminal(
                 {
                     break;
                 }
+                if let Some(request) = state.editor_request.take() {
+                    open_in_editor(
+                        &mut terminal,
+                        &mut state,
+                        &request,
+                        workspaces_source,
+                        diff_source,
+                    )?;
+                }
                 needs_redraw = true;
             }
             Event::Resize(_, _) => {
@@ -258,18 +331,44 @@ fn handle_input_mode(
     source: &mut JjLog,
     diff_source: &JjDiff,
     describe_source: &JjDescribe,
+    operation_source: &JjOperation,
+    recovery_source: &JjRecovery,
     command_repository: Option<&Path>,
     key: KeyEvent,
 ) -> InputModeResult {
     if matches!(state.modes.active(), Some(InputMode::ViewOptions { .. })) {
-        return handle_view_options_mode(state, source, diff_source, key);
+        return handle_view_options_mode(state, source, diff_source, operation_source, key);
     }
     if matches!(state.modes.active(), Some(InputMode::DiffFileList { .. })) {
-        return handle_diff_file_list_mode(state, key);
+        return handle_diff_file_list_mode(state, command_repository, key);
+    }
+    if matches!(state.modes.active(), Some(InputMode::DiffNote { .. })) {
+        return handle_diff_note_mode(state, diff_source, key);
+    }
+    if matches!(
+        state.modes.active(),
+        Some(InputMode::DiffNotesExport { .. })
+    ) {
+        return handle_diff_notes_export_mode(state, key);
+    }
+    if matches!(state.modes.active(), Some(InputMode::DiffPatch)) {
+        return handle_diff_patch_mode(state, diff_source, key);
+    }
+    if matches!(state.modes.active(), Some(InputMode::DiffPatchSave { .. })) {
+        return handle_diff_patch_save_mode(state, diff_source, key);
+    }
+    if matches!(state.modes.active(), Some(InputMode::LogCopy)) {
+        return handle_log_copy_mode(state, key);
     }
     if matches!(state.modes.active(), Some(InputMode::LogTemplate { .. })) {
         return handle_template_mode(state, source, key);
     }
+    if matches!(
+        state.modes.active(),
+        Some(InputMode::CheckpointPicker { .. })
+    ) {
+        return handle_checkpoint_picker_mode(state, operation_source, recovery_source, key);
+    }
     if matches!(
         state.modes.active(),
         Some(InputMode::CommandDiscovery { .. })
@@ -277,11 +376,20 @@ fn handle_input_mode(
         return handle_command_discovery_mode(state, key);
     }
     if matches!(state.modes.active(), Some(InputMode::CommandPreview { .. })) {
-        return handle_command_preview_mode(state, source, key);
+        return handle_command_preview_mode(state, source, diff_source, key);
     }
     if matches!(state.modes.active(), Some(InputMode::JjCommand { .. })) {
         return handle_jj_command_mode(state, command_repository, key);
     }
+    if matches!(state.modes.active(), Some(InputMode::LogRevset { .. })) {
+        return handle_log_revset_mode(state, source, command_repository, key);
+    }
+    if matches!(state.modes.active(), Some(InputMode::RevisionFinder { .. })) {
+        return handle_revision_finder_mode(state, source, command_repository, key);
+    }
+    if matches!(state.modes.active(), Some(InputMode::WidenRevset { .. })) {
+        return handle_widen_revset_mode(state, source, key);
+    }
 
     let Some(mode) = state.modes.active_mut() else {
         return InputModeResult::Unhandled;
@@ -305,21 +413,39 @@ fn handle_input_mode(
                     if message.trim().is_empty() {
                         return InputModeResult::Handled;
                     }
-                    let preview = describe_source
+                    let mut preview = describe_source
                         .spec_for(&DescribeQuery::new(rev.clone(), message.clone()))
                         .command_preview();
+                    if let AppView::Log(log) = state.views.active() {
+                        preview = with_immutable_target_warning(preview, log, slice::from_ref(rev));
+                    }
                     state.modes.pop();
                     state.modes.push(InputMode::CommandPreview {
                         pending: PendingCommandPreview::describe(preview),
                     });
                     return InputModeResult::Handled;
                 }
+                InputMode::CheckpointName { name } => {
+                    let name = name.clone();
+                    state.modes.pop();
+                    record_checkpoint(state, operation_source, &name);
+                    return InputModeResult::Handled;
+                }
+                InputMode::CheckpointPicker { .. } => unreachable!(),
                 InputMode::ViewOptions { .. } => unreachable!(),
                 InputMode::DiffFileList { .. } => unreachable!(),
+                InputMode::DiffNote { .. } => unreachable!(),
+                InputMode::DiffNotesExport { .. } => unreachable!(),
+                InputMode::DiffPatch => unreachable!(),
+                InputMode::DiffPatchSave { .. } => unreachable!(),
                 InputMode::CommandDiscovery { .. } => unreachable!(),
                 InputMode::CommandPreview { .. } => unreachable!(),
                 InputMode::JjCommand { .. } => unreachable!(),
+                InputMode::LogCopy => unreachable!(),
                 InputMode::LogTemplate { .. } => unreachable!(),
+                InputMode::LogRevset { .. } => unreachable!(),
+                InputMode::RevisionFinder { .. } => unreachable!(),
+                InputMode::WidenRevset { .. } => unreachable!(),
             };
             state.modes.pop();
             apply_search_submit(state, action);
@@ -329,10 +455,11 @@ fn handle_input_mode(
             code: KeyCode::Backspace,
             ..
         } => {
-            if let InputMode::DescribeMessage { message, .. } = mode
-                && !message.is_empty()
+            if let InputMode::DescribeMessage { message: text, .. }
+            | InputMode::CheckpointName { name: text } = mode
+                && !text.is_empty()
             {
-                message.pop();
+                text.pop();
                 return InputModeResult::Handled;
             }
             state.modes.pop();
@@ -343,8 +470,10 @@ fn handle_input_mode(
             modifiers,
             ..
         } if modifiers == KeyModifiers::CONTROL => {
-            if let InputMode::DescribeMessage { message, .. } = mode {
-                message.clear();
+            if let InputMode::DescribeMessage { message: text, .. }
+            | InputMode::CheckpointName { name: text } = mode
+            {
+                text.clear();
             }
             InputModeResult::Handled
         }
@@ -357,15 +486,25 @@ fn handle_input_mode(
                 InputMode::DiffSearch { query } | InputMode::InspectionSearch { query } => {
                     query.push(character);
                 }
-                InputMode::DescribeMessage { message, .. } => {
-                    message.push(character);
+                InputMode::DescribeMessage { message: text, .. }
+                | InputMode::CheckpointName { name: text } => {
+                    text.push(character);
                 }
+                InputMode::CheckpointPicker { .. } => unreachable!(),
                 InputMode::ViewOptions { .. } => unreachable!(),
                 InputMode::DiffFileList { .. } => unreachable!(),
+                InputMode::DiffNote { .. } => unreachable!(),
+                InputMode::DiffNotesExport { .. } => unreachable!(),
+                InputMode::DiffPatch => unreachable!(),
+                InputMode::DiffPatchSave { .. } => unreachable!(),
                 InputMode::CommandDiscovery { .. } => unreachable!(),
                 InputMode::CommandPreview { .. } => unreachable!(),
                 InputMode::JjCommand { .. } => unreachable!(),
+                InputMode::LogCopy => unreachable!(),
                 InputMode::LogTemplate { .. } => unreachable!(),
+                InputMode::LogRevset { .. } => unreachable!(),
+                InputMode::RevisionFinder { .. } => unreachable!(),
+                InputMode::WidenRevset { .. } => unreachable!(),
             }
             InputModeResult::Handled
         }
@@ -434,6 +573,7 @@ fn handle_command_discovery_mode(state: &mut AppState, key: KeyEvent) -> InputMo
 fn handle_command_preview_mode(
     state: &mut AppState,
     source: &mut JjLog,
+    diff_source: &JjDiff,
     key: KeyEvent,
 ) -> InputModeResult {
     match key {
@@ -456,7 +596,7 @@ fn handle_command_preview_mode(
             let Some(InputMode::CommandPreview { pending }) = state.modes.pop() else {
                 return InputModeResult::Handled;
             };
-            confirm_command_preview(state, source, pending);
+            confirm_command_preview(state, source, diff_source, pending);
             InputModeResult::Handled
         }
         KeyEvent {
@@ -467,6 +607,16 @@ fn handle_command_preview_mode(
             copy_pending_command(state);
             InputModeResult::Handled
         }
+        KeyEvent {
+            code: KeyCode::Char('i'),
+            modifiers: KeyModifiers::NONE,
+            ..
+        } => {
+            if let Some(InputMode::CommandPreview { pending }) = state.modes.active_mut() {
+                pending.toggle_immutability();
+            }
+            InputModeResult::Handled
+        }
         _ => InputModeResult::Handled,
     }
 }
@@ -476,6 +626,13 @@ fn handle_jj_command_mode(
     repository: Option<&Path>,
     key: KeyEvent,
 ) -> InputModeResult {
+    if let Some(direction) = completion_direction(key) {
+        complete_prompt(state, repository, direction);
+        return InputModeResult::Handled;
+    }
+    if dismiss_completion_popup(state, key) {
+        return InputModeResult::Handled;
+    }
     match key {
         KeyEvent {
             code: KeyCode::Esc, ..
@@ -542,6 +699,7 @@ fn open_jj_command_mode(state: &mut AppState) {
 }
 
 fn open_jj_command_mode_with_input(state: &mut AppState, input: String) {
+    reset_prompt_completion(state);
     state
         .modes
         .push(InputMode::JjCommand { input, error: None });
@@ -635,8 +793,9 @@ fn open_abandon_preview(state: &mut AppState, abandon_source: &JjAbandon) {
     };
 
     let preview = abandon_source
-        .spec_for(&AbandonQuery::new(rev))
+        .spec_for(&AbandonQuery::new(rev.clone()))
         .command_preview();
+    let preview = with_immutable_target_warning(preview, log, &[rev]);
     state.modes.push(InputMode::CommandPreview {
         pending: PendingCommandPreview::abandon(preview),
     });
@@ -652,6 +811,7 @@ fn open_new_preview(state: &mut AppState, new_source: &JjNew) {
         return;
     }
 
+    // `jj new` only adds a child, so immutable parents are expected and need no warning.
     let preview = new_source
         .spec_for(&NewQuery::new(parents))
         .command_preview();
@@ -669,7 +829,10 @@ fn open_edit_preview(state: &mut AppState, edit_source: &JjEdit) {
         return;
     };
 
-    let preview = edit_source.spec_for(&EditQuery::new(rev)).command_preview();
+    let preview = edit_source
+        .spec_for(&EditQuery::new(rev.clone()))
+        .command_preview();
+    let preview = with_immutable_target_warning(preview, log, &[rev]);
     state.modes.push(InputMode::CommandPreview {
         pending: PendingCommandPreview::edit(preview),
     });
@@ -708,8 +871,9 @@ fn selected_command_copy_action(state: &mut AppState) -> Option<CommandHistoryAc
 
 fn handle_view_options_mode(
     state: &mut AppState,
-    source: &JjLog,
+    source: &mut JjLog,
     diff_source: &JjDiff,
+    operation_source: &JjOperation,
     key: KeyEvent,
 ) -> InputModeResult {
     match key {
@@ -733,7 +897,7 @@ fn handle_view_options_mode(
             modifiers: KeyModifiers::NONE,
             ..
         } => {
-            move_view_options_selection(state, MenuDirection::Previous);
+            move_view_options_selection(state, source, MenuDirection::Previous);
             InputModeResult::Handled
         }
         KeyEvent {
@@ -745,21 +909,32 @@ fn handle_view_options_mode(
             modifiers: KeyModifiers::NONE,
             ..
         } => {
-            move_view_options_selection(state, MenuDirection::Next);
+            move_view_options_selection(state, source, MenuDirection::Next);
             InputModeResult::Handled
         }
         KeyEvent {
             code: KeyCode::Enter,
             ..
         } => {
-            let selected = selected_view_option(state);
+            let selected = selected_view_option(state, source);
             state.modes.pop();
             match selected {
-                Some(ViewOptionRow::LogTemplate) => {
+                Some(ViewOptionRow::Log(LogViewOptionRow::Template)) => {
                     open_template_selector(&mut state.modes, source);
                 }
-                Some(ViewOptionRow::DiffFormat(format)) => {
-                    apply_diff_format_option(state, diff_source, format);
+                Some(ViewOptionRow::Log(LogViewOptionRow::Preview)) => cycle_preview_mode(state),
+                Some(ViewOptionRow::Log(LogViewOptionRow::DefaultRevset)) => {
+                    apply_revset_preset(state, source, None, SystemJjCommandRunner);
+                }
+                Some(ViewOptionRow::Log(LogViewOptionRow::RevsetPreset(preset))) => {
+                    apply_revset_preset(state, source, Some(preset), SystemJjCommandRunner);
+                }
+                Some(ViewOptionRow::Diff(row)) => apply_diff_option(state, diff_source, row),
+                Some(ViewOptionRow::OperationDiffPatch) => {
+                    toggle_operation_diff_patch(state, operation_source);
+                }
+                Some(ViewOptionRow::OperationLogFilter(row)) => {
+                    apply_operation_log_filter(state, row);
                 }
                 Some(ViewOptionRow::Placeholder) | None => {}
             }
@@ -769,118 +944,102 @@ fn handle_view_options_mode(
     }
 }
 
-fn handle_diff_file_list_mode(state: &mut AppState, key: KeyEvent) -> InputModeResult {
-    match key {
-        KeyEvent {
-            code: KeyCode::Esc | KeyCode::Backspace,
-            ..
-        }
-        | KeyEvent {
-            code: KeyCode::Char('q'),
-            modifiers: KeyModifiers::NONE,
-            ..
-        } => {
-            state.modes.pop();
-            InputModeResult::Handled
-        }
-        KeyEvent {
-            code: KeyCode::Up, ..
-        }
-        | KeyEvent {
-            code: KeyCode::Char('k'),
-            modifiers: KeyModifiers::NONE,
-            ..
-        } => {
-            move_diff_file_list_selection(state, MenuDirection::Previous);
-            InputModeResult::Handled
-        }
-        KeyEvent {
-            code: KeyCode::Down,
-            ..
+fn apply_diff_option(state: &mut AppState, diff_source: &JjDiff, row: DiffViewOptionRow) {
+    let AppView::Diff { query, .. } = state.views.active() else {
+        return;
+    };
+    let options = query.options().clone();
+    let options = match row {
+        DiffViewOptionRow::Format(format) => options.with_format(format).with_tool(None),
+        DiffViewOptionRow::Context => {
+            let context = next_diff_context(options.context);
+            options.with_context(context)
         }
-        | KeyEvent {
-            code: KeyCode::Char('j'),
-            modifiers: KeyModifiers::NONE,
-            ..
-        } => {
-            move_diff_file_list_selection(state, MenuDirection::Next);
-            InputModeResult::Handled
+        DiffViewOptionRow::Whitespace => {
+            let whitespace = options.whitespace.next();
+            options.with_whitespace(whitespace)
         }
-        KeyEvent {
-            code: KeyCode::Enter,
-            ..
-        } => {
-            apply_diff_file_list_selection(state);
-            InputModeResult::Handled
+        DiffViewOptionRow::Tool(tool) => {
+            let tool = tool.and_then(|tool| state.diff_tools().get(tool).cloned());
+            options.with_tool(tool)
         }
-        _ => InputModeResult::Handled,
-    }
-}
-
-fn open_diff_file_list(state: &mut AppState) {
-    let AppView::Diff { view, .. } = state.views.active() else {
-        return;
     };
-    let selected = view.selected_file_index().unwrap_or_default();
-    state.modes.push(InputMode::DiffFileList { selected });
+    apply_diff_options_with_runner(state, diff_source, options, SystemJjCommandRunner);
 }
 
-fn move_diff_file_list_selection(state: &mut AppState, direction: MenuDirection) {
-    let row_count = active_diff_file_count(state);
-    let Some(InputMode::DiffFileList { selected }) = state.modes.active_mut() else {
-        return;
+/// Reloads the active diff with `options`, keeping the previous output when `jj` fails.
+fn apply_diff_options_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    diff_source: &JjDiff,
+    options: DiffOptions,
+    runner: R,
+) {
+    let new_query = match state.views.active() {
+        AppView::Diff { query, .. } => query.with_options(options),
+        _ => return,
     };
-    if row_count == 0 {
-        *selected = 0;
-        return;
-    }
 
-    *selected = wrapped_selection(*selected, row_count, direction);
-}
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(SourceView::Diff, SourceAction::Refresh),
+    );
+    let result = diff_source.load_query_with_runner(&new_query, &mut runner);
 
-fn active_diff_file_count(state: &AppState) -> usize {
-    match state.views.active() {
-        AppView::Diff { view, .. } => view.file_count(),
-        _ => 0,
+    let AppView::Diff { view, query } = state.views.active_mut() else {
+        return;
+    };
+    match result {
+        Ok(snapshot) => {
+            let options = new_query.options();
+            view.set_side_by_side(
+                options.format == DiffFormat::SideBySide && options.tool.is_none(),
+            );
+            *query = new_query;
+            view.refresh(snapshot);
+        }
+        Err(error) => view.show_error(error.to_string()),
     }
 }
 
-fn apply_diff_file_list_selection(state: &mut AppState) {
-    let selected = match state.modes.active() {
-        Some(InputMode::DiffFileList { selected }) => *selected,
-        _ => return,
-    };
-    state.modes.pop();
-
-    let AppView::Diff { view, .. } = state.views.active_mut() else {
+fn apply_operation_log_filter(state: &mut AppState, row: OperationLogFilterRow) {
+    let AppView::OperationLog { view } = state.views.active_mut() else {
         return;
     };
-    view.select_file_index(selected);
+    match row {
+        OperationLogFilterRow::MyUser => {
+            if let Some(user) = &state.operation_user {
+                view.toggle_user_filter(user);
+            }
+        }
+        OperationLogFilterRow::Host => view.cycle_host_filter(),
+        OperationLogFilterRow::TimeWindow => view.cycle_time_window(operation_log::unix_now()),
+        OperationLogFilterRow::JkOnly => view.toggle_jk_only(),
+    }
 }
 
-fn apply_diff_format_option(state: &mut AppState, diff_source: &JjDiff, format: DiffFormat) {
-    apply_diff_format_option_with_runner(state, diff_source, format, SystemJjCommandRunner);
+fn toggle_operation_diff_patch(state: &mut AppState, operation_source: &JjOperation) {
+    toggle_operation_diff_patch_with_runner(state, operation_source, SystemJjCommandRunner);
 }
 
-fn apply_diff_format_option_with_runner<R: JjCommandRunner>(
+fn toggle_operation_diff_patch_with_runner<R: JjCommandRunner>(
     state: &mut AppState,
-    diff_source: &JjDiff,
-    format: DiffFormat,
+    operation_source: &JjOperation,
     runner: R,
 ) {
     let new_query = match state.views.active() {
-        AppView::Diff { query, .. } => query.with_format(format),
+        AppView::OperationDiff { query, .. } => query.with_patch(!query.patch()),
         _ => return,
     };
 
     let mut runner = RecordingJjCommandRunner::new(
         runner,
         &mut state.history,
-        CommandSource::new(SourceView::Diff, SourceAction::Refresh),
+        CommandSource::new(SourceView::OperationDiff, SourceAction::Refresh),
     );
-    let result = diff_source.load_query_with_runner(&new_query, &mut runner);
+    let result = operation_source.load_query_with_runner(&new_query, &mut runner);
 
-    let AppView::Diff { view, query } = state.views.active_mut() else {
+    let AppView::OperationDiff { view, query } = state.views.active_mut() else {
         return;
     };
     match result {
@@ -901,10 +1060,41 @@ fn open_command_discovery(state: &mut AppState) {
     });
 }
 
-fn open_view_options(state: &mut AppState) {
+fn open_view_options(
+    state: &mut AppState,
+    source: &mut JjLog,
+    diff_source: &JjDiff,
+    operation_source: &JjOperation,
+) {
+    open_view_options_with_runner(
+        state,
+        source,
+        diff_source,
+        operation_source,
+        SystemJjCommandRunner,
+    );
+}
+
+fn open_view_options_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    source: &mut JjLog,
+    diff_source: &JjDiff,
+    operation_source: &JjOperation,
+    runner: R,
+) {
     if matches!(state.views.active(), AppView::CommandHistory { .. }) {
         return;
     }
+    match state.views.active() {
+        AppView::Log(_) => load_revset_presets(state, source, runner),
+        AppView::Diff { .. } if state.diff_tools.is_none() => {
+            load_diff_tools(state, diff_source, runner);
+        }
+        AppView::OperationLog { .. } if state.operation_user.is_none() => {
+            operation_log::load_operation_user(state, operation_source, runner);
+        }
+        _ => {}
+    }
 
     state.modes.push(InputMode::ViewOptions {
         context: active_binding_context(state),
@@ -914,9 +1104,16 @@ fn open_view_options(state: &mut AppState) {
 
 fn active_view_option_index(state: &AppState) -> usize {
     match state.views.active() {
-        AppView::Diff { query, .. } => view_option_rows(BindingContext::Diff)
+        AppView::Diff { query, .. } => diff_view_option_rows(state.diff_tools().len())
             .iter()
-            .position(|row| *row == ViewOptionRow::DiffFormat(query.format()))
+            .position(|row| match &query.options().tool {
+                Some(tool) => {
+                    *row == DiffViewOptionRow::Tool(
+                        state.diff_tools().iter().position(|name| name == tool),
+                    )
+                }
+                None => *row == DiffViewOptionRow::Format(query.format()),
+            })
             .unwrap_or_default(),
         _ => 0,
     }
@@ -958,11 +1155,14 @@ fn move_command_discovery_scroll(state: &mut AppState, direction: MenuDirection)
     };
 }
 
-fn move_view_options_selection(state: &mut AppState, direction: MenuDirection) {
+fn move_view_options_selection(state: &mut AppState, source: &JjLog, direction: MenuDirection) {
+    let operation_diff = matches!(state.views.active(), AppView::OperationDiff { .. });
+    let diff_tool_count = state.diff_tools().len();
     let Some(InputMode::ViewOptions { context, selected }) = state.modes.active_mut() else {
         return;
     };
-    let row_count = view_option_rows(*context).len();
+    let row_count =
+        active_view_option_rows(*context, operation_diff, source, diff_tool_count).len();
     if row_count == 0 {
         *selected = 0;
         return;
@@ -971,11 +1171,97 @@ fn move_view_options_selection(state: &mut AppState, direction: MenuDirection) {
     *selected = wrapped_selection(*selected, row_count, direction);
 }
 
-fn selected_view_option(state: &AppState) -> Option<ViewOptionRow> {
+fn selected_view_option(state: &AppState, source: &JjLog) -> Option<ViewOptionRow> {
     let Some(InputMode::ViewOptions { context, selected }) = state.modes.active() else {
         return None;
     };
-    view_option_rows(*context).get(*selected).copied()
+    let operation_diff = matches!(state.views.active(), AppView::OperationDiff { .. });
+    active_view_option_rows(*context, operation_diff, source, state.diff_tools().len())
+        .get(*selected)
+        .copied()
+}
+
+/// Returns option rows for the active view; operation diff shares the inspection keymap but owns
+/// its own `--patch` row, the log adds one row per loaded revset preset, and the diff adds one
+/// row per configured diff tool.
+fn active_view_option_rows(
+    context: BindingContext,
+    operation_diff: bool,
+    source: &JjLog,
+    diff_tool_count: usize,
+) -> Vec<ViewOptionRow> {
+    if operation_diff {
+        OPERATION_DIFF_VIEW_OPTION_ROWS.to_vec()
+    } else {
+        view_option_rows(context, source.revset_presets().len(), diff_tool_count)
+    }
+}
+
+/// Loads configured diff tools the first time diff options open; a failed load retries next time.
+fn load_diff_tools<R: JjCommandRunner>(state: &mut AppState, diff_source: &JjDiff, runner: R) {
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(SourceView::Diff, SourceAction::LoadDiffTools).with_key("V"),
+    );
+    match diff_source.load_diff_tools_with_runner(&mut runner) {
+        Ok(tools) => state.diff_tools = Some(tools),
+        Err(error) => tracing::warn!(%error, "failed to load diff tools"),
+    }
+}
+
+/// Reloads `[jk.revsets]` so presets edited in jj config show up without restarting.
+fn load_revset_presets<R: JjCommandRunner>(state: &mut AppState, source: &mut JjLog, runner: R) {
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(SourceView::Log, SourceAction::LoadRevsetPresets).with_key("V"),
+    );
+    match source.load_revset_presets_with_runner(&mut runner) {
+        Ok(presets) => *source = source.clone().with_revset_presets(presets),
+        Err(error) => tracing::warn!(%error, "failed to load revset presets"),
+    }
+}
+
+/// Reloads the log with a preset revset, or the configured default when `preset` is `None`.
+fn apply_revset_preset<R: JjCommandRunner>(
+    state: &mut AppState,
+    source: &mut JjLog,
+    preset: Option<usize>,
+    runner: R,
+) {
+    if !matches!(state.views.active(), AppView::Log(_)) {
+        return;
+    }
+    let preset = preset.and_then(|index| source.revset_presets().get(index).cloned());
+    let next_source = match &preset {
+        Some(preset) => source.clone().with_revset_preset(preset),
+        None => source.clone().without_revset(),
+    };
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(SourceView::Log, SourceAction::SetRevset).with_key("V"),
+    );
+    let result = next_source.load_with_runner(&mut runner);
+    drop(runner);
+
+    let AppView::Log(log) = state.views.active_mut() else {
+        return;
+    };
+    match result {
+        Ok(snapshot) => {
+            log.refresh(snapshot);
+            match &preset {
+                Some(preset) => {
+                    log.show_status(format!("revset {}: {}", preset.name(), preset.revset()));
+                }
+                None => log.show_status("revset reset to configured default"),
+            }
+            *source = next_source;
+        }
+        Err(error) => log.show_error(error.to_string()),
+    }
 }
 
 fn handle_template_mode(
@@ -1065,6 +1351,9 @@ enum SearchDirection {
 
 fn apply_search_submit(state: &mut AppState, action: SearchSubmit) {
     match (state.views.active_mut(), action) {
+        (AppView::Log(log), SearchSubmit::Inspection(query)) => {
+            log.search(&query);
+        }
         (AppView::Diff { view, .. }, SearchSubmit::Diff(query)) => {
             let _ = view.apply(DiffAction::Search(query));
         }
@@ -1137,8 +1426,11 @@ fn apply_search_action(state: &mut AppState, direction: SearchDirection) {
             };
             let _ = view.apply(action);
         }
-        AppView::Log(_)
-        | AppView::Workspaces { .. }
+        AppView::Log(log) => match direction {
+            SearchDirection::Next => log.search_next(),
+            SearchDirection::Previous => log.search_previous(),
+        },
+        AppView::Workspaces { .. }
         | AppView::CommandHistory { .. }
         | AppView::OperationLog { .. } => {}
     }
@@ -1177,9 +1469,17 @@ fn apply_action(
     action: jk_tui::log_view::LogAction,
 ) -> AppLoop {
     let transition = {
-        let AppState { views, history, .. } = state;
+        let AppState {
+            views,
+            history,
+            checkpoints,
+            output_cache,
+            ..
+        } = state;
         match views.active_mut() {
-            AppView::Log(log) => apply_log_action(log, history, source, diff_source, action),
+            AppView::Log(log) => {
+                apply_log_action(log, history, output_cache, source, diff_source, action)
+            }
             AppView::Diff { view, query } => {
                 apply_diff_action(view, query, history, diff_source, action)
             }
@@ -1199,7 +1499,7 @@ fn apply_action(
             AppView::CommandHistoryDetails { view } => apply_static_rendered_action(view, action),
             AppView::CommandOutput { view, .. } => apply_static_rendered_action(view, action),
             AppView::OperationLog { view } => {
-                apply_operation_log_action(view, history, operation_source, action)
+                apply_operation_log_action(view, history, checkpoints, operation_source, action)
             }
             AppView::WorkspaceStatus { view, query } => apply_workspace_inspection_action(
                 view,
@@ -1247,7 +1547,8 @@ fn apply_action(
     match transition {
         AppTransition::Continue => AppLoop::Continue,
         AppTransition::Push(view) => {
-            state.views.push(view);
+            state.push_view(view);
+            show_review_notes(state, diff_source);
             AppLoop::Continue
         }
         AppTransition::PushLog {
@@ -1275,7 +1576,7 @@ fn apply_action(
             AppLoop::Continue
         }
         AppTransition::OpenViewOptions => {
-            open_view_options(state);
+            open_view_options(state, source, diff_source, operation_source);
             AppLoop::Continue
         }
         AppTransition::Quit => AppLoop::Quit,
@@ -1286,6 +1587,7 @@ fn apply_action(
 fn apply_log_action(
     log: &mut LogView,
     history: &mut CommandHistory,
+    output_cache: &mut JjOutputCache,
     source: &mut JjLog,
     diff_source: &JjDiff,
     action: jk_tui::log_view::LogAction,
@@ -1304,35 +1606,64 @@ fn apply_log_action(
         }
         ActionResult::SwitchLog => switch_log_command(log, history, source, JjLogCommand::Log),
         ActionResult::DrillElision => return drill_log_elision(log, history, source),
+        ActionResult::LoadMore => load_more_log(log, history, source),
         ActionResult::Quit => return AppTransition::Quit,
         _ => {}
     }
 
     if action == jk_tui::log_view::LogAction::OpenDiff {
-        let Some(change_id) = log.selected_revision_id().map(ToOwned::to_owned) else {
-            return AppTransition::Continue;
-        };
-
-        let query = DiffQuery::Revision {
-            rev: change_id,
-            format: DiffFormat::Patch,
-        };
-        let mut runner = recording_runner(
+        return open_log_diff_with_runner(
+            log,
             history,
-            CommandSource::new(SourceView::Log, SourceAction::OpenDiff),
+            output_cache,
+            diff_source,
+            SystemJjCommandRunner,
         );
-        match diff_source.load_query_with_runner(&query, &mut runner) {
-            Ok(snapshot) => {
-                let diff = DiffView::new(snapshot);
-                return AppTransition::Push(AppView::Diff { view: diff, query });
-            }
-            Err(error) => log.show_error(error.to_string()),
-        }
     }
 
     AppTransition::Continue
 }
 
+/// Loads the diff `d` opens from the log.
+///
+/// Views opened from the log read the working-copy snapshot the last log refresh took, so
+/// reopening the same diff at the same operation replays cached output instead of running `jj`.
+fn open_log_diff_with_runner<R: JjCommandRunner>(
+    log: &mut LogView,
+    history: &mut CommandHistory,
+    output_cache: &mut JjOutputCache,
+    diff_source: &JjDiff,
+    runner: R,
+) -> AppTransition {
+    let query = match log_diff_query(log) {
+        Ok(Some(query)) => query,
+        Ok(None) => return AppTransition::Continue,
+        Err(status) => {
+            log.show_status(status);
+            return AppTransition::Continue;
+        }
+    };
+    let mut runner = RecordingJjCommandRunner::new(
+        CachingJjCommandRunner::new(runner, output_cache),
+        history,
+        CommandSource::new(SourceView::Log, SourceAction::OpenDiff),
+    );
+    match diff_source
+        .clone()
+        .with_working_copy(WorkingCopyPolicy::Ignore)
+        .load_query_with_runner(&query, &mut runner)
+    {
+        Ok(snapshot) => {
+            let diff = DiffView::new(snapshot);
+            AppTransition::Push(AppView::Diff { view: diff, query })
+        }
+        Err(error) => {
+            log.show_error(error.to_string());
+            AppTransition::Continue
+        }
+    }
+}
+
 fn push_selected_show(state: &mut AppState, show_source: &JjShow) {
     let change_id = {
         let AppView::Log(log) = state.views.active_mut() else {
@@ -1345,11 +1676,16 @@ fn push_selected_show(state: &mut AppState, show_source: &JjShow) {
     };
 
     let query = ShowQuery::from(change_id);
-    let mut runner = recording_runner(
+    let mut runner = cached_recording_runner(
         &mut state.history,
+        &mut state.output_cache,
         CommandSource::new(SourceView::Log, SourceAction::OpenShow),
     );
-    match show_source.load_query_with_runner(&query, &mut runner) {
+    match show_source
+        .clone()
+        .with_working_copy(WorkingCopyPolicy::Ignore)
+        .load_query_with_runner(&query, &mut runner)
+    {
         Ok(snapshot) => {
             state.views.push(AppView::Show {
                 view: RenderedView::new(snapshot),
@@ -1364,33 +1700,105 @@ fn push_selected_show(state: &mut AppState, show_source: &JjShow) {
     }
 }
 
-fn push_selected_evolog(state: &mut AppState, evolog_source: &JjEvolog) {
-    let change_id = {
-        let AppView::Log(log) = state.views.active_mut() else {
-            return;
-        };
-        let Some(change_id) = log.selected_revision_id().map(ToOwned::to_owned) else {
-            return;
-        };
-        change_id
-    };
+/// Returns the diff `d` opens: first mark to second mark, or else the selected revision.
+///
+/// More than two marks return the status to show instead of guessing a range.
+fn log_diff_query(log: &LogView) -> Result<Option<DiffQuery>, String> {
+    let marks = log.marked_revision_ids();
+    if let Some((from, to)) = marked_pair(&marks, "revision")? {
+        return Ok(Some(DiffQuery::FromTo {
+            from: from.clone(),
+            to: to.clone(),
+            options: DiffOptions::default(),
+        }));
+    }
+    Ok(log.selected_revision_id().map(|rev| DiffQuery::Revision {
+        rev: rev.to_owned(),
+        options: DiffOptions::default(),
+    }))
+}
 
-    let query = EvologQuery::from(change_id);
-    let mut runner = recording_runner(
-        &mut state.history,
-        CommandSource::new(SourceView::Log, SourceAction::OpenEvolog),
-    );
-    match evolog_source.load_query_with_runner(&query, &mut runner) {
-        Ok(snapshot) => push_evolog_view(state, query, RenderedView::new(snapshot)),
-        Err(error) => {
-            if let AppView::Log(log) = state.views.active_mut() {
-                log.show_error(error.to_string());
-            }
-        }
-    }
+fn push_marked_interdiff(state: &mut AppState, diff_source: &JjDiff) {
+    push_marked_interdiff_with_runner(state, diff_source, SystemJjCommandRunner);
 }
 
-fn push_evolog_view(state: &mut AppState, query: EvologQuery, view: RenderedView) {
+/// Opens `jj interdiff` from the first marked revision to the second.
+fn push_marked_interdiff_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    diff_source: &JjDiff,
+    runner: R,
+) {
+    let AppView::Log(log) = state.views.active_mut() else {
+        return;
+    };
+    let marks = log.marked_revision_ids();
+    let [from, to] = marks.as_slice() else {
+        log.show_status("Mark two revisions to compare them with jj interdiff.");
+        return;
+    };
+
+    let query = DiffQuery::Interdiff {
+        from: from.clone(),
+        to: to.clone(),
+        options: DiffOptions::default(),
+    };
+    let mut runner = RecordingJjCommandRunner::new(
+        CachingJjCommandRunner::new(runner, &mut state.output_cache),
+        &mut state.history,
+        CommandSource::new(SourceView::Log, SourceAction::OpenInterdiff).with_key("D"),
+    );
+    match diff_source
+        .clone()
+        .with_working_copy(WorkingCopyPolicy::Ignore)
+        .load_query_with_runner(&query, &mut runner)
+    {
+        Ok(snapshot) => {
+            state.push_view(AppView::Diff {
+                view: DiffView::new(snapshot),
+                query,
+            });
+            show_review_notes(state, diff_source);
+        }
+        Err(error) => {
+            if let AppView::Log(log) = state.views.active_mut() {
+                log.show_error(error.to_string());
+            }
+        }
+    }
+}
+
+fn push_selected_evolog(state: &mut AppState, evolog_source: &JjEvolog) {
+    let change_id = {
+        let AppView::Log(log) = state.views.active_mut() else {
+            return;
+        };
+        let Some(change_id) = log.selected_revision_id().map(ToOwned::to_owned) else {
+            return;
+        };
+        change_id
+    };
+
+    let query = EvologQuery::from(change_id);
+    let mut runner = cached_recording_runner(
+        &mut state.history,
+        &mut state.output_cache,
+        CommandSource::new(SourceView::Log, SourceAction::OpenEvolog),
+    );
+    match evolog_source
+        .clone()
+        .with_working_copy(WorkingCopyPolicy::Ignore)
+        .load_query_with_runner(&query, &mut runner)
+    {
+        Ok(snapshot) => push_evolog_view(state, query, RenderedView::new(snapshot)),
+        Err(error) => {
+            if let AppView::Log(log) = state.views.active_mut() {
+                log.show_error(error.to_string());
+            }
+        }
+    }
+}
+
+fn push_evolog_view(state: &mut AppState, query: EvologQuery, view: RenderedView) {
     if !matches!(state.views.active(), AppView::Log(_)) {
         return;
     }
@@ -1426,6 +1834,7 @@ fn push_selected_operation_show(state: &mut AppState, operation_source: &JjOpera
 fn apply_operation_log_action(
     view: &mut OperationLogView,
     history: &mut CommandHistory,
+    checkpoints: &SessionCheckpoints,
     operation_source: &JjOperation,
     action: jk_tui::log_view::LogAction,
 ) -> AppTransition {
@@ -1435,9 +1844,9 @@ fn apply_operation_log_action(
         jk_tui::log_view::LogAction::ScrollPreviousLine => OperationLogAction::Previous,
         jk_tui::log_view::LogAction::ScrollNextLine => OperationLogAction::Next,
         jk_tui::log_view::LogAction::PagePrevious => OperationLogAction::PagePrevious,
-        jk_tui::log_view::LogAction::PageNext | jk_tui::log_view::LogAction::ToggleMark => {
-            OperationLogAction::PageNext
-        }
+        jk_tui::log_view::LogAction::PageNext => OperationLogAction::PageNext,
+        jk_tui::log_view::LogAction::ToggleMark => OperationLogAction::ToggleMark,
+        jk_tui::log_view::LogAction::ClearMarks => OperationLogAction::ClearMarks,
         jk_tui::log_view::LogAction::First => OperationLogAction::First,
         jk_tui::log_view::LogAction::Last => OperationLogAction::Last,
         jk_tui::log_view::LogAction::Refresh => OperationLogAction::Refresh,
@@ -1451,7 +1860,9 @@ fn apply_operation_log_action(
     };
 
     match view.apply(operation_action) {
-        OperationLogActionResult::Refresh => refresh_operation_log(view, history, operation_source),
+        OperationLogActionResult::Refresh => {
+            refresh_operation_log(view, history, checkpoints, operation_source);
+        }
         OperationLogActionResult::OperationShow { operation_id } => {
             let query = OperationQuery::show(operation_id);
             return operation_rendered_transition(
@@ -1474,6 +1885,17 @@ fn apply_operation_log_action(
                 OperationRenderedKind::Diff,
             );
         }
+        OperationLogActionResult::OperationDiffFromTo { from, to } => {
+            let query = OperationQuery::diff_from_to(from, to);
+            return operation_rendered_transition(
+                history,
+                operation_source,
+                query,
+                SourceView::OperationLog,
+                SourceAction::OperationDiff,
+                OperationRenderedKind::Diff,
+            );
+        }
         OperationLogActionResult::ReturnBack => return AppTransition::PopView,
         OperationLogActionResult::Quit => return AppTransition::Quit,
         OperationLogActionResult::Continue => {}
@@ -1842,11 +2264,13 @@ impl Drop for TerminalRestore {
 #[cfg(test)]
 mod tests {
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use jk_cli::{DiffWhitespace, JjRestore, RestoreQuery};
     use jk_tui::workspaces_view::WorkspaceViewRow;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
 
     use super::*;
+    use crate::diff_file_list::diff_file_list_lines;
     use crate::test_support::*;
 
     #[test]
@@ -1871,6 +2295,49 @@ mod tests {
         assert_eq!(state.command_history().records().count(), 1);
     }
 
+    #[test]
+    fn reopening_the_same_log_diff_replays_cached_output() {
+        let repository = std::env::temp_dir().join(format!("jk-log-diff-{}", std::process::id()));
+        let heads = repository.join(".jj/repo/op_heads/heads");
+        std::fs::create_dir_all(&heads).expect("create op heads");
+        std::fs::write(heads.join("aaaa"), "").expect("write op head");
+        let diff_source = JjDiff::default().with_repository(&repository);
+        let AppView::Log(mut log) = log_app_view("abc") else {
+            unreachable!("log_app_view builds a log view");
+        };
+        let mut history = CommandHistory::new(4);
+        let mut output_cache = JjOutputCache::default();
+
+        let first = open_log_diff_with_runner(
+            &mut log,
+            &mut history,
+            &mut output_cache,
+            &diff_source,
+            SequencedRunner::successes(vec![
+                output(0, "diff --git a/f b/f\n", ""),
+                output(0, "f | 1 +\n", ""),
+            ]),
+        );
+        let second = open_log_diff_with_runner(
+            &mut log,
+            &mut history,
+            &mut output_cache,
+            &diff_source,
+            SequencedRunner::successes(vec![]),
+        );
+        let _ = std::fs::remove_dir_all(&repository);
+
+        let (
+            AppTransition::Push(AppView::Diff { view: first, .. }),
+            AppTransition::Push(AppView::Diff { view: second, .. }),
+        ) = (first, second)
+        else {
+            panic!("both opens push a diff view");
+        };
+        assert_eq!(first, second);
+        assert_eq!(history.records().count(), 4);
+    }
+
     #[test]
     fn opening_command_history_from_log_pushes_snapshot_without_recording() {
         let mut history = CommandHistory::new(4);
@@ -2037,7 +2504,12 @@ mod tests {
             view: CommandHistoryView::new(CommandHistorySnapshot::new(Vec::new())),
         });
 
-        open_view_options(&mut state);
+        open_view_options(
+            &mut state,
+            &mut JjLog::default(),
+            &JjDiff::default(),
+            &JjOperation::default(),
+        );
 
         assert_eq!(state.modes.active(), None);
     }
@@ -2099,14 +2571,28 @@ mod tests {
         open_command_history_operation_with_runner(
             &mut state,
             &source,
-            SequencedRunner::successes(vec![output(
-                0,
-                "@ abc123def456 user@example.test now\n│  current operation\n",
-                "",
-            )]),
+            SequencedRunner::successes(vec![
+                output(
+                    0,
+                    "@ abc123def456 user@example.test now\n│  current operation\n",
+                    "",
+                ),
+                output(
+                    0,
+                    concat!(
+                        "@ {\"id\":\"abc123def456\",\"parents\":[],\"user\":\"user@example.test\",",
+                        "\"time_start\":\"s\",\"time_end\":\"e\",",
+                        "\"description\":\"current operation\",\"tags\":\"\",\"current\":true}\n",
+                    ),
+                    "",
+                ),
+            ]),
         );
 
-        assert!(matches!(state.views.active(), AppView::OperationLog { .. }));
+        let AppView::OperationLog { view } = state.views.active() else {
+            panic!("expected operation log view");
+        };
+        assert_eq!(view.selected_operation_id(), Some("abc123def456"));
         let newest = state.command_history().records().last().expect("record");
         assert_eq!(newest.source.view, SourceView::CommandHistory);
         assert_eq!(newest.source.action, SourceAction::OperationLog);
@@ -2531,6 +3017,8 @@ mod tests {
             &mut source,
             &JjDiff::default(),
             &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
             None,
             KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
         );
@@ -2546,6 +3034,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn log_search_moves_between_matches_until_esc_restores_new_preview() {
+        let mut state = AppState::new(log_app_view_with_changes(["aaa", "xbb", "ccc", "xdd"]));
+        let mut source = JjLog::default();
+        state.modes.push(InputMode::InspectionSearch {
+            query: "x".to_owned(),
+        });
+        let _ = handle_input_mode(
+            &mut state,
+            &mut source,
+            &JjDiff::default(),
+            &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
+            None,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        );
+        let AppView::Log(mut expected) = log_app_view_with_changes(["aaa", "xbb", "ccc", "xdd"])
+        else {
+            panic!("expected log view");
+        };
+        expected.search("x");
+        assert_eq!(state.views.active(), &AppView::Log(expected.clone()));
+
+        let (diff, evolog, show, status) = (
+            JjDiff::default(),
+            JjEvolog::default(),
+            JjShow::default(),
+            JjStatus::default(),
+        );
+        let (abandon, new_change, edit) =
+            (JjAbandon::default(), JjNew::default(), JjEdit::default());
+        let (operation, recovery, workspaces) = (
+            JjOperation::default(),
+            JjRecovery::default(),
+            JjWorkspaces::default(),
+        );
+        let mut sources = AppSources {
+            log: &mut source,
+            diff: &diff,
+            evolog: &evolog,
+            show: &show,
+            status: &status,
+            abandon: &abandon,
+            new_change: &new_change,
+            edit: &edit,
+            operation: &operation,
+            recovery: &recovery,
+            workspaces: &workspaces,
+        };
+        let mut press = |state: &mut AppState, code| {
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            let app_key = AppKey::from_crossterm_in(key, active_binding_context(state));
+            dispatch_app_key(state, &mut sources, key, app_key)
+        };
+
+        let _ = press(&mut state, KeyCode::Char('n'));
+        expected.search_next();
+        assert_eq!(state.views.active(), &AppView::Log(expected.clone()));
+        assert_eq!(state.modes.active(), None);
+
+        assert_eq!(press(&mut state, KeyCode::Esc), DispatchResult::Continue);
+        let _ = press(&mut state, KeyCode::Char('n'));
+        assert!(matches!(
+            state.modes.active(),
+            Some(InputMode::CommandPreview { .. })
+        ));
+    }
+
     #[test]
     fn command_discovery_opens_for_active_context() {
         let mut state = AppState::new(diff_app_view("aaa"));
@@ -2577,6 +3134,8 @@ mod tests {
             &mut source,
             &JjDiff::default(),
             &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
             None,
             KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE),
         );
@@ -2654,6 +3213,8 @@ mod tests {
             &mut source,
             &JjDiff::default(),
             &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
             None,
             KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
         );
@@ -2678,6 +3239,8 @@ mod tests {
             &mut source,
             &JjDiff::default(),
             &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
             None,
             KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE),
         );
@@ -2730,6 +3293,8 @@ mod tests {
             &mut source,
             &JjDiff::default(),
             &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
             None,
             KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL),
         );
@@ -2758,6 +3323,8 @@ mod tests {
             &mut source,
             &JjDiff::default(),
             &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
             None,
             KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
         );
@@ -2791,6 +3358,8 @@ mod tests {
             &mut source,
             &JjDiff::default(),
             &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
             None,
             KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
         );
@@ -2922,6 +3491,7 @@ mod tests {
         confirm_command_preview_with_runner(
             &mut state,
             &mut source,
+            &JjDiff::default(),
             PendingCommandPreview::describe(preview),
             runner,
         );
@@ -2947,6 +3517,47 @@ mod tests {
         assert_eq!(records[2].source.action, SourceAction::Refresh);
     }
 
+    #[test]
+    fn confirming_restore_from_diff_reloads_the_diff() {
+        let mut state = AppState::new(AppView::Diff {
+            view: real_diff_view("aaa"),
+            query: diff_query("aaa"),
+        });
+        let mut source = JjLog::default();
+        let preview = JjRestore::default()
+            .spec_for(&RestoreQuery::changes_in("aaa", ["src/a.rs"]))
+            .command_preview();
+        let runner = SequencedRunner::successes(vec![
+            output(0, "111111111111\n", ""),
+            output(0, "Restored 1 files.\n", ""),
+            output(0, "222222222222\n", ""),
+            output(0, "Modified regular file src/b.rs:\n b\n", ""),
+            output(0, "src/b.rs | 1 +\n", ""),
+        ]);
+
+        confirm_command_preview_with_runner(
+            &mut state,
+            &mut source,
+            &JjDiff::default(),
+            PendingCommandPreview::restore(preview),
+            runner,
+        );
+
+        let records = state.command_history().records().collect::<Vec<_>>();
+        assert_eq!(
+            records[0].command.title,
+            "jj restore --changes-in aaa src/a.rs"
+        );
+        assert_eq!(records[0].source.view, SourceView::Diff);
+        assert_eq!(records[0].source.action, SourceAction::RestorePaths);
+        assert_eq!(records[1].source.view, SourceView::Diff);
+        assert_eq!(records[1].source.action, SourceAction::Refresh);
+        let AppView::Diff { view, .. } = state.views.active() else {
+            panic!("diff stays active");
+        };
+        assert_eq!(view.file_paths(), ["src/b.rs"]);
+    }
+
     #[test]
     fn confirming_abandon_preview_records_destructive_mutation() {
         let mut state = AppState::new(log_app_view("abc123"));
@@ -2965,6 +3576,7 @@ mod tests {
         confirm_command_preview_with_runner(
             &mut state,
             &mut source,
+            &JjDiff::default(),
             PendingCommandPreview::abandon(preview),
             runner,
         );
@@ -3000,6 +3612,7 @@ mod tests {
         confirm_command_preview_with_runner(
             &mut state,
             &mut source,
+            &JjDiff::default(),
             PendingCommandPreview::new_change(preview),
             runner,
         );
@@ -3035,6 +3648,7 @@ mod tests {
         confirm_command_preview_with_runner(
             &mut state,
             &mut source,
+            &JjDiff::default(),
             PendingCommandPreview::edit(preview),
             runner,
         );
@@ -3070,6 +3684,7 @@ mod tests {
         confirm_command_preview_with_runner(
             &mut state,
             &mut source,
+            &JjDiff::default(),
             PendingCommandPreview::describe(preview),
             runner,
         );
@@ -3232,6 +3847,7 @@ mod tests {
         confirm_command_preview_with_runner(
             &mut state,
             &mut source,
+            &JjDiff::default(),
             PendingCommandPreview::undo(preview),
             runner,
         );
@@ -3245,11 +3861,75 @@ mod tests {
         assert_eq!(records[1].source.action, SourceAction::Refresh);
     }
 
+    #[test]
+    fn log_view_options_load_revset_presets_and_apply_one() {
+        let mut state = AppState::new(log_app_view("abc"));
+        let mut source = JjLog::default();
+
+        open_view_options_with_runner(
+            &mut state,
+            &mut source,
+            &JjDiff::default(),
+            &JjOperation::default(),
+            SequencedRunner::successes(vec![output(
+                0,
+                "jk.revsets.mine = \"mine()\"\njk.revsets.conflicts = \"conflicts()\"\n",
+                "",
+            )]),
+        );
+        for _ in 0..4 {
+            move_view_options_selection(&mut state, &source, MenuDirection::Next);
+        }
+        assert_eq!(
+            selected_view_option(&state, &source),
+            Some(ViewOptionRow::Log(LogViewOptionRow::RevsetPreset(1)))
+        );
+
+        state.modes.pop();
+        apply_revset_preset(
+            &mut state,
+            &mut source,
+            Some(1),
+            SequencedRunner::successes(vec![
+                output(0, "@  def def summary\n", ""),
+                output(
+                    0,
+                    "{\"change_id\":\"def\",\"commit_id\":\"c\",\"description\":\"def summary\"}\t\"\"\n",
+                    "",
+                ),
+            ]),
+        );
+
+        assert_eq!(source.revset(), Some("conflicts()"));
+        assert_eq!(source.revset_preset(), Some("conflicts"));
+        let AppView::Log(log) = state.views.active() else {
+            panic!("log stays active");
+        };
+        assert_eq!(log.title(), "jj log -r conflicts()  [conflicts]");
+        assert_eq!(
+            state
+                .command_history()
+                .records()
+                .map(|record| record.source.action.clone())
+                .collect::<Vec<_>>(),
+            [
+                SourceAction::LoadRevsetPresets,
+                SourceAction::SetRevset,
+                SourceAction::SetRevset
+            ]
+        );
+    }
+
     #[test]
     fn view_options_opens_for_active_context() {
         let mut state = AppState::new(diff_app_view("aaa"));
 
-        open_view_options(&mut state);
+        open_view_options(
+            &mut state,
+            &mut JjLog::default(),
+            &JjDiff::default(),
+            &JjOperation::default(),
+        );
 
         assert_eq!(
             state.modes.active(),
@@ -3266,11 +3946,16 @@ mod tests {
             view: diff_view("aaa"),
             query: DiffQuery::Revision {
                 rev: "aaa".to_owned(),
-                format: DiffFormat::Summary,
+                options: DiffFormat::Summary.into(),
             },
         });
 
-        open_view_options(&mut state);
+        open_view_options(
+            &mut state,
+            &mut JjLog::default(),
+            &JjDiff::default(),
+            &JjOperation::default(),
+        );
 
         assert_eq!(
             state.modes.active(),
@@ -3289,17 +3974,17 @@ mod tests {
             selected: 0,
         });
 
-        move_view_options_selection(&mut state, MenuDirection::Previous);
+        move_view_options_selection(&mut state, &JjLog::default(), MenuDirection::Previous);
 
         assert_eq!(
             state.modes.active(),
             Some(&InputMode::ViewOptions {
                 context: BindingContext::Diff,
-                selected: view_option_rows(BindingContext::Diff).len() - 1,
+                selected: diff_view_option_rows(0).len() - 1,
             })
         );
 
-        move_view_options_selection(&mut state, MenuDirection::Next);
+        move_view_options_selection(&mut state, &JjLog::default(), MenuDirection::Next);
 
         assert_eq!(
             state.modes.active(),
@@ -3321,32 +4006,12 @@ mod tests {
 
         open_diff_file_list(&mut state);
 
+        let Some(InputMode::DiffFileList { list }) = state.modes.active() else {
+            panic!("expected diff file list");
+        };
         assert_eq!(
-            state.modes.active(),
-            Some(&InputMode::DiffFileList { selected: 1 })
-        );
-    }
-
-    #[test]
-    fn diff_file_list_navigation_wraps_between_edges() {
-        let mut state = AppState::new(AppView::Diff {
-            view: real_diff_view("aaa"),
-            query: diff_query("aaa"),
-        });
-        state.modes.push(InputMode::DiffFileList { selected: 0 });
-
-        move_diff_file_list_selection(&mut state, MenuDirection::Previous);
-
-        assert_eq!(
-            state.modes.active(),
-            Some(&InputMode::DiffFileList { selected: 1 })
-        );
-
-        move_diff_file_list_selection(&mut state, MenuDirection::Next);
-
-        assert_eq!(
-            state.modes.active(),
-            Some(&InputMode::DiffFileList { selected: 0 })
+            diff_file_list_lines(list)[2..5],
+            ["   ▾ src/", "       a.rs", ">      b.rs"]
         );
     }
 
@@ -3356,7 +4021,7 @@ mod tests {
             view: real_diff_view("aaa"),
             query: diff_query("aaa"),
         });
-        state.modes.push(InputMode::DiffFileList { selected: 0 });
+        open_diff_file_list(&mut state);
         let mut source = JjLog::default();
 
         let result = handle_input_mode(
@@ -3364,6 +4029,8 @@ mod tests {
             &mut source,
             &JjDiff::default(),
             &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
             None,
             KeyEvent::new(KeyCode::Down, KeyModifiers::NONE),
         );
@@ -3374,6 +4041,8 @@ mod tests {
             &mut source,
             &JjDiff::default(),
             &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
             None,
             KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
         );
@@ -3386,21 +4055,6 @@ mod tests {
         assert_eq!(view.selected_file_index(), Some(1));
     }
 
-    #[test]
-    fn diff_file_list_lines_mark_selected_path() {
-        let view = real_diff_view("aaa");
-
-        assert_eq!(
-            diff_file_list_lines(&view, 1),
-            vec![
-                "   1/2 src/a.rs",
-                ">  2/2 src/b.rs",
-                "",
-                "j/k or arrows move   enter jump   esc close",
-            ]
-        );
-    }
-
     #[test]
     fn view_options_enter_opens_log_template_selector() {
         let mut state = AppState::new(AppView::Log(LogView::default()));
@@ -3415,6 +4069,8 @@ mod tests {
             &mut source,
             &JjDiff::default(),
             &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
             None,
             KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
         );
@@ -3480,6 +4136,8 @@ mod tests {
             &mut source,
             &JjDiff::default(),
             &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
             None,
             KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
         );
@@ -3488,14 +4146,72 @@ mod tests {
         assert_eq!(state.modes.active(), None);
     }
 
+    #[test]
+    fn two_marks_open_a_from_to_diff_and_interdiff() {
+        let mut state = AppState::new(log_app_view_with_changes(["aaa", "bbb", "ccc"]));
+        let AppView::Log(log) = state.views.active_mut() else {
+            panic!("expected log");
+        };
+        assert_eq!(log_diff_query(log), Ok(Some(diff_query("aaa"))));
+        push_marked_interdiff_with_runner(
+            &mut state,
+            &JjDiff::default(),
+            SequencedRunner::successes(Vec::new()),
+        );
+        assert_eq!(state.views.len(), 1, "interdiff needs two marks");
+
+        let AppView::Log(log) = state.views.active_mut() else {
+            panic!("expected log");
+        };
+        let _ = log.apply(LogAction::Last);
+        let _ = log.apply(LogAction::ToggleMark);
+        let _ = log.apply(LogAction::First);
+        let _ = log.apply(LogAction::ToggleMark);
+        assert_eq!(
+            log_diff_query(log),
+            Ok(Some(DiffQuery::FromTo {
+                from: "ccc".to_owned(),
+                to: "aaa".to_owned(),
+                options: DiffOptions::default(),
+            }))
+        );
+        let _ = log.apply(LogAction::Next);
+        let _ = log.apply(LogAction::ToggleMark);
+        assert_eq!(
+            log_diff_query(log),
+            Err("3 revisions marked; mark exactly two to compare".to_owned())
+        );
+        let _ = log.apply(LogAction::ToggleMark);
+
+        push_marked_interdiff_with_runner(
+            &mut state,
+            &JjDiff::default(),
+            SequencedRunner::successes(vec![
+                output(0, "Modified regular file src/a.rs:\n", ""),
+                output(0, "src/a.rs | 1 +\n", ""),
+            ]),
+        );
+
+        let AppView::Diff { view, query } = state.views.active() else {
+            panic!("expected interdiff view");
+        };
+        assert_eq!(query.range(), Some(("ccc", "aaa")));
+        assert_eq!(
+            view.title(),
+            "interdiff ccc → aaa  jj interdiff --from ccc --to aaa"
+        );
+        let newest = state.command_history().records().last().expect("record");
+        assert_eq!(newest.source.action, SourceAction::OpenInterdiff);
+    }
+
     #[test]
     fn diff_view_options_apply_selected_format() {
         let mut state = AppState::new(diff_app_view("aaa"));
 
-        apply_diff_format_option_with_runner(
+        apply_diff_options_with_runner(
             &mut state,
             &JjDiff::default(),
-            DiffFormat::Summary,
+            DiffFormat::Summary.into(),
             SequencedRunner::successes(vec![
                 output(0, "M src/a.rs\n", ""),
                 output(0, "src/a.rs | 1 +\n", ""),
@@ -3505,9 +4221,9 @@ mod tests {
         assert!(matches!(
             state.views.active(),
             AppView::Diff {
-                query: DiffQuery::Revision { rev, format },
+                query: DiffQuery::Revision { rev, options },
                 ..
-            } if rev == "aaa" && *format == DiffFormat::Summary
+            } if rev == "aaa" && options.format == DiffFormat::Summary
         ));
         let newest = state.command_history().records().last().expect("record");
         assert_eq!(newest.command.title, "jj diff -r aaa --stat");
@@ -3515,6 +4231,188 @@ mod tests {
         assert_eq!(newest.source.action, SourceAction::Refresh);
     }
 
+    #[test]
+    fn diff_view_options_load_tools_and_keep_context_and_whitespace() {
+        let mut state = AppState::new(diff_app_view("aaa"));
+
+        open_view_options_with_runner(
+            &mut state,
+            &mut JjLog::default(),
+            &JjDiff::default(),
+            &JjOperation::default(),
+            SequencedRunner::successes(vec![output(
+                0,
+                "merge-tools.difft.diff-args = [\"--color=always\", \"$left\", \"$right\"]\n",
+                "",
+            )]),
+        );
+        assert_eq!(state.diff_tools(), ["difft"]);
+        let rows = diff_view_option_rows(state.diff_tools().len());
+        assert_eq!(rows.last(), Some(&DiffViewOptionRow::Tool(Some(0))));
+        assert_eq!(
+            state
+                .command_history()
+                .records()
+                .last()
+                .map(|record| record.source.action.clone()),
+            Some(SourceAction::LoadDiffTools)
+        );
+        state.modes.pop();
+        open_view_options_with_runner(
+            &mut state,
+            &mut JjLog::default(),
+            &JjDiff::default(),
+            &JjOperation::default(),
+            SequencedRunner::successes(Vec::new()),
+        );
+        assert_eq!(state.diff_tools(), ["difft"], "tools load once per session");
+
+        let options = DiffOptions::default()
+            .with_context(next_diff_context(None))
+            .with_whitespace(DiffWhitespace::IgnoreAll)
+            .with_tool(Some("difft".to_owned()));
+        apply_diff_options_with_runner(
+            &mut state,
+            &JjDiff::default(),
+            options,
+            SequencedRunner::successes(vec![
+                output(0, "src/a.rs --- 1 rust\n", ""),
+                output(0, "src/a.rs | 1 +\n", ""),
+            ]),
+        );
+
+        let titles = state
+            .command_history()
+            .records()
+            .map(|record| record.command.title.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            titles[titles.len() - 2..],
+            [
+                "jj diff -r aaa --tool difft --context 0 --ignore-all-space",
+                "jj diff -r aaa --stat --context 0 --ignore-all-space",
+            ]
+        );
+    }
+
+    #[test]
+    fn operation_diff_view_options_toggle_patch_for_range() {
+        let query = OperationQuery::diff_from_to("op1", "op2");
+        let mut state = AppState::new(AppView::OperationDiff {
+            view: RenderedView::from_error("op1..op2", "jj op diff", "fixture".to_owned()),
+            query,
+        });
+        state.modes.push(InputMode::ViewOptions {
+            context: BindingContext::Inspection,
+            selected: 0,
+        });
+        assert_eq!(
+            selected_view_option(&state, &JjLog::default()),
+            Some(ViewOptionRow::OperationDiffPatch)
+        );
+        state.modes.pop();
+
+        toggle_operation_diff_patch_with_runner(
+            &mut state,
+            &JjOperation::default(),
+            SequencedRunner::successes(vec![output(0, "Changed commits:\n", "")]),
+        );
+
+        assert!(matches!(
+            state.views.active(),
+            AppView::OperationDiff { query, .. } if query.patch()
+        ));
+        let newest = state.command_history().records().last().expect("record");
+        assert_eq!(
+            newest.command.title,
+            "jj op diff --from op1 --to op2 --patch"
+        );
+        assert_eq!(newest.source.view, SourceView::OperationDiff);
+    }
+
+    #[test]
+    fn operation_log_view_options_toggle_jk_only_filter() {
+        use jk_tui::operation_log_view::{OperationLogRow, OperationLogSnapshot};
+
+        let mut state = AppState::new(AppView::OperationLog {
+            view: OperationLogView::new(OperationLogSnapshot::new(vec![
+                OperationLogRow::new("op1", "op1", "jk describe", true)
+                    .with_jk_action("log describe"),
+                OperationLogRow::new("op2", "op2", "agent snapshot", false),
+            ])),
+        });
+        open_view_options_with_runner(
+            &mut state,
+            &mut JjLog::default(),
+            &JjDiff::default(),
+            &JjOperation::default(),
+            SequencedRunner::successes(vec![output(0, "me\n", "")]),
+        );
+        move_view_options_selection(&mut state, &JjLog::default(), MenuDirection::Previous);
+        assert_eq!(
+            selected_view_option(&state, &JjLog::default()),
+            Some(ViewOptionRow::OperationLogFilter(
+                OperationLogFilterRow::JkOnly
+            ))
+        );
+
+        let result = handle_input_mode(
+            &mut state,
+            &mut JjLog::default(),
+            &JjDiff::default(),
+            &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
+            None,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        );
+
+        assert_eq!(result, InputModeResult::Handled);
+        assert_eq!(state.modes.active(), None);
+        let AppView::OperationLog { view } = state.views.active() else {
+            panic!("expected operation log view");
+        };
+        assert!(view.filter().jk_only);
+        assert_eq!(view.selected_operation_id(), Some("op1"));
+    }
+
+    #[test]
+    fn operation_log_my_user_filter_uses_jj_operation_username() {
+        use jk_tui::operation_log_view::{OperationLogRow, OperationLogSnapshot};
+
+        let mut state = AppState::new(AppView::OperationLog {
+            view: OperationLogView::new(OperationLogSnapshot::new(vec![
+                OperationLogRow::new("op1", "op1", "snapshot", true).with_origin("ci-bot", "ci"),
+                OperationLogRow::new("op2", "op2", "describe", false)
+                    .with_origin("alice", "laptop"),
+            ])),
+        });
+        open_view_options_with_runner(
+            &mut state,
+            &mut JjLog::default(),
+            &JjDiff::default(),
+            &JjOperation::default(),
+            SequencedRunner::successes(vec![output(0, "alice\n", "")]),
+        );
+        assert_eq!(state.operation_user.as_deref(), Some("alice"));
+        assert_eq!(
+            state
+                .history
+                .records()
+                .last()
+                .map(|record| record.source.action.clone()),
+            Some(SourceAction::LoadOperationUser)
+        );
+
+        apply_operation_log_filter(&mut state, OperationLogFilterRow::MyUser);
+
+        let AppView::OperationLog { view } = state.views.active() else {
+            panic!("expected operation log view");
+        };
+        assert_eq!(view.filter().user.as_deref(), Some("alice"));
+        assert_eq!(view.selected_operation_id(), Some("op2"));
+    }
+
     #[test]
     fn view_options_close_without_changing_source() {
         let mut state = AppState::new(AppView::Log(LogView::default()));
@@ -3530,6 +4428,8 @@ mod tests {
             &mut source,
             &JjDiff::default(),
             &JjDescribe::default(),
+            &JjOperation::default(),
+            &JjRecovery::default(),
             None,
             KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE),
         );
@@ -3569,6 +4469,27 @@ mod tests {
                 "    name only     ",
                 "    git           ",
                 "    color words   ",
+                "    side by side  ",
+                "",
+                "  Context lines    default",
+                "  Whitespace       exact",
+                "",
+                "j/k or arrows move   enter apply   esc close",
+            ]
+        );
+        let options = DiffOptions::default()
+            .with_context(Some(5))
+            .with_tool(Some("difft".to_owned()));
+        assert_eq!(
+            diff_view_options_lines(11, &options, &["difft".to_owned()])[8..],
+            [
+                "",
+                "  Context lines    5",
+                "  Whitespace       exact",
+                "",
+                "  Diff tools:",
+                "    builtin        jj's own formatter",
+                "> * difft",
                 "",
                 "j/k or arrows move   enter apply   esc close",
             ]
@@ -3712,7 +4633,7 @@ mod tests {
             diff_args.query(),
             DiffQuery::Revision {
                 rev: "@".to_owned(),
-                format: DiffFormat::Patch,
+                options: DiffFormat::Patch.into(),
             }
         );
     }
@@ -3752,7 +4673,7 @@ mod tests {
             diff_args.query(),
             DiffQuery::Revision {
                 rev: "abc123".to_owned(),
-                format: DiffFormat::Stat,
+                options: DiffFormat::Stat.into(),
             }
         );
     }
@@ -3768,7 +4689,7 @@ mod tests {
             diff_args.query(),
             DiffQuery::Revision {
                 rev: "abc123".to_owned(),
-                format: DiffFormat::Patch,
+                options: DiffFormat::Patch.into(),
             }
         );
     }
@@ -3781,6 +4702,7 @@ mod tests {
             ("--name-only", DiffFormat::NameOnly),
             ("--git", DiffFormat::Git),
             ("--color-words", DiffFormat::ColorWords),
+            ("--side-by-side", DiffFormat::SideBySide),
         ] {
             let args = Args::try_parse_from(["jk", "diff", "-r", "abc123", flag])
                 .expect("valid diff args");
@@ -3792,7 +4714,7 @@ mod tests {
                 diff_args.query(),
                 DiffQuery::Revision {
                     rev: "abc123".to_owned(),
-                    format,
+                    options: format.into(),
                 }
             );
         }
@@ -3803,6 +4725,54 @@ mod tests {
         assert!(Args::try_parse_from(["jk", "diff", "--stat", "--summary"]).is_err());
     }
 
+    #[test]
+    fn diff_args_carry_context_whitespace_and_tool() {
+        let args = Args::try_parse_from([
+            "jk",
+            "diff",
+            "-r",
+            "abc123",
+            "--context",
+            "1",
+            "-b",
+            "--tool",
+            "difft",
+        ])
+        .expect("valid diff args");
+        let Some(Command::Diff(diff_args)) = args.command else {
+            panic!("expected diff command");
+        };
+
+        assert_eq!(
+            diff_args.query().options(),
+            &DiffOptions::default()
+                .with_context(Some(1))
+                .with_whitespace(DiffWhitespace::IgnoreChange)
+                .with_tool(Some("difft".to_owned()))
+        );
+        assert!(Args::try_parse_from(["jk", "diff", "-w", "-b"]).is_err());
+    }
+
+    #[test]
+    fn diff_args_resolve_interdiff_query() {
+        let args =
+            Args::try_parse_from(["jk", "diff", "--from", "v1", "--to", "v2", "--interdiff"])
+                .expect("valid diff args");
+        let Some(Command::Diff(diff_args)) = args.command else {
+            panic!("expected diff command");
+        };
+
+        assert_eq!(
+            diff_args.query(),
+            DiffQuery::Interdiff {
+                from: "v1".to_owned(),
+                to: "v2".to_owned(),
+                options: DiffOptions::default(),
+            }
+        );
+        assert!(Args::try_parse_from(["jk", "diff", "--interdiff"]).is_err());
+    }
+
     #[test]
     fn diff_args_resolve_from_to_query() {
         let args = Args::try_parse_from(["jk", "diff", "--from", "main", "--to", "@"])
@@ -3816,7 +4786,7 @@ mod tests {
             DiffQuery::FromTo {
                 from: "main".to_owned(),
                 to: "@".to_owned(),
-                format: DiffFormat::Patch,
+                options: DiffFormat::Patch.into(),
             }
         );
     }
@@ -3866,4 +4836,55 @@ mod tests {
 
         assert_eq!(spec.repository(), None);
     }
+
+    #[test]
+    fn load_more_reloads_a_longer_page_and_keeps_marks_and_selection() {
+        let snapshot = jk_core::LogSnapshot::new(
+            "○  aaa\n○  bbb\n",
+            vec![
+                jk_core::LogEntry::new("aaa", "1", "a").with_rendered_line(0),
+                jk_core::LogEntry::new("bbb", "2", "b").with_rendered_line(1),
+            ],
+        )
+        .with_more_available(true);
+        let mut log = LogView::new(snapshot);
+        let _ = log.apply(jk_tui::log_view::LogAction::ToggleMark);
+        assert_eq!(
+            log.apply(jk_tui::log_view::LogAction::Next),
+            ActionResult::LoadMore
+        );
+        let mut history = CommandHistory::new(4);
+        let mut source = JjLog::default().with_page_size(Some(2));
+
+        refresh::load_more_log_with_runner(
+            &mut log,
+            &mut history,
+            &mut source,
+            SequencedRunner::successes(vec![
+                output(0, "○  aaa\n○  bbb\n○  ccc\n", ""),
+                output(
+                    0,
+                    concat!(
+                        "{\"change_id\":\"aaa\",\"commit_id\":\"1\",\"description\":\"a\"}\t\"\"\n",
+                        "{\"change_id\":\"bbb\",\"commit_id\":\"2\",\"description\":\"b\"}\t\"\"\n",
+                        "{\"change_id\":\"ccc\",\"commit_id\":\"3\",\"description\":\"c\"}\t\"\"\n",
+                    ),
+                    "",
+                ),
+            ]),
+        );
+
+        assert_eq!(source.effective_limit(), Some(4));
+        assert_eq!(log.selected_change_id(), Some("bbb"));
+        assert_eq!(log.marked_change_ids(), ["aaa"]);
+        assert_eq!(log.entries().len(), 3);
+        assert!(!log.selected_is_load_more());
+        assert_eq!(
+            history
+                .records()
+                .map(|record| record.source.action.clone())
+                .collect::<Vec<_>>(),
+            [SourceAction::LoadMoreLog, SourceAction::LoadMoreLog]
+        );
+    }
 }
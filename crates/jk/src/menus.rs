@@ -1,6 +1,8 @@
-use jk_cli::{DiffFormat, LogTemplateSelection};
+use jk_cli::{DiffFormat, DiffOptions, JjLog, LogTemplateSelection};
 use jk_tui::command_discovery::BindingContext;
-use jk_tui::diff_view::DiffView;
+use jk_tui::operation_log_view::OperationLogFilter;
+
+use crate::diff_preview::PreviewMode;
 
 #[derive(Clone, Copy)]
 pub enum MenuDirection {
@@ -10,21 +12,61 @@ pub enum MenuDirection {
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ViewOptionRow {
-    LogTemplate,
-    DiffFormat(DiffFormat),
+    Log(LogViewOptionRow),
+    Diff(DiffViewOptionRow),
+    OperationDiffPatch,
+    OperationLogFilter(OperationLogFilterRow),
     Placeholder,
 }
 
-const DIFF_VIEW_OPTION_ROWS: &[ViewOptionRow] = &[
-    ViewOptionRow::DiffFormat(DiffFormat::Patch),
-    ViewOptionRow::DiffFormat(DiffFormat::Summary),
-    ViewOptionRow::DiffFormat(DiffFormat::Stat),
-    ViewOptionRow::DiffFormat(DiffFormat::Types),
-    ViewOptionRow::DiffFormat(DiffFormat::NameOnly),
-    ViewOptionRow::DiffFormat(DiffFormat::Git),
-    ViewOptionRow::DiffFormat(DiffFormat::ColorWords),
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LogViewOptionRow {
+    Template,
+    Preview,
+    DefaultRevset,
+    RevsetPreset(usize),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiffViewOptionRow {
+    Format(DiffFormat),
+    Context,
+    Whitespace,
+    /// `None` is jj's own formatter; `Some` indexes the configured diff tools.
+    Tool(Option<usize>),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OperationLogFilterRow {
+    MyUser,
+    Host,
+    TimeWindow,
+    JkOnly,
+}
+
+const OPERATION_LOG_FILTER_ROWS: &[OperationLogFilterRow] = &[
+    OperationLogFilterRow::MyUser,
+    OperationLogFilterRow::Host,
+    OperationLogFilterRow::TimeWindow,
+    OperationLogFilterRow::JkOnly,
+];
+
+pub const OPERATION_DIFF_VIEW_OPTION_ROWS: &[ViewOptionRow] = &[ViewOptionRow::OperationDiffPatch];
+
+const DIFF_FORMAT_ROWS: &[DiffViewOptionRow] = &[
+    DiffViewOptionRow::Format(DiffFormat::Patch),
+    DiffViewOptionRow::Format(DiffFormat::Summary),
+    DiffViewOptionRow::Format(DiffFormat::Stat),
+    DiffViewOptionRow::Format(DiffFormat::Types),
+    DiffViewOptionRow::Format(DiffFormat::NameOnly),
+    DiffViewOptionRow::Format(DiffFormat::Git),
+    DiffViewOptionRow::Format(DiffFormat::ColorWords),
+    DiffViewOptionRow::Format(DiffFormat::SideBySide),
 ];
 
+/// Context line counts that `Enter` cycles through after jj's configured default.
+const DIFF_CONTEXT_STEPS: &[usize] = &[0, 1, 5, 10, 25];
+
 pub fn wrapped_selection(selected: usize, row_count: usize, direction: MenuDirection) -> usize {
     if row_count == 0 {
         return 0;
@@ -37,15 +79,163 @@ pub fn wrapped_selection(selected: usize, row_count: usize, direction: MenuDirec
     }
 }
 
-pub const fn view_option_rows(context: BindingContext) -> &'static [ViewOptionRow] {
+/// Returns the option rows for `context`, given the loaded revset preset and diff tool counts.
+pub fn view_option_rows(
+    context: BindingContext,
+    preset_count: usize,
+    tool_count: usize,
+) -> Vec<ViewOptionRow> {
     match context {
-        BindingContext::Log => &[ViewOptionRow::LogTemplate],
-        BindingContext::Diff => DIFF_VIEW_OPTION_ROWS,
+        BindingContext::Log => log_view_option_rows(preset_count)
+            .into_iter()
+            .map(ViewOptionRow::Log)
+            .collect(),
+        BindingContext::Diff => diff_view_option_rows(tool_count)
+            .into_iter()
+            .map(ViewOptionRow::Diff)
+            .collect(),
+        BindingContext::OperationLog => OPERATION_LOG_FILTER_ROWS
+            .iter()
+            .copied()
+            .map(ViewOptionRow::OperationLogFilter)
+            .collect(),
         BindingContext::Inspection
         | BindingContext::Workspaces
-        | BindingContext::CommandHistory
-        | BindingContext::OperationLog => &[ViewOptionRow::Placeholder],
+        | BindingContext::CommandHistory => vec![ViewOptionRow::Placeholder],
+    }
+}
+
+/// Returns log option rows: the template and split preview, then `[jk.revsets]` presets behind a
+/// default-revset row.
+pub fn log_view_option_rows(preset_count: usize) -> Vec<LogViewOptionRow> {
+    let mut rows = vec![LogViewOptionRow::Template, LogViewOptionRow::Preview];
+    if preset_count > 0 {
+        rows.push(LogViewOptionRow::DefaultRevset);
+        rows.extend((0..preset_count).map(LogViewOptionRow::RevsetPreset));
     }
+    rows
+}
+
+/// Returns diff option rows: formats, context and whitespace, then configured diff tools behind a
+/// builtin row.
+pub fn diff_view_option_rows(tool_count: usize) -> Vec<DiffViewOptionRow> {
+    let mut rows = DIFF_FORMAT_ROWS.to_vec();
+    rows.extend([DiffViewOptionRow::Context, DiffViewOptionRow::Whitespace]);
+    if tool_count > 0 {
+        rows.push(DiffViewOptionRow::Tool(None));
+        rows.extend((0..tool_count).map(|tool| DiffViewOptionRow::Tool(Some(tool))));
+    }
+    rows
+}
+
+/// Returns the context line count after `current`, wrapping back to jj's default.
+pub fn next_diff_context(current: Option<usize>) -> Option<usize> {
+    DIFF_CONTEXT_STEPS
+        .iter()
+        .copied()
+        .find(|step| current.is_none_or(|current| *step > current))
+}
+
+pub fn diff_view_options_lines(
+    selected: usize,
+    options: &DiffOptions,
+    tools: &[String],
+) -> Vec<String> {
+    let mut lines = diff_view_option_rows(tools.len())
+        .into_iter()
+        .enumerate()
+        .flat_map(|(index, row)| {
+            let marker = if index == selected { ">" } else { " " };
+            let active = |active: bool| if active { "*" } else { " " };
+            match row {
+                DiffViewOptionRow::Format(format) => vec![format!(
+                    "{marker} {} {:<14}",
+                    active(options.tool.is_none() && format == options.format),
+                    format.label()
+                )],
+                DiffViewOptionRow::Context => {
+                    let context = options
+                        .context
+                        .map_or_else(|| "default".to_owned(), |context| context.to_string());
+                    vec![
+                        String::new(),
+                        format!("{marker} {:<16} {context}", "Context lines"),
+                    ]
+                }
+                DiffViewOptionRow::Whitespace => vec![format!(
+                    "{marker} {:<16} {}",
+                    "Whitespace",
+                    options.whitespace.label()
+                )],
+                DiffViewOptionRow::Tool(None) => vec![
+                    String::new(),
+                    "  Diff tools:".to_owned(),
+                    format!(
+                        "{marker} {} {:<14} jj's own formatter",
+                        active(options.tool.is_none()),
+                        "builtin"
+                    ),
+                ],
+                DiffViewOptionRow::Tool(Some(tool)) => {
+                    let tool = &tools[tool];
+                    vec![format!(
+                        "{marker} {} {tool}",
+                        active(options.tool.as_ref() == Some(tool))
+                    )]
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    lines.push(String::new());
+    lines.push("j/k or arrows move   enter apply   esc close".to_owned());
+    lines
+}
+
+pub fn log_view_options_lines(
+    selected: usize,
+    source: &JjLog,
+    preview: PreviewMode,
+) -> Vec<String> {
+    let presets = source.revset_presets();
+    let mut lines = log_view_option_rows(presets.len())
+        .into_iter()
+        .enumerate()
+        .flat_map(|(index, row)| {
+            let marker = if index == selected { ">" } else { " " };
+            let active = |active: bool| if active { "*" } else { " " };
+            match row {
+                LogViewOptionRow::Template => vec![format!(
+                    "{marker} {:<18} {}",
+                    "Template",
+                    source.template().label()
+                )],
+                LogViewOptionRow::Preview => {
+                    vec![format!("{marker} {:<18} {}", "Preview", preview.label())]
+                }
+                LogViewOptionRow::DefaultRevset => vec![
+                    String::new(),
+                    "  Revset presets:".to_owned(),
+                    format!(
+                        "{marker} {} {:<16} configured revsets.log",
+                        active(source.revset().is_none()),
+                        "default"
+                    ),
+                ],
+                LogViewOptionRow::RevsetPreset(preset) => {
+                    let preset = &presets[preset];
+                    vec![format!(
+                        "{marker} {} {:<16} {}",
+                        active(source.revset_preset() == Some(preset.name())),
+                        preset.name(),
+                        preset.revset()
+                    )]
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    lines.push(String::new());
+    lines.push("j/k or arrows move   enter open/apply   esc close".to_owned());
+    lines
 }
 
 pub fn view_options_lines(
@@ -63,24 +253,11 @@ pub fn view_options_lines(
                 "j/k or arrows move   enter open   esc close".to_owned(),
             ]
         }
-        BindingContext::Diff => {
-            let active = active_diff_format.unwrap_or(DiffFormat::Patch);
-            let mut lines = DIFF_VIEW_OPTION_ROWS
-                .iter()
-                .enumerate()
-                .map(|(index, row)| {
-                    let marker = if index == selected { ">" } else { " " };
-                    let ViewOptionRow::DiffFormat(format) = row else {
-                        unreachable!("diff view rows are all formats");
-                    };
-                    let active_marker = if *format == active { "*" } else { " " };
-                    format!("{marker} {active_marker} {:<14}", format.label())
-                })
-                .collect::<Vec<_>>();
-            lines.push(String::new());
-            lines.push("j/k or arrows move   enter apply   esc close".to_owned());
-            lines
-        }
+        BindingContext::Diff => diff_view_options_lines(
+            selected,
+            &DiffOptions::from(active_diff_format.unwrap_or(DiffFormat::Patch)),
+            &[],
+        ),
         BindingContext::Inspection => vec![
             "No view options in this slice.".to_owned(),
             String::new(),
@@ -96,36 +273,63 @@ pub fn view_options_lines(
             String::new(),
             "esc close".to_owned(),
         ],
-        BindingContext::OperationLog => vec![
-            "No operation log options in this slice.".to_owned(),
-            String::new(),
-            "esc close".to_owned(),
-        ],
+        BindingContext::OperationLog => {
+            operation_log_view_options_lines(selected, &OperationLogFilter::default(), None)
+        }
     }
 }
 
-pub fn diff_file_list_lines(view: &DiffView, selected: usize) -> Vec<String> {
-    let paths = view.file_paths();
-    if paths.is_empty() {
-        return vec![
-            "No files in this diff.".to_owned(),
-            String::new(),
-            "esc close".to_owned(),
-        ];
-    }
-
-    paths
+pub fn operation_log_view_options_lines(
+    selected: usize,
+    filter: &OperationLogFilter,
+    local_user: Option<&str>,
+) -> Vec<String> {
+    let mut lines = OPERATION_LOG_FILTER_ROWS
         .iter()
         .enumerate()
-        .map(|(index, path)| {
+        .map(|(index, row)| {
             let marker = if index == selected { ">" } else { " " };
-            format!("{marker} {:>2}/{} {path}", index + 1, paths.len())
+            let (label, state) = match row {
+                OperationLogFilterRow::MyUser => (
+                    format!("My user ({})", local_user.unwrap_or("unknown")),
+                    on_off(filter.user.is_some()).to_owned(),
+                ),
+                OperationLogFilterRow::Host => (
+                    "Host".to_owned(),
+                    filter.host.clone().unwrap_or_else(|| "all".to_owned()),
+                ),
+                OperationLogFilterRow::TimeWindow => (
+                    "Time window".to_owned(),
+                    filter
+                        .time_window
+                        .map_or("all", |window| window.label())
+                        .to_owned(),
+                ),
+                OperationLogFilterRow::JkOnly => (
+                    "jk operations only".to_owned(),
+                    on_off(filter.jk_only).to_owned(),
+                ),
+            };
+            format!("{marker} {label:<22} {state}")
         })
-        .chain(std::iter::once(String::new()))
-        .chain(std::iter::once(
-            "j/k or arrows move   enter jump   esc close".to_owned(),
-        ))
-        .collect()
+        .collect::<Vec<_>>();
+    lines.push(String::new());
+    lines.push("j/k or arrows move   enter toggle/cycle   esc close".to_owned());
+    lines
+}
+
+const fn on_off(enabled: bool) -> &'static str {
+    if enabled { "on" } else { "off" }
+}
+
+pub fn operation_diff_view_options_lines(selected: usize, patch: bool) -> Vec<String> {
+    let marker = if selected == 0 { ">" } else { " " };
+    let state = if patch { "on" } else { "off" };
+    vec![
+        format!("{marker} {:<18} {state}", "Patch (--patch)"),
+        String::new(),
+        "j/k or arrows move   enter toggle   esc close".to_owned(),
+    ]
 }
 
 pub fn template_selector_lines(options: &[LogTemplateSelection], selected: usize) -> Vec<String> {
@@ -146,6 +350,8 @@ pub fn template_selector_lines(options: &[LogTemplateSelection], selected: usize
 
 #[cfg(test)]
 mod tests {
+    use jk_cli::RevsetPreset;
+
     use super::*;
 
     #[test]
@@ -182,6 +388,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn log_view_options_list_revset_presets_with_active_marker() {
+        let presets = vec![
+            RevsetPreset::new("mine", "mine()"),
+            RevsetPreset::new("conflicts", "conflicts()"),
+        ];
+        let source = JjLog::default()
+            .with_revset_presets(presets.clone())
+            .with_revset_preset(&presets[1]);
+
+        assert_eq!(
+            log_view_option_rows(2),
+            [
+                LogViewOptionRow::Template,
+                LogViewOptionRow::Preview,
+                LogViewOptionRow::DefaultRevset,
+                LogViewOptionRow::RevsetPreset(0),
+                LogViewOptionRow::RevsetPreset(1),
+            ]
+        );
+        assert_eq!(
+            log_view_options_lines(4, &source, PreviewMode::Stat),
+            vec![
+                "  Template           configured".to_owned(),
+                "  Preview            stat".to_owned(),
+                String::new(),
+                "  Revset presets:".to_owned(),
+                "    default          configured revsets.log".to_owned(),
+                "    mine             mine()".to_owned(),
+                "> * conflicts        conflicts()".to_owned(),
+                String::new(),
+                "j/k or arrows move   enter open/apply   esc close".to_owned(),
+            ]
+        );
+        assert_eq!(
+            log_view_option_rows(0),
+            [LogViewOptionRow::Template, LogViewOptionRow::Preview]
+        );
+    }
+
+    #[test]
+    fn operation_diff_view_options_show_patch_state() {
+        assert_eq!(
+            operation_diff_view_options_lines(0, true),
+            vec![
+                "> Patch (--patch)    on".to_owned(),
+                String::new(),
+                "j/k or arrows move   enter toggle   esc close".to_owned(),
+            ]
+        );
+        assert!(operation_diff_view_options_lines(0, false)[0].ends_with("off"));
+    }
+
+    #[test]
+    fn operation_log_view_options_show_filter_state() {
+        let filter = OperationLogFilter {
+            host: Some("laptop".to_owned()),
+            jk_only: true,
+            ..OperationLogFilter::default()
+        };
+
+        assert_eq!(
+            operation_log_view_options_lines(1, &filter, Some("me")),
+            vec![
+                "  My user (me)           off".to_owned(),
+                "> Host                   laptop".to_owned(),
+                "  Time window            all".to_owned(),
+                "  jk operations only     on".to_owned(),
+                String::new(),
+                "j/k or arrows move   enter toggle/cycle   esc close".to_owned(),
+            ]
+        );
+    }
+
     #[test]
     fn template_selector_lines_show_template_names() {
         assert_eq!(
@@ -1,4 +1,6 @@
-use jk_core::{CommandPreview, SourceAction};
+use jk_core::{
+    ChangeFlag, CommandPreview, CommandPreviewWarning, ImmutabilityPolicy, SourceAction,
+};
 use jk_tui::log_view::LogView;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -51,6 +53,36 @@ impl PendingCommandPreview {
         }
     }
 
+    pub(crate) const fn rebase(preview: CommandPreview) -> Self {
+        Self {
+            preview,
+            source_action: SourceAction::RebaseRevision,
+            source_key: "B",
+            failure_label: "jj rebase",
+            copy_status: None,
+        }
+    }
+
+    pub(crate) const fn squash(preview: CommandPreview) -> Self {
+        Self {
+            preview,
+            source_action: SourceAction::SquashRevision,
+            source_key: "S",
+            failure_label: "jj squash",
+            copy_status: None,
+        }
+    }
+
+    pub(crate) const fn restore(preview: CommandPreview) -> Self {
+        Self {
+            preview,
+            source_action: SourceAction::RestorePaths,
+            source_key: "Ctrl-r",
+            failure_label: "jj restore",
+            copy_status: None,
+        }
+    }
+
     pub(crate) const fn undo(preview: CommandPreview) -> Self {
         Self {
             preview,
@@ -61,6 +93,16 @@ impl PendingCommandPreview {
         }
     }
 
+    pub(crate) const fn restore_checkpoint(preview: CommandPreview) -> Self {
+        Self {
+            preview,
+            source_action: SourceAction::RestoreCheckpoint,
+            source_key: "R",
+            failure_label: "jj op restore",
+            copy_status: None,
+        }
+    }
+
     pub(crate) const fn redo(preview: CommandPreview) -> Self {
         Self {
             preview,
@@ -70,6 +112,50 @@ impl PendingCommandPreview {
             copy_status: None,
         }
     }
+
+    /// Flips `--ignore-immutable` for previews that target immutable revisions.
+    pub(crate) fn toggle_immutability(&mut self) {
+        let targets_immutable = self
+            .preview
+            .warnings
+            .iter()
+            .any(|warning| matches!(warning, CommandPreviewWarning::ImmutableTargets(_)));
+        if !targets_immutable {
+            return;
+        }
+        let immutability = match self.preview.immutability() {
+            ImmutabilityPolicy::Enforce => ImmutabilityPolicy::Ignore,
+            _ => ImmutabilityPolicy::Enforce,
+        };
+        self.preview = self.preview.clone().with_immutability(immutability);
+        self.copy_status = None;
+    }
+}
+
+/// Warns when the loaded log flags any of `revisions` as immutable.
+///
+/// The log's semantic pass already carries the `immutable` flag for every visible change, so the
+/// check needs no extra `jj` call. `revisions` are the short change ids previews are built from.
+pub fn with_immutable_target_warning(
+    preview: CommandPreview,
+    log: &LogView,
+    revisions: &[String],
+) -> CommandPreview {
+    let immutable = revisions
+        .iter()
+        .filter(|revision| {
+            log.entries().iter().any(|entry| {
+                entry.change_id().starts_with(revision.as_str())
+                    && entry.has_flag(ChangeFlag::Immutable)
+            })
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    if immutable.is_empty() {
+        preview
+    } else {
+        preview.with_warning(CommandPreviewWarning::ImmutableTargets(immutable))
+    }
 }
 
 pub fn selected_new_parents(log: &LogView) -> Vec<String> {
@@ -108,7 +194,7 @@ pub fn command_failure_message(command: &str, stderr: &[u8], stdout: &[u8]) -> S
 
 #[cfg(test)]
 mod tests {
-    use jk_core::{JjCommandSpec, LogEntry, LogSnapshot};
+    use jk_core::{JjCommandSpec, LogEntry, LogSnapshot, SafetyClass};
     use jk_tui::log_view::LogAction;
 
     use super::*;
@@ -135,6 +221,42 @@ mod tests {
         assert_eq!(redo.failure_label, "jj redo");
     }
 
+    #[test]
+    fn immutable_targets_warn_and_toggle_ignore_immutable() {
+        let log = LogView::new(LogSnapshot::new(
+            "◆  trunk\n○  mine\n",
+            vec![
+                LogEntry::new("trunkabc", "commit", "trunk")
+                    .with_flags(vec![ChangeFlag::Immutable]),
+                LogEntry::new("mineabcd", "commit", "mine").with_rendered_line(1),
+            ],
+        ));
+        let spec = JjCommandSpec::confirm_mutation(["abandon", "trunk"], SafetyClass::LocalRewrite);
+
+        let clean =
+            with_immutable_target_warning(spec.command_preview(), &log, &["mine".to_owned()]);
+        assert_eq!(clean.warnings, [CommandPreviewWarning::LocalRewrite]);
+
+        let mut pending = PendingCommandPreview::abandon(with_immutable_target_warning(
+            spec.command_preview(),
+            &log,
+            &["trunk".to_owned(), "mine".to_owned()],
+        ));
+        assert_eq!(
+            pending.preview.warnings,
+            [
+                CommandPreviewWarning::LocalRewrite,
+                CommandPreviewWarning::ImmutableTargets(vec!["trunk".to_owned()])
+            ]
+        );
+
+        pending.toggle_immutability();
+        assert_eq!(pending.preview.immutability(), ImmutabilityPolicy::Ignore);
+        assert!(pending.preview.command_line.contains("--ignore-immutable"));
+        pending.toggle_immutability();
+        assert_eq!(pending.preview.immutability(), ImmutabilityPolicy::Enforce);
+    }
+
     #[test]
     fn describe_prompt_lines_include_revision_message_and_controls() {
         assert_eq!(
@@ -1,5 +1,5 @@
 use jk_cli::{
-    JjCommandRunner, JjLog, JjRecovery, RecordingJjCommandRunner, RecoveryCommand,
+    JjCommandRunner, JjDiff, JjLog, JjRecovery, RecordingJjCommandRunner, RecoveryCommand,
     SystemJjCommandRunner,
 };
 use jk_core::{CommandSource, SourceAction, SourceView};
@@ -12,25 +12,37 @@ pub const POST_MUTATION_RECOVERY_STATUS: &str = "u undo  U redo  o operation  C
 pub fn confirm_command_preview(
     state: &mut AppState,
     source: &mut JjLog,
+    diff_source: &JjDiff,
     pending: PendingCommandPreview,
 ) {
-    confirm_command_preview_with_runner(state, source, pending, SystemJjCommandRunner);
+    confirm_command_preview_with_runner(state, source, diff_source, pending, SystemJjCommandRunner);
 }
 
+/// Runs a confirmed preview from the log or the diff file list, then reloads the view it came from.
 pub fn confirm_command_preview_with_runner<R: JjCommandRunner>(
     state: &mut AppState,
     source: &mut JjLog,
+    diff_source: &JjDiff,
     pending: PendingCommandPreview,
     runner: R,
 ) {
-    let command_source = CommandSource::new(SourceView::Log, pending.source_action.clone())
-        .with_key(pending.source_key);
+    let view = if matches!(state.views.active(), AppView::Diff { .. }) {
+        SourceView::Diff
+    } else {
+        SourceView::Log
+    };
+    let command_source =
+        CommandSource::new(view, pending.source_action.clone()).with_key(pending.source_key);
     let mut runner = RecordingJjCommandRunner::new(runner, &mut state.history, command_source);
     let result = runner.run_confirmed_mutation(&pending.preview.spec);
     let runner = runner.into_inner();
     match result {
         Ok(output) if output.status.success() => {
-            refresh_after_mutation_with_runner(state, source, runner)
+            if matches!(state.views.active(), AppView::Diff { .. }) {
+                refresh_diff_after_mutation_with_runner(state, diff_source, runner);
+            } else {
+                refresh_after_mutation_with_runner(state, source, runner);
+            }
         }
         Ok(output) => {
             let message =
@@ -84,8 +96,33 @@ fn refresh_after_mutation_with_runner<R: JjCommandRunner>(
     }
 }
 
+fn refresh_diff_after_mutation_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    diff_source: &JjDiff,
+    runner: R,
+) {
+    let AppView::Diff { view, query } = state.views.active_mut() else {
+        return;
+    };
+
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(SourceView::Diff, SourceAction::Refresh),
+    );
+    match diff_source.load_query_with_runner(query, &mut runner) {
+        Ok(snapshot) => {
+            view.refresh(snapshot);
+            view.show_status(POST_MUTATION_RECOVERY_STATUS);
+        }
+        Err(error) => view.show_error(error.to_string()),
+    }
+}
+
 fn show_log_error(state: &mut AppState, message: String) {
-    if let AppView::Log(log) = state.views.active_mut() {
-        log.show_error(message);
+    match state.views.active_mut() {
+        AppView::Log(log) => log.show_error(message),
+        AppView::Diff { view, .. } => view.show_error(message),
+        _ => {}
     }
 }
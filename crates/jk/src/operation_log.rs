@@ -1,122 +1,189 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use jk_cli::{JjCommandRunner, JjOperation, RecordingJjCommandRunner};
+use jk_core::{
+    CommandHistory, CommandSource, OperationEntry, OperationLogSnapshot as JjOperationLogSnapshot,
+    SourceAction, SourceView,
+};
+use jk_tui::command_history_view::command_source_label;
 use jk_tui::operation_log_view::{OperationLogRow, OperationLogSnapshot};
 
-pub fn operation_log_snapshot(title: &str, rendered: &str) -> OperationLogSnapshot {
-    let rendered_lines = rendered.lines().map(str::to_owned).collect::<Vec<_>>();
-    let rows = operation_log_rows(&rendered_lines);
-    OperationLogSnapshot::from_rendered(rows, rendered_lines).with_title(title)
-}
+use crate::checkpoints::SessionCheckpoints;
+use crate::state::AppState;
 
-fn operation_log_rows(rendered_lines: &[String]) -> Vec<OperationLogRow> {
-    let mut rows = Vec::new();
-    for (rendered_line, line) in rendered_lines.iter().enumerate() {
-        let plain_line = strip_ansi(line);
-        if let Some(row) = parse_operation_row(&plain_line, rendered_line) {
-            rows.push(row);
-        } else if let Some(title) = parse_operation_title_line(&plain_line)
-            && let Some(row) = rows.last_mut()
-            && row.title.trim().is_empty()
-        {
-            row.title = title.to_owned();
-        }
-    }
-    rows
+const DISPLAY_ID_LEN: usize = 12;
+
+/// Converts semantic op log entries into view rows, labelling operations that `jk` produced and
+/// session checkpoints.
+pub fn operation_log_snapshot(
+    snapshot: &JjOperationLogSnapshot,
+    history: &CommandHistory,
+    checkpoints: &SessionCheckpoints,
+) -> OperationLogSnapshot {
+    let rendered_lines = snapshot
+        .rendered()
+        .lines()
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    let rows = snapshot
+        .entries()
+        .iter()
+        .map(|entry| {
+            let mut row = operation_log_row(entry);
+            if let Some(jk_action) = jk_action_for(history, entry.id()) {
+                row = row.with_jk_action(jk_action);
+            }
+            if let Some(checkpoint) = checkpoints.name_for(entry.id()) {
+                row = row.with_checkpoint(checkpoint);
+            }
+            row
+        })
+        .collect();
+    OperationLogSnapshot::from_rendered(rows, rendered_lines).with_title(snapshot.title())
 }
 
-fn parse_operation_row(line: &str, rendered_line: usize) -> Option<OperationLogRow> {
-    let mut fields = line.split_whitespace();
-    let marker = fields.next()?;
-    let operation_id = fields.next()?;
-    if !is_operation_marker(marker) || !looks_like_operation_id(operation_id) {
-        return None;
-    }
-    Some(
-        OperationLogRow::new(
-            operation_id,
-            operation_id.chars().take(12).collect::<String>(),
-            String::new(),
-            marker == "@",
-        )
-        .with_rendered_line(rendered_line),
+fn operation_log_row(entry: &OperationEntry) -> OperationLogRow {
+    OperationLogRow::new(
+        entry.id(),
+        entry.id().chars().take(DISPLAY_ID_LEN).collect::<String>(),
+        entry.description().lines().next().unwrap_or_default(),
+        entry.current(),
     )
+    .with_rendered_line(entry.rendered_line())
+    .with_origin(entry.user(), entry.host())
+    .with_time_range(entry.time_start(), entry.time_end())
+    .with_tags(entry.tags().to_vec())
 }
 
-fn parse_operation_title_line(line: &str) -> Option<&str> {
-    line.strip_prefix("│  ")
-        .map(str::trim)
-        .filter(|line| !line.is_empty())
+/// Loads the user name `jj` records on new operations into `state`, for the "my user" filter.
+///
+/// `operation.username` is what jj writes into each operation; the login name it defaults to is
+/// the fallback when the key cannot be read.
+pub fn load_operation_user<R: JjCommandRunner>(
+    state: &mut AppState,
+    operation_source: &JjOperation,
+    runner: R,
+) {
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(SourceView::OperationLog, SourceAction::LoadOperationUser).with_key("V"),
+    );
+    let configured = match operation_source.load_operation_username_with_runner(&mut runner) {
+        Ok(user) => user,
+        Err(error) => {
+            tracing::warn!(%error, "failed to load operation user");
+            None
+        }
+    };
+    state.operation_user = configured.or_else(login_user);
 }
 
-fn is_operation_marker(marker: &str) -> bool {
-    matches!(marker, "@" | "○" | "◆" | "×" | "◉")
+/// Returns the login name `jj` uses when `operation.username` is unset.
+fn login_user() -> Option<String> {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+        .filter(|user| !user.is_empty())
 }
 
-fn looks_like_operation_id(value: &str) -> bool {
-    value.len() >= 8 && value.chars().all(|character| character.is_ascii_hexdigit())
+/// Returns the current time in Unix seconds for operation time-window filters.
+pub fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX)
+        })
 }
 
-fn strip_ansi(text: &str) -> String {
-    let mut output = String::with_capacity(text.len());
-    let mut chars = text.chars().peekable();
-    while let Some(character) = chars.next() {
-        if character == '\u{1b}' && chars.peek() == Some(&'[') {
-            let _ = chars.next();
-            for code in chars.by_ref() {
-                if code.is_ascii_alphabetic() {
-                    break;
-                }
-            }
-        } else {
-            output.push(character);
-        }
-    }
-    output
+/// Returns the source label of the newest history record that produced `operation_id`.
+///
+/// History may hold short or full ids, so either id being a prefix of the other counts as a match.
+fn jk_action_for(history: &CommandHistory, operation_id: &str) -> Option<String> {
+    history
+        .records()
+        .rev()
+        .find(|record| {
+            record.operation_id.as_deref().is_some_and(|recorded| {
+                !recorded.is_empty()
+                    && (operation_id.starts_with(recorded) || recorded.starts_with(operation_id))
+            })
+        })
+        .map(|record| command_source_label(&record.source))
 }
 
 #[cfg(test)]
 mod tests {
+    use jk_core::{JjCommandSpec, SafetyClass, SourceAction, SourceView};
+
     use super::*;
+    use crate::test_support::append_history_record_with_operation_id;
 
     #[test]
-    fn snapshot_extracts_operation_rows_and_titles() {
+    fn snapshot_converts_semantic_operation_entries() {
         let rendered = "\
 @ abcdef1234567890 user@example.test now
 │  latest operation
 ○ 0123456789abcdef user@example.test earlier
 │  previous operation
 ";
+        let snapshot = JjOperationLogSnapshot::new(
+            rendered,
+            vec![
+                OperationEntry::new("abcdef1234567890", "latest operation\n\nbody")
+                    .with_origin("user", "example.test")
+                    .with_time_range("start", "end")
+                    .with_tags(vec![("args".to_owned(), "jj new".to_owned())])
+                    .with_current(true),
+                OperationEntry::new("0123456789abcdef", "previous operation").with_rendered_line(2),
+            ],
+        )
+        .with_title("jj op log");
 
-        let snapshot = operation_log_snapshot("jj op log", rendered);
+        let snapshot = operation_log_snapshot(
+            &snapshot,
+            &CommandHistory::new(4),
+            &SessionCheckpoints::default(),
+        );
 
         assert_eq!(snapshot.title(), "jj op log");
-        assert_eq!(
-            snapshot.rendered_lines(),
-            [
-                "@ abcdef1234567890 user@example.test now",
-                "│  latest operation",
-                "○ 0123456789abcdef user@example.test earlier",
-                "│  previous operation",
-            ]
-        );
+        assert_eq!(snapshot.rendered_lines().len(), 4);
         assert_eq!(snapshot.rows().len(), 2);
         assert_eq!(snapshot.rows()[0].operation_id, "abcdef1234567890");
         assert_eq!(snapshot.rows()[0].display_id, "abcdef123456");
         assert_eq!(snapshot.rows()[0].title, "latest operation");
+        assert_eq!(snapshot.rows()[0].user, "user");
+        assert_eq!(snapshot.rows()[0].host, "example.test");
+        assert_eq!(snapshot.rows()[0].time_start, "start");
+        assert_eq!(
+            snapshot.rows()[0].tags,
+            vec![("args".to_owned(), "jj new".to_owned())]
+        );
         assert!(snapshot.rows()[0].current);
         assert_eq!(snapshot.rows()[0].rendered_line, 0);
-        assert_eq!(snapshot.rows()[1].operation_id, "0123456789abcdef");
         assert_eq!(snapshot.rows()[1].title, "previous operation");
         assert_eq!(snapshot.rows()[1].rendered_line, 2);
         assert!(!snapshot.rows()[1].current);
     }
 
     #[test]
-    fn snapshot_ignores_ansi_sequences_before_parsing() {
+    fn snapshot_keeps_ansi_rendered_lines_verbatim() {
         let rendered = "\u{1b}[32m@ abcdef1234567890 user@example.test now\u{1b}[0m\n\
 │  colored current operation\n";
+        let snapshot = JjOperationLogSnapshot::new(
+            rendered,
+            vec![
+                OperationEntry::new("abcdef1234567890", "colored current operation")
+                    .with_current(true),
+            ],
+        );
 
-        let snapshot = operation_log_snapshot("jj op log", rendered);
+        let snapshot = operation_log_snapshot(
+            &snapshot,
+            &CommandHistory::new(4),
+            &SessionCheckpoints::default(),
+        );
 
-        assert_eq!(snapshot.rows().len(), 1);
         assert_eq!(
             snapshot.rendered_lines(),
             [
@@ -124,8 +191,41 @@ mod tests {
                 "│  colored current operation",
             ]
         );
-        assert_eq!(snapshot.rows()[0].operation_id, "abcdef1234567890");
         assert_eq!(snapshot.rows()[0].title, "colored current operation");
-        assert!(snapshot.rows()[0].current);
+    }
+
+    #[test]
+    fn snapshot_labels_jk_operations_and_session_checkpoints() {
+        let mut history = CommandHistory::new(4);
+        append_history_record_with_operation_id(
+            &mut history,
+            JjCommandSpec::confirm_mutation(["describe", "-m", "x"], SafetyClass::LocalRewrite),
+            SourceView::Log,
+            SourceAction::DescribeRevision,
+            Some("abcdef123456"),
+        );
+        let snapshot = JjOperationLogSnapshot::new(
+            "@ abcdef1234567890\n○ 0123456789abcdef\n",
+            vec![
+                OperationEntry::new("abcdef1234567890", "describe commit").with_current(true),
+                OperationEntry::new("0123456789abcdef", "snapshot").with_rendered_line(1),
+            ],
+        );
+
+        let mut checkpoints = SessionCheckpoints::default();
+        checkpoints.record("session start", "0123456789abcdef");
+
+        let snapshot = operation_log_snapshot(&snapshot, &history, &checkpoints);
+
+        assert_eq!(
+            snapshot.rows()[0].jk_action.as_deref(),
+            Some("log describe")
+        );
+        assert_eq!(snapshot.rows()[0].checkpoint, None);
+        assert_eq!(snapshot.rows()[1].jk_action, None);
+        assert_eq!(
+            snapshot.rows()[1].checkpoint.as_deref(),
+            Some("session start")
+        );
     }
 }
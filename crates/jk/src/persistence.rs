@@ -0,0 +1,90 @@
+//! Small per-user state files that outlive one `jk` session.
+//!
+//! State lives under `$XDG_STATE_HOME/jk`, falling back to `~/.local/state/jk`. Files are plain
+//! newline-separated text so they stay easy to inspect or delete by hand; a missing or unreadable
+//! file reads as empty instead of blocking startup.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use jk_cli::repository_store;
+
+const STATE_DIR_NAME: &str = "jk";
+
+/// Returns the directory `jk` keeps persistent state in, if one can be resolved.
+pub fn state_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_STATE_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .filter(|value| !value.is_empty())
+                .map(|home| PathBuf::from(home).join(".local").join("state"))
+        })?;
+    Some(base.join(STATE_DIR_NAME))
+}
+
+/// Returns the state file named after the jj repository containing `repository`, or the current
+/// directory, inside the `kind` subdirectory of the state directory.
+pub fn repository_state_file(repository: Option<&Path>, kind: &str) -> Option<PathBuf> {
+    let start =
+        repository.map_or_else(|| std::env::current_dir().ok(), |path| Some(path.into()))?;
+    let store = repository_store(&start)?;
+    Some(state_dir()?.join(kind).join(store_file_name(&store)))
+}
+
+/// Flattens a store path into a single file name.
+fn store_file_name(store: &Path) -> String {
+    store
+        .to_string_lossy()
+        .trim_start_matches('/')
+        .replace(['/', '\\', ':'], "%")
+}
+
+/// Reads non-empty lines from `path`, treating a missing or unreadable file as empty.
+pub fn read_lines(path: &Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .map(|contents| {
+            contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(ToOwned::to_owned)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Writes `lines` to `path`, creating parent directories as needed.
+pub fn write_lines(path: &Path, lines: &[String]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_round_trip_through_nested_directories() {
+        let path = std::env::temp_dir()
+            .join(format!("jk-persistence-{}", std::process::id()))
+            .join("nested")
+            .join("lines");
+
+        let written = write_lines(&path, &["first".to_owned(), "second".to_owned()]);
+
+        assert!(written.is_ok());
+        assert_eq!(read_lines(&path), ["first", "second"]);
+        let _ = fs::remove_dir_all(path.parent().and_then(Path::parent).unwrap_or(&path));
+    }
+
+    #[test]
+    fn missing_file_reads_as_empty() {
+        assert!(read_lines(Path::new("/nonexistent/jk/state")).is_empty());
+    }
+}
@@ -0,0 +1,377 @@
+//! Tab completion for the `:` command prompt and the log revset prompt.
+//!
+//! Repository names are loaded once per prompt on the first Tab, so completion never runs `jj`
+//! while the user is only typing. The first Tab applies the first candidate and, when there are
+//! several, opens a popup list; further Tab and Shift-Tab presses cycle through it. Any other key
+//! closes the popup and keeps whatever candidate is in the input.
+
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use jk_cli::{
+    Completion, CompletionSources, JjCommandRunner, JjCompletion, RecordingJjCommandRunner,
+    SystemJjCommandRunner, complete_command_arg, complete_revset,
+};
+use jk_core::{CommandSource, LogEntry, SourceAction, SourceView};
+
+use crate::command_mode::parse_jj_command_args;
+use crate::state::{AppState, InputMode};
+
+const POPUP_ROWS: usize = 8;
+
+/// Cached completion sources and the open popup for the active prompt.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PromptCompletion {
+    sources: Option<CompletionSources>,
+    popup: Option<CompletionPopup>,
+}
+
+/// Candidates cycling through the prompt input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct CompletionPopup {
+    input: String,
+    completion: Completion,
+    selected: usize,
+}
+
+/// Which way Tab cycles through an open popup.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompletionDirection {
+    Next,
+    Previous,
+}
+
+impl PromptCompletion {
+    /// Returns whether the candidate popup is open.
+    pub(crate) const fn is_open(&self) -> bool {
+        self.popup.is_some()
+    }
+
+    /// Closes the popup, keeping loaded sources for the rest of the prompt.
+    pub(crate) fn dismiss(&mut self) {
+        self.popup = None;
+    }
+}
+
+/// Forgets loaded sources so the next prompt sees current repository names.
+pub fn reset_prompt_completion(state: &mut AppState) {
+    state.completion = PromptCompletion::default();
+}
+
+/// Maps Tab and Shift-Tab to prompt completion.
+pub const fn completion_direction(key: KeyEvent) -> Option<CompletionDirection> {
+    match key.code {
+        KeyCode::Tab => Some(CompletionDirection::Next),
+        KeyCode::BackTab => Some(CompletionDirection::Previous),
+        _ => None,
+    }
+}
+
+/// Closes the completion popup before a non-Tab key; returns true when Esc only closed the popup.
+pub fn dismiss_completion_popup(state: &mut AppState, key: KeyEvent) -> bool {
+    if !state.completion.is_open() {
+        return false;
+    }
+    state.completion.dismiss();
+    matches!(key.code, KeyCode::Esc)
+}
+
+/// Completes the token at the end of the active prompt, or cycles the open popup.
+pub fn complete_prompt(
+    state: &mut AppState,
+    repository: Option<&Path>,
+    direction: CompletionDirection,
+) {
+    complete_prompt_with_runner(state, repository, direction, SystemJjCommandRunner);
+}
+
+pub fn complete_prompt_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    repository: Option<&Path>,
+    direction: CompletionDirection,
+    runner: R,
+) {
+    if let Some(popup) = &mut state.completion.popup {
+        let count = popup.completion.candidates.len();
+        popup.selected = match direction {
+            CompletionDirection::Next => (popup.selected + 1) % count,
+            CompletionDirection::Previous => (popup.selected + count - 1) % count,
+        };
+        let completed = popup.completion.apply(&popup.input, popup.selected);
+        if let Some(completed) = completed {
+            set_prompt_input(state, completed);
+        }
+        return;
+    }
+
+    let Some((input, view)) = prompt_input(state) else {
+        return;
+    };
+    let sources = load_sources(state, repository, view, runner);
+    let entries = state
+        .views
+        .nearest_log()
+        .map(jk_tui::log_view::LogView::entries)
+        .unwrap_or_default();
+    let completion = match state.modes.active() {
+        Some(InputMode::LogRevset { .. }) => complete_revset(&input, &sources, entries),
+        _ => command_completion(&input, &sources, entries),
+    };
+    state.completion.sources = Some(sources);
+
+    let Some(completed) = completion.apply(&input, 0) else {
+        return;
+    };
+    set_prompt_input(state, completed);
+    if completion.candidates.len() > 1 {
+        state.completion.popup = Some(CompletionPopup {
+            input,
+            completion,
+            selected: 0,
+        });
+    }
+}
+
+/// Returns popup rows to append under the prompt, with the selected row marked.
+pub fn completion_popup_lines(completion: &PromptCompletion) -> Vec<String> {
+    let Some(popup) = &completion.popup else {
+        return Vec::new();
+    };
+    let candidates = &popup.completion.candidates;
+    let first = popup
+        .selected
+        .saturating_sub(POPUP_ROWS - 1)
+        .min(candidates.len().saturating_sub(POPUP_ROWS));
+    let text_width = candidates
+        .iter()
+        .map(|candidate| candidate.text.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![String::new()];
+    for (index, candidate) in candidates.iter().enumerate().skip(first).take(POPUP_ROWS) {
+        let marker = if index == popup.selected { ">" } else { " " };
+        let mut line = format!(
+            "{marker} {:text_width$}  {}",
+            candidate.text,
+            candidate.kind.label()
+        );
+        if let Some(detail) = &candidate.detail {
+            line.push_str("  ");
+            line.push_str(detail);
+        }
+        lines.push(line);
+    }
+    lines.push(format!(
+        "  {}/{}   tab next   shift-tab previous",
+        popup.selected + 1,
+        candidates.len()
+    ));
+    lines
+}
+
+fn prompt_input(state: &AppState) -> Option<(String, SourceView)> {
+    match state.modes.active()? {
+        InputMode::LogRevset { input, .. } => Some((input.clone(), SourceView::Log)),
+        InputMode::JjCommand { input, .. } => {
+            Some((input.clone(), SourceView::Other("command mode".to_owned())))
+        }
+        _ => None,
+    }
+}
+
+fn set_prompt_input(state: &mut AppState, completed: String) {
+    match state.modes.active_mut() {
+        Some(InputMode::LogRevset {
+            input,
+            error,
+            recall,
+        }) => {
+            *input = completed;
+            *error = None;
+            *recall = None;
+        }
+        Some(InputMode::JjCommand { input, error }) => {
+            *input = completed;
+            *error = None;
+        }
+        _ => {}
+    }
+}
+
+fn load_sources<R: JjCommandRunner>(
+    state: &mut AppState,
+    repository: Option<&Path>,
+    view: SourceView,
+    runner: R,
+) -> CompletionSources {
+    if let Some(sources) = state.completion.sources.take() {
+        return sources;
+    }
+    let mut loader = JjCompletion::default();
+    if let Some(repository) = repository {
+        loader = loader.with_repository(repository);
+    }
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(view, SourceAction::Complete).with_key("tab"),
+    );
+    loader.load_sources_with_runner(&mut runner)
+}
+
+/// Completes the last argument of a `:` command line.
+fn command_completion(
+    input: &str,
+    sources: &CompletionSources,
+    entries: &[LogEntry],
+) -> Completion {
+    let token_start = unquoted_token_start(input);
+    let token = &input[token_start..];
+    let previous = if token.starts_with(['\'', '"']) {
+        None
+    } else {
+        parse_jj_command_args(&input[..token_start]).ok()
+    };
+    let Some(mut previous) = previous else {
+        return Completion {
+            replace_start: token_start,
+            candidates: Vec::new(),
+        };
+    };
+    if previous.first().is_some_and(|arg| arg == "jj") {
+        previous.remove(0);
+    }
+    complete_command_arg(&previous, token, token_start, sources, entries)
+}
+
+/// Returns the byte offset after the last whitespace outside quotes.
+fn unquoted_token_start(input: &str) -> usize {
+    let mut start = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, character) in input.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if character == '\\' => escaped = true,
+            Some(open) if character == open => quote = None,
+            Some(_) => {}
+            None if character == '\'' || character == '"' => quote = Some(character),
+            None if character.is_whitespace() => start = index + character.len_utf8(),
+            None => {}
+        }
+    }
+    start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{SequencedRunner, log_app_view_with_changes, output};
+
+    fn sources_runner() -> SequencedRunner {
+        SequencedRunner::successes(vec![
+            output(0, "main\nmaint\n", ""),
+            output(0, "", ""),
+            output(0, "origin https://example.test\n", ""),
+            output(0, "default\n", ""),
+            output(0, "", ""),
+        ])
+    }
+
+    fn command_state(input: &str) -> AppState {
+        let mut state = AppState::new(log_app_view_with_changes(["kkmpptxzrspx", "mzvwutvl"]));
+        state.modes.push(InputMode::JjCommand {
+            input: input.to_owned(),
+            error: None,
+        });
+        state
+    }
+
+    fn active_input(state: &AppState) -> Option<&str> {
+        match state.modes.active() {
+            Some(InputMode::JjCommand { input, .. } | InputMode::LogRevset { input, .. }) => {
+                Some(input)
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn single_candidate_completes_without_popup() {
+        let mut state = command_state("jj squa");
+
+        complete_prompt_with_runner(
+            &mut state,
+            None,
+            CompletionDirection::Next,
+            sources_runner(),
+        );
+
+        assert_eq!(active_input(&state), Some("jj squash"));
+        assert!(!state.completion.is_open());
+        assert_eq!(state.command_history().records().count(), 5);
+    }
+
+    #[test]
+    fn tab_cycles_popup_candidates_and_reuses_sources() {
+        let mut state = command_state("rebase -d 'x y' -o mai");
+
+        complete_prompt_with_runner(
+            &mut state,
+            None,
+            CompletionDirection::Next,
+            sources_runner(),
+        );
+        assert_eq!(active_input(&state), Some("rebase -d 'x y' -o main"));
+        assert!(
+            completion_popup_lines(&state.completion).contains(&"> main   bookmark".to_owned())
+        );
+
+        let no_runs = SequencedRunner::successes(Vec::new());
+        complete_prompt_with_runner(&mut state, None, CompletionDirection::Next, no_runs);
+        assert_eq!(active_input(&state), Some("rebase -d 'x y' -o maint"));
+
+        let no_runs = SequencedRunner::successes(Vec::new());
+        complete_prompt_with_runner(&mut state, None, CompletionDirection::Previous, no_runs);
+        assert_eq!(active_input(&state), Some("rebase -d 'x y' -o main"));
+
+        state.completion.dismiss();
+        let no_runs = SequencedRunner::successes(Vec::new());
+        complete_prompt_with_runner(&mut state, None, CompletionDirection::Next, no_runs);
+        assert_eq!(state.command_history().records().count(), 5);
+    }
+
+    #[test]
+    fn revset_prompt_completes_change_ids_from_the_log() {
+        let mut state = AppState::new(log_app_view_with_changes(["kkmpptxzrspx", "mzvwutvl"]));
+        state.modes.push(InputMode::LogRevset {
+            input: "::kk".to_owned(),
+            error: Some("stale".to_owned()),
+            recall: Some(1),
+        });
+
+        complete_prompt_with_runner(
+            &mut state,
+            None,
+            CompletionDirection::Next,
+            sources_runner(),
+        );
+
+        assert_eq!(
+            state.modes.active(),
+            Some(&InputMode::LogRevset {
+                input: "::kkmpptxz".to_owned(),
+                error: None,
+                recall: None,
+            })
+        );
+    }
+
+    #[test]
+    fn token_start_ignores_whitespace_inside_quotes() {
+        assert_eq!(unquoted_token_start("describe -m 'a b"), 12);
+        assert_eq!(unquoted_token_start("log -r \"x \\\" y\" ma"), 16);
+        assert_eq!(unquoted_token_start("log"), 0);
+    }
+}
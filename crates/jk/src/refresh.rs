@@ -10,6 +10,7 @@ use jk_tui::operation_log_view::OperationLogView;
 use jk_tui::rendered_view::RenderedView;
 use jk_tui::workspaces_view::WorkspacesView;
 
+use crate::checkpoints::SessionCheckpoints;
 use crate::operation_log::operation_log_snapshot;
 use crate::state::{AppState, AppView};
 use crate::workspaces::workspace_view_snapshot;
@@ -51,6 +52,26 @@ pub fn refresh_log_with_runner<R: JjCommandRunner>(
     }
 }
 
+/// Reloads the log one page longer, keeping marks and selection.
+pub fn load_more_log(app: &mut LogView, history: &mut CommandHistory, source: &mut JjLog) {
+    load_more_log_with_runner(app, history, source, SystemJjCommandRunner);
+}
+
+pub fn load_more_log_with_runner<R: JjCommandRunner>(
+    app: &mut LogView,
+    history: &mut CommandHistory,
+    source: &mut JjLog,
+    runner: R,
+) {
+    let next_source = source.clone().with_next_page();
+    let command_source = CommandSource::new(SourceView::Log, SourceAction::LoadMoreLog);
+    if refresh_log_with_runner(app, history, &next_source, command_source, runner) {
+        *source = next_source;
+        let loaded = app.entries().len();
+        app.show_status(format!("{loaded} changes loaded"));
+    }
+}
+
 /// Reloads the active diff without replacing the view on failure.
 pub fn refresh_diff(
     app: &mut DiffView,
@@ -148,17 +169,17 @@ pub fn refresh_workspaces_with_runner<R: JjCommandRunner>(
 pub fn refresh_operation_log(
     app: &mut OperationLogView,
     history: &mut CommandHistory,
+    checkpoints: &SessionCheckpoints,
     source: &JjOperation,
 ) {
     let mut runner = crate::recording_runner(
         history,
         CommandSource::new(SourceView::OperationLog, SourceAction::Refresh),
     );
-    match source.load_query_with_runner(&OperationQuery::log(), &mut runner) {
-        Ok(snapshot) => app.refresh(operation_log_snapshot(
-            snapshot.title(),
-            snapshot.rendered(),
-        )),
+    let result = source.load_log_with_runner(&mut runner);
+    drop(runner);
+    match result {
+        Ok(snapshot) => app.refresh(operation_log_snapshot(&snapshot, history, checkpoints)),
         Err(_error) => {}
     }
 }
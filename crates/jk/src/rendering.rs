@@ -1,64 +1,142 @@
-use jk_cli::LogTemplateSelection;
+use jk_cli::{JjLog, LogTemplateSelection};
 use jk_tui::command_discovery::{BindingContext, discovery_lines_for_width_and_rows};
 use jk_tui::command_preview_view::CommandPreviewView;
 use ratatui::prelude::{Color, Line, Modifier, Span, Style};
 
+use crate::checkpoints::{checkpoint_name_lines, checkpoint_picker_lines};
 use crate::command_mode::jj_command_lines;
-use crate::menus::{diff_file_list_lines, template_selector_lines, view_options_lines};
+use crate::diff_file_list::diff_file_list_lines;
+use crate::diff_patch::{diff_patch_lines, diff_patch_save_lines};
+use crate::graph_navigation::widen_revset_lines;
+use crate::log_copy::log_copy_lines;
+use crate::menus::{
+    diff_view_options_lines, log_view_options_lines, operation_diff_view_options_lines,
+    operation_log_view_options_lines, template_selector_lines, view_options_lines,
+};
 use crate::mutation_preview::describe_message_lines;
+use crate::prompt_completion::completion_popup_lines;
+use crate::review_notes::{diff_note_lines, diff_notes_export_lines};
+use crate::revision_finder::revision_finder_lines;
+use crate::revset_prompt::revset_prompt_lines;
 use crate::state::{AppState, AppView, InputMode};
 
-pub fn render_app(
-    frame: &mut ratatui::Frame<'_>,
-    state: &mut AppState,
-    template: &LogTemplateSelection,
-) {
+pub fn render_app(frame: &mut ratatui::Frame<'_>, state: &mut AppState, source: &JjLog) {
+    let template = source.template();
     let mode = state.modes.active().cloned();
+    let prompt_lines = match &mode {
+        Some(InputMode::CheckpointName { name }) => checkpoint_name_lines(&state.checkpoints, name),
+        Some(InputMode::DiffNotesExport { path }) => diff_notes_export_lines(state, path),
+        Some(InputMode::DiffPatchSave { path }) => diff_patch_save_lines(path),
+        _ => Vec::new(),
+    };
+    let completion_lines = &completion_popup_lines(&state.completion);
+    let split = if matches!(state.views.active(), AppView::Log(_)) {
+        state.diff_preview.split_areas(frame.area())
+    } else {
+        None
+    };
+    if let Some((_, preview_area)) = split {
+        state.diff_preview.render(frame, preview_area);
+    }
+    let log_area = split.map_or_else(|| frame.area(), |(log_area, _)| log_area);
+    let preview_mode = state.diff_preview.mode();
+    let diff_tools = state.diff_tools.as_deref().unwrap_or_default();
+    let operation_user = &state.operation_user;
     match state.views.active_mut() {
         AppView::Log(log) => match &mode {
-            Some(InputMode::ViewOptions { context, selected }) => {
-                let lines = view_options_lines(*context, *selected, template, None);
-                log.render_with_selector(frame, "View Options", &lines);
+            Some(InputMode::ViewOptions { selected, .. }) => {
+                let lines = log_view_options_lines(*selected, source, preview_mode);
+                log.render_with_selector_in(frame, log_area, "View Options", &lines);
             }
             Some(InputMode::LogTemplate { options, selected }) => {
                 let lines = template_selector_lines(options, *selected);
-                log.render_with_selector(frame, "Log template", &lines);
+                log.render_with_selector_in(frame, log_area, "Log template", &lines);
             }
             Some(InputMode::CommandDiscovery {
                 context,
                 query,
                 scroll_offset,
             }) => {
-                log.render(frame);
+                log.render_in(frame, log_area, None);
                 render_command_discovery_overlay(frame, *context, query, *scroll_offset);
             }
             Some(InputMode::JjCommand { input, error }) => {
-                log.render(frame);
-                let lines = jj_command_lines(input, error.as_deref());
+                log.render_in(frame, log_area, None);
+                let lines =
+                    with_completion(jj_command_lines(input, error.as_deref()), completion_lines);
                 render_mode_overlay(frame, "jj command", &lines);
             }
             Some(InputMode::DescribeMessage { rev, message }) => {
-                log.render(frame);
+                log.render_in(frame, log_area, None);
                 let lines = describe_message_lines(rev, message);
                 render_mode_overlay(frame, "Describe revision", &lines);
             }
+            Some(InputMode::InspectionSearch { query }) => {
+                let status = format!("/{query}");
+                log.render_in(frame, log_area, Some(&status));
+            }
+            Some(InputMode::LogRevset { input, error, .. }) => {
+                log.render_in(frame, log_area, None);
+                let lines = with_completion(
+                    revset_prompt_lines(input, error.as_deref()),
+                    completion_lines,
+                );
+                render_mode_overlay(frame, "Log revset", &lines);
+            }
+            Some(InputMode::RevisionFinder { finder }) => {
+                log.render_in(frame, log_area, None);
+                let lines = revision_finder_lines(finder);
+                render_mode_overlay(frame, &finder.purpose().title(), &lines);
+            }
+            Some(InputMode::WidenRevset { graph_move, revset }) => {
+                log.render_in(frame, log_area, None);
+                let lines = widen_revset_lines(*graph_move, revset);
+                render_mode_overlay(frame, "Widen revset", &lines);
+            }
+            Some(InputMode::LogCopy) => {
+                log.render_in(frame, log_area, None);
+                let lines = log_copy_lines(log);
+                render_mode_overlay(frame, "Copy", &lines);
+            }
+            Some(InputMode::CheckpointName { .. }) => {
+                log.render_in(frame, log_area, None);
+                render_mode_overlay(frame, "Checkpoint", &prompt_lines);
+            }
+            Some(InputMode::CheckpointPicker { selected }) => {
+                let lines = checkpoint_picker_lines(&state.checkpoints, *selected);
+                log.render_with_selector_in(frame, log_area, "Restore checkpoint", &lines);
+            }
             Some(InputMode::CommandPreview { pending }) => {
-                log.render(frame);
+                log.render_in(frame, log_area, None);
                 CommandPreviewView::new(pending.preview.clone())
                     .with_status(pending.copy_status.clone())
                     .render(frame);
             }
-            _ => log.render(frame),
+            _ => log.render_in(frame, log_area, None),
         },
         AppView::Diff { view, query } => match &mode {
-            Some(InputMode::ViewOptions { context, selected }) => {
-                let lines = view_options_lines(*context, *selected, template, Some(query.format()));
+            Some(InputMode::ViewOptions { selected, .. }) => {
+                let lines = diff_view_options_lines(*selected, query.options(), diff_tools);
                 view.render_with_overlay(frame, "View Options", &lines);
             }
-            Some(InputMode::DiffFileList { selected }) => {
-                let lines = diff_file_list_lines(view, *selected);
+            Some(InputMode::DiffFileList { list }) => {
+                let lines = diff_file_list_lines(list);
                 view.render_with_overlay(frame, "Diff files", &lines);
             }
+            Some(InputMode::DiffNote { anchor, comment }) => {
+                let lines = diff_note_lines(anchor, comment);
+                view.render_with_overlay(frame, "Review note", &lines);
+            }
+            Some(InputMode::DiffNotesExport { .. }) => {
+                view.render_with_overlay(frame, "Export review notes", &prompt_lines);
+            }
+            Some(InputMode::DiffPatch) => {
+                let lines = diff_patch_lines(view);
+                view.render_with_overlay(frame, "Copy patch", &lines);
+            }
+            Some(InputMode::DiffPatchSave { .. }) => {
+                view.render_with_overlay(frame, "Save patch", &prompt_lines);
+            }
             Some(InputMode::DiffSearch { query }) => {
                 let status = format!("/{query}");
                 view.render_with_status(frame, &status);
@@ -72,14 +150,27 @@ pub fn render_app(
                 render_command_discovery_overlay(frame, *context, query, *scroll_offset);
             }
             Some(InputMode::JjCommand { input, error }) => {
-                let lines = jj_command_lines(input, error.as_deref());
+                let lines =
+                    with_completion(jj_command_lines(input, error.as_deref()), completion_lines);
                 view.render_with_overlay(frame, "jj command", &lines);
             }
+            Some(InputMode::CommandPreview { pending }) => {
+                view.render(frame);
+                CommandPreviewView::new(pending.preview.clone())
+                    .with_status(pending.copy_status.clone())
+                    .render(frame);
+            }
             _ => view.render(frame),
         },
-        AppView::Show { view, .. } => render_inspection(frame, view, &mode, template),
-        AppView::Evolog { view, .. } => render_inspection(frame, view, &mode, template),
-        AppView::Status { view, .. } => render_inspection(frame, view, &mode, template),
+        AppView::Show { view, .. } => {
+            render_inspection(frame, view, &mode, template, completion_lines)
+        }
+        AppView::Evolog { view, .. } => {
+            render_inspection(frame, view, &mode, template, completion_lines)
+        }
+        AppView::Status { view, .. } => {
+            render_inspection(frame, view, &mode, template, completion_lines)
+        }
         AppView::Workspaces { view } => match &mode {
             Some(InputMode::ViewOptions { context, selected }) => {
                 let lines = view_options_lines(*context, *selected, template, None);
@@ -96,7 +187,8 @@ pub fn render_app(
             }
             Some(InputMode::JjCommand { input, error }) => {
                 view.render(frame);
-                let lines = jj_command_lines(input, error.as_deref());
+                let lines =
+                    with_completion(jj_command_lines(input, error.as_deref()), completion_lines);
                 render_mode_overlay(frame, "jj command", &lines);
             }
             _ => view.render(frame),
@@ -112,14 +204,19 @@ pub fn render_app(
             }
             Some(InputMode::JjCommand { input, error }) => {
                 view.render(frame);
-                let lines = jj_command_lines(input, error.as_deref());
+                let lines =
+                    with_completion(jj_command_lines(input, error.as_deref()), completion_lines);
                 render_mode_overlay(frame, "jj command", &lines);
             }
             _ => view.render(frame),
         },
         AppView::OperationLog { view } => match &mode {
-            Some(InputMode::ViewOptions { context, selected }) => {
-                let lines = view_options_lines(*context, *selected, template, None);
+            Some(InputMode::ViewOptions { selected, .. }) => {
+                let lines = operation_log_view_options_lines(
+                    *selected,
+                    view.filter(),
+                    operation_user.as_deref(),
+                );
                 view.render(frame);
                 render_mode_overlay(frame, "View Options", &lines);
             }
@@ -133,9 +230,14 @@ pub fn render_app(
             }
             Some(InputMode::JjCommand { input, error }) => {
                 view.render(frame);
-                let lines = jj_command_lines(input, error.as_deref());
+                let lines =
+                    with_completion(jj_command_lines(input, error.as_deref()), completion_lines);
                 render_mode_overlay(frame, "jj command", &lines);
             }
+            Some(InputMode::CheckpointName { .. }) => {
+                view.render(frame);
+                render_mode_overlay(frame, "Checkpoint", &prompt_lines);
+            }
             _ => view.render(frame),
         },
         AppView::CommandHistoryDetails { view }
@@ -143,8 +245,16 @@ pub fn render_app(
         | AppView::WorkspaceLog { view, .. }
         | AppView::WorkspaceStatus { view, .. }
         | AppView::WorkspaceDiff { view, .. }
-        | AppView::OperationShow { view, .. }
-        | AppView::OperationDiff { view, .. } => render_inspection(frame, view, &mode, template),
+        | AppView::OperationShow { view, .. } => {
+            render_inspection(frame, view, &mode, template, completion_lines)
+        }
+        AppView::OperationDiff { view, query } => match &mode {
+            Some(InputMode::ViewOptions { selected, .. }) => {
+                let lines = operation_diff_view_options_lines(*selected, query.patch());
+                view.render_with_overlay(frame, "View Options", &lines);
+            }
+            _ => render_inspection(frame, view, &mode, template, completion_lines),
+        },
     }
 }
 
@@ -153,6 +263,7 @@ fn render_inspection(
     view: &mut jk_tui::rendered_view::RenderedView,
     mode: &Option<InputMode>,
     template: &LogTemplateSelection,
+    completion_lines: &[String],
 ) {
     match mode {
         Some(InputMode::ViewOptions { context, selected }) => {
@@ -172,13 +283,21 @@ fn render_inspection(
             render_command_discovery_overlay(frame, *context, query, *scroll_offset);
         }
         Some(InputMode::JjCommand { input, error }) => {
-            let lines = jj_command_lines(input, error.as_deref());
+            let lines =
+                with_completion(jj_command_lines(input, error.as_deref()), completion_lines);
             view.render_with_overlay(frame, "jj command", &lines);
         }
         _ => view.render(frame),
     }
 }
 
+/// Shows the completion popup directly under the prompt input line.
+fn with_completion(mut lines: Vec<String>, completion_lines: &[String]) -> Vec<String> {
+    let insert_at = lines.len().min(1);
+    lines.splice(insert_at..insert_at, completion_lines.iter().cloned());
+    lines
+}
+
 fn render_command_discovery_overlay(
     frame: &mut ratatui::Frame<'_>,
     context: BindingContext,
@@ -0,0 +1,525 @@
+//! Local review notes on diff lines and hunks, and their markdown export.
+//!
+//! Notes belong to the changes a diff shows and are saved per repository, one note per line. The
+//! diff's revsets are resolved to change ids first, so a note left on `@` stays with that change
+//! after `jj new` moves `@` elsewhere. The diff view re-anchors them by quoted content whenever the
+//! diff reloads. Export renders every note for the open diff as markdown that can be copied or
+//! written to a file and pasted into an agent prompt.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use jk_cli::{
+    CachingJjCommandRunner, JjCommandRunner, JjDiff, RecordingJjCommandRunner,
+    SystemJjCommandRunner,
+};
+use jk_core::{CommandSource, SourceAction, SourceView};
+use jk_tui::diff_view::{DiffAnchor, DiffNote, notes_markdown};
+
+use crate::clipboard::copy_text;
+use crate::persistence::{read_lines, repository_state_file, write_lines};
+use crate::state::{AppState, AppView, InputMode, InputModeResult};
+
+const REVIEW_NOTES_DIR: &str = "notes";
+
+/// Review notes for one repository, keyed by the change ids a diff compares.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReviewNotes {
+    notes: Vec<(String, DiffNote)>,
+    path: Option<PathBuf>,
+}
+
+impl ReviewNotes {
+    /// Loads notes for the repository containing `repository`, or the current directory.
+    pub(crate) fn load(repository: Option<&Path>) -> Self {
+        repository_state_file(repository, REVIEW_NOTES_DIR)
+            .map_or_else(Self::default, Self::at_path)
+    }
+
+    /// Loads notes from `path` and saves later changes back to it.
+    pub(crate) fn at_path(path: PathBuf) -> Self {
+        Self {
+            notes: read_lines(&path)
+                .iter()
+                .filter_map(|line| decode_note(line))
+                .collect(),
+            path: Some(path),
+        }
+    }
+
+    /// Returns whether no notes are recorded.
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// Returns the notes recorded for `revision`.
+    pub(crate) fn for_revision(&self, revision: &str) -> Vec<DiffNote> {
+        self.notes
+            .iter()
+            .filter(|(note_revision, _)| note_revision == revision)
+            .map(|(_, note)| note.clone())
+            .collect()
+    }
+
+    /// Replaces the notes for `revision` and saves them.
+    pub(crate) fn replace(&mut self, revision: &str, notes: Vec<DiffNote>) {
+        self.notes
+            .retain(|(note_revision, _)| note_revision != revision);
+        self.notes
+            .extend(notes.into_iter().map(|note| (revision.to_owned(), note)));
+        let lines = self
+            .notes
+            .iter()
+            .map(|(revision, note)| encode_note(revision, note))
+            .collect::<Vec<_>>();
+        if let Some(path) = &self.path
+            && let Err(error) = write_lines(path, &lines)
+        {
+            tracing::warn!(%error, path = %path.display(), "failed to save review notes");
+        }
+    }
+}
+
+/// Places the saved notes for the active diff's changes on it.
+pub fn show_review_notes(state: &mut AppState, diff_source: &JjDiff) {
+    show_review_notes_with_runner(state, diff_source, SystemJjCommandRunner);
+}
+
+fn show_review_notes_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    diff_source: &JjDiff,
+    runner: R,
+) {
+    if state.review_notes.is_empty() || !matches!(state.views.active(), AppView::Diff { .. }) {
+        return;
+    }
+    let Some(key) = note_key_with_runner(state, diff_source, runner) else {
+        return;
+    };
+    let notes = state.review_notes.for_revision(&key);
+    if let AppView::Diff { view, .. } = state.views.active_mut() {
+        view.set_notes(notes);
+    }
+}
+
+/// Resolves the active diff's revisions to the change-id key its notes are saved under.
+///
+/// A failed lookup is shown on the diff and returns `None`.
+fn note_key_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    diff_source: &JjDiff,
+    runner: R,
+) -> Option<String> {
+    let AppView::Diff { view, query } = state.views.active_mut() else {
+        return None;
+    };
+    let mut runner = RecordingJjCommandRunner::new(
+        CachingJjCommandRunner::new(runner, &mut state.output_cache),
+        &mut state.history,
+        CommandSource::new(SourceView::Diff, SourceAction::ResolveNoteTarget),
+    );
+    match diff_source.load_change_key_with_runner(query, &mut runner) {
+        Ok(key) => Some(key),
+        Err(error) => {
+            view.show_error(format!("review notes unavailable: {error}"));
+            None
+        }
+    }
+}
+
+/// Opens the note prompt for the line at the top of the active diff.
+pub fn open_diff_note_prompt(state: &mut AppState) {
+    let AppView::Diff { view, .. } = state.views.active_mut() else {
+        return;
+    };
+    let Some(anchor) = view.anchor_at_cursor() else {
+        view.show_status("no file here to note");
+        return;
+    };
+    let comment = view.note_for(&anchor).unwrap_or_default().to_owned();
+    state.modes.push(InputMode::DiffNote { anchor, comment });
+}
+
+/// Returns the note prompt lines.
+pub fn diff_note_lines(anchor: &DiffAnchor, comment: &str) -> Vec<String> {
+    let target = match (anchor.line_text(), anchor.context().first()) {
+        (Some(line), _) => format!("{}  {line}", anchor.path()),
+        (None, Some(header)) => format!("{}  {header}", anchor.path()),
+        (None, None) => anchor.path().to_owned(),
+    };
+    vec![
+        target,
+        format!("Note: {comment}"),
+        String::new(),
+        "type note   enter save (empty deletes)   Ctrl-u clear   esc cancel".to_owned(),
+    ]
+}
+
+/// Handles keys while the note prompt is open.
+pub fn handle_diff_note_mode(
+    state: &mut AppState,
+    diff_source: &JjDiff,
+    key: KeyEvent,
+) -> InputModeResult {
+    handle_diff_note_mode_with_runner(state, diff_source, key, SystemJjCommandRunner)
+}
+
+fn handle_diff_note_mode_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    diff_source: &JjDiff,
+    key: KeyEvent,
+    runner: R,
+) -> InputModeResult {
+    let Some(InputMode::DiffNote { anchor, comment }) = state.modes.active_mut() else {
+        return InputModeResult::Unhandled;
+    };
+    match key {
+        KeyEvent {
+            code: KeyCode::Esc, ..
+        } => {
+            state.modes.pop();
+        }
+        KeyEvent {
+            code: KeyCode::Enter,
+            ..
+        } => {
+            let anchor = anchor.clone();
+            let comment = comment.clone();
+            state.modes.pop();
+            save_note_with_runner(state, diff_source, anchor, &comment, runner);
+        }
+        KeyEvent {
+            code: KeyCode::Backspace,
+            ..
+        } => {
+            comment.pop();
+        }
+        KeyEvent {
+            code: KeyCode::Char('u'),
+            modifiers,
+            ..
+        } if modifiers == KeyModifiers::CONTROL => comment.clear(),
+        KeyEvent {
+            code: KeyCode::Char(character),
+            modifiers,
+            ..
+        } if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+            comment.push(character);
+        }
+        _ => {}
+    }
+    InputModeResult::Handled
+}
+
+/// Sets the note on the diff, then saves the diff's notes under its resolved change ids.
+fn save_note_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    diff_source: &JjDiff,
+    anchor: DiffAnchor,
+    comment: &str,
+    runner: R,
+) {
+    let AppView::Diff { view, .. } = state.views.active_mut() else {
+        return;
+    };
+    view.set_note(anchor, comment);
+    let Some(key) = note_key_with_runner(state, diff_source, runner) else {
+        return;
+    };
+    let AppView::Diff { view, .. } = state.views.active_mut() else {
+        return;
+    };
+    let notes = view.notes();
+    let count = notes.len();
+    view.show_status(format!("{count} review note{}", plural(count)));
+    state.review_notes.replace(&key, notes);
+}
+
+/// Opens the export prompt for the active diff's notes.
+pub fn open_diff_notes_export(state: &mut AppState) {
+    let AppView::Diff { view, .. } = state.views.active_mut() else {
+        return;
+    };
+    if view.notes().is_empty() {
+        view.show_status("no review notes to export; c adds one");
+        return;
+    }
+    let path = temp_export_path("review", view.change_id(), "md");
+    state.modes.push(InputMode::DiffNotesExport {
+        path: path.display().to_string(),
+    });
+}
+
+/// Returns the export prompt lines.
+pub fn diff_notes_export_lines(state: &AppState, path: &str) -> Vec<String> {
+    let count = match state.views.active() {
+        AppView::Diff { view, .. } => view.notes().len(),
+        _ => 0,
+    };
+    vec![
+        format!("{count} review note{} as markdown", plural(count)),
+        format!("Write to: {path}"),
+        String::new(),
+        "enter write file   Ctrl-y copy   Ctrl-u clear   esc cancel".to_owned(),
+    ]
+}
+
+/// Handles keys while the export prompt is open.
+pub fn handle_diff_notes_export_mode(state: &mut AppState, key: KeyEvent) -> InputModeResult {
+    let Some(InputMode::DiffNotesExport { path }) = state.modes.active_mut() else {
+        return InputModeResult::Unhandled;
+    };
+    match key {
+        KeyEvent {
+            code: KeyCode::Esc, ..
+        } => {
+            state.modes.pop();
+        }
+        KeyEvent {
+            code: KeyCode::Enter,
+            ..
+        } => {
+            let path = PathBuf::from(path.trim());
+            if path.as_os_str().is_empty() {
+                return InputModeResult::Handled;
+            }
+            state.modes.pop();
+            export_notes(state, |markdown| match write_export(&path, markdown) {
+                Ok(()) => format!("wrote review notes to {}", path.display()),
+                Err(error) => format!("write failed: {error}"),
+            });
+        }
+        KeyEvent {
+            code: KeyCode::Char('y'),
+            modifiers,
+            ..
+        } if modifiers == KeyModifiers::CONTROL => {
+            state.modes.pop();
+            export_notes(state, |markdown| copy_text(markdown, "review notes"));
+        }
+        KeyEvent {
+            code: KeyCode::Backspace,
+            ..
+        } => {
+            path.pop();
+        }
+        KeyEvent {
+            code: KeyCode::Char('u'),
+            modifiers,
+            ..
+        } if modifiers == KeyModifiers::CONTROL => path.clear(),
+        KeyEvent {
+            code: KeyCode::Char(character),
+            modifiers,
+            ..
+        } if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+            path.push(character);
+        }
+        _ => {}
+    }
+    InputModeResult::Handled
+}
+
+/// Renders the active diff's notes and shows the status `deliver` returns for them.
+fn export_notes(state: &mut AppState, deliver: impl FnOnce(&str) -> String) {
+    let AppView::Diff { view, .. } = state.views.active_mut() else {
+        return;
+    };
+    let markdown = notes_markdown(view.title(), &view.notes());
+    let status = deliver(&markdown);
+    view.show_status(status);
+}
+
+/// Writes `contents` to `path`, creating missing parent directories.
+pub fn write_export(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Returns `jk-{kind}-{revision}.{extension}` in the temporary directory, outside the working
+/// copy so jj does not snapshot the export.
+pub fn temp_export_path(kind: &str, revision: &str, extension: &str) -> PathBuf {
+    let name = revision
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() || "-_".contains(character) {
+                character
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    std::env::temp_dir().join(format!("jk-{kind}-{name}.{extension}"))
+}
+
+const fn plural(count: usize) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+/// Encodes a note as `revision`, path, anchored line, quoted lines and comment separated by tabs.
+fn encode_note(revision: &str, note: &DiffNote) -> String {
+    let anchor = note.anchor();
+    [
+        escape(revision),
+        escape(anchor.path()),
+        anchor
+            .line()
+            .map_or_else(|| "-".to_owned(), |line| line.to_string()),
+        escape(&anchor.context().join("\n")),
+        escape(note.comment()),
+    ]
+    .join("\t")
+}
+
+fn decode_note(line: &str) -> Option<(String, DiffNote)> {
+    let mut fields = line.split('\t');
+    let revision = unescape(fields.next()?);
+    let path = unescape(fields.next()?);
+    let anchored = fields.next()?.parse().ok();
+    let context = unescape(fields.next()?);
+    let comment = unescape(fields.next()?);
+    let context = if context.is_empty() {
+        Vec::new()
+    } else {
+        context.split('\n').map(ToOwned::to_owned).collect()
+    };
+    Some((
+        revision,
+        DiffNote::new(DiffAnchor::new(path, context, anchored), comment),
+    ))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut characters = text.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue;
+        }
+        match characters.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{SequencedRunner, diff_query, output, real_diff_view};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn note_prompt_saves_a_note_on_the_diff_and_in_the_store() {
+        let dir = std::env::temp_dir().join(format!("jk-review-prompt-{}", std::process::id()));
+        let mut state = AppState::new(AppView::Diff {
+            view: real_diff_view("abc"),
+            query: diff_query("abc"),
+        });
+        state.review_notes = ReviewNotes::at_path(dir.join("repo"));
+
+        open_diff_note_prompt(&mut state);
+        for character in "ok".chars() {
+            handle_diff_note_mode(
+                &mut state,
+                &JjDiff::default(),
+                key(KeyCode::Char(character)),
+            );
+        }
+        handle_diff_note_mode_with_runner(
+            &mut state,
+            &JjDiff::default(),
+            key(KeyCode::Enter),
+            SequencedRunner::successes(vec![output(0, "qpvuntsm\n", "")]),
+        );
+
+        let AppView::Diff { view, .. } = state.views.active() else {
+            panic!("diff view stays active");
+        };
+        assert!(state.modes.active().is_none());
+        assert_eq!(view.notes()[0].anchor().path(), "src/a.rs");
+        assert_eq!(view.notes()[0].comment(), "ok");
+        assert_eq!(state.review_notes.for_revision("qpvuntsm"), view.notes());
+        assert!(state.review_notes.for_revision("abc").is_empty());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn notes_follow_the_change_a_revset_resolves_to() {
+        let dir = std::env::temp_dir().join(format!("jk-review-resolve-{}", std::process::id()));
+        let note = DiffNote::new(DiffAnchor::new("src/a.rs", Vec::new(), None), "later");
+        let mut notes = ReviewNotes::at_path(dir.join("repo"));
+        notes.replace("qpvuntsm..rlvkpnrz", vec![note.clone()]);
+        let mut state = AppState::new(AppView::Diff {
+            view: real_diff_view("main..@"),
+            query: jk_cli::DiffQuery::FromTo {
+                from: "main".to_owned(),
+                to: "@".to_owned(),
+                options: jk_cli::DiffOptions::default(),
+            },
+        });
+        state.review_notes = notes;
+
+        show_review_notes_with_runner(
+            &mut state,
+            &JjDiff::default(),
+            SequencedRunner::successes(vec![
+                output(0, "qpvuntsm\n", ""),
+                output(0, "rlvkpnrz\n", ""),
+            ]),
+        );
+
+        let AppView::Diff { view, .. } = state.views.active() else {
+            panic!("diff view stays active");
+        };
+        assert_eq!(view.notes(), vec![note]);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn notes_round_trip_per_revision() {
+        let dir = std::env::temp_dir().join(format!("jk-review-notes-{}", std::process::id()));
+        let path = dir.join("repo");
+        let anchor = DiffAnchor::new(
+            "src/a b.rs",
+            vec!["@@ -1,1 +1,1 @@".to_owned(), "+\tnew \\ line".to_owned()],
+            Some(1),
+        );
+        let note = DiffNote::new(anchor, "Split this\ninto two.");
+
+        let mut notes = ReviewNotes::at_path(path.clone());
+        notes.replace("abc", vec![note.clone()]);
+        notes.replace("def", Vec::new());
+
+        let loaded = ReviewNotes::at_path(path);
+        assert_eq!(loaded.for_revision("abc"), vec![note]);
+        assert!(loaded.for_revision("def").is_empty());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn temp_export_path_stays_out_of_the_working_copy() {
+        let path = temp_export_path("review", "main..@", "md");
+
+        assert!(path.starts_with(std::env::temp_dir()));
+        assert!(path.ends_with("jk-review-main---.md"));
+    }
+}
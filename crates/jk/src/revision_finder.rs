@@ -0,0 +1,711 @@
+//! Fuzzy revision finder for jumping to changes and picking rebase or squash targets.
+//!
+//! The finder loads a bounded `jj log -r 'all()'` through the semantic finder template once when it
+//! opens, then filters in memory as the user types. Matching is a case-insensitive subsequence
+//! match over change ids, description first lines, authors, and local bookmarks, with change-id
+//! prefixes ranked first. Picking a change either selects it in the active log or, when the log's
+//! revset hides it, reloads the log around it. Rebase and squash reuse the same list to choose a
+//! destination and hand the result to the normal command preview.
+
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use jk_cli::{
+    JjCommandRunner, JjLog, JjRebase, JjSquash, RebaseQuery, RecordingJjCommandRunner,
+    RevisionCandidate, SquashQuery, SystemJjCommandRunner,
+};
+use jk_core::{CommandSource, SourceAction, SourceView};
+
+use crate::graph_navigation::{current_revset, widened_revset};
+use crate::mutation_preview::{PendingCommandPreview, selected_new_parents};
+use crate::state::{AppState, AppView, InputMode, InputModeResult};
+
+/// Revisions loaded when the finder opens.
+const FINDER_LIMIT: usize = 500;
+const FINDER_ROWS: usize = 12;
+const SHORT_ID_LEN: usize = 12;
+/// Ancestors and descendants shown around a change that the current revset hid.
+const REVEAL_DEPTH: usize = 10;
+
+/// What picking a revision does.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FinderPurpose {
+    /// Select the revision in the log.
+    Jump,
+    /// Rebase `revisions` onto the picked destination.
+    Rebase { revisions: Vec<String> },
+    /// Squash `from` into the picked revision.
+    Squash { from: String },
+}
+
+impl FinderPurpose {
+    const fn key(&self) -> &'static str {
+        match self {
+            Self::Jump => "p",
+            Self::Rebase { .. } => "B",
+            Self::Squash { .. } => "S",
+        }
+    }
+
+    /// Returns the overlay title.
+    pub(crate) fn title(&self) -> String {
+        match self {
+            Self::Jump => "Find revision".to_owned(),
+            Self::Rebase { revisions } => format!("Rebase {} onto", revisions.join(", ")),
+            Self::Squash { from } => format!("Squash {from} into"),
+        }
+    }
+
+    const fn verb(&self) -> &'static str {
+        match self {
+            Self::Jump => "jump",
+            Self::Rebase { .. } => "preview rebase",
+            Self::Squash { .. } => "preview squash",
+        }
+    }
+
+    /// Returns whether `candidate` is one of the revisions being moved.
+    fn is_source(&self, candidate: &RevisionCandidate) -> bool {
+        match self {
+            Self::Jump => false,
+            Self::Rebase { revisions } => revisions
+                .iter()
+                .any(|revision| candidate.change_id.starts_with(revision.as_str())),
+            Self::Squash { from } => candidate.change_id.starts_with(from.as_str()),
+        }
+    }
+}
+
+/// Loaded candidates and the current filter.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevisionFinder {
+    purpose: FinderPurpose,
+    query: String,
+    candidates: Vec<RevisionCandidate>,
+    matches: Vec<usize>,
+    selected: usize,
+}
+
+impl RevisionFinder {
+    fn new(purpose: FinderPurpose, candidates: Vec<RevisionCandidate>) -> Self {
+        let candidates = candidates
+            .into_iter()
+            .filter(|candidate| !purpose.is_source(candidate))
+            .collect();
+        let mut finder = Self {
+            purpose,
+            query: String::new(),
+            candidates,
+            matches: Vec::new(),
+            selected: 0,
+        };
+        finder.refilter();
+        finder
+    }
+
+    /// Returns what picking a revision does.
+    pub(crate) const fn purpose(&self) -> &FinderPurpose {
+        &self.purpose
+    }
+
+    fn selected_candidate(&self) -> Option<&RevisionCandidate> {
+        self.matches
+            .get(self.selected)
+            .and_then(|index| self.candidates.get(*index))
+    }
+
+    fn refilter(&mut self) {
+        let query = self.query.trim().to_lowercase();
+        let mut scored = self
+            .candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(index, candidate)| {
+                candidate_score(candidate, &query).map(|score| (index, score))
+            })
+            .collect::<Vec<_>>();
+        // Stable sort keeps jj's log order among equally good matches.
+        scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        self.matches = scored.into_iter().map(|(index, _)| index).collect();
+        self.selected = 0;
+    }
+
+    fn move_selection(&mut self, forward: bool) {
+        let count = self.matches.len();
+        if count == 0 {
+            return;
+        }
+        self.selected = if forward {
+            (self.selected + 1) % count
+        } else {
+            (self.selected + count - 1) % count
+        };
+    }
+}
+
+/// Scores `candidate` against a lowercased query; `None` means it does not match.
+fn candidate_score(candidate: &RevisionCandidate, query: &str) -> Option<usize> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    if candidate.change_id.starts_with(query) {
+        return Some(usize::MAX);
+    }
+    let summary = candidate.description.lines().next().unwrap_or_default();
+    [
+        candidate.change_id.as_str(),
+        summary,
+        candidate.author.as_str(),
+        &candidate.bookmarks.join(" "),
+    ]
+    .into_iter()
+    .filter_map(|field| fuzzy_score(&field.to_lowercase(), query))
+    .max()
+}
+
+/// Greedy subsequence match rewarding consecutive characters and word starts.
+pub(crate) fn fuzzy_score(haystack: &str, needle: &str) -> Option<usize> {
+    let mut needle = needle.chars().peekable();
+    let mut score = 0;
+    let mut previous_matched = false;
+    let mut previous = None;
+    for character in haystack.chars() {
+        let Some(&wanted) = needle.peek() else {
+            break;
+        };
+        if character == wanted {
+            needle.next();
+            score += 1;
+            if previous_matched {
+                score += 3;
+            }
+            if previous.is_none_or(|previous: char| !previous.is_alphanumeric()) {
+                score += 2;
+            }
+            previous_matched = true;
+        } else {
+            previous_matched = false;
+        }
+        previous = Some(character);
+    }
+    needle.peek().is_none().then_some(score)
+}
+
+/// Opens the finder to jump to a revision in the log.
+pub fn open_jump_finder(state: &mut AppState, source: &JjLog) {
+    open_revision_finder_with_runner(state, source, FinderPurpose::Jump, SystemJjCommandRunner);
+}
+
+/// Opens the finder to pick a rebase destination for the marked or selected revisions.
+pub fn open_rebase_finder(state: &mut AppState, source: &JjLog) {
+    let AppView::Log(log) = state.views.active_mut() else {
+        return;
+    };
+    let revisions = selected_new_parents(log);
+    if revisions.is_empty() {
+        log.show_error("No revision selected");
+        return;
+    }
+    open_revision_finder_with_runner(
+        state,
+        source,
+        FinderPurpose::Rebase { revisions },
+        SystemJjCommandRunner,
+    );
+}
+
+/// Opens the finder to pick a squash target for the selected revision.
+pub fn open_squash_finder(state: &mut AppState, source: &JjLog) {
+    let AppView::Log(log) = state.views.active_mut() else {
+        return;
+    };
+    let Some(from) = log.selected_revision_id().map(ToOwned::to_owned) else {
+        log.show_error("No revision selected");
+        return;
+    };
+    open_revision_finder_with_runner(
+        state,
+        source,
+        FinderPurpose::Squash { from },
+        SystemJjCommandRunner,
+    );
+}
+
+pub fn open_revision_finder_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    source: &JjLog,
+    purpose: FinderPurpose,
+    runner: R,
+) {
+    if !matches!(state.views.active(), AppView::Log(_)) {
+        return;
+    }
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(SourceView::Log, SourceAction::FindRevision).with_key(purpose.key()),
+    );
+    let result = source.load_revision_candidates_with_runner(&mut runner, FINDER_LIMIT);
+    drop(runner);
+
+    match result {
+        Ok(candidates) => state.modes.push(InputMode::RevisionFinder {
+            finder: RevisionFinder::new(purpose, candidates),
+        }),
+        Err(error) => {
+            if let AppView::Log(log) = state.views.active_mut() {
+                log.show_error(format!("revision finder failed: {error}"));
+            }
+        }
+    }
+}
+
+/// Handles key input while the revision finder is open.
+pub fn handle_revision_finder_mode(
+    state: &mut AppState,
+    source: &mut JjLog,
+    repository: Option<&Path>,
+    key: KeyEvent,
+) -> InputModeResult {
+    let Some(InputMode::RevisionFinder { finder }) = state.modes.active_mut() else {
+        return InputModeResult::Unhandled;
+    };
+    match key {
+        KeyEvent {
+            code: KeyCode::Esc, ..
+        } => {
+            state.modes.pop();
+        }
+        KeyEvent {
+            code: KeyCode::Enter,
+            ..
+        } => submit_revision_finder(state, source, repository),
+        KeyEvent {
+            code: KeyCode::Up, ..
+        } => finder.move_selection(false),
+        KeyEvent {
+            code: KeyCode::Down,
+            ..
+        } => finder.move_selection(true),
+        KeyEvent {
+            code: KeyCode::Char('p'),
+            modifiers: KeyModifiers::CONTROL,
+            ..
+        } => finder.move_selection(false),
+        KeyEvent {
+            code: KeyCode::Char('n'),
+            modifiers: KeyModifiers::CONTROL,
+            ..
+        } => finder.move_selection(true),
+        KeyEvent {
+            code: KeyCode::Char('u'),
+            modifiers: KeyModifiers::CONTROL,
+            ..
+        } => {
+            finder.query.clear();
+            finder.refilter();
+        }
+        KeyEvent {
+            code: KeyCode::Backspace,
+            ..
+        } => {
+            finder.query.pop();
+            finder.refilter();
+        }
+        KeyEvent {
+            code: KeyCode::Char(character),
+            modifiers,
+            ..
+        } if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+            finder.query.push(character);
+            finder.refilter();
+        }
+        _ => {}
+    }
+    InputModeResult::Handled
+}
+
+fn submit_revision_finder(state: &mut AppState, source: &mut JjLog, repository: Option<&Path>) {
+    submit_revision_finder_with_runner(state, source, repository, SystemJjCommandRunner);
+}
+
+pub fn submit_revision_finder_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    source: &mut JjLog,
+    repository: Option<&Path>,
+    runner: R,
+) {
+    let Some(InputMode::RevisionFinder { finder }) = state.modes.active() else {
+        return;
+    };
+    let Some(change_id) = finder
+        .selected_candidate()
+        .map(|candidate| candidate.change_id.clone())
+    else {
+        return;
+    };
+    let purpose = finder.purpose.clone();
+    state.modes.pop();
+
+    match purpose {
+        FinderPurpose::Jump => jump_to_change(state, source, &change_id, runner),
+        FinderPurpose::Rebase { revisions } => {
+            let mut rebase = JjRebase::default();
+            if let Some(repository) = repository {
+                rebase = rebase.with_repository(repository);
+            }
+            let preview = rebase
+                .spec_for(&RebaseQuery::new(revisions, short_id(&change_id)))
+                .command_preview();
+            state.modes.push(InputMode::CommandPreview {
+                pending: PendingCommandPreview::rebase(preview),
+            });
+        }
+        FinderPurpose::Squash { from } => {
+            let mut squash = JjSquash::default();
+            if let Some(repository) = repository {
+                squash = squash.with_repository(repository);
+            }
+            let preview = squash
+                .spec_for(&SquashQuery::new(from, short_id(&change_id)))
+                .command_preview();
+            state.modes.push(InputMode::CommandPreview {
+                pending: PendingCommandPreview::squash(preview),
+            });
+        }
+    }
+}
+
+/// Selects `change_id` in the log, widening the revset around it when it is not visible.
+fn jump_to_change<R: JjCommandRunner>(
+    state: &mut AppState,
+    source: &mut JjLog,
+    change_id: &str,
+    runner: R,
+) {
+    if let AppView::Log(log) = state.views.active_mut()
+        && log.select_change_id(change_id)
+    {
+        return;
+    }
+
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(SourceView::Log, SourceAction::SetRevset).with_key("enter"),
+    );
+    let revset = reveal_revset(&current_revset(source, &mut runner), short_id(change_id));
+    let next_source = source.clone().with_revset(revset.clone());
+    let result = next_source.load_with_runner(&mut runner);
+    drop(runner);
+
+    let AppView::Log(log) = state.views.active_mut() else {
+        return;
+    };
+    match result {
+        Ok(snapshot) => {
+            log.refresh(snapshot);
+            let _ = log.select_change_id(change_id);
+            log.show_status(format!("revset: {revset}"));
+            *source = next_source;
+        }
+        Err(error) => log.show_error(format!("could not show {}: {error}", short_id(change_id))),
+    }
+}
+
+/// Keeps the current scope and adds the neighbourhood of `change_id`.
+fn reveal_revset(scope: &str, change_id: &str) -> String {
+    widened_revset(
+        scope,
+        &format!(
+            "ancestors({change_id}, {REVEAL_DEPTH}) | descendants({change_id}, {REVEAL_DEPTH})"
+        ),
+    )
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..SHORT_ID_LEN).unwrap_or(id)
+}
+
+/// Returns overlay lines: the query, the visible window of matches, and a footer.
+pub fn revision_finder_lines(finder: &RevisionFinder) -> Vec<String> {
+    let mut lines = vec![format!("find {}", finder.query), String::new()];
+    if finder.matches.is_empty() {
+        lines.push("  no matching revisions".to_owned());
+    }
+    let first = finder
+        .selected
+        .saturating_sub(FINDER_ROWS - 1)
+        .min(finder.matches.len().saturating_sub(FINDER_ROWS));
+    for (position, index) in finder
+        .matches
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(FINDER_ROWS)
+    {
+        let candidate = &finder.candidates[*index];
+        let marker = if position == finder.selected {
+            ">"
+        } else {
+            " "
+        };
+        let mut line = format!("{marker} {:SHORT_ID_LEN$}", short_id(&candidate.change_id));
+        if !candidate.bookmarks.is_empty() {
+            line.push_str("  ");
+            line.push_str(&candidate.bookmarks.join(" "));
+        }
+        let summary = candidate.description.lines().next().unwrap_or_default();
+        line.push_str("  ");
+        line.push_str(if summary.is_empty() {
+            "(no description set)"
+        } else {
+            summary
+        });
+        if !candidate.author.is_empty() {
+            line.push_str(&format!("  ({})", candidate.author));
+        }
+        lines.push(line);
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "{}/{}   ↑/↓ select   enter {}   Ctrl-u clear   esc cancel",
+        finder.matches.len().min(finder.selected + 1),
+        finder.matches.len(),
+        finder.purpose.verb()
+    ));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use jk_core::SourceAction;
+
+    use super::*;
+    use crate::test_support::{SequencedRunner, log_app_view_with_changes, output};
+
+    fn finder_record(change_id: &str, summary: &str, author: &str, bookmarks: &str) -> String {
+        format!(
+            "{{\"change_id\":\"{change_id}\",\"commit_id\":\"c\",\"description\":\"{summary}\\n\",\"author\":{{\"name\":\"{author}\",\"email\":\"\"}}}}\t\"{bookmarks}\"\n"
+        )
+    }
+
+    fn finder_runner() -> SequencedRunner {
+        let stdout = [
+            finder_record("aaa", "aaa summary", "Ada", ""),
+            finder_record("bbb", "bbb summary", "Ada", ""),
+            finder_record("qqqzzz", "fix parser crash", "Grace", "hotfix"),
+            finder_record("rrr", "docs", "Linus", "main"),
+        ]
+        .concat();
+        SequencedRunner::successes(vec![output(0, &stdout, "")])
+    }
+
+    fn open(state: &mut AppState, purpose: FinderPurpose) {
+        open_revision_finder_with_runner(state, &JjLog::default(), purpose, finder_runner());
+    }
+
+    fn type_query(state: &mut AppState, query: &str) {
+        let mut source = JjLog::default();
+        for character in query.chars() {
+            let key = KeyEvent::new(KeyCode::Char(character), KeyModifiers::NONE);
+            let _ = handle_revision_finder_mode(state, &mut source, None, key);
+        }
+    }
+
+    fn finder(state: &AppState) -> &RevisionFinder {
+        match state.modes.active() {
+            Some(InputMode::RevisionFinder { finder }) => finder,
+            other => panic!("expected revision finder, got {other:?}"),
+        }
+    }
+
+    fn selected_change(state: &AppState) -> Option<&str> {
+        finder(state)
+            .selected_candidate()
+            .map(|candidate| candidate.change_id.as_str())
+    }
+
+    #[test]
+    fn typing_matches_descriptions_authors_and_bookmarks() {
+        let mut state = AppState::new(log_app_view_with_changes(["aaa", "bbb"]));
+        open(&mut state, FinderPurpose::Jump);
+        assert_eq!(finder(&state).matches.len(), 4);
+        assert_eq!(
+            state
+                .command_history()
+                .records()
+                .map(|record| record.source.action.clone())
+                .collect::<Vec<_>>(),
+            [SourceAction::FindRevision]
+        );
+
+        type_query(&mut state, "prsr");
+        assert_eq!(selected_change(&state), Some("qqqzzz"));
+
+        let mut source = JjLog::default();
+        let clear = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL);
+        let _ = handle_revision_finder_mode(&mut state, &mut source, None, clear);
+        type_query(&mut state, "grace");
+        assert_eq!(selected_change(&state), Some("qqqzzz"));
+
+        let _ = handle_revision_finder_mode(&mut state, &mut source, None, clear);
+        type_query(&mut state, "main");
+        assert_eq!(selected_change(&state), Some("rrr"));
+        assert!(
+            revision_finder_lines(finder(&state))
+                .contains(&"> rrr           main  docs  (Linus)".to_owned())
+        );
+    }
+
+    #[test]
+    fn change_id_prefix_ranks_before_fuzzy_matches() {
+        let mut state = AppState::new(log_app_view_with_changes(["aaa"]));
+        open(&mut state, FinderPurpose::Jump);
+
+        type_query(&mut state, "r");
+
+        assert_eq!(selected_change(&state), Some("rrr"));
+    }
+
+    #[test]
+    fn picking_a_visible_change_selects_it_without_reloading() {
+        let mut state = AppState::new(log_app_view_with_changes(["aaa", "bbb"]));
+        open(&mut state, FinderPurpose::Jump);
+        type_query(&mut state, "bbb");
+        let mut source = JjLog::default();
+
+        submit_revision_finder_with_runner(
+            &mut state,
+            &mut source,
+            None,
+            SequencedRunner::successes(Vec::new()),
+        );
+
+        assert_eq!(state.modes.active(), None);
+        let AppView::Log(log) = state.views.active() else {
+            panic!("log stays active");
+        };
+        assert_eq!(log.selected_change_id(), Some("bbb"));
+        assert_eq!(source.revset(), None);
+    }
+
+    #[test]
+    fn picking_a_hidden_change_reloads_the_log_around_it() {
+        let mut state = AppState::new(log_app_view_with_changes(["aaa"]));
+        open(&mut state, FinderPurpose::Jump);
+        type_query(&mut state, "docs");
+        let mut source = JjLog::default().with_revset("mine()");
+
+        submit_revision_finder_with_runner(
+            &mut state,
+            &mut source,
+            None,
+            SequencedRunner::successes(vec![
+                output(0, "@  aaa\n○  rrr\n", ""),
+                output(
+                    0,
+                    concat!(
+                        "{\"change_id\":\"aaa\",\"commit_id\":\"1\",\"description\":\"a\"}\t\"\"\n",
+                        "{\"change_id\":\"rrr\",\"commit_id\":\"2\",\"description\":\"docs\"}\t\"\"\n",
+                    ),
+                    "",
+                ),
+            ]),
+        );
+
+        assert_eq!(
+            source.revset(),
+            Some("(mine()) | ancestors(rrr, 10) | descendants(rrr, 10)")
+        );
+        let AppView::Log(log) = state.views.active() else {
+            panic!("log stays active");
+        };
+        assert_eq!(log.selected_change_id(), Some("rrr"));
+    }
+
+    #[test]
+    fn picking_a_hidden_change_from_the_default_log_keeps_revsets_log() {
+        let mut state = AppState::new(log_app_view_with_changes(["aaa"]));
+        open(&mut state, FinderPurpose::Jump);
+        type_query(&mut state, "docs");
+        let mut source = JjLog::default();
+
+        submit_revision_finder_with_runner(
+            &mut state,
+            &mut source,
+            None,
+            SequencedRunner::successes(vec![
+                output(0, "trunk()..@\n", ""),
+                output(0, "@  aaa\n○  rrr\n", ""),
+                output(
+                    0,
+                    concat!(
+                        "{\"change_id\":\"aaa\",\"commit_id\":\"1\",\"description\":\"a\"}\t\"\"\n",
+                        "{\"change_id\":\"rrr\",\"commit_id\":\"2\",\"description\":\"docs\"}\t\"\"\n",
+                    ),
+                    "",
+                ),
+            ]),
+        );
+
+        assert_eq!(
+            source.revset(),
+            Some("(trunk()..@) | ancestors(rrr, 10) | descendants(rrr, 10)")
+        );
+        let AppView::Log(log) = state.views.active() else {
+            panic!("log stays active");
+        };
+        assert_eq!(log.selected_change_id(), Some("rrr"));
+    }
+
+    #[test]
+    fn rebase_and_squash_pick_targets_into_command_previews() {
+        let mut state = AppState::new(log_app_view_with_changes(["aaa", "bbb"]));
+        open(
+            &mut state,
+            FinderPurpose::Rebase {
+                revisions: vec!["aaa".to_owned()],
+            },
+        );
+        assert!(
+            finder(&state)
+                .matches
+                .iter()
+                .all(|index| finder(&state).candidates[*index].change_id != "aaa")
+        );
+        type_query(&mut state, "main");
+        let mut source = JjLog::default();
+        submit_revision_finder_with_runner(
+            &mut state,
+            &mut source,
+            None,
+            SequencedRunner::successes(Vec::new()),
+        );
+        let Some(InputMode::CommandPreview { pending }) = state.modes.active() else {
+            panic!("rebase preview should open");
+        };
+        assert_eq!(pending.source_action, SourceAction::RebaseRevision);
+        assert_eq!(pending.preview.title, "jj rebase -r aaa -d rrr");
+
+        state.modes.pop();
+        open(
+            &mut state,
+            FinderPurpose::Squash {
+                from: "bbb".to_owned(),
+            },
+        );
+        type_query(&mut state, "aaa");
+        submit_revision_finder_with_runner(
+            &mut state,
+            &mut source,
+            None,
+            SequencedRunner::successes(Vec::new()),
+        );
+        let Some(InputMode::CommandPreview { pending }) = state.modes.active() else {
+            panic!("squash preview should open");
+        };
+        assert_eq!(pending.source_action, SourceAction::SquashRevision);
+        assert_eq!(pending.preview.title, "jj squash --from bbb --into aaa");
+    }
+}
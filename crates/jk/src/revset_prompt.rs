@@ -0,0 +1,491 @@
+//! Interactive revset prompt for the log view.
+//!
+//! The prompt reloads the active log through `JjLog::with_revset`. When jj rejects the revset, its
+//! error stays inline so the user can fix the expression without losing it. Accepted revsets are
+//! kept in a small persisted history that Up and Down recall, and submitting an empty prompt resets
+//! the log to jj's configured `revsets.log` default.
+
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use jk_cli::{JjCommandRunner, JjLog, JjLogError, RecordingJjCommandRunner, SystemJjCommandRunner};
+use jk_core::{CommandSource, SourceAction, SourceView};
+
+use crate::persistence::{read_lines, state_dir, write_lines};
+use crate::prompt_completion::{
+    complete_prompt, completion_direction, dismiss_completion_popup, reset_prompt_completion,
+};
+use crate::state::{AppState, AppView, InputMode, InputModeResult};
+
+const REVSET_HISTORY_FILE: &str = "revset-history";
+const REVSET_HISTORY_LIMIT: usize = 50;
+const ERROR_LINE_LIMIT: usize = 8;
+
+/// Revsets accepted by the prompt, oldest first.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RevsetHistory {
+    entries: Vec<String>,
+    path: Option<PathBuf>,
+}
+
+impl RevsetHistory {
+    /// Loads history from the per-user state directory.
+    pub(crate) fn load() -> Self {
+        state_dir().map_or_else(Self::default, |dir| {
+            Self::at_path(dir.join(REVSET_HISTORY_FILE))
+        })
+    }
+
+    /// Loads history from `path` and saves later changes back to it.
+    pub(crate) fn at_path(path: PathBuf) -> Self {
+        Self {
+            entries: read_lines(&path),
+            path: Some(path),
+        }
+    }
+
+    /// Returns remembered revsets, oldest first.
+    #[cfg(test)]
+    pub(crate) fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Returns the entry `offset` steps back from the newest one.
+    fn recall(&self, offset: usize) -> Option<&str> {
+        self.entries
+            .len()
+            .checked_sub(offset + 1)
+            .and_then(|index| self.entries.get(index))
+            .map(String::as_str)
+    }
+
+    /// Moves `revset` to the newest position and saves the history.
+    pub(crate) fn record(&mut self, revset: &str) {
+        let revset = revset.split_whitespace().collect::<Vec<_>>().join(" ");
+        if revset.is_empty() {
+            return;
+        }
+        self.entries.retain(|entry| *entry != revset);
+        self.entries.push(revset);
+        let overflow = self.entries.len().saturating_sub(REVSET_HISTORY_LIMIT);
+        self.entries.drain(..overflow);
+        if let Some(path) = &self.path
+            && let Err(error) = write_lines(path, &self.entries)
+        {
+            tracing::warn!(%error, path = %path.display(), "failed to save revset history");
+        }
+    }
+}
+
+/// Opens the revset prompt prefilled with the active log revset.
+pub fn open_revset_prompt(state: &mut AppState, source: &JjLog) {
+    if !matches!(state.views.active(), AppView::Log(_)) {
+        return;
+    }
+    reset_prompt_completion(state);
+    state.modes.push(InputMode::LogRevset {
+        input: source.revset().unwrap_or_default().to_owned(),
+        error: None,
+        recall: None,
+    });
+}
+
+/// Returns the revset prompt lines, including any inline jj error.
+pub fn revset_prompt_lines(input: &str, error: Option<&str>) -> Vec<String> {
+    let mut lines = vec![format!("-r {input}")];
+    if let Some(error) = error {
+        lines.push(String::new());
+        lines.extend(error.lines().map(ToOwned::to_owned));
+    }
+    lines.push(String::new());
+    lines.push(
+        "enter apply (empty resets to default)   tab complete   ↑/↓ history   Ctrl-u clear   esc cancel"
+            .to_owned(),
+    );
+    lines
+}
+
+/// Handles key input while the revset prompt is active.
+pub fn handle_log_revset_mode(
+    state: &mut AppState,
+    source: &mut JjLog,
+    repository: Option<&Path>,
+    key: KeyEvent,
+) -> InputModeResult {
+    if let Some(direction) = completion_direction(key) {
+        complete_prompt(state, repository, direction);
+        return InputModeResult::Handled;
+    }
+    if dismiss_completion_popup(state, key) {
+        return InputModeResult::Handled;
+    }
+    match key {
+        KeyEvent {
+            code: KeyCode::Esc, ..
+        } => {
+            state.modes.pop();
+        }
+        KeyEvent {
+            code: KeyCode::Enter,
+            ..
+        } => submit_log_revset(state, source),
+        KeyEvent {
+            code: KeyCode::Up, ..
+        } => recall_revset(state, RecallDirection::Older),
+        KeyEvent {
+            code: KeyCode::Down,
+            ..
+        } => recall_revset(state, RecallDirection::Newer),
+        KeyEvent {
+            code: KeyCode::Backspace,
+            ..
+        } => {
+            let should_close = match state.modes.active_mut() {
+                Some(InputMode::LogRevset { input, .. }) if input.is_empty() => true,
+                Some(InputMode::LogRevset {
+                    input,
+                    error,
+                    recall,
+                }) => {
+                    input.pop();
+                    *error = None;
+                    *recall = None;
+                    false
+                }
+                _ => false,
+            };
+            if should_close {
+                state.modes.pop();
+            }
+        }
+        KeyEvent {
+            code: KeyCode::Char('u'),
+            modifiers,
+            ..
+        } if modifiers == KeyModifiers::CONTROL => {
+            if let Some(InputMode::LogRevset {
+                input,
+                error,
+                recall,
+            }) = state.modes.active_mut()
+            {
+                input.clear();
+                *error = None;
+                *recall = None;
+            }
+        }
+        KeyEvent {
+            code: KeyCode::Char(character),
+            modifiers,
+            ..
+        } if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+            if let Some(InputMode::LogRevset {
+                input,
+                error,
+                recall,
+            }) = state.modes.active_mut()
+            {
+                input.push(character);
+                *error = None;
+                *recall = None;
+            }
+        }
+        _ => {}
+    }
+    InputModeResult::Handled
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum RecallDirection {
+    Older,
+    Newer,
+}
+
+fn recall_revset(state: &mut AppState, direction: RecallDirection) {
+    let AppState {
+        modes,
+        revset_history,
+        ..
+    } = state;
+    let Some(InputMode::LogRevset {
+        input,
+        error,
+        recall,
+    }) = modes.active_mut()
+    else {
+        return;
+    };
+    let next = match (direction, *recall) {
+        (RecallDirection::Older, None) => Some(0),
+        (RecallDirection::Older, Some(offset)) => Some(offset + 1),
+        (RecallDirection::Newer, None | Some(0)) => None,
+        (RecallDirection::Newer, Some(offset)) => Some(offset - 1),
+    };
+    match next {
+        Some(offset) => {
+            let Some(entry) = revset_history.recall(offset) else {
+                return;
+            };
+            entry.clone_into(input);
+            *recall = Some(offset);
+        }
+        None => {
+            input.clear();
+            *recall = None;
+        }
+    }
+    *error = None;
+}
+
+fn submit_log_revset(state: &mut AppState, source: &mut JjLog) {
+    submit_log_revset_with_runner(state, source, SystemJjCommandRunner);
+}
+
+pub fn submit_log_revset_with_runner<R: JjCommandRunner>(
+    state: &mut AppState,
+    source: &mut JjLog,
+    runner: R,
+) {
+    let Some(InputMode::LogRevset { input, .. }) = state.modes.active() else {
+        return;
+    };
+    let revset = input.trim().to_owned();
+    let next_source = if revset.is_empty() {
+        source.clone().without_revset()
+    } else {
+        source.clone().with_revset(revset.clone())
+    };
+
+    let mut runner = RecordingJjCommandRunner::new(
+        runner,
+        &mut state.history,
+        CommandSource::new(SourceView::Log, SourceAction::SetRevset),
+    );
+    let result = next_source.load_with_runner(&mut runner);
+    drop(runner);
+
+    match result {
+        Ok(snapshot) => {
+            state.modes.pop();
+            if let AppView::Log(log) = state.views.active_mut() {
+                log.refresh(snapshot);
+                if revset.is_empty() {
+                    log.show_status("revset reset to configured default");
+                } else {
+                    log.show_status(format!("revset: {revset}"));
+                }
+            }
+            *source = next_source;
+            state.revset_history.record(&revset);
+        }
+        Err(load_error) => {
+            if let Some(InputMode::LogRevset { error, .. }) = state.modes.active_mut() {
+                *error = Some(revset_error_message(&load_error));
+            }
+        }
+    }
+}
+
+/// Keeps jj's own revset diagnostic, which already points at the offending token.
+fn revset_error_message(error: &JjLogError) -> String {
+    let message = match error {
+        JjLogError::CommandFailed(stderr) if !stderr.is_empty() => stderr.clone(),
+        _ => error.to_string(),
+    };
+    message
+        .lines()
+        .take(ERROR_LINE_LIMIT)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{SequencedRunner, log_app_view, output};
+
+    fn revset_mode(input: &str) -> InputMode {
+        InputMode::LogRevset {
+            input: input.to_owned(),
+            error: None,
+            recall: None,
+        }
+    }
+
+    fn semantic_record(change_id: &str) -> String {
+        format!(
+            "{{\"change_id\":\"{change_id}\",\"commit_id\":\"commit\",\"description\":\"{change_id} summary\"}}\t\"\"\n"
+        )
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn history_dedupes_normalizes_and_caps_entries() {
+        let mut history = RevsetHistory::default();
+        for index in 0..REVSET_HISTORY_LIMIT + 2 {
+            history.record(&format!("ancestors(@, {index})"));
+        }
+        history.record("ancestors(@,\n 5)");
+        history.record("mine()");
+        history.record("   ");
+
+        assert_eq!(history.entries().len(), REVSET_HISTORY_LIMIT);
+        assert_eq!(history.recall(0), Some("mine()"));
+        assert_eq!(history.recall(1), Some("ancestors(@, 5)"));
+        assert_eq!(
+            history
+                .entries()
+                .iter()
+                .filter(|entry| entry.as_str() == "ancestors(@, 5)")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn history_persists_to_its_path() {
+        let path = std::env::temp_dir().join(format!("jk-revset-history-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut history = RevsetHistory::at_path(path.clone());
+        history.record("trunk()..@");
+
+        assert_eq!(
+            RevsetHistory::at_path(path.clone()).entries(),
+            ["trunk()..@"]
+        );
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn up_and_down_recall_history_newest_first() {
+        let mut state = AppState::new(log_app_view("abc"));
+        state.revset_history.record("older()");
+        state.revset_history.record("newer()");
+        state.modes.push(revset_mode("draft"));
+        let mut source = JjLog::default();
+
+        let _ = handle_log_revset_mode(&mut state, &mut source, None, key(KeyCode::Up));
+        assert_eq!(
+            state.modes.active(),
+            Some(&InputMode::LogRevset {
+                input: "newer()".to_owned(),
+                error: None,
+                recall: Some(0),
+            })
+        );
+        let _ = handle_log_revset_mode(&mut state, &mut source, None, key(KeyCode::Up));
+        let _ = handle_log_revset_mode(&mut state, &mut source, None, key(KeyCode::Up));
+        assert_eq!(
+            state.modes.active(),
+            Some(&InputMode::LogRevset {
+                input: "older()".to_owned(),
+                error: None,
+                recall: Some(1),
+            })
+        );
+        let _ = handle_log_revset_mode(&mut state, &mut source, None, key(KeyCode::Down));
+        let _ = handle_log_revset_mode(&mut state, &mut source, None, key(KeyCode::Down));
+        assert_eq!(state.modes.active(), Some(&revset_mode("")));
+    }
+
+    #[test]
+    fn accepted_revset_reloads_log_and_records_history() {
+        let mut state = AppState::new(log_app_view("abc"));
+        state.modes.push(revset_mode(" mine() "));
+        let mut source = JjLog::default();
+
+        submit_log_revset_with_runner(
+            &mut state,
+            &mut source,
+            SequencedRunner::successes(vec![
+                output(0, "@  def def summary\n", ""),
+                output(0, &semantic_record("def"), ""),
+            ]),
+        );
+
+        assert_eq!(state.modes.active(), None);
+        assert_eq!(source.revset(), Some("mine()"));
+        assert_eq!(state.revset_history.entries(), ["mine()"]);
+        let AppView::Log(log) = state.views.active() else {
+            panic!("log stays active");
+        };
+        assert_eq!(log.selected_change_id(), Some("def"));
+        assert_eq!(
+            state
+                .command_history()
+                .records()
+                .map(|record| record.source.action.clone())
+                .collect::<Vec<_>>(),
+            [SourceAction::SetRevset, SourceAction::SetRevset]
+        );
+    }
+
+    #[test]
+    fn rejected_revset_keeps_prompt_with_jj_error() {
+        let mut state = AppState::new(log_app_view("abc"));
+        state.modes.push(revset_mode("nope("));
+        let mut source = JjLog::default().with_revset("mine()");
+
+        submit_log_revset_with_runner(
+            &mut state,
+            &mut source,
+            SequencedRunner::successes(vec![output(
+                1,
+                "",
+                "Error: Failed to parse revset: Syntax error\nCaused by:  --> 1:6\n",
+            )]),
+        );
+
+        assert_eq!(
+            state.modes.active(),
+            Some(&InputMode::LogRevset {
+                input: "nope(".to_owned(),
+                error: Some(
+                    "Error: Failed to parse revset: Syntax error\nCaused by:  --> 1:6".to_owned()
+                ),
+                recall: None,
+            })
+        );
+        assert_eq!(source.revset(), Some("mine()"));
+        assert!(state.revset_history.entries().is_empty());
+    }
+
+    #[test]
+    fn empty_revset_resets_to_configured_default() {
+        let mut state = AppState::new(log_app_view("abc"));
+        state.modes.push(revset_mode(""));
+        let mut source = JjLog::default().with_revset("mine()");
+
+        submit_log_revset_with_runner(
+            &mut state,
+            &mut source,
+            SequencedRunner::successes(vec![
+                output(0, "@  abc abc summary\n", ""),
+                output(0, &semantic_record("abc"), ""),
+            ]),
+        );
+
+        assert_eq!(state.modes.active(), None);
+        assert_eq!(source.revset(), None);
+        assert!(state.revset_history.entries().is_empty());
+    }
+
+    #[test]
+    fn prompt_lines_show_multiline_errors_inline() {
+        assert_eq!(
+            revset_prompt_lines("nope(", Some("Error: bad\nCaused by: x")),
+            [
+                "-r nope(",
+                "",
+                "Error: bad",
+                "Caused by: x",
+                "",
+                "enter apply (empty resets to default)   tab complete   ↑/↓ history   Ctrl-u clear   esc cancel",
+            ]
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use jk_cli::{DiffQuery, JjDiff, JjLog, JjShow, JjStatus, JjWorkspaces, ShowQuery, StatusQuery};
+use jk_cli::{
+    DiffFormat, DiffQuery, JjDiff, JjLog, JjShow, JjStatus, JjWorkspaces, ShowQuery, StatusQuery,
+};
 use jk_core::{CommandHistory, CommandSource, SourceAction, SourceView};
 use jk_tui::diff_view::DiffView;
 use jk_tui::log_view::LogView;
@@ -29,7 +31,9 @@ pub fn root_diff_view(
     );
     let snapshot = diff_source.load_query_with_runner(&query, &mut runner);
     let diff = match snapshot {
-        Ok(snapshot) => DiffView::new(snapshot),
+        Ok(snapshot) => {
+            DiffView::new(snapshot).with_side_by_side(query.format() == DiffFormat::SideBySide)
+        }
         Err(error) => DiffView::from_error(
             query.target_label(),
             diff_source.spec_for(&query).title().to_owned(),
@@ -1,4 +1,6 @@
-use jk_cli::{RecordingJjCommandRunner, SystemJjCommandRunner};
+use jk_cli::{
+    CachingJjCommandRunner, JjOutputCache, RecordingJjCommandRunner, SystemJjCommandRunner,
+};
 use jk_core::{CommandHistory, CommandSource};
 
 pub const fn recording_runner(
@@ -7,3 +9,19 @@ pub const fn recording_runner(
 ) -> RecordingJjCommandRunner<'_, SystemJjCommandRunner> {
     RecordingJjCommandRunner::new(SystemJjCommandRunner, history, source)
 }
+
+/// Records like [`recording_runner`], replaying read-only output cached at the current operation.
+///
+/// Only `--ignore-working-copy` specs are replayed; specs that snapshot the working copy always run
+/// so file edits show up.
+pub const fn cached_recording_runner<'a>(
+    history: &'a mut CommandHistory,
+    cache: &'a mut JjOutputCache,
+    source: CommandSource,
+) -> RecordingJjCommandRunner<'a, CachingJjCommandRunner<'a, SystemJjCommandRunner>> {
+    RecordingJjCommandRunner::new(
+        CachingJjCommandRunner::new(SystemJjCommandRunner, cache),
+        history,
+        source,
+    )
+}
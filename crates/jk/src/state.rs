@@ -1,17 +1,26 @@
 use jk_cli::{
-    DiffQuery, EvologQuery, JjLog, LogTemplateSelection, OperationQuery, ShowQuery, StatusQuery,
-    WorkspaceInspectionQuery,
+    DiffQuery, EvologQuery, JjLog, JjOutputCache, LogTemplateSelection, OperationQuery, ShowQuery,
+    StatusQuery, WorkspaceInspectionQuery,
 };
 use jk_core::CommandHistory;
 use jk_tui::command_discovery::BindingContext;
 use jk_tui::command_history_view::CommandHistoryView;
-use jk_tui::diff_view::DiffView;
-use jk_tui::log_view::LogView;
+use jk_tui::diff_view::{DiffAnchor, DiffView};
+use jk_tui::log_view::{GraphMove, LogView};
 use jk_tui::operation_log_view::OperationLogView;
 use jk_tui::rendered_view::RenderedView;
 use jk_tui::workspaces_view::WorkspacesView;
 
+use crate::checkpoints::SessionCheckpoints;
+use crate::diff_file_list::DiffFileList;
+use crate::diff_preview::DiffPreview;
+use crate::editor::EditorRequest;
 use crate::mutation_preview::PendingCommandPreview;
+use crate::prompt_completion::PromptCompletion;
+use crate::review_notes::ReviewNotes;
+use crate::revision_finder::RevisionFinder;
+use crate::revset_prompt::RevsetHistory;
+use crate::viewed_marks::ViewedMarks;
 
 /// Active top-level application view.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -77,6 +86,17 @@ pub struct AppState {
     pub(crate) views: ViewStack,
     pub(crate) modes: ModeStack,
     pub(crate) history: CommandHistory,
+    pub(crate) checkpoints: SessionCheckpoints,
+    pub(crate) revset_history: RevsetHistory,
+    pub(crate) viewed_marks: ViewedMarks,
+    pub(crate) review_notes: ReviewNotes,
+    pub(crate) editor_request: Option<EditorRequest>,
+    pub(crate) completion: PromptCompletion,
+    pub(crate) diff_preview: DiffPreview,
+    pub(crate) output_cache: JjOutputCache,
+    /// Configured diff tools, loaded once the first time diff options open.
+    pub(crate) diff_tools: Option<Vec<String>>,
+    pub(crate) operation_user: Option<String>,
     log_source_stack: Vec<JjLog>,
 }
 
@@ -91,10 +111,58 @@ impl AppState {
             views: ViewStack::new(root),
             modes: ModeStack::default(),
             history,
+            checkpoints: SessionCheckpoints::default(),
+            revset_history: RevsetHistory::default(),
+            viewed_marks: ViewedMarks::default(),
+            review_notes: ReviewNotes::default(),
+            editor_request: None,
+            completion: PromptCompletion::default(),
+            diff_preview: DiffPreview::default(),
+            output_cache: JjOutputCache::default(),
+            diff_tools: None,
+            operation_user: None,
             log_source_stack: Vec::new(),
         }
     }
 
+    #[must_use]
+    pub(crate) fn with_checkpoints(mut self, checkpoints: SessionCheckpoints) -> Self {
+        self.checkpoints = checkpoints;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_revset_history(mut self, revset_history: RevsetHistory) -> Self {
+        self.revset_history = revset_history;
+        self
+    }
+
+    /// Installs viewed marks and applies them to the root view.
+    #[must_use]
+    pub(crate) fn with_viewed_marks(mut self, viewed_marks: ViewedMarks) -> Self {
+        viewed_marks.apply(self.views.active_mut());
+        self.viewed_marks = viewed_marks;
+        self
+    }
+
+    /// Installs review notes; `review_notes::show_review_notes` places them on diffs.
+    #[must_use]
+    pub(crate) fn with_review_notes(mut self, review_notes: ReviewNotes) -> Self {
+        self.review_notes = review_notes;
+        self
+    }
+
+    /// Pushes `view`, applying remembered viewed marks when it is a diff.
+    pub(crate) fn push_view(&mut self, mut view: AppView) {
+        self.viewed_marks.apply(&mut view);
+        self.views.push(view);
+    }
+
+    /// Returns the configured diff tools, or none before they are loaded.
+    pub(crate) fn diff_tools(&self) -> &[String] {
+        self.diff_tools.as_deref().unwrap_or_default()
+    }
+
     #[cfg(test)]
     pub(crate) const fn command_history(&self) -> &CommandHistory {
         &self.history
@@ -162,6 +230,14 @@ impl ViewStack {
         true
     }
 
+    /// Returns the topmost log view, which may sit below the active view.
+    pub(crate) fn nearest_log(&self) -> Option<&LogView> {
+        self.views.iter().rev().find_map(|view| match view {
+            AppView::Log(log) => Some(log),
+            _ => None,
+        })
+    }
+
     fn active_is_log_with_log_parent(&self) -> bool {
         if self.views.len() < 2 {
             return false;
@@ -211,7 +287,7 @@ pub enum InputMode {
         selected: usize,
     },
     DiffFileList {
-        selected: usize,
+        list: DiffFileList,
     },
     DiffSearch {
         query: String,
@@ -228,6 +304,23 @@ pub enum InputMode {
         rev: String,
         message: String,
     },
+    CheckpointName {
+        name: String,
+    },
+    CheckpointPicker {
+        selected: usize,
+    },
+    DiffNote {
+        anchor: DiffAnchor,
+        comment: String,
+    },
+    DiffNotesExport {
+        path: String,
+    },
+    DiffPatch,
+    DiffPatchSave {
+        path: String,
+    },
     CommandPreview {
         pending: PendingCommandPreview,
     },
@@ -235,10 +328,23 @@ pub enum InputMode {
         input: String,
         error: Option<String>,
     },
+    LogCopy,
     LogTemplate {
         options: Vec<LogTemplateSelection>,
         selected: usize,
     },
+    LogRevset {
+        input: String,
+        error: Option<String>,
+        recall: Option<usize>,
+    },
+    RevisionFinder {
+        finder: RevisionFinder,
+    },
+    WidenRevset {
+        graph_move: GraphMove,
+        revset: String,
+    },
 }
 
 /// Whether an input-mode handler consumed a key event.
@@ -2,12 +2,13 @@ use std::collections::VecDeque;
 use std::io;
 use std::process::Output;
 
-use jk_cli::{DiffFormat, DiffQuery, JjCommandRunner};
+use jk_cli::{DiffOptions, DiffQuery, JjCommandRunner};
 use jk_core::{CommandHistory, CommandSource, SourceAction, SourceView};
 use jk_tui::diff_view::DiffView;
 use jk_tui::log_view::LogView;
 use jk_tui::workspaces_view::{WorkspaceViewRow, WorkspaceViewSnapshot, WorkspacesView};
 
+use crate::clipboard::{Clipboard, ClipboardBackend};
 use crate::state::AppView;
 
 pub fn diff_app_view(change_id: &str) -> AppView {
@@ -20,7 +21,7 @@ pub fn diff_app_view(change_id: &str) -> AppView {
 pub fn diff_query(change_id: &str) -> DiffQuery {
     DiffQuery::Revision {
         rev: change_id.to_owned(),
-        format: DiffFormat::Patch,
+        options: DiffOptions::default(),
     }
 }
 
@@ -127,6 +128,19 @@ pub fn output(code: i32, stdout: &str, stderr: &str) -> Output {
     }
 }
 
+/// Clipboard that keeps what was copied instead of touching the terminal.
+#[derive(Debug, Default)]
+pub struct RecordingClipboard {
+    pub copied: Vec<String>,
+}
+
+impl Clipboard for RecordingClipboard {
+    fn copy(&mut self, text: &str) -> io::Result<ClipboardBackend> {
+        self.copied.push(text.to_owned());
+        Ok(ClipboardBackend::Osc52)
+    }
+}
+
 pub struct SequencedRunner {
     outputs: VecDeque<io::Result<Output>>,
 }
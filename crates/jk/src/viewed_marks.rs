@@ -0,0 +1,96 @@
+//! Persistent "viewed" marks for diff files and hunks.
+//!
+//! Marks are keyed by path plus a hash of the marked content (see `DiffState::toggle_viewed`), so
+//! a mark clears itself as soon as the content changes. Each repository keeps its own file.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::persistence::{read_lines, repository_state_file, write_lines};
+use crate::state::{AppState, AppView};
+
+const VIEWED_MARKS_DIR: &str = "viewed";
+const VIEWED_MARKS_LIMIT: usize = 10_000;
+
+/// Viewed-mark keys for one repository, oldest first.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ViewedMarks {
+    marks: Vec<String>,
+    path: Option<PathBuf>,
+}
+
+impl ViewedMarks {
+    /// Loads marks for the repository containing `repository`, or the current directory.
+    pub(crate) fn load(repository: Option<&Path>) -> Self {
+        repository_state_file(repository, VIEWED_MARKS_DIR)
+            .map_or_else(Self::default, Self::at_path)
+    }
+
+    /// Loads marks from `path` and saves later changes back to it.
+    pub(crate) fn at_path(path: PathBuf) -> Self {
+        Self {
+            marks: read_lines(&path),
+            path: Some(path),
+        }
+    }
+
+    /// Returns every remembered mark.
+    pub(crate) fn marks(&self) -> BTreeSet<String> {
+        self.marks.iter().cloned().collect()
+    }
+
+    /// Applies the remembered marks to `view` when it is a diff.
+    pub(crate) fn apply(&self, view: &mut AppView) {
+        if let AppView::Diff { view, .. } = view {
+            view.set_viewed_marks(&self.marks());
+        }
+    }
+
+    /// Adds or removes `key` and saves the marks.
+    pub(crate) fn record(&mut self, key: &str, viewed: bool) {
+        self.marks.retain(|mark| mark != key);
+        if viewed {
+            self.marks.push(key.to_owned());
+        }
+        let overflow = self.marks.len().saturating_sub(VIEWED_MARKS_LIMIT);
+        self.marks.drain(..overflow);
+        if let Some(path) = &self.path
+            && let Err(error) = write_lines(path, &self.marks)
+        {
+            tracing::warn!(%error, path = %path.display(), "failed to save viewed marks");
+        }
+    }
+}
+
+/// Toggles the viewed mark on the active diff's selected hunk or file and saves it.
+pub fn toggle_diff_viewed(state: &mut AppState) {
+    let AppView::Diff { view, .. } = state.views.active_mut() else {
+        return;
+    };
+    let Some((key, viewed)) = view.toggle_viewed() else {
+        return;
+    };
+    state.viewed_marks.record(&key, viewed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_round_trip_and_clear() {
+        let dir = std::env::temp_dir().join(format!("jk-viewed-marks-{}", std::process::id()));
+        let path = dir.join("repo");
+
+        let mut marks = ViewedMarks::at_path(path.clone());
+        marks.record("file\t1\ta.rs", true);
+        marks.record("hunk\t2\tb.rs", true);
+        marks.record("file\t1\ta.rs", false);
+
+        assert_eq!(
+            ViewedMarks::at_path(path).marks(),
+            BTreeSet::from(["hunk\t2\tb.rs".to_owned()])
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}